@@ -0,0 +1,54 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Batch public key derivation against one `Keypair` per private key.
+//!
+//! Run with `cargo bench --bench derive_public_keys --features parallel` for the rayon backend.
+//!
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use symbol_crypto_core::prelude::{KeyPairSchema, Keypair, KpNis1, KpSym, PrivateKey};
+
+const COUNTS: [usize; 2] = [1_000, 10_000];
+
+fn private_keys(count: usize) -> Vec<PrivateKey> {
+    (0..count as u64)
+        .map(|i| {
+            let mut bytes = [0u8; 32];
+            bytes[..8].copy_from_slice(&i.to_le_bytes());
+            PrivateKey::from(bytes)
+        })
+        .collect()
+}
+
+fn bench_schema<Kp: KeyPairSchema>(c: &mut Criterion) {
+    let mut group = c.benchmark_group(format!("derive_public_keys/{}", Kp::SCHEMA_NAME));
+    for count in COUNTS {
+        let keys = private_keys(count);
+        group.throughput(Throughput::Elements(count as u64));
+        group.bench_with_input(BenchmarkId::new("batch", count), &keys, |b, keys| {
+            b.iter(|| Keypair::<Kp>::derive_public_keys(keys))
+        });
+        group.bench_with_input(BenchmarkId::new("one_by_one", count), &keys, |b, keys| {
+            b.iter(|| {
+                keys.iter()
+                    .map(|private_key| Keypair::<Kp>::from_private_key(*private_key).public_key())
+                    .collect::<Vec<_>>()
+            })
+        });
+    }
+    group.finish();
+}
+
+fn derive_public_keys(c: &mut Criterion) {
+    bench_schema::<KpSym>(c);
+    bench_schema::<KpNis1>(c);
+}
+
+criterion_group!(benches, derive_public_keys);
+criterion_main!(benches);
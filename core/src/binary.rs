@@ -0,0 +1,108 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Borsh and bincode encodings of the key, signature and hash types.
+//!
+//! With the `borsh` feature, `PrivateKey`, `PublicKey`, `Signature`, `H256` and `H512` encode
+//! as their raw bytes, without length prefix. A `Keypair` encodes only its public key and
+//! cannot be decoded, as with serde: wrap it in `SecretSerde` to store the full keypair, the
+//! `to_bytes` layout.
+//!
+//! The `bincode` feature encodes the serde implementations with `to_bincode` and
+//! `from_bincode`.
+//!
+
+#[cfg(feature = "borsh")]
+use std::io::{Read, Result as IoResult, Write};
+
+#[cfg(feature = "bincode")]
+use anyhow::Result;
+#[cfg(feature = "borsh")]
+use borsh::{BorshDeserialize, BorshSerialize};
+#[cfg(feature = "bincode")]
+use serde::{de::DeserializeOwned, Serialize};
+#[cfg(feature = "borsh")]
+use zeroize::Zeroizing;
+
+#[cfg(feature = "borsh")]
+use super::{
+    KeyPairSchema, Keypair, PrivateKey, PublicKey, SecretSerde, Signature, H256, H512,
+    KEYPAIR_LENGTH,
+};
+
+/// Encode a value with bincode, through its serde implementation.
+///
+/// # Returns
+///
+/// A `Result` whose okay value is the encoded `Vec<u8>` or whose error value
+/// is an `failure::Error` describing the error that occurred.
+#[cfg(feature = "bincode")]
+pub fn to_bincode<T: Serialize + ?Sized>(value: &T) -> Result<Vec<u8>> {
+    Ok(bincode::serialize(value)?)
+}
+
+/// Decode a value encoded with `to_bincode`.
+///
+/// # Returns
+///
+/// A `Result` whose okay value is the decoded value or whose error value
+/// is an `failure::Error` describing the error that occurred.
+#[cfg(feature = "bincode")]
+pub fn from_bincode<T: DeserializeOwned>(bytes: &[u8]) -> Result<T> {
+    Ok(bincode::deserialize(bytes)?)
+}
+
+#[cfg(feature = "borsh")]
+macro_rules! impl_borsh {
+    ($($name:ident),*) => {
+        $(
+            impl BorshSerialize for $name {
+                fn serialize<W: Write>(&self, writer: &mut W) -> IoResult<()> {
+                    writer.write_all(self.as_bytes())
+                }
+            }
+
+            impl BorshDeserialize for $name {
+                fn deserialize_reader<R: Read>(reader: &mut R) -> IoResult<Self> {
+                    let mut bytes = Zeroizing::new([0u8; $name::len_bytes()]);
+                    reader.read_exact(&mut bytes[..])?;
+                    Ok($name::from_slice(&bytes[..]))
+                }
+            }
+        )*
+    };
+}
+
+#[cfg(feature = "borsh")]
+impl_borsh!(PrivateKey, PublicKey, Signature, H256, H512);
+
+#[cfg(feature = "borsh")]
+impl<C: KeyPairSchema> BorshSerialize for Keypair<C> {
+    fn serialize<W: Write>(&self, writer: &mut W) -> IoResult<()> {
+        // only the public half, see `SecretSerde` for the full keypair.
+        BorshSerialize::serialize(&self.public_key(), writer)
+    }
+}
+
+#[cfg(feature = "borsh")]
+impl<C: KeyPairSchema> BorshSerialize for SecretSerde<Keypair<C>> {
+    fn serialize<W: Write>(&self, writer: &mut W) -> IoResult<()> {
+        writer.write_all(&Zeroizing::new(self.0.to_bytes())[..])
+    }
+}
+
+#[cfg(feature = "borsh")]
+impl<C: KeyPairSchema> BorshDeserialize for SecretSerde<Keypair<C>> {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> IoResult<Self> {
+        let mut bytes = Zeroizing::new([0u8; KEYPAIR_LENGTH]);
+        reader.read_exact(&mut bytes[..])?;
+        Keypair::from_bytes(&bytes[..])
+            .map(SecretSerde)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e.to_string()))
+    }
+}
@@ -10,23 +10,90 @@ use anyhow::Result;
 use curve25519::edwards::CompressedEdwardsY;
 use curve25519::scalar::Scalar;
 use sha3::Digest;
+use zeroize::Zeroizing;
 
-use super::{PrivateKey, PublicKey, SharedSecret, H256, KEY_BYTES_SIZE};
+use super::envelope_mode::{pad_to_bucket, unpad_bucket, EnvelopeMode};
+use super::sealed::Sealed;
+use super::{clamped_scalar, PrivateKey, PublicKey, Seed32, SharedSecret, H256, KEY_BYTES_SIZE};
 
 /// This trait defines an association of symbol or nis1 encrypt and decrypt message.
 ///
-pub trait BlockCipher: Sized {
+/// The trait is sealed, it is implemented by the Symbol and Nis1 ciphers only.
+///
+pub trait BlockCipher: Sealed + Sized {
+    /// Encrypt a message from the signer to the receiver.
+    ///
+    fn encrypt_for(
+        signer_sk: &PrivateKey,
+        receiver_pk: &PublicKey,
+        msg: &[u8],
+    ) -> Result<Vec<u8>>;
+
+    /// Decrypt a message the signer encrypted for the receiver.
+    ///
+    fn decrypt_from(
+        receiver_sk: &PrivateKey,
+        signer_pk: &PublicKey,
+        enc_msg: &[u8],
+    ) -> Result<Vec<u8>>;
+
+    /// Encrypt a message in an explicit `EnvelopeMode`, e.g. `EnvelopeMode::ConstantSize` so
+    /// observers cannot tell messages apart by their length.
+    ///
+    fn encrypt_for_mode(
+        signer_sk: &PrivateKey,
+        receiver_pk: &PublicKey,
+        msg: &[u8],
+        mode: EnvelopeMode,
+    ) -> Result<Vec<u8>> {
+        match mode {
+            EnvelopeMode::Standard => Self::encrypt_for(signer_sk, receiver_pk, msg),
+            EnvelopeMode::ConstantSize => {
+                Self::encrypt_for(signer_sk, receiver_pk, &pad_to_bucket(msg)?)
+            }
+        }
+    }
+
+    /// Decrypt a message encrypted with `encrypt_for_mode` in the same `EnvelopeMode`.
+    ///
+    fn decrypt_from_mode(
+        receiver_sk: &PrivateKey,
+        signer_pk: &PublicKey,
+        enc_msg: &[u8],
+        mode: EnvelopeMode,
+    ) -> Result<Vec<u8>> {
+        match mode {
+            EnvelopeMode::Standard => Self::decrypt_from(receiver_sk, signer_pk, enc_msg),
+            EnvelopeMode::ConstantSize => {
+                let frame = Zeroizing::new(Self::decrypt_from(receiver_sk, signer_pk, enc_msg)?);
+                unpad_bucket(&frame)
+            }
+        }
+    }
+
+    /// Encrypt a message from the raw key bytes, the secret key is the `Seed32`, not its
+    /// scalar.
+    ///
+    #[deprecated(note = "use `encrypt_for`, which takes typed keys")]
     fn encrypt_message(
-        signer_sk: &[u8; KEY_BYTES_SIZE],
+        signer_sk: &Seed32,
         receiver_pk: &[u8; KEY_BYTES_SIZE],
         msg: &[u8],
-    ) -> Result<Vec<u8>>;
+    ) -> Result<Vec<u8>> {
+        Self::encrypt_for(&signer_sk.into(), &receiver_pk.into(), msg)
+    }
 
+    /// Decrypt a message from the raw key bytes, the secret key is the `Seed32`, not its
+    /// scalar.
+    ///
+    #[deprecated(note = "use `decrypt_from`, which takes typed keys")]
     fn decrypt_message(
-        receiver_sk: &[u8; KEY_BYTES_SIZE],
+        receiver_sk: &Seed32,
         signer_pk: &[u8; KEY_BYTES_SIZE],
         enc_msg: &[u8],
-    ) -> Result<Vec<u8>>;
+    ) -> Result<Vec<u8>> {
+        Self::decrypt_from(&receiver_sk.into(), &signer_pk.into(), enc_msg)
+    }
 }
 
 // internal functions.
@@ -49,12 +116,5 @@ pub fn derive_shared_secret<D: Digest>(
 fn scalar_from_sk<D: Digest>(secret_key: PrivateKey) -> Scalar {
     let sk_hash = D::digest(secret_key.as_bytes());
 
-    let mut sk_hash_fix: H256 = H256::default();
-    sk_hash_fix.assign_from_slice(&sk_hash.as_slice()[0..32]);
-
-    sk_hash_fix.0[0] &= 0xF8; // The lowest three bits must be 0
-    sk_hash_fix.0[31] &= 0x7F; // The highest bit must be 0
-    sk_hash_fix.0[31] |= 0x40; // The second highest bit must be 1
-
-    Scalar::from_bits(sk_hash_fix.into())
+    clamped_scalar(&H256::from_slice(&sk_hash.as_slice()[0..32]))
 }
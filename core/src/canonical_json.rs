@@ -0,0 +1,179 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! JSON Canonicalization Scheme ([RFC 8785](https://www.rfc-editor.org/rfc/rfc8785)) for signing
+//! structured data.
+//!
+//! Signing the canonical form instead of an arbitrary serialization means any implementation
+//! of JCS produces the same bytes, and so verifies the same signature, for the same value.
+//!
+
+use anyhow::Result;
+use serde_json::{Number, Value};
+
+use super::{KeyPairSchema, Keypair, PublicKey, Signature};
+
+/// Serialize a JSON value to its canonical (JCS) form.
+///
+pub fn to_signing_bytes(value: &Value) -> Vec<u8> {
+    let mut out = String::new();
+    write_value(&mut out, value);
+    out.into_bytes()
+}
+
+/// Signs the canonical form of a JSON value with a `Keypair`.
+///
+/// # Inputs
+///
+/// * `kp`: The signer `Keypair`.
+///
+/// * `value`: The JSON value to sign.
+///
+/// # Returns
+///
+/// A `Signature` the signature hash, panics as `Keypair::sign` for a keypair that cannot sign.
+#[allow(deprecated)]
+pub fn sign_json<Kp: KeyPairSchema>(kp: &Keypair<Kp>, value: &Value) -> Signature {
+    kp.sign(&to_signing_bytes(value))
+}
+
+/// Verify a `Signature` on the canonical form of a JSON value.
+///
+/// # Inputs
+///
+/// * `public_key`: The signer public key.
+///
+/// * `value`: The JSON value that was signed.
+///
+/// * `signature`: an `Signature` the signature hash.
+///
+/// # Returns
+///
+/// Returns `Ok` if the `Signature` was a valid signature created by the signer.
+pub fn verify_json<Kp: KeyPairSchema>(
+    public_key: &PublicKey,
+    value: &Value,
+    signature: Signature,
+) -> Result<()> {
+    Kp::from_null_private_key(*public_key).verify(&to_signing_bytes(value), signature)
+}
+
+// internal functions.
+fn write_value(out: &mut String, value: &Value) {
+    match value {
+        Value::Null => out.push_str("null"),
+        Value::Bool(b) => out.push_str(if *b { "true" } else { "false" }),
+        Value::Number(n) => write_number(out, n),
+        Value::String(s) => write_string(out, s),
+        Value::Array(values) => {
+            out.push('[');
+            for (i, v) in values.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_value(out, v);
+            }
+            out.push(']');
+        }
+        Value::Object(map) => {
+            // members are sorted by the UTF-16 code units of their names.
+            let mut members: Vec<(Vec<u16>, &String, &Value)> = map
+                .iter()
+                .map(|(k, v)| (k.encode_utf16().collect(), k, v))
+                .collect();
+            members.sort_by(|a, b| a.0.cmp(&b.0));
+
+            out.push('{');
+            for (i, (_, k, v)) in members.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_string(out, k);
+                out.push(':');
+                write_value(out, v);
+            }
+            out.push('}');
+        }
+    }
+}
+
+fn write_string(out: &mut String, s: &str) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\u{08}' => out.push_str("\\b"),
+            '\u{0C}' => out.push_str("\\f"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+const MAX_SAFE_INTEGER: u64 = (1 << 53) - 1;
+
+fn write_number(out: &mut String, n: &Number) {
+    if let Some(u) = n.as_u64() {
+        if u <= MAX_SAFE_INTEGER {
+            out.push_str(&u.to_string());
+            return;
+        }
+    } else if let Some(i) = n.as_i64() {
+        if i.unsigned_abs() <= MAX_SAFE_INTEGER {
+            out.push_str(&i.to_string());
+            return;
+        }
+    }
+
+    write_f64(out, n.as_f64().unwrap_or_default());
+}
+
+/// Formats a finite double like ECMAScript `Number.prototype.toString`.
+fn write_f64(out: &mut String, value: f64) {
+    if value == 0.0 {
+        out.push('0');
+        return;
+    }
+    if value < 0.0 {
+        out.push('-');
+    }
+
+    // shortest round-trip digits and exponent, e.g. "1.2345e-7".
+    let exponential = format!("{:e}", value.abs());
+    let (mantissa, exponent) = exponential.split_once('e').unwrap_or((&exponential, "0"));
+    let digits: String = mantissa.chars().filter(|c| *c != '.').collect();
+    let k = digits.len() as i32;
+    let n = exponent.parse::<i32>().unwrap_or_default() + 1;
+
+    if k <= n && n <= 21 {
+        out.push_str(&digits);
+        out.push_str(&"0".repeat((n - k) as usize));
+    } else if 0 < n && n <= 21 {
+        out.push_str(&digits[..n as usize]);
+        out.push('.');
+        out.push_str(&digits[n as usize..]);
+    } else if -6 < n && n <= 0 {
+        out.push_str("0.");
+        out.push_str(&"0".repeat((-n) as usize));
+        out.push_str(&digits);
+    } else {
+        out.push_str(&digits[..1]);
+        if k > 1 {
+            out.push('.');
+            out.push_str(&digits[1..]);
+        }
+        out.push('e');
+        out.push(if n - 1 < 0 { '-' } else { '+' });
+        out.push_str(&(n - 1).abs().to_string());
+    }
+}
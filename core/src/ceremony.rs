@@ -0,0 +1,607 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Multi-party key ceremony with an auditable transcript.
+//!
+//! Every participant contributes secret entropy; the ceremony keypair private key is
+//! `SHA3-256(domain || entropy_1 || ... || entropy_M)` in participant order, so no subset of
+//! participants controls it. The `CeremonyTranscript` records the participants, a commitment
+//! to each contribution and the resulting public key, and is signed by every participant.
+//!
+//! The transcript never contains the contributions: reproducing the key for an audit requires
+//! every participant to reveal their entropy, which can then be checked against the commitments.
+//!
+//! `verify_transcript` audits a transcript, e.g. loaded from JSON by a command line tool, and
+//! returns a `TranscriptReport` locating every failure instead of stopping at the first one;
+//! `verify_transcript_with_contributions` also checks the revealed contributions and the key
+//! they derive.
+//!
+
+use std::fmt;
+use std::marker::PhantomData;
+
+use anyhow::{anyhow, ensure, Result};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+use zeroize::{Zeroize, Zeroizing};
+
+use super::{KeyPairSchema, Keypair, PrivateKey, PublicKey, Signature, H256};
+
+/// Minimum size of an entropy contribution.
+pub const CEREMONY_MIN_ENTROPY: usize = 32;
+
+const CEREMONY_KEY_DOMAIN: &[u8] = b"symbol-crypto-core/ceremony/key/v1";
+const CEREMONY_TRANSCRIPT_DOMAIN: &[u8] = b"symbol-crypto-core/ceremony/transcript/v1";
+
+/// Orchestrates the generation of a keypair across a fixed set of participants.
+///
+pub struct Ceremony<Kp: KeyPairSchema> {
+    label: String,
+    participants: Vec<PublicKey>,
+    contributions: Vec<Option<Zeroizing<Vec<u8>>>>,
+    _schema: PhantomData<Kp>,
+}
+
+impl<Kp: KeyPairSchema> Ceremony<Kp> {
+    /// Start a ceremony.
+    ///
+    /// # Inputs
+    ///
+    /// * `label`: A description of the generated key, e.g. `treasury 2021`.
+    ///
+    /// * `participants`: The public keys identifying the participants, in contribution order.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is a `Ceremony` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn new<S: Into<String>>(label: S, participants: Vec<PublicKey>) -> Result<Self> {
+        ensure!(!participants.is_empty(), "ceremony has no participants.");
+        for (i, participant) in participants.iter().enumerate() {
+            ensure!(
+                !participants[..i].contains(participant),
+                "duplicated ceremony participant {:x}.",
+                participant
+            );
+        }
+
+        Ok(Self {
+            label: label.into(),
+            contributions: vec![None; participants.len()],
+            participants,
+            _schema: PhantomData,
+        })
+    }
+
+    /// Record the entropy contribution of a participant.
+    ///
+    /// # Inputs
+    ///
+    /// * `participant`: The participant public key.
+    ///
+    /// * `entropy`: At least `CEREMONY_MIN_ENTROPY` bytes of secret randomness.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the contribution commitment `H256` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn contribute(&mut self, participant: &PublicKey, entropy: &[u8]) -> Result<H256> {
+        ensure!(
+            entropy.len() >= CEREMONY_MIN_ENTROPY,
+            "entropy contribution is shorter than {} bytes.",
+            CEREMONY_MIN_ENTROPY
+        );
+
+        let index = self.index_of(participant)?;
+        ensure!(
+            self.contributions[index].is_none(),
+            "participant {:x} already contributed.",
+            participant
+        );
+
+        self.contributions[index] = Some(Zeroizing::new(entropy.to_vec()));
+        Ok(commitment(entropy))
+    }
+
+    /// Whether every participant contributed.
+    ///
+    pub fn is_complete(&self) -> bool {
+        self.contributions.iter().all(Option::is_some)
+    }
+
+    /// Derive the ceremony keypair and its unsigned transcript.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the ceremony `Keypair` and `CeremonyTranscript` or whose
+    /// error value is an `failure::Error` describing the error that occurred.
+    pub fn finalize(self) -> Result<(Keypair<Kp>, CeremonyTranscript)> {
+        let contributions = self
+            .contributions
+            .iter()
+            .zip(&self.participants)
+            .map(|(contribution, participant)| {
+                contribution
+                    .as_ref()
+                    .ok_or_else(|| anyhow!("participant {:x} did not contribute.", participant))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let keypair = derive_keypair::<Kp, _>(contributions.iter().map(|c| c.as_slice()));
+
+        let transcript = CeremonyTranscript {
+            label: self.label.clone(),
+            schema: Kp::SCHEMA_NAME.to_string(),
+            participants: self.participants.clone(),
+            commitments: contributions.iter().map(|c| commitment(c)).collect(),
+            public_key: keypair.public_key(),
+            signatures: vec![],
+        };
+        Ok((keypair, transcript))
+    }
+
+    // internal functions.
+    fn index_of(&self, participant: &PublicKey) -> Result<usize> {
+        self.participants
+            .iter()
+            .position(|p| p == participant)
+            .ok_or_else(|| anyhow!("{:x} is not a ceremony participant.", participant))
+    }
+}
+
+/// The auditable record of a `Ceremony`.
+///
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct CeremonyTranscript {
+    pub label: String,
+    pub schema: String,
+    pub participants: Vec<PublicKey>,
+    pub commitments: Vec<H256>,
+    pub public_key: PublicKey,
+    pub signatures: Vec<(PublicKey, Signature)>,
+}
+
+impl CeremonyTranscript {
+    /// The transcript hash signed by the participants, signatures excluded.
+    ///
+    pub fn hash(&self) -> H256 {
+        let mut h = Sha3_256::new();
+        h.update(CEREMONY_TRANSCRIPT_DOMAIN);
+        h.update((self.label.len() as u32).to_le_bytes());
+        h.update(self.label.as_bytes());
+        h.update((self.schema.len() as u32).to_le_bytes());
+        h.update(self.schema.as_bytes());
+        h.update((self.participants.len() as u32).to_le_bytes());
+        for (participant, commitment) in self.participants.iter().zip(&self.commitments) {
+            h.update(participant.as_bytes());
+            h.update(commitment.as_bytes());
+        }
+        h.update(self.public_key.as_bytes());
+        H256::from_slice(h.finalize().as_slice())
+    }
+
+    /// Sign the transcript with a participant `Keypair` and record the signature.
+    ///
+    /// # Inputs
+    ///
+    /// * `kp`: The participant `Keypair`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the recorded `Signature` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn sign<Kp: KeyPairSchema>(&mut self, kp: &Keypair<Kp>) -> Result<Signature> {
+        let public_key = kp.public_key();
+        ensure!(
+            self.participants.contains(&public_key),
+            "{:x} is not a ceremony participant.",
+            public_key
+        );
+        ensure!(
+            !self.signatures.iter().any(|(pk, _)| *pk == public_key),
+            "participant {:x} already signed.",
+            public_key
+        );
+
+        let signature = kp.try_sign(self.hash().as_bytes())?;
+        self.signatures.push((public_key, signature));
+        Ok(signature)
+    }
+
+    /// Verify the transcript is consistent and signed by every participant.
+    ///
+    /// The participant signatures are verified with the `Kp` schema.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok` if every participant signed the transcript hash.
+    pub fn verify<Kp: KeyPairSchema>(&self) -> Result<()> {
+        verify_transcript::<Kp>(self).into_result()
+    }
+
+    /// Check a revealed entropy contribution against the commitment of a participant.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok` if `entropy` is the contribution committed by `participant`.
+    pub fn verify_contribution(&self, participant: &PublicKey, entropy: &[u8]) -> Result<()> {
+        let index = self
+            .participants
+            .iter()
+            .position(|p| p == participant)
+            .ok_or_else(|| anyhow!("{:x} is not a ceremony participant.", participant))?;
+        ensure!(
+            self.commitments.get(index) == Some(&commitment(entropy)),
+            "entropy does not match the commitment of {:x}.",
+            participant
+        );
+        Ok(())
+    }
+}
+
+/// A failed check of `verify_transcript`, located by participant index (in transcript order)
+/// or by position in the transcript signatures.
+///
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate", tag = "kind", rename_all = "camelCase")
+)]
+pub enum TranscriptFailure {
+    /// The transcript does not have one commitment per participant.
+    CommitmentCount { participants: usize, commitments: usize },
+    /// The participant is listed twice, the first time at `first`.
+    DuplicateParticipant { index: usize, first: usize, participant: PublicKey },
+    /// The commitment repeats the one of `first`, the participants reused a contribution.
+    DuplicateCommitment { index: usize, first: usize },
+    /// The participant did not sign the transcript.
+    MissingSignature { index: usize, participant: PublicKey },
+    /// The signature of the participant is not valid for the transcript hash.
+    InvalidSignature { index: usize, participant: PublicKey },
+    /// The signer is not a participant.
+    UnknownSigner { position: usize, signer: PublicKey },
+    /// The signer already signed at `first`.
+    DuplicateSignature { position: usize, first: usize, signer: PublicKey },
+    /// The ceremony key is checked with another schema than the transcript one.
+    SchemaMismatch { transcript: String, checked: String },
+    /// The number of revealed contributions is not the number of participants.
+    ContributionCount { participants: usize, contributions: usize },
+    /// The revealed contribution is shorter than `CEREMONY_MIN_ENTROPY`.
+    ShortContribution { index: usize, participant: PublicKey },
+    /// The revealed contribution does not match the participant commitment.
+    CommitmentMismatch { index: usize, participant: PublicKey },
+    /// The revealed contributions derive another key than the transcript one.
+    PublicKeyMismatch { transcript: PublicKey, derived: PublicKey },
+}
+
+/// The outcome of `verify_transcript`.
+///
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate", rename_all = "camelCase")
+)]
+pub struct TranscriptReport {
+    /// The hash signed by the participants.
+    pub transcript_hash: H256,
+    pub participants: usize,
+    /// The number of participants whose signature is valid.
+    pub valid_signatures: usize,
+    /// Whether the revealed contributions and the derived key were checked.
+    pub key_checked: bool,
+    pub failures: Vec<TranscriptFailure>,
+}
+
+impl TranscriptReport {
+    /// Whether every check passed.
+    ///
+    pub fn is_valid(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    /// The report as a `Result`, whose error describes the first failure.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok` if every check passed.
+    pub fn into_result(self) -> Result<()> {
+        match self.failures.first() {
+            None => Ok(()),
+            Some(failure) => Err(anyhow!("{}.", failure)),
+        }
+    }
+}
+
+impl fmt::Display for TranscriptFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TranscriptFailure::CommitmentCount {
+                participants,
+                commitments,
+            } => write!(
+                f,
+                "transcript has {} participants but {} commitments",
+                participants, commitments
+            ),
+            TranscriptFailure::DuplicateParticipant {
+                index,
+                first,
+                participant,
+            } => write!(
+                f,
+                "participant #{} {:x} duplicates participant #{}",
+                index, participant, first
+            ),
+            TranscriptFailure::DuplicateCommitment { index, first } => write!(
+                f,
+                "commitment of participant #{} duplicates the one of participant #{}",
+                index, first
+            ),
+            TranscriptFailure::MissingSignature { index, participant } => {
+                write!(f, "participant #{} {:x} did not sign", index, participant)
+            }
+            TranscriptFailure::InvalidSignature { index, participant } => write!(
+                f,
+                "signature of participant #{} {:x} is not valid",
+                index, participant
+            ),
+            TranscriptFailure::UnknownSigner { position, signer } => write!(
+                f,
+                "signature #{} is from {:x}, not a participant",
+                position, signer
+            ),
+            TranscriptFailure::DuplicateSignature {
+                position,
+                first,
+                signer,
+            } => write!(
+                f,
+                "signature #{} of {:x} duplicates signature #{}",
+                position, signer, first
+            ),
+            TranscriptFailure::SchemaMismatch {
+                transcript,
+                checked,
+            } => write!(
+                f,
+                "transcript key schema is {} but {} was checked",
+                transcript, checked
+            ),
+            TranscriptFailure::ContributionCount {
+                participants,
+                contributions,
+            } => write!(
+                f,
+                "{} contributions revealed for {} participants",
+                contributions, participants
+            ),
+            TranscriptFailure::ShortContribution { index, participant } => write!(
+                f,
+                "contribution of participant #{} {:x} is shorter than {} bytes",
+                index, participant, CEREMONY_MIN_ENTROPY
+            ),
+            TranscriptFailure::CommitmentMismatch { index, participant } => write!(
+                f,
+                "contribution of participant #{} {:x} does not match its commitment",
+                index, participant
+            ),
+            TranscriptFailure::PublicKeyMismatch {
+                transcript,
+                derived,
+            } => write!(
+                f,
+                "contributions derive {:x}, not the transcript key {:x}",
+                derived, transcript
+            ),
+        }
+    }
+}
+
+impl fmt::Display for TranscriptReport {
+    /// A human readable summary, one failure per line.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "transcript {:x}: {}/{} valid signatures, key {}, {}",
+            self.transcript_hash,
+            self.valid_signatures,
+            self.participants,
+            if self.key_checked { "checked" } else { "not checked" },
+            if self.is_valid() { "valid" } else { "invalid" }
+        )?;
+        for failure in &self.failures {
+            write!(f, "\n- {}", failure)?;
+        }
+        Ok(())
+    }
+}
+
+/// Audit a transcript: one commitment per participant, no duplicated participant, commitment
+/// or signature, and a valid signature of the transcript hash by every participant.
+///
+/// # Inputs
+///
+/// * `transcript`: The `CeremonyTranscript` to audit, signatures are verified with `Kp`.
+///
+/// # Returns
+///
+/// The `TranscriptReport` of every failed check.
+pub fn verify_transcript<Kp: KeyPairSchema>(transcript: &CeremonyTranscript) -> TranscriptReport {
+    let mut failures = vec![];
+
+    if transcript.participants.len() != transcript.commitments.len() {
+        failures.push(TranscriptFailure::CommitmentCount {
+            participants: transcript.participants.len(),
+            commitments: transcript.commitments.len(),
+        });
+    }
+    for (index, participant) in transcript.participants.iter().enumerate() {
+        if let Some(first) = first_index(&transcript.participants[..index], participant) {
+            failures.push(TranscriptFailure::DuplicateParticipant {
+                index,
+                first,
+                participant: *participant,
+            });
+        }
+    }
+    for (index, commitment) in transcript.commitments.iter().enumerate() {
+        if let Some(first) = first_index(&transcript.commitments[..index], commitment) {
+            failures.push(TranscriptFailure::DuplicateCommitment { index, first });
+        }
+    }
+
+    for (position, (signer, _)) in transcript.signatures.iter().enumerate() {
+        let previous = transcript.signatures[..position]
+            .iter()
+            .position(|(pk, _)| pk == signer);
+        if let Some(first) = previous {
+            failures.push(TranscriptFailure::DuplicateSignature {
+                position,
+                first,
+                signer: *signer,
+            });
+        } else if !transcript.participants.contains(signer) {
+            failures.push(TranscriptFailure::UnknownSigner {
+                position,
+                signer: *signer,
+            });
+        }
+    }
+
+    let transcript_hash = transcript.hash();
+    let mut valid_signatures = 0;
+    for (index, participant) in transcript.participants.iter().enumerate() {
+        let signature = transcript
+            .signatures
+            .iter()
+            .find(|(pk, _)| pk == participant)
+            .map(|(_, signature)| *signature);
+        let failure = match signature {
+            None => TranscriptFailure::MissingSignature {
+                index,
+                participant: *participant,
+            },
+            Some(signature) => {
+                let verified = Kp::from_null_private_key(*participant)
+                    .verify(transcript_hash.as_bytes(), signature);
+                if verified.is_ok() {
+                    valid_signatures += 1;
+                    continue;
+                }
+                TranscriptFailure::InvalidSignature {
+                    index,
+                    participant: *participant,
+                }
+            }
+        };
+        failures.push(failure);
+    }
+
+    TranscriptReport {
+        transcript_hash,
+        participants: transcript.participants.len(),
+        valid_signatures,
+        key_checked: false,
+        failures,
+    }
+}
+
+/// Audit a transcript as `verify_transcript`, then check the revealed contributions against
+/// the commitments and the key they derive against the transcript public key.
+///
+/// # Inputs
+///
+/// * `transcript`: The `CeremonyTranscript` to audit, signatures are verified with `Kp`.
+///
+/// * `contributions`: The entropy revealed by every participant, in transcript order.
+///
+/// # Returns
+///
+/// The `TranscriptReport` of every failed check, the derived key is checked with `Ck`, the
+/// schema of the ceremony key.
+pub fn verify_transcript_with_contributions<Kp: KeyPairSchema, Ck: KeyPairSchema>(
+    transcript: &CeremonyTranscript,
+    contributions: &[&[u8]],
+) -> TranscriptReport {
+    let mut report = verify_transcript::<Kp>(transcript);
+    report.key_checked = true;
+    let failures = &mut report.failures;
+
+    if transcript.schema != Ck::SCHEMA_NAME {
+        failures.push(TranscriptFailure::SchemaMismatch {
+            transcript: transcript.schema.clone(),
+            checked: Ck::SCHEMA_NAME.to_string(),
+        });
+    }
+    if contributions.len() != transcript.participants.len() {
+        failures.push(TranscriptFailure::ContributionCount {
+            participants: transcript.participants.len(),
+            contributions: contributions.len(),
+        });
+        return report;
+    }
+
+    for (index, (participant, contribution)) in
+        transcript.participants.iter().zip(contributions).enumerate()
+    {
+        if contribution.len() < CEREMONY_MIN_ENTROPY {
+            failures.push(TranscriptFailure::ShortContribution {
+                index,
+                participant: *participant,
+            });
+        }
+        if transcript.commitments.get(index) != Some(&commitment(contribution)) {
+            failures.push(TranscriptFailure::CommitmentMismatch {
+                index,
+                participant: *participant,
+            });
+        }
+    }
+
+    let derived = derive_keypair::<Ck, _>(contributions.iter().copied()).public_key();
+    if derived != transcript.public_key {
+        failures.push(TranscriptFailure::PublicKeyMismatch {
+            transcript: transcript.public_key,
+            derived,
+        });
+    }
+    report
+}
+
+// internal functions.
+fn commitment(entropy: &[u8]) -> H256 {
+    H256::from_slice(Sha3_256::digest(entropy).as_slice())
+}
+
+fn derive_keypair<'a, Kp, I>(contributions: I) -> Keypair<Kp>
+where
+    Kp: KeyPairSchema,
+    I: IntoIterator<Item = &'a [u8]>,
+{
+    let mut h = Sha3_256::new();
+    h.update(CEREMONY_KEY_DOMAIN);
+    for contribution in contributions {
+        h.update(contribution);
+    }
+    let mut seed = h.finalize();
+    let keypair = Keypair::<Kp>::from_private_key(PrivateKey::from_slice(seed.as_slice()));
+    seed.as_mut_slice().zeroize();
+    keypair
+}
+
+fn first_index<T: PartialEq>(items: &[T], item: &T) -> Option<usize> {
+    items.iter().position(|i| i == item)
+}
@@ -29,3 +29,15 @@ pub const SIGNATURE_LENGTH: usize = 64;
 
 /// The length of an `Keypair` in bytes.
 pub const KEYPAIR_LENGTH: usize = KEY_BYTES_SIZE + KEY_BYTES_SIZE;
+
+/// The size a Symbol encrypted message adds to its plaintext: tag and iv.
+pub const SYM_ENCRYPT_OVERHEAD: usize = AES_TAG_LENGTH + SYM_AES_IV_LENGTH;
+
+/// The maximum size a Nis1 encrypted message adds to its plaintext: salt, iv and padding.
+pub const NIS_ENCRYPT_MAX_OVERHEAD: usize = NIS_SALT_LENGTH + NIS_AES_IV_LENGTH + 16;
+
+/// The maximum size of a transfer message plaintext, the catapult `maxMessageSize` default.
+pub const MAX_MESSAGE_SIZE: usize = 1024;
+
+/// The maximum length of an English mnemonic: 24 words of at most 8 letters and their spaces.
+pub const MNEMONIC_MAX_STR_SIZE: usize = 24 * 8 + 23;
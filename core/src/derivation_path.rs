@@ -0,0 +1,187 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::{anyhow, ensure, Result};
+#[cfg(feature = "serde")]
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
+
+/// The offset of the hardened child indexes.
+pub const HARDENED_OFFSET: u32 = 0x8000_0000;
+
+/// The SLIP-44 coin type of Symbol.
+pub const SYMBOL_COIN_TYPE: u32 = 4343;
+
+/// The SLIP-44 coin type of Nis1.
+pub const NIS1_COIN_TYPE: u32 = 43;
+
+/// The maximum depth of a derivation path.
+pub const DERIVATION_PATH_MAX_DEPTH: usize = 255;
+
+/// A hardened only (SLIP-10 ed25519) derivation path, e.g. `m/44'/4343'/0'/0'/0'`.
+///
+/// The components are stored with the `HARDENED_OFFSET` bit set. Parsing accepts `'`, `h` and
+/// `H` as hardened markers and rejects non hardened components, which ed25519 cannot derive.
+///
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct DerivationPath(Vec<u32>);
+
+impl DerivationPath {
+    /// Construct a `DerivationPath` from hardened child indexes.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is a `DerivationPath` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn new(components: Vec<u32>) -> Result<Self> {
+        ensure!(
+            components.len() <= DERIVATION_PATH_MAX_DEPTH,
+            "derivation path is deeper than {}.",
+            DERIVATION_PATH_MAX_DEPTH
+        );
+        for &component in components.iter() {
+            ensure!(
+                component >= HARDENED_OFFSET,
+                "derivation path component {} is not hardened.",
+                component
+            );
+        }
+        Ok(Self(components))
+    }
+
+    /// The BIP-44 path of a Symbol account, `m/44'/4343'/{account}'/0'/0'`.
+    ///
+    pub fn symbol_account(account: u32) -> Result<Self> {
+        Self::bip44(SYMBOL_COIN_TYPE, account)
+    }
+
+    /// The BIP-44 path of a Nis1 account, `m/44'/43'/{account}'/0'/0'`.
+    ///
+    pub fn nis1_account(account: u32) -> Result<Self> {
+        Self::bip44(NIS1_COIN_TYPE, account)
+    }
+
+    /// The path of the hardened child `index` of this path.
+    ///
+    pub fn child(&self, index: u32) -> Result<Self> {
+        let mut components = self.0.clone();
+        components.push(harden(index)?);
+        Self::new(components)
+    }
+
+    /// The child indexes, with the `HARDENED_OFFSET` bit set.
+    ///
+    pub fn components(&self) -> &[u32] {
+        &self.0
+    }
+
+    /// Iterate over the child indexes, with the `HARDENED_OFFSET` bit set.
+    ///
+    pub fn iter(&self) -> std::slice::Iter<'_, u32> {
+        self.0.iter()
+    }
+
+    pub fn depth(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_master(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    // internal functions.
+    fn bip44(coin_type: u32, account: u32) -> Result<Self> {
+        Self::new(vec![
+            harden(44)?,
+            harden(coin_type)?,
+            harden(account)?,
+            HARDENED_OFFSET,
+            HARDENED_OFFSET,
+        ])
+    }
+}
+
+impl<'a> IntoIterator for &'a DerivationPath {
+    type Item = &'a u32;
+    type IntoIter = std::slice::Iter<'a, u32>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl fmt::Display for DerivationPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "m")?;
+        for component in self.0.iter() {
+            write!(f, "/{}'", component - HARDENED_OFFSET)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for DerivationPath {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut parts = s.split('/');
+        ensure!(
+            parts.next() == Some("m"),
+            "derivation path must start with m."
+        );
+
+        let components = parts
+            .map(|part| {
+                let index = part
+                    .strip_suffix(|c| c == '\'' || c == 'h' || c == 'H')
+                    .ok_or_else(|| {
+                        anyhow!("derivation path component {} is not hardened.", part)
+                    })?;
+                ensure!(
+                    !index.is_empty() && index.bytes().all(|b| b.is_ascii_digit()),
+                    "invalid derivation path component {}.",
+                    part
+                );
+                harden(index.parse()?)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Self::new(components)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for DerivationPath {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'d> Deserialize<'d> for DerivationPath {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'d>,
+    {
+        let path = String::deserialize(deserializer)?;
+        DerivationPath::from_str(&path).map_err(D::Error::custom)
+    }
+}
+
+fn harden(index: u32) -> Result<u32> {
+    ensure!(
+        index < HARDENED_OFFSET,
+        "derivation path index {} is too large.",
+        index
+    );
+    Ok(index | HARDENED_OFFSET)
+}
@@ -0,0 +1,157 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! W3C [did:key](https://w3c-ccg.github.io/did-method-key/) method for ed25519 public keys.
+//!
+//! A did:key is `did:key:` followed by the multibase (base58btc, `z` prefix) encoding of the
+//! `ed25519-pub` multicodec (`0xed 0x01`) and the 32 public key bytes.
+//!
+//! Symbol public keys are plain Ed25519 keys and can be used directly with
+//! `Ed25519VerificationKey2020` verifiers. Nis1 public keys use the same encoding, but Nis1
+//! signatures are produced with Keccak-512 and can only be verified with the Nis1 schema.
+//!
+
+use anyhow::{anyhow, ensure, Result};
+use serde::{Deserialize, Serialize};
+
+use super::{PublicKey, KEY_BYTES_SIZE};
+
+/// The did:key method prefix.
+pub const DID_KEY_PREFIX: &str = "did:key:";
+
+/// The multicodec prefix of an ed25519 public key.
+pub const ED25519_MULTICODEC: [u8; 2] = [0xed, 0x01];
+
+const MULTIBASE_BASE58BTC: char = 'z';
+const DID_CONTEXT: &str = "https://www.w3.org/ns/did/v1";
+const ED25519_2020_CONTEXT: &str = "https://w3id.org/security/suites/ed25519-2020/v1";
+const ED25519_2020_TYPE: &str = "Ed25519VerificationKey2020";
+
+/// A verification method of a DID document.
+///
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(crate = "serde_crate", rename_all = "camelCase")]
+pub struct VerificationMethod {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub type_: String,
+    pub controller: String,
+    pub public_key_multibase: String,
+}
+
+/// A basic DID document for a did:key.
+///
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(crate = "serde_crate", rename_all = "camelCase")]
+pub struct DidDocument {
+    #[serde(rename = "@context")]
+    pub context: Vec<String>,
+    pub id: String,
+    pub verification_method: Vec<VerificationMethod>,
+    pub authentication: Vec<String>,
+    pub assertion_method: Vec<String>,
+    pub capability_invocation: Vec<String>,
+    pub capability_delegation: Vec<String>,
+}
+
+/// Encode a public key as a multibase `ed25519-pub` value, e.g. `z6Mk…`.
+///
+pub fn to_public_key_multibase(public_key: &PublicKey) -> String {
+    let mut bytes = Vec::with_capacity(ED25519_MULTICODEC.len() + KEY_BYTES_SIZE);
+    bytes.extend_from_slice(&ED25519_MULTICODEC);
+    bytes.extend_from_slice(public_key.as_bytes());
+
+    format!(
+        "{}{}",
+        MULTIBASE_BASE58BTC,
+        bs58::encode(bytes).into_string()
+    )
+}
+
+/// Decode a multibase `ed25519-pub` value into a public key.
+///
+pub fn from_public_key_multibase(multibase: &str) -> Result<PublicKey> {
+    let encoded = multibase
+        .strip_prefix(MULTIBASE_BASE58BTC)
+        .ok_or_else(|| anyhow!("multibase value is not base58btc."))?;
+
+    let bytes = bs58::decode(encoded).into_vec()?;
+    ensure!(
+        bytes.len() == ED25519_MULTICODEC.len() + KEY_BYTES_SIZE,
+        format!("multibase value has unexpected size {}", bytes.len())
+    );
+    ensure!(
+        bytes[..ED25519_MULTICODEC.len()] == ED25519_MULTICODEC,
+        "multibase value is not an ed25519 public key."
+    );
+
+    Ok(PublicKey::from_slice(&bytes[ED25519_MULTICODEC.len()..]))
+}
+
+/// Encode a public key as a did:key.
+///
+/// # Example
+///
+/// ```
+/// use std::str::FromStr;
+/// use core_crypto::{did, PublicKey};
+///
+/// let pk = PublicKey::from_str("2E834140FD66CF87B254A693A2C7862C819217B676D3943267156625E816EC6F").unwrap();
+///
+/// let did = did::to_did_key(&pk);
+/// assert!(did.starts_with("did:key:z6Mk"));
+/// assert_eq!(did::from_did_key(&did).unwrap(), pk);
+/// ```
+pub fn to_did_key(public_key: &PublicKey) -> String {
+    format!("{}{}", DID_KEY_PREFIX, to_public_key_multibase(public_key))
+}
+
+/// Decode a did:key, or a did:key verification method id, into a public key.
+///
+pub fn from_did_key(did: &str) -> Result<PublicKey> {
+    let method_specific_id = did
+        .strip_prefix(DID_KEY_PREFIX)
+        .ok_or_else(|| anyhow!("did is not a did:key."))?;
+
+    let (multibase, fragment) = match method_specific_id.split_once('#') {
+        Some((multibase, fragment)) => (multibase, Some(fragment)),
+        None => (method_specific_id, None),
+    };
+
+    let public_key = from_public_key_multibase(multibase)?;
+    if let Some(fragment) = fragment {
+        ensure!(
+            fragment == multibase,
+            "did:key fragment does not match the key."
+        );
+    }
+    Ok(public_key)
+}
+
+/// Create the DID document of a did:key.
+///
+pub fn did_document(public_key: &PublicKey) -> DidDocument {
+    let multibase = to_public_key_multibase(public_key);
+    let did = format!("{}{}", DID_KEY_PREFIX, multibase);
+    let method_id = format!("{}#{}", did, multibase);
+
+    DidDocument {
+        context: vec![DID_CONTEXT.to_string(), ED25519_2020_CONTEXT.to_string()],
+        id: did.clone(),
+        verification_method: vec![VerificationMethod {
+            id: method_id.clone(),
+            type_: ED25519_2020_TYPE.to_string(),
+            controller: did,
+            public_key_multibase: multibase,
+        }],
+        authentication: vec![method_id.clone()],
+        assertion_method: vec![method_id.clone()],
+        capability_invocation: vec![method_id.clone()],
+        capability_delegation: vec![method_id],
+    }
+}
@@ -0,0 +1,125 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Private keys sealed by a trusted execution environment.
+//!
+//! A `Sealer` wraps the platform sealing of the enclave the crate runs in (e.g. SGX
+//! `sgx_seal_data` with the MRENCLAVE or MRSIGNER policy): only that enclave can unseal what it
+//! sealed. A `SealedKeypair` keeps the sealed private key with its public key, and is safe to
+//! store outside the enclave; its private key is only unsealed, inside the enclave, for the time
+//! of a signature.
+//!
+//! The sealed data is bound to `ENCLAVE_SEAL_TAG || schema || 0x00 || public key` as additional
+//! authenticated data, so a sealed key cannot be unsealed for another schema or public key.
+//!
+
+use std::fmt;
+
+use anyhow::{ensure, Result};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
+
+use super::{KeyPairSchema, Keypair, PrivateKey, PublicKey, Signature, KEY_BYTES_SIZE};
+
+/// The prefix of the additional authenticated data of every sealed key.
+pub const ENCLAVE_SEAL_TAG: &[u8] = b"symbol-crypto-core/enclave/v1\0";
+
+/// The platform sealing of a trusted execution environment.
+///
+pub trait Sealer {
+    /// Seal `plaintext`, authenticating `aad` with it.
+    ///
+    fn seal(&self, plaintext: &[u8], aad: &[u8]) -> Result<Vec<u8>>;
+
+    /// Unseal data sealed with the same `aad`.
+    ///
+    fn unseal(&self, sealed: &[u8], aad: &[u8]) -> Result<Zeroizing<Vec<u8>>>;
+}
+
+/// A private key sealed by a `Sealer`, with its public key.
+///
+#[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct SealedKeypair {
+    pub public_key: PublicKey,
+    pub sealed: Vec<u8>,
+}
+
+impl SealedKeypair {
+    /// Seal the private key of a `Keypair`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the `SealedKeypair` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn seal<Kp: KeyPairSchema, S: Sealer>(sealer: &S, kp: &Keypair<Kp>) -> Result<Self> {
+        let public_key = kp.public_key();
+        let private_key = Zeroizing::new(kp.private_key().to_fixed_bytes());
+        Ok(Self {
+            public_key,
+            sealed: sealer.seal(&private_key[..], &seal_aad::<Kp>(&public_key))?,
+        })
+    }
+
+    /// Unseal the `Keypair`, inside the enclave.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the `Keypair` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn unseal<Kp: KeyPairSchema, S: Sealer>(&self, sealer: &S) -> Result<Keypair<Kp>> {
+        let private_key = sealer.unseal(&self.sealed, &seal_aad::<Kp>(&self.public_key))?;
+        ensure!(
+            private_key.len() == KEY_BYTES_SIZE,
+            "sealed private key has unexpected size {}.",
+            private_key.len()
+        );
+
+        let kp = Keypair::<Kp>::from_private_key(PrivateKey::from_slice(&private_key));
+        ensure!(
+            kp.public_key() == self.public_key,
+            "sealed private key does not match its public key."
+        );
+        Ok(kp)
+    }
+
+    /// Sign `data`, the private key being unsealed for the time of the signature only.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the `Signature` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn sign<Kp: KeyPairSchema, S: Sealer>(&self, sealer: &S, data: &[u8]) -> Result<Signature> {
+        self.unseal::<Kp, S>(sealer)?.try_sign(data)
+    }
+}
+
+impl fmt::Debug for SealedKeypair {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SealedKeypair")
+            .field("public_key", &self.public_key)
+            .field("sealed", &format_args!("[{} bytes]", self.sealed.len()))
+            .finish()
+    }
+}
+
+// internal functions.
+fn seal_aad<Kp: KeyPairSchema>(public_key: &PublicKey) -> Vec<u8> {
+    [
+        ENCLAVE_SEAL_TAG,
+        Kp::SCHEMA_NAME.as_bytes(),
+        &[0],
+        public_key.as_bytes(),
+    ]
+    .concat()
+}
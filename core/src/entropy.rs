@@ -0,0 +1,212 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The process wide source of the random bytes of the crate.
+//!
+//! Keypair generation, mnemonic generation, the salts, ivs and nonces of the ciphers and the
+//! `random()` of the key, signature and hash types all draw from `try_fill_random`, which reads
+//! the source installed with `set_entropy_source`, or the thread local `rand` generator when
+//! none is. An application can install e.g. a DRBG seeded from a hardware TRNG with
+//! `ExternalEntropy`, or, with the `test-util` feature, a `SeededEntropy` for reproducible
+//! integration tests.
+//!
+//! The `try_` functions return the failure of the source; the others panic on it, so no key
+//! material is ever built from a partial fill.
+//!
+
+#[cfg(feature = "test-util")]
+use std::sync::Mutex;
+use std::sync::{Arc, RwLock};
+
+use anyhow::{anyhow, Result};
+use rand::rngs::OsRng;
+use rand::RngCore;
+#[cfg(feature = "test-util")]
+use rand::{rngs::StdRng, SeedableRng};
+
+use super::{PrivateKey, PublicKey, Signature, H256, H512};
+
+static ENTROPY_SOURCE: RwLock<Option<Arc<dyn EntropySource>>> = RwLock::new(None);
+
+/// A source of cryptographically secure random bytes.
+///
+pub trait EntropySource: Send + Sync {
+    /// Fill `dest` with random bytes.
+    ///
+    fn fill_bytes(&self, dest: &mut [u8]) -> Result<()>;
+}
+
+/// The operating system random generator.
+///
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OsEntropy;
+
+impl EntropySource for OsEntropy {
+    fn fill_bytes(&self, dest: &mut [u8]) -> Result<()> {
+        OsRng.try_fill_bytes(dest).map_err(|e| anyhow!("{}", e))
+    }
+}
+
+/// A deterministic generator from a seed, for reproducible tests only.
+///
+/// Every key generated while it is installed is known to whoever knows the seed, it is only
+/// built with the `test-util` feature.
+///
+#[cfg(feature = "test-util")]
+pub struct SeededEntropy {
+    rng: Mutex<StdRng>,
+}
+
+#[cfg(feature = "test-util")]
+impl SeededEntropy {
+    pub fn new(seed: [u8; 32]) -> Self {
+        Self {
+            rng: Mutex::new(StdRng::from_seed(seed)),
+        }
+    }
+}
+
+#[cfg(feature = "test-util")]
+impl EntropySource for SeededEntropy {
+    fn fill_bytes(&self, dest: &mut [u8]) -> Result<()> {
+        let mut rng = self.rng.lock().unwrap_or_else(|e| e.into_inner());
+        rng.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+/// An external generator, e.g. an HSM, behind a fill function.
+///
+pub struct ExternalEntropy<F> {
+    fill: F,
+}
+
+impl<F> ExternalEntropy<F>
+where
+    F: Fn(&mut [u8]) -> Result<()> + Send + Sync,
+{
+    pub fn new(fill: F) -> Self {
+        Self { fill }
+    }
+}
+
+impl<F> EntropySource for ExternalEntropy<F>
+where
+    F: Fn(&mut [u8]) -> Result<()> + Send + Sync,
+{
+    fn fill_bytes(&self, dest: &mut [u8]) -> Result<()> {
+        (self.fill)(dest)
+    }
+}
+
+/// Install the entropy source of the process, replacing the previous one.
+///
+pub fn set_entropy_source(source: Arc<dyn EntropySource>) {
+    *ENTROPY_SOURCE.write().unwrap_or_else(|e| e.into_inner()) = Some(source);
+}
+
+/// Restore the default thread local `rand` generator.
+///
+pub fn reset_entropy_source() {
+    *ENTROPY_SOURCE.write().unwrap_or_else(|e| e.into_inner()) = None;
+}
+
+/// Fill `dest` from the installed entropy source.
+///
+/// # Returns
+///
+/// A `Result` whose okay value is `()` or whose error value is an `failure::Error`
+/// describing the failure of the source, `dest` is then zeroed.
+pub fn try_fill_random(dest: &mut [u8]) -> Result<()> {
+    let source = ENTROPY_SOURCE
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone();
+
+    let filled = match source {
+        Some(source) => source.fill_bytes(dest),
+        None => rand::thread_rng()
+            .try_fill_bytes(dest)
+            .map_err(|e| anyhow!("{}", e)),
+    };
+    filled.map_err(|e| {
+        dest.iter_mut().for_each(|b| *b = 0);
+        anyhow!("entropy source failure: {}", e)
+    })
+}
+
+/// Fill `dest` from the installed entropy source.
+///
+/// # Panics
+///
+/// If the installed source fails, see `try_fill_random`.
+pub fn fill_random(dest: &mut [u8]) {
+    try_fill_random(dest).unwrap_or_else(|e| panic!("{}", e))
+}
+
+/// Random bytes from the installed `EntropySource`, see `set_entropy_source`.
+///
+/// # Panics
+///
+/// If the installed source fails, see `try_random_bytes`.
+pub fn random_bytes<const COUNT: usize>() -> [u8; COUNT] {
+    let mut buf = [0u8; COUNT];
+    fill_random(&mut buf);
+    buf
+}
+
+/// Random bytes from the installed `EntropySource`.
+///
+/// # Returns
+///
+/// A `Result` whose okay value is the random bytes or whose error value
+/// is an `failure::Error` describing the failure of the source.
+pub fn try_random_bytes<const COUNT: usize>() -> Result<[u8; COUNT]> {
+    let mut buf = [0u8; COUNT];
+    try_fill_random(&mut buf)?;
+    Ok(buf)
+}
+
+macro_rules! impl_random {
+    ($($name:ident),*) => {
+        $(
+            impl $name {
+                /// A random value from the installed `EntropySource`.
+                ///
+                /// # Panics
+                ///
+                /// If the installed source fails, see `try_random`.
+                pub fn random() -> Self {
+                    let mut value = Self::zero();
+                    value.randomize();
+                    value
+                }
+
+                /// A random value from the installed `EntropySource`.
+                ///
+                /// # Returns
+                ///
+                /// A `Result` whose okay value is the random value or whose error value
+                /// is an `failure::Error` describing the failure of the source.
+                pub fn try_random() -> Result<Self> {
+                    let mut value = Self::zero();
+                    try_fill_random(value.as_bytes_mut())?;
+                    Ok(value)
+                }
+
+                /// Overwrite with random bytes from the installed `EntropySource`.
+                ///
+                pub fn randomize(&mut self) {
+                    fill_random(self.as_bytes_mut())
+                }
+            }
+        )*
+    };
+}
+
+impl_random!(PrivateKey, PublicKey, Signature, H256, H512);
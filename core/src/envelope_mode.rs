@@ -0,0 +1,96 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Constant size envelopes, hiding the length of encrypted messages from observers.
+//!
+//! In `EnvelopeMode::ConstantSize`, the message is framed as `length (u32 little endian) ||
+//! message || zero padding` up to the smallest of `ENVELOPE_BUCKETS` that holds it, then
+//! encrypted by the cipher as usual: every message of a bucket gives a ciphertext of the same
+//! size, whatever the cipher. The outer fields (salt, iv, tag) keep the fixed order of each
+//! cipher; the frame is encrypted, so its layout is not observable.
+//!
+//! The mode is not recorded in the ciphertext, both sides must use the same one, see
+//! `BlockCipher::encrypt_for_mode` and `BlockCipher::decrypt_from_mode`.
+//!
+
+use std::convert::TryInto;
+
+use anyhow::{anyhow, ensure, Result};
+use zeroize::Zeroizing;
+
+/// The plaintext sizes of constant size envelopes, multiples of the AES block size.
+pub const ENVELOPE_BUCKETS: [usize; 4] = [64, 256, 1024, 4096];
+
+const FRAME_HEADER_SIZE: usize = 4;
+
+/// How a message is laid out before encryption.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum EnvelopeMode {
+    /// The message as is, the ciphertext size follows the message size.
+    #[default]
+    Standard,
+    /// The message padded to one of `ENVELOPE_BUCKETS`.
+    ConstantSize,
+}
+
+/// The bucket of a message of `msg_len` bytes, `None` if it is too large for every bucket.
+///
+pub fn envelope_bucket(msg_len: usize) -> Option<usize> {
+    ENVELOPE_BUCKETS
+        .iter()
+        .copied()
+        .find(|bucket| msg_len <= bucket - FRAME_HEADER_SIZE)
+}
+
+/// Frame a message to its bucket size.
+///
+/// # Returns
+///
+/// A `Result` whose okay value is the frame or whose error value
+/// is an `failure::Error` describing the error that occurred.
+pub fn pad_to_bucket(msg: &[u8]) -> Result<Zeroizing<Vec<u8>>> {
+    let bucket = envelope_bucket(msg.len()).ok_or_else(|| {
+        anyhow!(
+            "message of {} bytes is too large for a constant size envelope.",
+            msg.len()
+        )
+    })?;
+
+    let mut frame = Zeroizing::new(Vec::with_capacity(bucket));
+    frame.extend_from_slice(&(msg.len() as u32).to_le_bytes());
+    frame.extend_from_slice(msg);
+    frame.resize(bucket, 0);
+    Ok(frame)
+}
+
+/// The message of a frame, see `pad_to_bucket`.
+///
+/// # Returns
+///
+/// A `Result` whose okay value is the message or whose error value
+/// is an `failure::Error` describing the error that occurred.
+pub fn unpad_bucket(frame: &[u8]) -> Result<Vec<u8>> {
+    ensure!(
+        ENVELOPE_BUCKETS.contains(&frame.len()),
+        "constant size envelope has unexpected size {}.",
+        frame.len()
+    );
+
+    let msg_len = u32::from_le_bytes(frame[..FRAME_HEADER_SIZE].try_into()?) as usize;
+    ensure!(
+        envelope_bucket(msg_len) == Some(frame.len()),
+        "constant size envelope length does not match its bucket."
+    );
+    let (msg, padding) = frame[FRAME_HEADER_SIZE..].split_at(msg_len);
+    ensure!(
+        padding.iter().all(|byte| *byte == 0),
+        "constant size envelope padding is not zero."
+    );
+    Ok(msg.to_vec())
+}
@@ -0,0 +1,164 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::Mutex;
+
+use anyhow::{anyhow, ensure, Result};
+
+use super::{KeyPairSchema, Keypair, PublicKey, Signature};
+
+/// The rate limit window, in seconds.
+pub const GUARD_RATE_WINDOW: u64 = 60;
+
+type PayloadPredicate = Box<dyn Fn(&[u8]) -> bool + Send + Sync>;
+type ApprovalHook = Box<dyn Fn(&[u8]) -> Result<()> + Send + Sync>;
+
+/// A `Keypair` that only signs within the policy of a custodial deployment.
+///
+/// Every `sign` runs, in order, the payload predicate, the rate limit and the manual approval
+/// hook (e.g. a second person confirming the payload); the keypair is never exposed, so the
+/// policy cannot be bypassed through this type.
+///
+pub struct GuardedKeypair<Kp: KeyPairSchema> {
+    keypair: Keypair<Kp>,
+    max_signs_per_minute: Option<usize>,
+    payload_predicate: Option<PayloadPredicate>,
+    approval_hook: Option<ApprovalHook>,
+    signed_at: Mutex<VecDeque<u64>>,
+}
+
+impl<Kp: KeyPairSchema> GuardedKeypair<Kp> {
+    /// Guard a `Keypair`, without any policy yet.
+    ///
+    pub fn new(keypair: Keypair<Kp>) -> Self {
+        Self {
+            keypair,
+            max_signs_per_minute: None,
+            payload_predicate: None,
+            approval_hook: None,
+            signed_at: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Allow at most `max` signatures in any `GUARD_RATE_WINDOW` seconds.
+    ///
+    pub fn with_rate_limit(mut self, max: usize) -> Self {
+        self.max_signs_per_minute = Some(max);
+        self
+    }
+
+    /// Only sign the payloads accepted by `predicate`.
+    ///
+    pub fn with_payload_predicate<F>(mut self, predicate: F) -> Self
+    where
+        F: Fn(&[u8]) -> bool + Send + Sync + 'static,
+    {
+        self.payload_predicate = Some(Box::new(predicate));
+        self
+    }
+
+    /// Only sign the payloads approved by `hook`, whose error is the denial reason.
+    ///
+    /// The hook is called last, after the cheaper checks, and may block, e.g. until a second
+    /// operator confirms.
+    ///
+    pub fn with_approval_hook<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&[u8]) -> Result<()> + Send + Sync + 'static,
+    {
+        self.approval_hook = Some(Box::new(hook));
+        self
+    }
+
+    pub fn public_key(&self) -> PublicKey {
+        self.keypair.public_key()
+    }
+
+    /// Signs a data bytes if the policy allows it.
+    ///
+    /// # Inputs
+    ///
+    /// * `data`: an `&[u8]` representing the data to sign.
+    ///
+    /// * `now`: The current unix timestamp (seconds).
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the `Signature` or whose error value
+    /// is an `failure::Error` describing the policy denial.
+    pub fn sign(&self, data: &[u8], now: u64) -> Result<Signature> {
+        if let Some(predicate) = &self.payload_predicate {
+            ensure!(predicate(data), "payload denied by the signing policy.");
+        }
+        self.check_rate(&mut self.lock_signed_at(), now)?;
+
+        if let Some(hook) = &self.approval_hook {
+            hook(data).map_err(|e| anyhow!("signing not approved: {}", e))?;
+        }
+
+        // the approval may take a while, the rate is checked again before signing.
+        let mut signed_at = self.lock_signed_at();
+        self.check_rate(&mut signed_at, now)?;
+        signed_at.push_back(now);
+        self.keypair.try_sign(data)
+    }
+
+    /// Verify a `Signature` on a data with this Keypair public key.
+    ///
+    pub fn verify(&self, data: &[u8], signature: Signature) -> Result<()> {
+        self.keypair.verify(data, signature)
+    }
+
+    /// The number of signatures in the rate limit window ending at `now`.
+    ///
+    pub fn signs_in_window(&self, now: u64) -> usize {
+        let mut signed_at = self.lock_signed_at();
+        prune(&mut signed_at, now);
+        signed_at.len()
+    }
+
+    // internal functions.
+    fn lock_signed_at(&self) -> std::sync::MutexGuard<'_, VecDeque<u64>> {
+        self.signed_at.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn check_rate(&self, signed_at: &mut VecDeque<u64>, now: u64) -> Result<()> {
+        if let Some(max) = self.max_signs_per_minute {
+            prune(signed_at, now);
+            ensure!(
+                signed_at.len() < max,
+                "signing rate limit of {} per {} seconds reached.",
+                max,
+                GUARD_RATE_WINDOW
+            );
+        }
+        Ok(())
+    }
+}
+
+impl<Kp: KeyPairSchema> fmt::Debug for GuardedKeypair<Kp> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GuardedKeypair")
+            .field("public_key", &self.public_key())
+            .field("max_signs_per_minute", &self.max_signs_per_minute)
+            .field("payload_predicate", &self.payload_predicate.is_some())
+            .field("approval_hook", &self.approval_hook.is_some())
+            .finish()
+    }
+}
+
+fn prune(signed_at: &mut VecDeque<u64>, now: u64) {
+    while let Some(&oldest) = signed_at.front() {
+        if oldest + GUARD_RATE_WINDOW > now {
+            break;
+        }
+        signed_at.pop_front();
+    }
+}
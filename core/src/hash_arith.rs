@@ -0,0 +1,90 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Unsigned integer arithmetic on `H256` and `H512`, e.g. for simulation or test code.
+//!
+//! The bytes are read as a big endian integer, consistently with the `Ord` implementation and
+//! `from_low_u64_be` of the fixed hashes.
+//!
+
+use super::{H256, H512};
+
+macro_rules! impl_hash_arith {
+    ($name:ident) => {
+        impl $name {
+            /// Add `rhs`, returning the wrapped sum and whether it overflowed.
+            ///
+            pub fn overflowing_add(&self, rhs: &Self) -> (Self, bool) {
+                let mut result = Self::zero();
+                let mut carry = 0u16;
+                for i in (0..Self::len_bytes()).rev() {
+                    let sum = self.0[i] as u16 + rhs.0[i] as u16 + carry;
+                    result.0[i] = sum as u8;
+                    carry = sum >> 8;
+                }
+                (result, carry != 0)
+            }
+
+            /// Subtract `rhs`, returning the wrapped difference and whether it overflowed.
+            ///
+            pub fn overflowing_sub(&self, rhs: &Self) -> (Self, bool) {
+                let mut result = Self::zero();
+                let mut borrow = 0i16;
+                for i in (0..Self::len_bytes()).rev() {
+                    let mut difference = self.0[i] as i16 - rhs.0[i] as i16 - borrow;
+                    borrow = 0;
+                    if difference < 0 {
+                        difference += 256;
+                        borrow = 1;
+                    }
+                    result.0[i] = difference as u8;
+                }
+                (result, borrow != 0)
+            }
+
+            /// Add `rhs`, wrapping around at the boundary of the type.
+            ///
+            pub fn wrapping_add(&self, rhs: &Self) -> Self {
+                self.overflowing_add(rhs).0
+            }
+
+            /// Subtract `rhs`, wrapping around at the boundary of the type.
+            ///
+            pub fn wrapping_sub(&self, rhs: &Self) -> Self {
+                self.overflowing_sub(rhs).0
+            }
+
+            /// Add `rhs`, `None` on overflow.
+            ///
+            pub fn checked_add(&self, rhs: &Self) -> Option<Self> {
+                match self.overflowing_add(rhs) {
+                    (result, false) => Some(result),
+                    (_, true) => None,
+                }
+            }
+
+            /// Subtract `rhs`, `None` on overflow.
+            ///
+            pub fn checked_sub(&self, rhs: &Self) -> Option<Self> {
+                match self.overflowing_sub(rhs) {
+                    (result, false) => Some(result),
+                    (_, true) => None,
+                }
+            }
+
+            /// Add a `u64`, wrapping around at the boundary of the type.
+            ///
+            pub fn wrapping_add_u64(&self, rhs: u64) -> Self {
+                self.wrapping_add(&Self::from_low_u64_be(rhs))
+            }
+        }
+    };
+}
+
+impl_hash_arith!(H256);
+impl_hash_arith!(H512);
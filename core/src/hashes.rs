@@ -6,11 +6,22 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+#[cfg(feature = "serde")]
+use serde_bytes::{ByteBuf as SerdeByteBuf, Bytes as SerdeBytes};
+
+use generic_array::typenum::{U32, U64};
+use generic_array::GenericArray;
+
 use super::{KEY_BYTES_SIZE, KEYPAIR_LENGTH};
 
 pub type AesKey = H256;
 pub type SharedSecret = H256;
 
+// The fixed-hash features provide `from_low_u64_be/le`, `to_low_u64_be/le`, the bit operators
+// and the byte order `Ord`; `random` and `randomize` come from the `entropy` module and the
+// `hash_arith` feature adds big endian integer add and sub helpers.
 construct_fixed_hash! {
     /// 256 bit hash type.
     pub struct H256(KEY_BYTES_SIZE);
@@ -20,3 +31,101 @@ construct_fixed_hash! {
     /// 512 bit hash type.
     pub struct H512(KEYPAIR_LENGTH);
 }
+
+impl H256 {
+    /// View the hash as a `GenericArray`, without copying, e.g. as the key of a RustCrypto
+    /// cipher: `Aes256Gcm::new(key.as_generic_array())`.
+    ///
+    pub fn as_generic_array(&self) -> &GenericArray<u8, U32> {
+        GenericArray::from_slice(self.as_bytes())
+    }
+
+    /// View the hash as a mutable `GenericArray`, without copying, e.g. as a digest output.
+    ///
+    pub fn as_generic_array_mut(&mut self) -> &mut GenericArray<u8, U32> {
+        GenericArray::from_mut_slice(self.as_bytes_mut())
+    }
+}
+
+impl H512 {
+    /// View the hash as a `GenericArray`, without copying.
+    ///
+    pub fn as_generic_array(&self) -> &GenericArray<u8, U64> {
+        GenericArray::from_slice(self.as_bytes())
+    }
+
+    /// View the hash as a mutable `GenericArray`, without copying, e.g. as a SHA-512 or
+    /// Keccak-512 output.
+    ///
+    pub fn as_generic_array_mut(&mut self) -> &mut GenericArray<u8, U64> {
+        GenericArray::from_mut_slice(self.as_bytes_mut())
+    }
+}
+
+impl From<GenericArray<u8, U32>> for H256 {
+    fn from(array: GenericArray<u8, U32>) -> Self {
+        Self(array.into())
+    }
+}
+
+impl From<H256> for GenericArray<u8, U32> {
+    fn from(hash: H256) -> Self {
+        hash.0.into()
+    }
+}
+
+impl From<GenericArray<u8, U64>> for H512 {
+    fn from(array: GenericArray<u8, U64>) -> Self {
+        Self(array.into())
+    }
+}
+
+impl From<H512> for GenericArray<u8, U64> {
+    fn from(hash: H512) -> Self {
+        hash.0.into()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for H256 {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let bytes = self.as_bytes();
+        SerdeBytes::new(bytes).serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'d> Deserialize<'d> for H256 {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'d>,
+    {
+        let bytes = <SerdeByteBuf>::deserialize(deserializer)?;
+        Ok(H256::from_slice(bytes.as_ref()))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for H512 {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let bytes = self.as_bytes();
+        SerdeBytes::new(bytes).serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'d> Deserialize<'d> for H512 {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'d>,
+    {
+        let bytes = <SerdeByteBuf>::deserialize(deserializer)?;
+        Ok(H512::from_slice(bytes.as_ref()))
+    }
+}
@@ -0,0 +1,190 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Hierarchical deterministic keys, SLIP-10 ed25519 as derived by symbol-hd-wallets.
+//!
+//! The master key is `HMAC-SHA512(key = "ed25519 seed", seed)` and the hardened child `i` of
+//! a key is `HMAC-SHA512(key = chain code, 0x00 || private key || i (u32 big endian))`, the
+//! left half being the private key and the right half the chain code.
+//!
+//! symbol-hd-wallets has two variants over the same private keys:
+//!
+//! * `HdVariant::Symbol`: the public keys of the Symbol (SHA-512) schema;
+//! * `HdVariant::Optin`: the public keys of the Nis1 (Keccak-512) schema, used by the wallets
+//!   of the NIS1 to Symbol opt-in, so that an opt-in account is derived from the same
+//!   mnemonic as its Symbol account.
+//!
+//! An `ExtendedKey` remembers its variant and only builds the keypair of the matching schema.
+//!
+
+use std::fmt;
+
+use anyhow::{ensure, Result};
+use hmac::{Hmac, Mac};
+use sha2::Sha512;
+use zeroize::{Zeroize, Zeroizing};
+
+use super::{DerivationPath, KeyPairSchema, Keypair, PrivateKey, HARDENED_OFFSET, KEY_BYTES_SIZE};
+
+/// The HMAC key of the SLIP-10 ed25519 master key.
+pub const ED25519_SEED_KEY: &[u8] = b"ed25519 seed";
+
+const SEED_MIN_SIZE: usize = 16;
+const SEED_MAX_SIZE: usize = 64;
+
+type HmacSha512 = Hmac<Sha512>;
+
+/// The derivation variants of symbol-hd-wallets.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HdVariant {
+    /// Symbol accounts, the `symbol` schema.
+    Symbol,
+    /// NIS1 opt-in accounts, the `nis1` schema.
+    Optin,
+}
+
+impl HdVariant {
+    /// The name of the keypair schema of the variant, see `KeyPairSchema::SCHEMA_NAME`.
+    ///
+    pub fn schema_name(&self) -> &'static str {
+        match self {
+            HdVariant::Symbol => "symbol",
+            HdVariant::Optin => "nis1",
+        }
+    }
+}
+
+/// A private key and chain code of a derivation tree.
+///
+#[derive(Clone)]
+pub struct ExtendedKey {
+    variant: HdVariant,
+    private_key: PrivateKey,
+    chain_code: [u8; KEY_BYTES_SIZE],
+}
+
+impl ExtendedKey {
+    /// Construct the master key of a seed.
+    ///
+    /// # Inputs
+    ///
+    /// * `seed`: The seed, 16 to 64 bytes, e.g. the BIP39 seed of a mnemonic.
+    ///
+    /// * `variant`: The derivation variant.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the master `ExtendedKey` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn from_seed(seed: &[u8], variant: HdVariant) -> Result<Self> {
+        ensure!(
+            (SEED_MIN_SIZE..=SEED_MAX_SIZE).contains(&seed.len()),
+            "seed has unexpected size {}.",
+            seed.len()
+        );
+        Ok(Self::from_hmac(ED25519_SEED_KEY, &[seed], variant))
+    }
+
+    /// Construct the master key of a BIP39 mnemonic, the wallet backup of symbol-hd-wallets.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the master `ExtendedKey` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    #[cfg(feature = "with_mnemonic")]
+    pub fn from_mnemonic(mnemonic: &str, password: &str, variant: HdVariant) -> Result<Self> {
+        let mnemonic = bip39::Mnemonic::parse(mnemonic)?;
+        let seed = Zeroizing::new(mnemonic.to_seed(password));
+        Self::from_seed(&seed[..], variant)
+    }
+
+    /// Derive the hardened child `index`, with or without the `HARDENED_OFFSET` bit.
+    ///
+    pub fn derive_child(&self, index: u32) -> Self {
+        let index = index | HARDENED_OFFSET;
+        Self::from_hmac(
+            &self.chain_code,
+            &[&[0u8], self.private_key.as_bytes(), &index.to_be_bytes()],
+            self.variant,
+        )
+    }
+
+    /// Derive the key of a path from this key, e.g. the master key.
+    ///
+    pub fn derive_path(&self, path: &DerivationPath) -> Self {
+        path.iter()
+            .fold(self.clone(), |key, &index| key.derive_child(index))
+    }
+
+    pub fn variant(&self) -> HdVariant {
+        self.variant
+    }
+
+    pub fn private_key(&self) -> PrivateKey {
+        self.private_key
+    }
+
+    pub fn chain_code(&self) -> &[u8; KEY_BYTES_SIZE] {
+        &self.chain_code
+    }
+
+    /// The keypair of the key, in the schema of its variant.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the `Keypair` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn keypair<Kp: KeyPairSchema>(&self) -> Result<Keypair<Kp>> {
+        ensure!(
+            Kp::SCHEMA_NAME == self.variant.schema_name(),
+            "{:?} keys derive {} keypairs, not {}.",
+            self.variant,
+            self.variant.schema_name(),
+            Kp::SCHEMA_NAME
+        );
+        Ok(Keypair::<Kp>::from_private_key(self.private_key))
+    }
+
+    // internal functions.
+    fn from_hmac(key: &[u8], data: &[&[u8]], variant: HdVariant) -> Self {
+        let mut mac = HmacSha512::new_from_slice(key).expect("HMAC accepts keys of any size");
+        for part in data {
+            mac.update(part);
+        }
+        let mut out = Zeroizing::new([0u8; 64]);
+        let mut digest = mac.finalize().into_bytes();
+        out.copy_from_slice(&digest);
+        digest.as_mut_slice().zeroize();
+
+        let mut chain_code = [0u8; KEY_BYTES_SIZE];
+        chain_code.copy_from_slice(&out[KEY_BYTES_SIZE..]);
+        Self {
+            variant,
+            private_key: PrivateKey::from_slice(&out[..KEY_BYTES_SIZE]),
+            chain_code,
+        }
+    }
+}
+
+impl fmt::Debug for ExtendedKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExtendedKey")
+            .field("variant", &self.variant)
+            .field("private_key", &"[redacted]")
+            .field("chain_code", &"[redacted]")
+            .finish()
+    }
+}
+
+impl Drop for ExtendedKey {
+    fn drop(&mut self) {
+        self.private_key.zeroize();
+        self.chain_code.zeroize();
+    }
+}
@@ -0,0 +1,161 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Fixed capacity stack buffers, for the `heapless` paths of the ciphers, keys and mnemonics.
+//!
+//! Their capacity is a const generic, sized with the constants of `constants`, e.g.
+//! `StackBuf<{ MAX_MESSAGE_SIZE + SYM_ENCRYPT_OVERHEAD }>` for a Symbol encrypted message.
+//!
+
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+
+use anyhow::{anyhow, ensure, Result};
+use zeroize::Zeroize;
+
+/// A byte buffer of at most `N` bytes, stored inline.
+///
+/// It may hold plaintexts or secrets, so it is zeroed on drop and its `Debug` only shows its
+/// length.
+///
+#[derive(Clone)]
+pub struct StackBuf<const N: usize> {
+    bytes: [u8; N],
+    len: usize,
+}
+
+impl<const N: usize> StackBuf<N> {
+    /// An empty buffer.
+    ///
+    pub fn new() -> Self {
+        Self {
+            bytes: [0u8; N],
+            len: 0,
+        }
+    }
+
+    /// A buffer of `len` zero bytes, to be filled in place.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the `StackBuf` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn zeroed(len: usize) -> Result<Self> {
+        ensure!(len <= N, "{} bytes exceed the buffer capacity {}.", len, N);
+        Ok(Self {
+            bytes: [0u8; N],
+            len,
+        })
+    }
+
+    /// A buffer holding a copy of `bytes`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the `StackBuf` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn from_slice(bytes: &[u8]) -> Result<Self> {
+        let mut buf = Self::new();
+        buf.extend_from_slice(bytes)?;
+        Ok(buf)
+    }
+
+    /// The capacity of the buffer, `N`.
+    ///
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Append `bytes`.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok` if the bytes fit in the remaining capacity, the buffer is unchanged
+    /// otherwise.
+    pub fn extend_from_slice(&mut self, bytes: &[u8]) -> Result<()> {
+        let end = self
+            .len
+            .checked_add(bytes.len())
+            .filter(|&end| end <= N)
+            .ok_or_else(|| anyhow!("{} bytes exceed the buffer capacity {}.", bytes.len(), N))?;
+        self.bytes[self.len..end].copy_from_slice(bytes);
+        self.len = end;
+        Ok(())
+    }
+
+    /// Shorten the buffer to `len` bytes, zeroing the removed ones.
+    ///
+    pub fn truncate(&mut self, len: usize) {
+        if len < self.len {
+            self.bytes[len..self.len].zeroize();
+            self.len = len;
+        }
+    }
+
+    /// View the buffer as a `str`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the `str` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn as_str(&self) -> Result<&str> {
+        std::str::from_utf8(self).map_err(|e| anyhow!("{}", e))
+    }
+}
+
+impl<const N: usize> Default for StackBuf<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Deref for StackBuf<N> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.bytes[..self.len]
+    }
+}
+
+impl<const N: usize> DerefMut for StackBuf<N> {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        &mut self.bytes[..self.len]
+    }
+}
+
+impl<const N: usize> AsRef<[u8]> for StackBuf<N> {
+    fn as_ref(&self) -> &[u8] {
+        self
+    }
+}
+
+impl<const N: usize> PartialEq for StackBuf<N> {
+    fn eq(&self, other: &Self) -> bool {
+        self[..] == other[..]
+    }
+}
+
+impl<const N: usize> Eq for StackBuf<N> {}
+
+impl<const N: usize> fmt::Write for StackBuf<N> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.extend_from_slice(s.as_bytes()).map_err(|_| fmt::Error)
+    }
+}
+
+impl<const N: usize> fmt::Debug for StackBuf<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "StackBuf<{}>(len {})", N, self.len)
+    }
+}
+
+impl<const N: usize> Drop for StackBuf<N> {
+    fn drop(&mut self) {
+        self.bytes.zeroize();
+    }
+}
@@ -0,0 +1,96 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::fmt;
+
+use super::{PublicKey, Signature, H256, H512};
+
+/// Hex formatter for keys, signatures and hashes honoring the formatter flags.
+///
+/// * `{}` and `{:x}` print lowercase hex, `{:X}` prints uppercase hex.
+/// * `{:#x}` and `{:#X}` add the `0x` / `0X` prefix.
+/// * `{:.8}` prints only the first 8 hex digits followed by `…`.
+///
+/// The truncated form is meant for logs only, it is rejected by `FromStr`.
+///
+/// # Example
+///
+/// ```
+/// use std::str::FromStr;
+/// use core_crypto::PublicKey;
+///
+/// let pk = PublicKey::from_str("2E834140FD66CF87B254A693A2C7862C819217B676D3943267156625E816EC6F").unwrap();
+///
+/// assert_eq!(format!("{:.8X}", pk.hex_display()), "2E834140…");
+/// assert_eq!(format!("{:#.4x}", pk.hex_display()), "0x2e83…");
+/// ```
+#[derive(Clone, Copy)]
+pub struct HexDisplay<'a>(&'a [u8]);
+
+impl<'a> HexDisplay<'a> {
+    fn write(&self, f: &mut fmt::Formatter<'_>, upper: bool) -> fmt::Result {
+        if f.alternate() {
+            f.write_str(if upper { "0X" } else { "0x" })?;
+        }
+
+        let digits = self.0.len() * 2;
+        let shown = f.precision().map_or(digits, |p| p.min(digits));
+
+        let encoded = if upper {
+            hex::encode_upper(self.0)
+        } else {
+            hex::encode(self.0)
+        };
+        f.write_str(&encoded[..shown])?;
+
+        if shown < digits {
+            f.write_str("…")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for HexDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write(f, false)
+    }
+}
+
+impl fmt::LowerHex for HexDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write(f, false)
+    }
+}
+
+impl fmt::UpperHex for HexDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write(f, true)
+    }
+}
+
+impl fmt::Debug for HexDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write(f, false)
+    }
+}
+
+macro_rules! impl_hex_display {
+    ($($name:ident),*) => {
+        $(
+            impl $name {
+                /// Returns a formatter honoring the `#`, `X` and precision formatter flags.
+                ///
+                pub fn hex_display(&self) -> HexDisplay<'_> {
+                    HexDisplay(self.as_bytes())
+                }
+            }
+        )*
+    };
+}
+
+impl_hex_display!(PublicKey, Signature, H256, H512);
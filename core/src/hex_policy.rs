@@ -0,0 +1,116 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Hex casing policies of the ecosystem tools.
+//!
+//! The Symbol REST gateway and symbol-bootstrap write keys, signatures and hashes in upper case
+//! hex, while the Symbol and NEM SDKs and most tools write lower case. Parsing accepts both, a
+//! service only has to pick the casing it emits: `to_hex_with(HexPolicy::SYMBOL_REST)`, or the
+//! `ser_upper` and `ser_lower` serde adapters:
+//!
+//! ```ignore
+//! #[derive(Serialize, Deserialize)]
+//! struct Account {
+//!     #[serde(serialize_with = "hex_policy::ser_upper", deserialize_with = "hex_policy::de_hex")]
+//!     public_key: PublicKey,
+//! }
+//! ```
+//!
+
+#[cfg(feature = "serde")]
+use std::fmt::Display;
+#[cfg(feature = "serde")]
+use std::str::FromStr;
+
+#[cfg(feature = "serde")]
+use serde::de::Error as SerdeError;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serializer};
+
+use super::{PrivateKey, PublicKey, Signature, H256, H512};
+
+/// The casing of hex text.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HexPolicy {
+    Upper,
+    Lower,
+}
+
+impl HexPolicy {
+    /// The casing of the Symbol REST gateway and symbol-bootstrap.
+    pub const SYMBOL_REST: HexPolicy = HexPolicy::Upper;
+
+    /// The casing of the Symbol and NEM SDKs.
+    pub const SDK: HexPolicy = HexPolicy::Lower;
+
+    /// Hex encode bytes with this casing, without prefix.
+    ///
+    pub fn encode<T: AsRef<[u8]>>(&self, bytes: T) -> String {
+        match self {
+            HexPolicy::Upper => hex::encode_upper(bytes),
+            HexPolicy::Lower => hex::encode(bytes),
+        }
+    }
+}
+
+impl Default for HexPolicy {
+    fn default() -> Self {
+        HexPolicy::SYMBOL_REST
+    }
+}
+
+macro_rules! impl_hex_policy {
+    ($($name:ident),*) => {
+        $(
+            impl $name {
+                /// Hex encode with the casing of `policy`.
+                ///
+                pub fn to_hex_with(&self, policy: HexPolicy) -> String {
+                    policy.encode(self.as_bytes())
+                }
+            }
+        )*
+    };
+}
+
+impl_hex_policy!(PrivateKey, PublicKey, Signature, H256, H512);
+
+/// Serialize a key, signature or hash as upper case hex, see `HexPolicy::SYMBOL_REST`.
+///
+#[cfg(feature = "serde")]
+pub fn ser_upper<T: AsRef<[u8]>, S: Serializer>(
+    value: &T,
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    serializer.serialize_str(&HexPolicy::Upper.encode(value))
+}
+
+/// Serialize a key, signature or hash as lower case hex, see `HexPolicy::SDK`.
+///
+#[cfg(feature = "serde")]
+pub fn ser_lower<T: AsRef<[u8]>, S: Serializer>(
+    value: &T,
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    serializer.serialize_str(&HexPolicy::Lower.encode(value))
+}
+
+/// Deserialize a key, signature or hash from hex of either casing.
+///
+#[cfg(feature = "serde")]
+pub fn de_hex<'d, T, D>(deserializer: D) -> std::result::Result<T, D::Error>
+where
+    T: FromStr,
+    T::Err: Display,
+    D: Deserializer<'d>,
+{
+    String::deserialize(deserializer)?
+        .parse()
+        .map_err(SerdeError::custom)
+}
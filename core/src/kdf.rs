@@ -0,0 +1,87 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Key derivation from a raw ECDH shared secret, for protocols that bring their own KDF
+//! labels (TLS exporter style).
+//!
+//! The raw shared secret of a schema is `CryptoSym::derive_raw_shared_secret` or
+//! `CryptoNis1::derive_raw_shared_secret`; `expand` derives the key material of a protocol
+//! from it with `HKDF(salt = none, ikm = shared secret, info)`.
+//!
+
+use anyhow::{anyhow, ensure, Result};
+use hkdf::Hkdf;
+use sha2::{Sha256, Sha512};
+use zeroize::Zeroizing;
+
+use super::SharedSecret;
+
+/// The HKDF hash functions of `expand`.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KdfChoice {
+    /// HKDF-SHA256, up to 8160 bytes; the `CryptoSym` message key derivation.
+    HkdfSha256,
+    /// HKDF-SHA512, up to 16320 bytes.
+    HkdfSha512,
+}
+
+impl KdfChoice {
+    /// The maximum output length of the KDF, 255 blocks of its hash.
+    ///
+    pub fn max_len(&self) -> usize {
+        match self {
+            KdfChoice::HkdfSha256 => 255 * 32,
+            KdfChoice::HkdfSha512 => 255 * 64,
+        }
+    }
+}
+
+/// Derive key material from a raw shared secret.
+///
+/// # Inputs
+///
+/// * `shared`: The raw ECDH shared secret.
+///
+/// * `kdf`: The KDF.
+///
+/// * `info`: The protocol label and context, binding the output to its use.
+///
+/// * `len`: The output length, at most `kdf.max_len()`.
+///
+/// # Returns
+///
+/// A `Result` whose okay value is the key material or whose error value
+/// is an `failure::Error` describing the error that occurred.
+pub fn expand(
+    shared: &SharedSecret,
+    kdf: KdfChoice,
+    info: &[u8],
+    len: usize,
+) -> Result<Zeroizing<Vec<u8>>> {
+    ensure!(len > 0, "kdf output length must not be zero.");
+    ensure!(
+        len <= kdf.max_len(),
+        "kdf output length {} exceeds the {:?} maximum {}.",
+        len,
+        kdf,
+        kdf.max_len()
+    );
+
+    let mut out = Zeroizing::new(vec![0u8; len]);
+    match kdf {
+        KdfChoice::HkdfSha256 => {
+            Hkdf::<Sha256>::new(None, shared.as_bytes()).expand(info, &mut out)
+        }
+        KdfChoice::HkdfSha512 => {
+            Hkdf::<Sha512>::new(None, shared.as_bytes()).expand(info, &mut out)
+        }
+    }
+    .map_err(|e| anyhow!("{}", e))?;
+    Ok(out)
+}
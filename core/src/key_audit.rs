@@ -0,0 +1,123 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Checks for obviously weak private keys, e.g. before a wallet imports one.
+//!
+//! A key drawn from a proper random source passes every check except with a negligible
+//! probability (below 2^-40 for each check), so a flagged key was almost certainly typed, made
+//! up, or copied from documentation. Passing the audit does not prove a key was random.
+//!
+
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+use super::{PrivateKey, KEY_BYTES_SIZE};
+
+/// Below this many distinct byte values, a key is flagged `KeyWeakness::FewDistinctBytes`.
+pub const KEY_AUDIT_MIN_DISTINCT_BYTES: usize = 12;
+
+/// Private keys published in test vectors and documentation.
+const KNOWN_TEST_KEYS: [PrivateKey; 7] = [
+    // RFC 8032 ed25519 test vectors 1 to 3.
+    PrivateKey::from_hex_const("9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60"),
+    PrivateKey::from_hex_const("4ccd089b28ff96da9db6c346ec114e0f5b8a319f35aba624da8cf6ed4fb8a6fb"),
+    PrivateKey::from_hex_const("c5aa8df43f9f837bedb7442f31dcb7b166d38535076f094b85ce3a2e0b4458f7"),
+    // catapult key pair and signing test vectors.
+    PrivateKey::from_hex_const("575dbb3062267eff57c970a336ebbc8fbcfe12c5bd3ed7bc11eb0481d7704ced"),
+    PrivateKey::from_hex_const("abf4cf55a2b3f742d7543d9cc17f50447b969e6e06f5ea9195d428ab12b7318d"),
+    // SLIP-10 ed25519 test vector 1, master key.
+    PrivateKey::from_hex_const("2b4be7f19ee27bbf30c667b642d5f4aa69fd169872f8fc3059c08ebae2eb19e7"),
+    // the private key of the "abandon ... about" BIP39 test mnemonic, without password.
+    PrivateKey::from_hex_const("5eb00bbddcf069084889a8ab9155568165f5c453ccb85e70811aaed6f6da5fc1"),
+];
+
+/// A reason to distrust a private key.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize), serde(crate = "serde_crate"))]
+pub enum KeyWeakness {
+    /// Every byte is zero.
+    AllZero,
+    /// The key repeats a pattern of `period` bytes, e.g. `AB` or `DEADBEEF`.
+    RepeatedPattern { period: usize },
+    /// The bytes go up or down by a constant step, e.g. `00 01 02 ..`.
+    Sequential,
+    /// The key is published in test vectors or documentation.
+    KnownTestKey,
+    /// Every byte is printable ascii, e.g. a passphrase used as the key bytes.
+    AsciiText,
+    /// The key has fewer than `KEY_AUDIT_MIN_DISTINCT_BYTES` distinct byte values.
+    FewDistinctBytes { distinct: usize },
+}
+
+/// The result of `PrivateKey::audit`.
+///
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize), serde(crate = "serde_crate"))]
+pub struct KeyAuditReport {
+    /// The weaknesses found, empty for a key that passed every check.
+    pub weaknesses: Vec<KeyWeakness>,
+}
+
+impl KeyAuditReport {
+    /// Whether the key passed every check.
+    ///
+    pub fn is_ok(&self) -> bool {
+        self.weaknesses.is_empty()
+    }
+
+    pub fn has(&self, weakness: KeyWeakness) -> bool {
+        self.weaknesses.contains(&weakness)
+    }
+}
+
+impl PrivateKey {
+    /// Check the key for obviously weak values, see `KeyWeakness`.
+    ///
+    /// # Returns
+    ///
+    /// A `KeyAuditReport` listing every weakness found.
+    pub fn audit(&self) -> KeyAuditReport {
+        let bytes = self.as_bytes();
+        let mut weaknesses = Vec::new();
+
+        if bytes.iter().all(|&b| b == 0) {
+            weaknesses.push(KeyWeakness::AllZero);
+        } else if let Some(period) = repeat_period(bytes) {
+            weaknesses.push(KeyWeakness::RepeatedPattern { period });
+        }
+        if is_sequential(bytes) {
+            weaknesses.push(KeyWeakness::Sequential);
+        }
+        if KNOWN_TEST_KEYS.contains(self) {
+            weaknesses.push(KeyWeakness::KnownTestKey);
+        }
+        if bytes.iter().all(|b| (0x20..0x7f).contains(b)) {
+            weaknesses.push(KeyWeakness::AsciiText);
+        }
+
+        let mut seen = [false; 256];
+        bytes.iter().for_each(|&b| seen[b as usize] = true);
+        let distinct = seen.iter().filter(|&&seen| seen).count();
+        if distinct < KEY_AUDIT_MIN_DISTINCT_BYTES {
+            weaknesses.push(KeyWeakness::FewDistinctBytes { distinct });
+        }
+
+        KeyAuditReport { weaknesses }
+    }
+}
+
+// internal functions.
+fn repeat_period(bytes: &[u8]) -> Option<usize> {
+    (1..=KEY_BYTES_SIZE / 2).find(|&period| bytes.iter().zip(&bytes[period..]).all(|(a, b)| a == b))
+}
+
+fn is_sequential(bytes: &[u8]) -> bool {
+    let step = bytes[1].wrapping_sub(bytes[0]);
+    step != 0 && bytes.windows(2).all(|pair| pair[1].wrapping_sub(pair[0]) == step)
+}
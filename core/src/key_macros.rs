@@ -0,0 +1,39 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+/// Construct a `PublicKey` from a hex literal, validated at compile time.
+///
+/// ```
+/// use core_crypto::{public_key, PublicKey};
+///
+/// const NODE_IDENTITY: PublicKey =
+///     public_key!("2E834140FD66CF87B254A693A2C7862C819217B676D3943267156625E816EC6F");
+/// ```
+///
+/// An invalid literal does not compile:
+///
+/// ```compile_fail
+/// let key = core_crypto::public_key!("2E834140");
+/// ```
+#[macro_export]
+macro_rules! public_key {
+    ($hex:expr) => {{
+        const KEY: $crate::PublicKey = $crate::PublicKey::from_hex_const($hex);
+        KEY
+    }};
+}
+
+/// Construct a `PrivateKey` from a hex literal, validated at compile time.
+///
+#[macro_export]
+macro_rules! private_key {
+    ($hex:expr) => {{
+        const KEY: $crate::PrivateKey = $crate::PrivateKey::from_hex_const($hex);
+        KEY
+    }};
+}
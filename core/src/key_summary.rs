@@ -0,0 +1,58 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+
+use super::{KeyPairSchema, Keypair};
+
+/// Number of bytes of the public key hash shown as fingerprint.
+const FINGERPRINT_LENGTH: usize = 8;
+
+/// A public summary of a `Keypair` for key inventories.
+///
+/// Like the `Keypair` serde implementation, which only emits the public key, it never contains
+/// secret material; it adds the schema name and a fingerprint of the public key.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct KeySummary {
+    /// The keypair schema name, e.g. `symbol` or `nis1`.
+    pub schema: String,
+    /// The upper case hex public key.
+    pub public_key_hex: String,
+    /// The upper case hex of the first 8 bytes of `SHA3-256(public_key)`.
+    pub fingerprint: String,
+    /// Optional unix timestamp (seconds) of the key creation, set by the caller.
+    #[cfg_attr(
+        feature = "serde",
+        serde(skip_serializing_if = "Option::is_none", default)
+    )]
+    pub created_at: Option<u64>,
+}
+
+impl<Kp: KeyPairSchema> Keypair<Kp> {
+    /// Returns a public summary of this `Keypair`, safe to log or serialize.
+    ///
+    pub fn summary(&self) -> KeySummary {
+        let public_key = self.public_key();
+        let hash = Sha3_256::digest(public_key.as_bytes());
+
+        KeySummary {
+            schema: Kp::SCHEMA_NAME.to_string(),
+            public_key_hex: hex::encode_upper(public_key.as_bytes()),
+            fingerprint: hex::encode_upper(&hash[..FINGERPRINT_LENGTH]),
+            created_at: None,
+        }
+    }
+}
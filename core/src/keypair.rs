@@ -22,12 +22,12 @@ use std::fmt::Debug;
 
 use anyhow::Result;
 #[cfg(feature = "serde")]
-use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde::{Serialize, Serializer};
 #[cfg(feature = "serde")]
-use serde::de::Error as SerdeError;
-#[cfg(feature = "serde")]
-use serde_bytes::{ByteBuf as SerdeByteBuf, Bytes as SerdeBytes};
-use crate::{KeyPairSchema, PrivateKey, KEYPAIR_LENGTH, KEY_BYTES_SIZE, Signature, PublicKey, BlockCipher};
+use serde_bytes::Bytes as SerdeBytes;
+use crate::curve25519::edwards::EdwardsPoint;
+use crate::sealed::Sealed;
+use crate::{KeyPairSchema, PrivateKey, KEYPAIR_LENGTH, KEY_BYTES_SIZE, Signature, PublicKey, BlockCipher, Scalar32};
 
 /// It represents an asymmetric private/public encryption key.
 ///
@@ -52,6 +52,17 @@ impl<Kp: KeyPairSchema> Keypair<Kp> {
         Self(<Kp>::random())
     }
 
+    /// Generate a `Keypair` random, returning the failure of the `EntropySource` instead of
+    /// panicking.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the `Keypair` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn try_random() -> Result<Self> {
+        Ok(Self(<Kp>::from_private_key(PrivateKey::try_random()?)))
+    }
+
     /// Construct a `Keypair` from the bytes of a `PublicKey` and `PrivateKey`.
     ///
     /// # Inputs
@@ -124,6 +135,39 @@ impl<Kp: KeyPairSchema> Keypair<Kp> {
         Self(kp)
     }
 
+    /// Construct a `Keypair` from an expanded secret, for wallets that export the clamped
+    /// scalar rather than the private key (seed).
+    ///
+    /// The expanded secret is the schema hash of the private key: SHA-512 for Symbol,
+    /// Keccak-512 of the reversed key for Nis1, whose lower half clamped is the `scalar` and
+    /// upper half is the `nonce`. The `Keypair` signs like the one of the private key, but
+    /// its `private_key` is zero, so it cannot encrypt or be exported with `to_bytes`.
+    ///
+    /// # Inputs
+    ///
+    /// * `scalar`: The clamped secret scalar, see `Scalar32`.
+    ///
+    /// * `nonce`: The nonce prefix of the signatures.
+    pub fn from_expanded_scalar(scalar: &Scalar32, nonce: [u8; KEY_BYTES_SIZE]) -> Self {
+        Self(<Kp>::from_expanded_scalar(scalar, nonce))
+    }
+
+    /// Derive the public keys of many private keys in one call, e.g. for deposit addresses.
+    ///
+    /// The hasher is reused between keys and, with the `parallel` feature, the keys are
+    /// split between the rayon threads.
+    ///
+    /// # Inputs
+    ///
+    /// * `private_keys`: The private keys.
+    ///
+    /// # Returns
+    ///
+    /// The `PublicKey`s, in the order of the private keys.
+    pub fn derive_public_keys(private_keys: &[PrivateKey]) -> Vec<PublicKey> {
+        <Kp>::derive_public_keys(private_keys)
+    }
+
     /// Convert this keypair to bytes.
     ///
     /// # Returns
@@ -162,10 +206,31 @@ impl<Kp: KeyPairSchema> Keypair<Kp> {
     /// # Returns
     ///
     /// A `Signature` the signature hash.
+    ///
+    /// # Panics
+    ///
+    /// If the keypair has no private key for its public key, e.g. a verification only
+    /// keypair from `from_null_private_key`. Signers that may hold such a keypair should call
+    /// `try_sign`.
+    #[deprecated(note = "use try_sign")]
+    #[allow(deprecated)]
     pub fn sign(&self, data: &[u8]) -> Signature {
         self.0.sign(data)
     }
 
+    /// Signs a data bytes with this `Keypair`, returning an error instead of panicking for a
+    /// keypair without private key, see `sign`. The signature is verified against the public
+    /// key first when the schema crate has its `verify_after_sign` feature (root crate feature
+    /// `verify_after_sign`), for devices exposed to fault injection.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the `Signature` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn try_sign(&self, data: &[u8]) -> Result<Signature> {
+        self.0.try_sign(data)
+    }
+
     /// Verify a `Signature` on a data with this Keypair public key.
     ///
     /// # Inputs
@@ -182,6 +247,33 @@ impl<Kp: KeyPairSchema> Keypair<Kp> {
         self.0.verify(data, signature)
     }
 
+    /// Signs the concatenation of the data parts with this `Keypair`.
+    ///
+    /// The signature is the same as `sign` of the joined data, without the caller
+    /// allocating a joined buffer, e.g. for a header, body and footer in separate slices.
+    ///
+    /// # Inputs
+    ///
+    /// * `parts`: The data parts, in order.
+    ///
+    /// # Returns
+    ///
+    /// A `Signature` the signature hash.
+    pub fn sign_vectored(&self, parts: &[&[u8]]) -> Signature {
+        self.0.sign_vectored(parts)
+    }
+
+    /// Verify a `Signature` on the concatenation of the data parts with this Keypair public key.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok` if the `Signature` was a valid signature of the joined data created by
+    /// this `Keypair`
+    ///
+    pub fn verify_vectored(&self, parts: &[&[u8]], signature: Signature) -> Result<()> {
+        self.0.verify_vectored(parts, signature)
+    }
+
     pub fn private_key(&self) -> PrivateKey {
         self.0.private_key()
     }
@@ -201,7 +293,7 @@ impl<Kp: KeyPairSchema> Keypair<Kp> {
     ///
     /// # Example
     ///
-    /// ```
+    /// ```ignore
     /// # use std::str::FromStr;
     /// use symbol_crypto_core::prelude::{Keypair, Sym, PublicKey};
     ///
@@ -212,7 +304,7 @@ impl<Kp: KeyPairSchema> Keypair<Kp> {
     ///
     /// let message = b"Symbol is awesome from Rust!";
     ///
-    /// let encrypt_text = signer_kp.encrypt_message(receiver_pk.as_fixed_bytes(), message).unwrap();
+    /// let encrypt_text = signer_kp.encrypt_for(&receiver_pk, message).unwrap();
     /// # println!("{:?}", encrypt_text);
     /// # }
     /// ```
@@ -221,12 +313,8 @@ impl<Kp: KeyPairSchema> Keypair<Kp> {
     ///
     /// A `Result` whose okay value is an encrypt message `Vec<u8>` or whose error value
     /// is an `failure::Error` describing the error that occurred.
-    pub fn encrypt_message(
-        &self,
-        receiver_pk: &[u8; KEY_BYTES_SIZE],
-        msg: &[u8],
-    ) -> Result<Vec<u8>> {
-        <Kp>::Crypto::encrypt_message(self.private_key().as_fixed_bytes(), receiver_pk, msg)
+    pub fn encrypt_for(&self, receiver_pk: &PublicKey, msg: &[u8]) -> Result<Vec<u8>> {
+        <Kp>::Crypto::encrypt_for(&self.private_key(), receiver_pk, msg)
     }
 
     /// Decrypt a message text using the receiver's the PrivateKey of this Keypair and signer's
@@ -240,7 +328,7 @@ impl<Kp: KeyPairSchema> Keypair<Kp> {
     ///
     /// # Example
     ///
-    /// ```
+    /// ```ignore
     /// # use std::str::FromStr;
     /// use symbol_crypto_core::prelude::{Keypair, Sym, PublicKey};
     ///
@@ -256,7 +344,7 @@ impl<Kp: KeyPairSchema> Keypair<Kp> {
     ///     184, 66,
     /// ];
     ///
-    /// let decrypted_text = receiver_kp.decrypt_message( signer_pk.as_fixed_bytes(), &encrypt_text_vec).unwrap();
+    /// let decrypted_text = receiver_kp.decrypt_from(&signer_pk, &encrypt_text_vec).unwrap();
     /// # println!("{}", String::from_utf8(decrypted_text).unwrap());
     /// # }
     /// ```
@@ -265,12 +353,30 @@ impl<Kp: KeyPairSchema> Keypair<Kp> {
     ///
     /// A `Result` whose okay value is an decrypted message `Vec<u8>` or whose error value
     /// is an `failure::Error` describing the error that occurred.
+    pub fn decrypt_from(&self, signer_pk: &PublicKey, enc_msg: &[u8]) -> Result<Vec<u8>> {
+        <Kp>::Crypto::decrypt_from(&self.private_key(), signer_pk, enc_msg)
+    }
+
+    /// Encode a message text for the raw bytes of the receiver's public key, see `encrypt_for`.
+    ///
+    #[deprecated(note = "use `encrypt_for`, which takes a typed `PublicKey`")]
+    pub fn encrypt_message(
+        &self,
+        receiver_pk: &[u8; KEY_BYTES_SIZE],
+        msg: &[u8],
+    ) -> Result<Vec<u8>> {
+        self.encrypt_for(&receiver_pk.into(), msg)
+    }
+
+    /// Decrypt a message text from the raw bytes of the signer's public key, see `decrypt_from`.
+    ///
+    #[deprecated(note = "use `decrypt_from`, which takes a typed `PublicKey`")]
     pub fn decrypt_message(
         &self,
         signer_pk: &[u8; KEY_BYTES_SIZE],
         enc_msg: &[u8],
     ) -> Result<Vec<u8>> {
-        <Kp>::Crypto::decrypt_message(self.private_key().as_fixed_bytes(), signer_pk, enc_msg)
+        self.decrypt_from(&signer_pk.into(), enc_msg)
     }
 }
 
@@ -286,24 +392,91 @@ impl<C: KeyPairSchema> Serialize for Keypair<C> {
         where
             S: Serializer,
     {
-        let bytes = &self.to_bytes()[..];
-        SerdeBytes::new(bytes).serialize(serializer)
+        // only the public half, see `SecretSerde` for the full keypair.
+        SerdeBytes::new(self.public_key().as_bytes()).serialize(serializer)
     }
 }
 
-#[cfg(feature = "serde")]
-impl<'d, C: KeyPairSchema> Deserialize<'d> for Keypair<C> {
-    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
-        where
-            D: Deserializer<'d>,
-    {
-        let bytes = <SerdeByteBuf>::deserialize(deserializer)?;
-        Keypair::from_bytes(bytes.as_ref()).map_err(SerdeError::custom)
-    }
-}
 
 impl<C: KeyPairSchema> AsRef<C> for Keypair<C> {
     fn as_ref(&self) -> &C {
         &self.0
     }
 }
+
+impl<Kp: KeyPairSchema> Sealed for Keypair<Kp> {}
+
+/// The facade is a schema itself, so generic code bound by `KeyPairSchema` accepts either
+/// `Keypair<Kp>` or the bare `Kp`; every method delegates to the wrapped schema keypair.
+///
+impl<Kp: KeyPairSchema> KeyPairSchema for Keypair<Kp> {
+    type Crypto = Kp::Crypto;
+
+    const SCHEMA_NAME: &'static str = Kp::SCHEMA_NAME;
+
+    fn random() -> Self {
+        Self(Kp::random())
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        Kp::from_bytes(bytes).map(Self)
+    }
+
+    fn from_hex_private_key<S: AsRef<str>>(hex: S) -> Result<Self> {
+        Kp::from_hex_private_key(hex).map(Self)
+    }
+
+    fn from_private_key(pk: PrivateKey) -> Self {
+        Self(Kp::from_private_key(pk))
+    }
+
+    fn from_expanded_scalar(scalar: &Scalar32, nonce: [u8; KEY_BYTES_SIZE]) -> Self {
+        Self(Kp::from_expanded_scalar(scalar, nonce))
+    }
+
+    fn derive_public_keys(private_keys: &[PrivateKey]) -> Vec<PublicKey> {
+        Kp::derive_public_keys(private_keys)
+    }
+
+    fn private_key(&self) -> PrivateKey {
+        self.0.private_key()
+    }
+
+    fn public_key(&self) -> PublicKey {
+        self.0.public_key()
+    }
+
+    #[allow(deprecated)]
+    fn sign(&self, data: &[u8]) -> Signature {
+        self.0.sign(data)
+    }
+
+    fn try_sign(&self, data: &[u8]) -> Result<Signature> {
+        self.0.try_sign(data)
+    }
+
+    fn verify(&self, data: &[u8], signature: Signature) -> Result<()> {
+        self.0.verify(data, signature)
+    }
+
+    fn sign_vectored(&self, parts: &[&[u8]]) -> Signature {
+        self.0.sign_vectored(parts)
+    }
+
+    fn verify_vectored(&self, parts: &[&[u8]], signature: Signature) -> Result<()> {
+        self.0.verify_vectored(parts, signature)
+    }
+
+    fn verify_with_point(
+        public_key: &PublicKey,
+        point: &EdwardsPoint,
+        data: &[u8],
+        signature: Signature,
+    ) -> Result<()> {
+        Kp::verify_with_point(public_key, point, data, signature)
+    }
+
+    fn from_null_private_key(pk: PublicKey) -> Self {
+        Self(Kp::from_null_private_key(pk))
+    }
+}
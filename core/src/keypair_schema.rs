@@ -20,14 +20,25 @@
 use std::fmt::Debug;
 
 use anyhow::Result;
+use curve25519::edwards::EdwardsPoint;
 
-use super::{BlockCipher, PrivateKey, PublicKey, Signature, KEYPAIR_LENGTH, KEY_BYTES_SIZE};
+use super::sealed::Sealed;
+use super::{
+    BlockCipher, PrivateKey, PublicKey, Scalar32, Signature, KEYPAIR_LENGTH, KEY_BYTES_SIZE,
+};
 
 /// This trait defines a schema: an association of symbol or nis1 keypair type.
 ///
-pub trait KeyPairSchema: Sized + PartialEq + Debug + Copy {
+/// The trait is sealed, it is implemented by the Symbol and Nis1 schemas only, and by the
+/// `Keypair` facade over them.
+///
+pub trait KeyPairSchema: Sealed + Sized + PartialEq + Debug + Copy {
     type Crypto: BlockCipher;
 
+    /// The schema name, e.g. `symbol` or `nis1`.
+    ///
+    const SCHEMA_NAME: &'static str;
+
     /// Create a new `Keypair` with cryptographically random content.
     ///
     fn random() -> Self;
@@ -44,18 +55,59 @@ pub trait KeyPairSchema: Sized + PartialEq + Debug + Copy {
     ///
     fn from_private_key(pk: PrivateKey) -> Self;
 
+    /// Construct a signing only `Keypair` from an expanded secret, the clamped scalar and
+    /// the nonce prefix, without the private key it was derived from.
+    ///
+    fn from_expanded_scalar(scalar: &Scalar32, nonce: [u8; KEY_BYTES_SIZE]) -> Self;
+
+    /// Derive the public keys of many private keys, in parallel with the `rayon` feature.
+    ///
+    fn derive_public_keys(private_keys: &[PrivateKey]) -> Vec<PublicKey>;
+
     fn private_key(&self) -> PrivateKey;
 
     fn public_key(&self) -> PublicKey;
 
     /// Signs a data bytes with a `Keypair`.
     ///
+    /// # Panics
+    ///
+    /// If the keypair has no private key for its public key, e.g. from
+    /// `from_null_private_key`; `try_sign` returns the error instead.
+    #[deprecated(note = "use try_sign")]
     fn sign(&self, data: &[u8]) -> Signature;
 
+    /// Signs a data bytes with a `Keypair`, failing for a keypair without private key, and
+    /// verifying the signature against the public key before returning it when the schema
+    /// crate has its `verify_after_sign` feature.
+    ///
+    fn try_sign(&self, data: &[u8]) -> Result<Signature>;
+
     /// Verify a `Signature` on a data with this Keypair public key.
     ///
     fn verify(&self, data: &[u8], signature: Signature) -> Result<()>;
 
+    /// Signs the concatenation of the data parts, without joining them in a buffer.
+    ///
+    /// # Panics
+    ///
+    /// If the keypair has no private key for its public key, see `sign`.
+    fn sign_vectored(&self, parts: &[&[u8]]) -> Signature;
+
+    /// Verify a `Signature` on the concatenation of the data parts.
+    ///
+    fn verify_vectored(&self, parts: &[&[u8]], signature: Signature) -> Result<()>;
+
+    /// Verify a `Signature` on a data with the decompressed point of `public_key`, e.g. to
+    /// check a key against both schemas with a single decompression.
+    ///
+    fn verify_with_point(
+        public_key: &PublicKey,
+        point: &EdwardsPoint,
+        data: &[u8],
+        signature: Signature,
+    ) -> Result<()>;
+
     fn from_null_private_key(pk: PublicKey) -> Self;
 
     /// Convert this keypair to bytes.
@@ -0,0 +1,77 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Deterministic mapping of human account labels to HD derivation indexes.
+//!
+//! The index of a label is the first 4 bytes, big endian, of
+//! `SHA3-256(LABEL_INDEX_TAG || label)` with the top bit cleared, so it is always below the
+//! hardened offset `0x80000000` and can be used as is or hardened. The label is hashed as its
+//! exact UTF-8 bytes: wallets must agree on any normalization (case, trimming) before mapping.
+//!
+//! Indexes are 31 bits, so distinct labels may collide; check the labels of a wallet with
+//! `find_label_collisions` before deriving from them.
+//!
+
+use std::collections::BTreeMap;
+
+use sha3::{Digest, Sha3_256};
+
+/// The prefix hashed before every label.
+pub const LABEL_INDEX_TAG: &[u8] = b"symbol-label-index\0";
+
+/// Labels mapped to the same derivation index.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LabelCollision<'a> {
+    pub index: u32,
+    /// The colliding labels, in their input order.
+    pub labels: Vec<&'a str>,
+}
+
+/// Derive the derivation index of an account label.
+///
+/// # Inputs
+///
+/// * `label`: The account label, e.g. `savings`.
+///
+/// # Returns
+///
+/// The non hardened index, in `0..0x80000000`.
+pub fn derive_index_for_label(label: &str) -> u32 {
+    let mut h = Sha3_256::new();
+    h.update(LABEL_INDEX_TAG);
+    h.update(label.as_bytes());
+    let hash = h.finalize();
+
+    let mut index = [0u8; 4];
+    index.copy_from_slice(&hash[..4]);
+    u32::from_be_bytes(index) & 0x7FFF_FFFF
+}
+
+/// Find the labels mapped to the same derivation index.
+///
+/// Repeated labels are the same account and are not reported.
+///
+/// # Returns
+///
+/// The `LabelCollision`s, ordered by index; empty if every label has its own index.
+pub fn find_label_collisions<'a>(labels: &[&'a str]) -> Vec<LabelCollision<'a>> {
+    let mut indexes: BTreeMap<u32, Vec<&'a str>> = BTreeMap::new();
+    for &label in labels {
+        let entry = indexes.entry(derive_index_for_label(label)).or_default();
+        if !entry.contains(&label) {
+            entry.push(label);
+        }
+    }
+
+    indexes
+        .into_iter()
+        .filter(|(_, labels)| labels.len() > 1)
+        .map(|(index, labels)| LabelCollision { index, labels })
+        .collect()
+}
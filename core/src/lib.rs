@@ -8,6 +8,10 @@
 
 pub extern crate curve25519_dalek as curve25519;
 pub extern crate ed25519_dalek as ed25519;
+pub extern crate generic_array;
+pub extern crate zeroize;
+#[cfg(feature = "borsh")]
+pub extern crate borsh;
 #[macro_use]
 extern crate fixed_hash;
 #[cfg(feature = "serde")]
@@ -15,24 +19,107 @@ extern crate serde_crate as serde;
 
 pub use self::block_cipher::*;
 pub use self::constants::*;
+pub use self::derivation_path::*;
+pub use self::entropy::*;
+pub use self::envelope_mode::EnvelopeMode;
+pub use self::guarded_keypair::*;
 pub use self::hashes::*;
+#[cfg(feature = "heapless")]
+pub use self::heapless::StackBuf;
+pub use self::hex_display::HexDisplay;
+pub use self::hex_policy::HexPolicy;
+pub use self::key_audit::*;
+pub use self::key_summary::KeySummary;
 pub use self::keypair_schema::KeyPairSchema;
+pub use self::label_index::*;
+#[cfg(feature = "test-util")]
+pub use self::mock_signer::MockSigner;
 #[cfg(feature = "with_mnemonic")]
 pub use self::mnemonic::*;
 pub use self::private_key::*;
 pub use self::public_key::*;
+pub use self::raw_secret::*;
+#[cfg(any(feature = "serde", feature = "borsh"))]
+pub use self::secret_serde::SecretSerde;
+#[cfg(feature = "secrecy")]
+pub use self::secret_key::*;
+pub use self::rotating_keypair::RotatingKeypair;
+pub use self::scalar::*;
+pub use self::signable::{Signable, SigningBytes};
 pub use self::signature::*;
+pub use self::signer::Signer;
+pub use self::signature_audit::*;
+pub use self::signing_request::*;
 pub use self::utils::*;
+pub use self::verify_batch::VerifyItem;
+#[cfg(feature = "verifier_cache")]
+pub use self::verifier_cache::*;
 pub use self::keypair::*;
 
+#[cfg(any(feature = "borsh", feature = "bincode"))]
+pub mod binary;
 mod block_cipher;
+pub mod ceremony;
+#[cfg(feature = "json")]
+pub mod canonical_json;
 mod constants;
+mod derivation_path;
+#[cfg(feature = "enclave")]
+pub mod enclave;
+#[cfg(feature = "did")]
+pub mod did;
+mod entropy;
+pub mod envelope_mode;
+mod guarded_keypair;
+#[cfg(feature = "hash_arith")]
+mod hash_arith;
 mod hashes;
+#[cfg(feature = "hd")]
+pub mod hd;
+#[cfg(feature = "heapless")]
+mod heapless;
+mod hex_display;
+pub mod hex_policy;
+#[cfg(feature = "import")]
+pub mod import;
+mod key_audit;
+mod key_macros;
+mod key_summary;
 mod keypair_schema;
+pub mod kdf;
+mod label_index;
+#[cfg(feature = "memlock")]
+pub mod memlock;
+#[cfg(feature = "migration")]
+pub mod migration;
+#[cfg(feature = "test-util")]
+mod mock_signer;
 #[cfg(feature = "with_mnemonic")]
 mod mnemonic;
 mod private_key;
+pub mod possession;
 mod public_key;
+mod raw_secret;
+pub mod recovery;
+#[cfg(feature = "secrecy")]
+mod secret_key;
+#[cfg(any(feature = "serde", feature = "borsh"))]
+mod secret_serde;
+mod rotating_keypair;
+mod scalar;
+pub mod scalar_canonicality;
+#[doc(hidden)]
+pub mod sealed;
+pub mod shamir;
+mod signable;
 mod signature;
+mod signer;
+mod signature_audit;
+mod signing_request;
+#[cfg(feature = "test-keys")]
+pub mod test_keys;
 mod utils;
+mod verify_batch;
+#[cfg(feature = "verifier_cache")]
+mod verifier_cache;
 mod keypair;
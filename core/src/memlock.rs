@@ -0,0 +1,185 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Private keys kept in memory locked pages, never written to swap.
+//!
+//! A `LockedKeypair` moves the whole `Keypair`, private key and expanded secret included, to
+//! its own page aligned heap pages, locked with `mlock` on unix before the keypair is written to
+//! them. `mlock` works on whole pages and does not count the lockers of a page: no other
+//! allocation shares the pages of a keypair, so dropping one never unlocks another.
+//! Locking can fail, e.g. over `RLIMIT_MEMLOCK` or on other platforms: the keypair still
+//! works and `is_locked` reports it, so a deployment can refuse to start instead. The keypair
+//! is zeroed, then unlocked, on drop.
+//!
+//! Signatures are computed from the locked keypair in place, no copy of it is made; the
+//! signing scalars derived for a signature live on the stack for its duration.
+//!
+
+use std::alloc::{self, Layout};
+use std::fmt;
+use std::mem;
+use std::ptr::{self, NonNull};
+use std::sync::atomic::{compiler_fence, Ordering};
+
+use anyhow::Result;
+
+use super::{KeyPairSchema, Keypair, PrivateKey, PublicKey, Signature, Signer};
+
+/// A `Keypair` stored in locked memory.
+///
+pub struct LockedKeypair<Kp: KeyPairSchema> {
+    keypair: NonNull<Keypair<Kp>>,
+    layout: Layout,
+    locked: bool,
+}
+
+// the pages are owned by the `LockedKeypair`, as a `Box<Keypair<Kp>>` would be.
+unsafe impl<Kp: KeyPairSchema + Send> Send for LockedKeypair<Kp> {}
+unsafe impl<Kp: KeyPairSchema + Sync> Sync for LockedKeypair<Kp> {}
+
+impl<Kp: KeyPairSchema> LockedKeypair<Kp> {
+    /// Move a `Keypair` to locked memory, the moved value is zeroed.
+    ///
+    /// `Keypair` is `Copy`: copies the caller kept are not zeroed, move the last one here.
+    ///
+    pub fn from_keypair(mut kp: Keypair<Kp>) -> Self {
+        let layout = page_layout::<Keypair<Kp>>();
+        let pages = unsafe { alloc::alloc(layout) };
+        let keypair = NonNull::new(pages as *mut Keypair<Kp>)
+            .unwrap_or_else(|| alloc::handle_alloc_error(layout));
+
+        let locked = lock(pages, layout.size());
+        unsafe { keypair.as_ptr().write(kp) };
+        wipe(&mut kp);
+        Self {
+            keypair,
+            layout,
+            locked,
+        }
+    }
+
+    /// Construct a `LockedKeypair` from a `PrivateKey`.
+    ///
+    pub fn from_private_key(pk: PrivateKey) -> Self {
+        Self::from_keypair(Keypair::<Kp>::from_private_key(pk))
+    }
+
+    /// Whether the keypair memory is locked; `false` when `mlock` failed or is not
+    /// supported on this platform.
+    ///
+    pub fn is_locked(&self) -> bool {
+        self.locked
+    }
+
+    pub fn public_key(&self) -> PublicKey {
+        self.keypair().public_key()
+    }
+
+    /// Signs a data bytes, see `Keypair::try_sign`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the `Signature` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn try_sign(&self, data: &[u8]) -> Result<Signature> {
+        self.keypair().try_sign(data)
+    }
+
+    /// Verify a `Signature` on a data with this Keypair public key.
+    ///
+    pub fn verify(&self, data: &[u8], signature: Signature) -> Result<()> {
+        self.keypair().verify(data, signature)
+    }
+
+    // internal functions.
+    fn keypair(&self) -> &Keypair<Kp> {
+        // written in `from_keypair`, only wiped on drop.
+        unsafe { self.keypair.as_ref() }
+    }
+}
+
+impl<Kp: KeyPairSchema> Signer for LockedKeypair<Kp> {
+    fn public_key(&self) -> PublicKey {
+        LockedKeypair::public_key(self)
+    }
+
+    fn try_sign(&self, data: &[u8]) -> Result<Signature> {
+        LockedKeypair::try_sign(self, data)
+    }
+}
+
+impl<Kp: KeyPairSchema> fmt::Debug for LockedKeypair<Kp> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LockedKeypair")
+            .field("public_key", &self.public_key())
+            .field("private_key", &"[redacted]")
+            .field("locked", &self.locked)
+            .finish()
+    }
+}
+
+impl<Kp: KeyPairSchema> Drop for LockedKeypair<Kp> {
+    fn drop(&mut self) {
+        let pages = self.keypair.as_ptr() as *mut u8;
+        wipe(self.keypair.as_ptr());
+        if self.locked {
+            unlock(pages, self.layout.size());
+        }
+        unsafe { alloc::dealloc(pages, self.layout) };
+    }
+}
+
+/// Zero the bytes of a value with volatile writes; the value must not be read afterwards.
+fn wipe<T>(value: *mut T) {
+    let bytes = value as *mut u8;
+    for i in 0..mem::size_of::<T>() {
+        unsafe { ptr::write_volatile(bytes.add(i), 0) };
+    }
+    compiler_fence(Ordering::SeqCst);
+}
+
+/// The layout of the whole pages holding a `T`, aligned on a page.
+fn page_layout<T>() -> Layout {
+    let page_size = page_size().max(mem::align_of::<T>());
+    let size = mem::size_of::<T>().max(1);
+    Layout::from_size_align(size.div_ceil(page_size) * page_size, page_size)
+        .expect("page layout is valid.")
+}
+
+#[cfg(unix)]
+fn page_size() -> usize {
+    match unsafe { libc::sysconf(libc::_SC_PAGESIZE) } {
+        size if size > 0 => size as usize,
+        _ => 4096,
+    }
+}
+
+#[cfg(not(unix))]
+fn page_size() -> usize {
+    4096
+}
+
+#[cfg(unix)]
+fn lock(pages: *mut u8, len: usize) -> bool {
+    unsafe { libc::mlock(pages as *const libc::c_void, len) == 0 }
+}
+
+#[cfg(unix)]
+fn unlock(pages: *mut u8, len: usize) {
+    unsafe {
+        libc::munlock(pages as *const libc::c_void, len);
+    }
+}
+
+#[cfg(not(unix))]
+fn lock(_pages: *mut u8, _len: usize) -> bool {
+    false
+}
+
+#[cfg(not(unix))]
+fn unlock(_pages: *mut u8, _len: usize) {}
@@ -0,0 +1,419 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Migration of keypairs stored with the 0.1 serde format to the versioned `KeyMaterial`
+//! format.
+//!
+//! The 0.1 format is the 64 bytes `private key || public key` buffer, stored as is or through
+//! a serde format. `read_legacy` recognizes:
+//!
+//! * the raw 64 bytes;
+//! * bincode, the buffer prefixed by its length (`u64` little endian);
+//! * JSON, an array of 64 numbers.
+//!
+//! A `KeyMaterial` is `MIGRATION_MAGIC || version (u8) || kind (u8) || public key ||
+//! [private key]`, the private key only for the `KeyMaterialKind::Keypair` kind. Migrate to
+//! `KeyMaterialKind::PublicOnly` to drop the private keys of a store that only verifies.
+//!
+//! `migrate_dir` rewrites every legacy file of a directory tree in place, through a
+//! temporary file renamed over the original, so an interrupted migration leaves each file
+//! either legacy or migrated. Files already migrated are skipped, a migration can be run
+//! again until its report has no failure.
+//!
+
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, bail, ensure, Context, Result};
+use zeroize::Zeroizing;
+
+use super::{KeyPairSchema, Keypair, PrivateKey, PublicKey, KEYPAIR_LENGTH, KEY_BYTES_SIZE};
+
+/// The prefix of an encoded `KeyMaterial`.
+pub const MIGRATION_MAGIC: [u8; 4] = *b"SCKM";
+
+/// The version of the `KeyMaterial` encoding.
+pub const KEY_MATERIAL_VERSION: u8 = 1;
+
+const KEY_MATERIAL_HEADER_SIZE: usize = MIGRATION_MAGIC.len() + 2;
+
+// extensions of the files written next to the migrated one.
+const MIGRATING_EXTENSION: &str = "migrating";
+const BACKUP_EXTENSION: &str = "bak";
+
+/// The serde formats of 0.1 keypairs recognized by `read_legacy`.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LegacyEncoding {
+    /// The 64 bytes buffer.
+    Raw,
+    /// The buffer prefixed by its `u64` little endian length.
+    Bincode,
+    /// An array of 64 numbers.
+    Json,
+}
+
+/// What a `KeyMaterial` holds.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum KeyMaterialKind {
+    /// The public key alone.
+    PublicOnly = 0,
+    /// The public and private keys.
+    Keypair = 1,
+}
+
+/// Key material in the versioned format.
+///
+#[derive(Clone, PartialEq)]
+pub struct KeyMaterial {
+    pub public_key: PublicKey,
+    /// `None` for `KeyMaterialKind::PublicOnly`.
+    pub private_key: Option<PrivateKey>,
+}
+
+/// Options of `migrate_dir`.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MigrationOptions {
+    /// The kind of the migrated key material.
+    pub target: KeyMaterialKind,
+    /// Report what would be migrated without writing any file.
+    pub dry_run: bool,
+    /// Keep a copy of each legacy file, with the `.bak` extension appended.
+    pub keep_backup: bool,
+}
+
+/// The result of the migration of a file.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MigrationOutcome {
+    /// The file was a legacy keypair and is now migrated, or would be in a dry run.
+    Migrated(LegacyEncoding),
+    /// The file is already a `KeyMaterial`.
+    AlreadyMigrated,
+    /// The file is neither a legacy keypair nor a `KeyMaterial`, it is left untouched.
+    Unrecognized,
+    /// The file looks like a legacy keypair but could not be migrated, it is left untouched.
+    Failed(String),
+}
+
+/// The progress of `migrate_dir`, given to its callback after each file.
+///
+#[derive(Debug, Clone, Copy)]
+pub struct MigrationProgress<'a> {
+    pub path: &'a Path,
+    /// The number of files processed, this one included.
+    pub done: usize,
+    /// The number of files of the directory tree.
+    pub total: usize,
+    pub outcome: &'a MigrationOutcome,
+}
+
+/// The files processed by `migrate_dir`, by outcome.
+///
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MigrationReport {
+    pub migrated: Vec<PathBuf>,
+    pub already_migrated: Vec<PathBuf>,
+    pub unrecognized: Vec<PathBuf>,
+    pub failed: Vec<(PathBuf, String)>,
+}
+
+impl Default for MigrationOptions {
+    fn default() -> Self {
+        Self {
+            target: KeyMaterialKind::Keypair,
+            dry_run: false,
+            keep_backup: false,
+        }
+    }
+}
+
+impl KeyMaterial {
+    /// The key material of a keypair.
+    ///
+    pub fn from_keypair<Kp: KeyPairSchema>(kp: &Keypair<Kp>, kind: KeyMaterialKind) -> Self {
+        Self {
+            public_key: kp.public_key(),
+            private_key: match kind {
+                KeyMaterialKind::PublicOnly => None,
+                KeyMaterialKind::Keypair => Some(kp.private_key()),
+            },
+        }
+    }
+
+    /// What the key material holds.
+    ///
+    pub fn kind(&self) -> KeyMaterialKind {
+        match self.private_key {
+            Some(_) => KeyMaterialKind::Keypair,
+            None => KeyMaterialKind::PublicOnly,
+        }
+    }
+
+    /// The `Keypair` of a `KeyMaterialKind::Keypair` key material.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the `Keypair` or whose error value
+    /// is an `failure::Error` describing the error that occurred, e.g. a public key that
+    /// is not the one of the private key for the `Kp` schema.
+    pub fn keypair<Kp: KeyPairSchema>(&self) -> Result<Keypair<Kp>> {
+        let private_key = self
+            .private_key
+            .ok_or_else(|| anyhow!("key material has no private key."))?;
+        let kp = Keypair::<Kp>::from_private_key(private_key);
+        ensure!(
+            kp.public_key() == self.public_key,
+            "key material public key is not the one of its private key."
+        );
+        Ok(kp)
+    }
+
+    /// Encode the key material.
+    ///
+    pub fn to_bytes(&self) -> Zeroizing<Vec<u8>> {
+        let mut bytes = Vec::with_capacity(KEY_MATERIAL_HEADER_SIZE + KEYPAIR_LENGTH);
+        bytes.extend_from_slice(&MIGRATION_MAGIC);
+        bytes.push(KEY_MATERIAL_VERSION);
+        bytes.push(self.kind() as u8);
+        bytes.extend_from_slice(self.public_key.as_bytes());
+        if let Some(private_key) = &self.private_key {
+            bytes.extend_from_slice(private_key.as_bytes());
+        }
+        Zeroizing::new(bytes)
+    }
+
+    /// Decode a key material.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the `KeyMaterial` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        ensure!(is_key_material(bytes), "bytes are not a key material.");
+        let version = bytes[MIGRATION_MAGIC.len()];
+        ensure!(
+            version == KEY_MATERIAL_VERSION,
+            "unsupported key material version {}.",
+            version
+        );
+
+        let keys = &bytes[KEY_MATERIAL_HEADER_SIZE..];
+        let private_key = match bytes[MIGRATION_MAGIC.len() + 1] {
+            0 => {
+                ensure!(keys.len() == KEY_BYTES_SIZE, "public key material has unexpected size.");
+                None
+            }
+            1 => {
+                ensure!(keys.len() == KEYPAIR_LENGTH, "keypair material has unexpected size.");
+                Some(PrivateKey::from_slice(&keys[KEY_BYTES_SIZE..]))
+            }
+            kind => bail!("unknown key material kind {}.", kind),
+        };
+        Ok(Self {
+            public_key: PublicKey::from_slice(&keys[..KEY_BYTES_SIZE]),
+            private_key,
+        })
+    }
+}
+
+impl fmt::Debug for KeyMaterial {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KeyMaterial")
+            .field("public_key", &self.public_key)
+            .field("private_key", &self.private_key.map(|_| "<redacted>"))
+            .finish()
+    }
+}
+
+/// Whether bytes start like an encoded `KeyMaterial`.
+///
+pub fn is_key_material(bytes: &[u8]) -> bool {
+    bytes.len() >= KEY_MATERIAL_HEADER_SIZE && bytes[..MIGRATION_MAGIC.len()] == MIGRATION_MAGIC
+}
+
+/// Read a keypair stored with the 0.1 serde format.
+///
+/// The public key stored next to the private key must be the one of the `Kp` schema: a
+/// Symbol store read as Nis1 fails rather than migrating mismatched keys.
+///
+/// # Returns
+///
+/// A `Result` whose okay value is the `Keypair` and the encoding it was read from or whose
+/// error value is an `failure::Error` describing the error that occurred.
+pub fn read_legacy<Kp: KeyPairSchema>(blob: &[u8]) -> Result<(Keypair<Kp>, LegacyEncoding)> {
+    let (bytes, encoding) = legacy_bytes(blob)?;
+    let kp = Keypair::<Kp>::from_private_key(PrivateKey::from_slice(&bytes[..KEY_BYTES_SIZE]));
+    ensure!(
+        kp.public_key().as_bytes() == &bytes[KEY_BYTES_SIZE..],
+        "stored public key is not the one of the private key."
+    );
+    Ok((kp, encoding))
+}
+
+/// Migrate a keypair stored with the 0.1 serde format to an encoded `KeyMaterial`.
+///
+/// # Inputs
+///
+/// * `blob`: The legacy serialized keypair.
+///
+/// * `target`: The kind of the migrated key material.
+///
+/// # Returns
+///
+/// A `Result` whose okay value is the encoded `KeyMaterial` or whose error value
+/// is an `failure::Error` describing the error that occurred.
+pub fn migrate_blob<Kp: KeyPairSchema>(
+    blob: &[u8],
+    target: KeyMaterialKind,
+) -> Result<Zeroizing<Vec<u8>>> {
+    let (kp, _) = read_legacy::<Kp>(blob)?;
+    Ok(KeyMaterial::from_keypair(&kp, target).to_bytes())
+}
+
+/// Migrate every legacy keypair file of a directory tree.
+///
+/// Files are processed in path order; symbolic links and the temporary and backup files of
+/// a migration are ignored. A file that fails to migrate is reported and left untouched,
+/// it does not stop the migration of the others.
+///
+/// # Inputs
+///
+/// * `dir`: The root directory of the key store.
+///
+/// * `options`: The `MigrationOptions`.
+///
+/// * `progress`: Called after each file.
+///
+/// # Returns
+///
+/// A `Result` whose okay value is the `MigrationReport` or whose error value
+/// is an `failure::Error` describing the error that occurred while listing `dir`.
+pub fn migrate_dir<Kp, F>(
+    dir: &Path,
+    options: &MigrationOptions,
+    mut progress: F,
+) -> Result<MigrationReport>
+where
+    Kp: KeyPairSchema,
+    F: FnMut(MigrationProgress<'_>),
+{
+    let mut files = Vec::new();
+    collect_files(dir, &mut files)?;
+    files.sort();
+
+    let mut report = MigrationReport::default();
+    let total = files.len();
+    for (index, path) in files.into_iter().enumerate() {
+        let outcome = migrate_file::<Kp>(&path, options);
+        progress(MigrationProgress {
+            path: &path,
+            done: index + 1,
+            total,
+            outcome: &outcome,
+        });
+        match outcome {
+            MigrationOutcome::Migrated(_) => report.migrated.push(path),
+            MigrationOutcome::AlreadyMigrated => report.already_migrated.push(path),
+            MigrationOutcome::Unrecognized => report.unrecognized.push(path),
+            MigrationOutcome::Failed(reason) => report.failed.push((path, reason)),
+        }
+    }
+    Ok(report)
+}
+
+// internal functions.
+fn legacy_bytes(blob: &[u8]) -> Result<(Zeroizing<Vec<u8>>, LegacyEncoding)> {
+    if blob.len() == KEYPAIR_LENGTH {
+        return Ok((Zeroizing::new(blob.to_vec()), LegacyEncoding::Raw));
+    }
+    if blob.len() == 8 + KEYPAIR_LENGTH && blob[..8] == (KEYPAIR_LENGTH as u64).to_le_bytes() {
+        return Ok((Zeroizing::new(blob[8..].to_vec()), LegacyEncoding::Bincode));
+    }
+    if blob.iter().find(|c| !c.is_ascii_whitespace()) == Some(&b'[') {
+        let bytes = Zeroizing::new(
+            serde_json::from_slice::<Vec<u8>>(blob)
+                .map_err(|_| anyhow!("legacy json keypair is not a byte array."))?,
+        );
+        ensure!(
+            bytes.len() == KEYPAIR_LENGTH,
+            "legacy json keypair has unexpected size {}.",
+            bytes.len()
+        );
+        return Ok((bytes, LegacyEncoding::Json));
+    }
+    bail!("bytes are not a legacy keypair.")
+}
+
+fn migrate_file<Kp: KeyPairSchema>(path: &Path, options: &MigrationOptions) -> MigrationOutcome {
+    let blob = match fs::read(path) {
+        Ok(blob) => Zeroizing::new(blob),
+        Err(e) => return MigrationOutcome::Failed(format!("cannot read the file: {}.", e)),
+    };
+    if is_key_material(&blob) {
+        return MigrationOutcome::AlreadyMigrated;
+    }
+    if legacy_bytes(&blob).is_err() {
+        return MigrationOutcome::Unrecognized;
+    }
+
+    let result = read_legacy::<Kp>(&blob).and_then(|(kp, encoding)| {
+        if !options.dry_run {
+            let migrated = KeyMaterial::from_keypair(&kp, options.target).to_bytes();
+            replace_file(path, &blob, &migrated, options.keep_backup)?;
+        }
+        Ok(encoding)
+    });
+    match result {
+        Ok(encoding) => MigrationOutcome::Migrated(encoding),
+        Err(e) => MigrationOutcome::Failed(e.to_string()),
+    }
+}
+
+fn replace_file(path: &Path, legacy: &[u8], migrated: &[u8], keep_backup: bool) -> Result<()> {
+    if keep_backup {
+        fs::write(with_extension(path, BACKUP_EXTENSION), legacy)
+            .context("cannot write the backup file.")?;
+    }
+    let temporary = with_extension(path, MIGRATING_EXTENSION);
+    fs::write(&temporary, migrated).context("cannot write the migrated file.")?;
+    fs::rename(&temporary, path).context("cannot replace the legacy file.")?;
+    Ok(())
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    let entries =
+        fs::read_dir(dir).with_context(|| format!("cannot list directory {}.", dir.display()))?;
+    for entry in entries {
+        let entry = entry.with_context(|| format!("cannot list directory {}.", dir.display()))?;
+        let file_type = entry.file_type()?;
+        let path = entry.path();
+        if file_type.is_dir() {
+            collect_files(&path, files)?;
+        } else if file_type.is_file() && !is_migration_file(&path) {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+fn is_migration_file(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext == MIGRATING_EXTENSION || ext == BACKUP_EXTENSION)
+}
+
+fn with_extension(path: &Path, extension: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".");
+    name.push(extension);
+    PathBuf::from(name)
+}
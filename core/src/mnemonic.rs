@@ -6,12 +6,16 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+#[cfg(feature = "heapless")]
+use std::fmt::Write;
 use std::str::FromStr;
 
 use anyhow::Result;
 use bip39::{Language, Mnemonic};
 
-use super::PrivateKey;
+use super::{random_bytes, PrivateKey};
+#[cfg(feature = "heapless")]
+use super::{StackBuf, MNEMONIC_MAX_STR_SIZE};
 
 /// Re-construct a `PrivateKey` from the supplied mnemonic and password.
 ///
@@ -23,16 +27,28 @@ pub fn from_mnemonic(mnemonic: &str, password: &str) -> Result<PrivateKey> {
 /// Construct a `PrivateKey` and Mnemonic rand from the supplied password.
 ///
 pub fn create_with_mnemonic(password: &str) -> Result<(PrivateKey, String)> {
-    let mnemonic = Mnemonic::generate_in(Language::English, 24)?;
+    let mnemonic = Mnemonic::from_entropy_in(Language::English, &random_bytes::<32>())?;
     let secret_key = generate_with_mnemonic(mnemonic.clone(), password)?;
     Ok((secret_key, mnemonic.to_string()))
 }
 
+/// Construct a `PrivateKey` and Mnemonic rand from the supplied password, the mnemonic written
+/// in a stack buffer instead of a `String`.
+///
+#[cfg(feature = "heapless")]
+pub fn create_with_mnemonic_heapless(
+    password: &str,
+) -> Result<(PrivateKey, StackBuf<MNEMONIC_MAX_STR_SIZE>)> {
+    let mnemonic = Mnemonic::from_entropy_in(Language::English, &random_bytes::<32>())?;
+    let mut words = StackBuf::new();
+    write!(words, "{}", mnemonic)?;
+    let secret_key = generate_with_mnemonic(mnemonic, password)?;
+    Ok((secret_key, words))
+}
+
 fn generate_with_mnemonic(mnemonic: Mnemonic, password: &str) -> Result<PrivateKey> {
     let mut seed: PrivateKey = PrivateKey::default();
-    seed.assign_from_slice(
-        &(mnemonic.to_seed(password).to_vec())[..std::mem::size_of::<PrivateKey>()],
-    );
+    seed.assign_from_slice(&mnemonic.to_seed(password)[..std::mem::size_of::<PrivateKey>()]);
 
     Ok(seed)
 }
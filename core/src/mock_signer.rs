@@ -0,0 +1,89 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A scripted `Signer` for the unit tests of signing orchestration, without keys or randomness.
+//!
+
+use std::collections::VecDeque;
+use std::sync::{Mutex, MutexGuard};
+
+use anyhow::{anyhow, Result};
+
+use super::{PublicKey, Signature, Signer};
+
+/// A `Signer` returning pre-programmed signatures and errors, in order, and recording the
+/// data of every call.
+///
+/// The signatures are not checked, they do not have to verify with the public key.
+///
+#[derive(Debug, Default)]
+pub struct MockSigner {
+    public_key: PublicKey,
+    outputs: Mutex<VecDeque<std::result::Result<Signature, String>>>,
+    calls: Mutex<Vec<Vec<u8>>>,
+}
+
+impl MockSigner {
+    /// Construct a `MockSigner` of `public_key`, without any scripted output yet.
+    ///
+    pub fn new(public_key: PublicKey) -> Self {
+        Self {
+            public_key,
+            ..Default::default()
+        }
+    }
+
+    /// Return `signature` from the next unscripted call.
+    ///
+    pub fn with_signature(self, signature: Signature) -> Self {
+        self.lock_outputs().push_back(Ok(signature));
+        self
+    }
+
+    /// Fail the next unscripted call with `message`, e.g. a denied or timed out signing.
+    ///
+    pub fn with_error<S: Into<String>>(self, message: S) -> Self {
+        self.lock_outputs().push_back(Err(message.into()));
+        self
+    }
+
+    /// The data of every call, in order.
+    ///
+    pub fn calls(&self) -> Vec<Vec<u8>> {
+        self.lock_calls().clone()
+    }
+
+    /// The number of scripted outputs not returned yet.
+    ///
+    pub fn remaining(&self) -> usize {
+        self.lock_outputs().len()
+    }
+
+    // internal functions.
+    fn lock_outputs(&self) -> MutexGuard<'_, VecDeque<std::result::Result<Signature, String>>> {
+        self.outputs.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn lock_calls(&self) -> MutexGuard<'_, Vec<Vec<u8>>> {
+        self.calls.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Signer for MockSigner {
+    fn public_key(&self) -> PublicKey {
+        self.public_key
+    }
+
+    fn try_sign(&self, data: &[u8]) -> Result<Signature> {
+        self.lock_calls().push(data.to_vec());
+        match self.lock_outputs().pop_front() {
+            Some(output) => output.map_err(|e| anyhow!(e)),
+            None => Err(anyhow!("no scripted signature left.")),
+        }
+    }
+}
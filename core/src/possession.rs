@@ -0,0 +1,164 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Proof of private key possession, e.g. for an exchange to check that a user controls an
+//! account before whitelisting withdrawals to it.
+//!
+//! The verifier issues a `PossessionChallenge` bound to its domain and a validity window, the
+//! user signs it with `respond`, and the verifier checks the `PossessionResponse` with `verify`.
+//! The signed message is
+//! `POSSESSION_TAG || schema || 0x00 || domain || 0x00 || nonce || issued_at || expires_at`
+//! (timestamps as little endian `u64`), so a response can neither be replayed to another
+//! domain nor be mistaken for a transaction signature.
+//!
+
+use anyhow::{ensure, Result};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::{random_bytes, KeyPairSchema, Keypair, PublicKey, Signature, H256};
+
+/// The prefix of every signed possession challenge.
+pub const POSSESSION_TAG: &[u8] = b"symbol-crypto-core/possession/v1\0";
+
+/// A proof of possession challenge issued by a verifier.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct PossessionChallenge {
+    /// The verifier domain, e.g. `exchange.example.com`.
+    pub domain: String,
+    /// Random nonce of the challenge.
+    pub nonce: H256,
+    /// Unix timestamp (seconds) of the challenge creation.
+    pub issued_at: u64,
+    /// Unix timestamp (seconds) after which the challenge is rejected.
+    pub expires_at: u64,
+}
+
+/// The signed answer to a `PossessionChallenge`.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct PossessionResponse {
+    pub public_key: PublicKey,
+    pub signature: Signature,
+}
+
+impl PossessionChallenge {
+    /// The message signed by the response, for a schema.
+    ///
+    pub fn message<Kp: KeyPairSchema>(&self) -> Vec<u8> {
+        let mut message = POSSESSION_TAG.to_vec();
+        message.extend_from_slice(Kp::SCHEMA_NAME.as_bytes());
+        message.push(0);
+        message.extend_from_slice(self.domain.as_bytes());
+        message.push(0);
+        message.extend_from_slice(self.nonce.as_bytes());
+        message.extend_from_slice(&self.issued_at.to_le_bytes());
+        message.extend_from_slice(&self.expires_at.to_le_bytes());
+        message
+    }
+
+    // internal functions.
+    fn validate(&self) -> Result<()> {
+        ensure!(!self.domain.is_empty(), "challenge domain is empty.");
+        ensure!(
+            !self.domain.as_bytes().contains(&0),
+            "challenge domain contains a NUL byte."
+        );
+        ensure!(
+            self.expires_at > self.issued_at,
+            "challenge expires before it is issued."
+        );
+        Ok(())
+    }
+}
+
+/// Create a new random challenge.
+///
+/// # Inputs
+///
+/// * `domain`: The verifier domain.
+///
+/// * `now`: The current unix timestamp (seconds).
+///
+/// * `ttl`: The validity of the challenge in seconds.
+///
+/// # Returns
+///
+/// A `Result` whose okay value is a `PossessionChallenge` or whose error value
+/// is an `failure::Error` describing the error that occurred.
+pub fn create_challenge(domain: &str, now: u64, ttl: u64) -> Result<PossessionChallenge> {
+    let challenge = PossessionChallenge {
+        domain: domain.to_string(),
+        nonce: H256::from(random_bytes::<32>()),
+        issued_at: now,
+        expires_at: now.saturating_add(ttl),
+    };
+    challenge.validate()?;
+    Ok(challenge)
+}
+
+/// Answer a challenge with the account `Keypair`.
+///
+/// # Returns
+///
+/// A `Result` whose okay value is a `PossessionResponse` or whose error value
+/// is an `failure::Error` describing the error that occurred.
+pub fn respond<Kp: KeyPairSchema>(
+    kp: &Keypair<Kp>,
+    challenge: &PossessionChallenge,
+) -> Result<PossessionResponse> {
+    challenge.validate()?;
+    Ok(PossessionResponse {
+        public_key: kp.public_key(),
+        signature: kp.try_sign(&challenge.message::<Kp>())?,
+    })
+}
+
+/// Verify the response to a challenge for an expected public key.
+///
+/// # Inputs
+///
+/// * `public_key`: The public key of the account to whitelist.
+///
+/// * `challenge`: The challenge issued by this verifier.
+///
+/// * `response`: The user response.
+///
+/// * `now`: The current unix timestamp (seconds).
+///
+/// # Returns
+///
+/// Returns `Ok` if the response proves the possession of the `public_key` private key.
+///
+pub fn verify<Kp: KeyPairSchema>(
+    public_key: &PublicKey,
+    challenge: &PossessionChallenge,
+    response: &PossessionResponse,
+    now: u64,
+) -> Result<()> {
+    challenge.validate()?;
+    ensure!(now >= challenge.issued_at, "challenge is not valid yet.");
+    ensure!(now <= challenge.expires_at, "challenge is expired.");
+    ensure!(
+        response.public_key == *public_key,
+        "response public key does not match."
+    );
+
+    Kp::from_null_private_key(*public_key).verify(&challenge.message::<Kp>(), response.signature)
+}
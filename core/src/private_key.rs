@@ -6,13 +6,17 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-use anyhow::Result;
+use anyhow::{anyhow, bail, ensure, Result};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 #[cfg(feature = "serde")]
 use serde_bytes::{ByteBuf as SerdeByteBuf, Bytes as SerdeBytes};
+use zeroize::{Zeroize, Zeroizing};
 
-use super::{block_cipher::BlockCipher, KEY_BYTES_SIZE};
+use super::{
+    block_cipher::BlockCipher, ct_hex_decode_into, ct_hex_encode, ct_hex_encode_into, hex_to_bytes,
+    is_hex, PublicKey, KEY_BYTES_SIZE, KEY_STR_SIZE,
+};
 
 #[cfg(feature = "with_mnemonic")]
 use crate::mnemonic;
@@ -22,6 +26,74 @@ construct_fixed_hash! {
     pub struct PrivateKey(KEY_BYTES_SIZE);
 }
 
+impl PrivateKey {
+    /// Construct a `PrivateKey` from a hex string in a const context, see `private_key!`.
+    ///
+    /// # Panics
+    ///
+    /// If the string is not 64 hex characters.
+    pub const fn from_hex_const(hex: &str) -> Self {
+        Self(hex_to_bytes(hex))
+    }
+
+    /// Parse a `PrivateKey` from 64 hex characters in constant time, see `ct_hex_decode`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the `PrivateKey` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn from_hex_ct(hex: &str) -> Result<Self> {
+        ensure!(!hex.is_empty(), "private_key it's not hex.");
+        if hex.len() != KEY_STR_SIZE {
+            ensure!(
+                hex.len().is_multiple_of(2) && is_hex(hex),
+                "private_key it's not hex."
+            );
+            bail!("private key has unexpected size {}", hex.len());
+        }
+
+        let mut bytes = Zeroizing::new([0u8; KEY_BYTES_SIZE]);
+        ct_hex_decode_into(hex, &mut bytes[..]).map_err(|_| anyhow!("private_key it's not hex."))?;
+        Ok(Self(*bytes))
+    }
+
+    /// Format the `PrivateKey` as uppercase hex in constant time, see `ct_hex_encode`.
+    ///
+    pub fn to_hex_ct(&self) -> Zeroizing<String> {
+        Zeroizing::new(ct_hex_encode(self.as_bytes(), true))
+    }
+
+    /// Format the `PrivateKey` as uppercase hex ascii bytes in constant time, without allocating.
+    ///
+    pub fn to_hex_ct_array(&self) -> Zeroizing<[u8; KEY_STR_SIZE]> {
+        let mut hex = Zeroizing::new([0u8; KEY_STR_SIZE]);
+        ct_hex_encode_into(self.as_bytes(), true, &mut hex[..])
+            .expect("hex buffer has the encoded size");
+        hex
+    }
+
+    /// The key bytes in the order Nis1 hashes them, see `from_nis1_order`.
+    ///
+    /// Nis1 keys are written as the hex of a big endian integer (nem-library, NanoWallet),
+    /// while the Keccak-512 key expansion and the shared key derivation take its little endian
+    /// bytes: the reversed `as_bytes`.
+    ///
+    pub fn reverse_for_nis1(&self) -> Zeroizing<[u8; KEY_BYTES_SIZE]> {
+        let mut bytes = Zeroizing::new(self.to_fixed_bytes());
+        bytes.reverse();
+        bytes
+    }
+
+    /// Construct a `PrivateKey` from bytes in Nis1 hashing order, the inverse of
+    /// `reverse_for_nis1`.
+    ///
+    pub fn from_nis1_order(bytes: &[u8; KEY_BYTES_SIZE]) -> Self {
+        let mut key = Self(*bytes);
+        key.0.reverse();
+        key
+    }
+}
+
 #[cfg(feature = "with_mnemonic")]
 impl PrivateKey {
     /// Constructs a `PrivateKey` the supplied mnemonic and password
@@ -39,20 +111,61 @@ impl PrivateKey {
 }
 
 impl PrivateKey {
+    /// Encode a message text with the cipher `C`, see `BlockCipher::encrypt_for`.
+    ///
+    pub fn encrypt_for<C: BlockCipher>(
+        &self,
+        receiver_pk: &PublicKey,
+        msg: &[u8],
+    ) -> Result<Vec<u8>> {
+        C::encrypt_for(self, receiver_pk, msg)
+    }
+
+    /// Decrypt a message text with the cipher `C`, see `BlockCipher::decrypt_from`.
+    ///
+    pub fn decrypt_from<C: BlockCipher>(
+        &self,
+        signer_pk: &PublicKey,
+        enc_msg: &[u8],
+    ) -> Result<Vec<u8>> {
+        C::decrypt_from(self, signer_pk, enc_msg)
+    }
+
+    #[deprecated(note = "use `encrypt_for`, which takes a typed `PublicKey`")]
     pub fn encrypt_message<C: BlockCipher>(
         &self,
         receiver_pk: &[u8; KEY_BYTES_SIZE],
         msg: &[u8],
     ) -> Result<Vec<u8>> {
-        C::encrypt_message(self.as_fixed_bytes(), receiver_pk, msg)
+        self.encrypt_for::<C>(&receiver_pk.into(), msg)
     }
 
+    #[deprecated(note = "use `decrypt_from`, which takes a typed `PublicKey`")]
     pub fn decrypt_message<C: BlockCipher>(
         &self,
         signer_pk: &[u8; KEY_BYTES_SIZE],
         enc_msg: &[u8],
     ) -> Result<Vec<u8>> {
-        C::decrypt_message(self.as_fixed_bytes(), signer_pk, enc_msg)
+        self.decrypt_from::<C>(&signer_pk.into(), enc_msg)
+    }
+}
+
+impl From<&ed25519::SecretKey> for PrivateKey {
+    fn from(secret_key: &ed25519::SecretKey) -> Self {
+        Self(secret_key.to_bytes())
+    }
+}
+
+impl From<PrivateKey> for ed25519::SecretKey {
+    fn from(private_key: PrivateKey) -> Self {
+        ed25519::SecretKey::from_bytes(private_key.as_bytes())
+            .expect("unexpected error in ed25519 SecretKey length")
+    }
+}
+
+impl Zeroize for PrivateKey {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
     }
 }
 
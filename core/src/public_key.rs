@@ -6,18 +6,103 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+use anyhow::{anyhow, ensure, Result};
+use curve25519::edwards::{CompressedEdwardsY, EdwardsPoint};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 #[cfg(feature = "serde")]
 use serde_bytes::{ByteBuf as SerdeByteBuf, Bytes as SerdeBytes};
 
-use super::KEY_BYTES_SIZE;
+use super::{hex_to_bytes, KEY_BYTES_SIZE};
 
 construct_fixed_hash! {
     /// 256 bit hash type.
     pub struct PublicKey(KEY_BYTES_SIZE);
 }
 
+impl PublicKey {
+    /// Construct a `PublicKey` from a hex string in a const context, see `public_key!`.
+    ///
+    /// # Panics
+    ///
+    /// If the string is not 64 hex characters.
+    pub const fn from_hex_const(hex: &str) -> Self {
+        Self(hex_to_bytes(hex))
+    }
+
+    /// The compressed Edwards y coordinate of this public key, without validation.
+    ///
+    pub fn to_compressed(&self) -> CompressedEdwardsY {
+        CompressedEdwardsY(self.to_fixed_bytes())
+    }
+
+    /// Decompress this public key to a curve point, for custom protocols on managed keys.
+    ///
+    /// The encoding must be canonical and the point must not be of small order, the
+    /// identity included.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is an `EdwardsPoint` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn to_point(&self) -> Result<EdwardsPoint> {
+        let point = self
+            .to_compressed()
+            .decompress()
+            .ok_or_else(|| anyhow!("PointDecompressionError"))?;
+        ensure!(
+            point.compress() == self.to_compressed(),
+            "public key is not a canonical point encoding."
+        );
+        ensure!(!point.is_small_order(), "public key is a small order point.");
+        Ok(point)
+    }
+
+    /// Construct a `PublicKey` from a compressed point, with the `to_point` validation.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is a `PublicKey` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn from_compressed(compressed: &CompressedEdwardsY) -> Result<Self> {
+        let public_key = Self(compressed.to_bytes());
+        public_key.to_point()?;
+        Ok(public_key)
+    }
+
+    /// Construct a `PublicKey` from a curve point.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is a `PublicKey` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn from_point(point: &EdwardsPoint) -> Result<Self> {
+        ensure!(!point.is_small_order(), "public key is a small order point.");
+        Ok(Self(point.compress().to_bytes()))
+    }
+}
+
+impl From<ed25519::PublicKey> for PublicKey {
+    fn from(public_key: ed25519::PublicKey) -> Self {
+        Self(public_key.to_bytes())
+    }
+}
+
+impl From<&ed25519::PublicKey> for PublicKey {
+    fn from(public_key: &ed25519::PublicKey) -> Self {
+        Self(public_key.to_bytes())
+    }
+}
+
+impl TryFrom<PublicKey> for ed25519::PublicKey {
+    type Error = anyhow::Error;
+
+    /// Fails if the public key is not a valid curve point.
+    fn try_from(public_key: PublicKey) -> Result<Self> {
+        Ok(ed25519::PublicKey::from_bytes(public_key.as_bytes())?)
+    }
+}
+
 #[cfg(feature = "serde")]
 impl Serialize for PublicKey {
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
@@ -0,0 +1,141 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Raw 32 byte ed25519 secrets, without ASN.1 or PKCS#8 framing.
+//!
+//! An ed25519 secret comes in two 32 byte forms that are easily mixed up:
+//!
+//! * the seed, `Seed32`: the private key as stored, exported and shown to users (RFC 8032
+//!   calls it the private key). It is hashed, SHA-512 for Symbol or Keccak-512 of the reversed
+//!   bytes for Nis1, to derive the secret scalar and the nonce prefix;
+//! * the scalar, `Scalar32`: the clamped lower half of that hash, what some HSMs and libraries
+//!   (e.g. libsodium `crypto_sign_ed25519_sk_to_curve25519`, or an "expanded" key) export.
+//!
+//! Passing one where the other is expected gives a valid looking but unrelated key, so the
+//! cipher and keypair APIs take these types instead of a bare `[u8; 32]`. There is no
+//! conversion between them: a scalar cannot give back its seed, and a seed gives a different
+//! scalar for each schema.
+//!
+
+use std::fmt;
+
+use anyhow::Result;
+use curve25519::scalar::Scalar;
+use zeroize::Zeroize;
+
+use super::{check_clamped_scalar, PrivateKey, KEY_BYTES_SIZE};
+
+/// A 32 byte ed25519 seed, the private key that is hashed to derive the secret scalar.
+///
+/// It is zeroed on drop.
+///
+#[derive(Clone, PartialEq, Eq)]
+pub struct Seed32([u8; KEY_BYTES_SIZE]);
+
+/// A 32 byte clamped ed25519 secret scalar, little endian.
+///
+/// It is zeroed on drop.
+///
+#[derive(Clone, PartialEq, Eq)]
+pub struct Scalar32([u8; KEY_BYTES_SIZE]);
+
+impl Seed32 {
+    /// Construct a `Seed32` from raw seed bytes, e.g. an exported private key.
+    ///
+    pub fn new(bytes: [u8; KEY_BYTES_SIZE]) -> Self {
+        Self(bytes)
+    }
+
+    /// View the seed as a byte array.
+    ///
+    pub fn as_bytes(&self) -> &[u8; KEY_BYTES_SIZE] {
+        &self.0
+    }
+
+    /// The seed as a `PrivateKey`.
+    ///
+    pub fn to_private_key(&self) -> PrivateKey {
+        PrivateKey::from(self.0)
+    }
+}
+
+impl Scalar32 {
+    /// Construct a `Scalar32` from raw scalar bytes, little endian.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the `Scalar32` or whose error value
+    /// is an `failure::Error` describing the error that occurred, e.g. the bytes are not a
+    /// clamped scalar, the usual sign that a seed was given instead.
+    pub fn new(bytes: [u8; KEY_BYTES_SIZE]) -> Result<Self> {
+        check_clamped_scalar(bytes)?;
+        Ok(Self(bytes))
+    }
+
+    /// View the scalar as a byte array.
+    ///
+    pub fn as_bytes(&self) -> &[u8; KEY_BYTES_SIZE] {
+        &self.0
+    }
+
+    /// The scalar as a curve25519 `Scalar`, unreduced like the secret scalars of ed25519.
+    ///
+    pub fn to_scalar(&self) -> Scalar {
+        Scalar::from_bits(self.0)
+    }
+}
+
+impl From<PrivateKey> for Seed32 {
+    fn from(private_key: PrivateKey) -> Self {
+        Self(private_key.to_fixed_bytes())
+    }
+}
+
+impl<'a> From<&'a PrivateKey> for Seed32 {
+    fn from(private_key: &'a PrivateKey) -> Self {
+        Self(private_key.to_fixed_bytes())
+    }
+}
+
+impl<'a> From<&'a Seed32> for PrivateKey {
+    fn from(seed: &'a Seed32) -> Self {
+        seed.to_private_key()
+    }
+}
+
+impl TryFrom<[u8; KEY_BYTES_SIZE]> for Scalar32 {
+    type Error = anyhow::Error;
+
+    fn try_from(bytes: [u8; KEY_BYTES_SIZE]) -> Result<Self> {
+        Self::new(bytes)
+    }
+}
+
+impl fmt::Debug for Seed32 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Seed32(..)")
+    }
+}
+
+impl fmt::Debug for Scalar32 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Scalar32(..)")
+    }
+}
+
+impl Drop for Seed32 {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl Drop for Scalar32 {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
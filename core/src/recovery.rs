@@ -0,0 +1,175 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Recovery of a private key from a partially readable hex backup.
+//!
+//! A pattern is the 64 characters private key hex where unreadable characters are written
+//! `?` (any hex digit) and ambiguous characters as a bracket group of the possible digits,
+//! e.g. `[8B]` or `[0D]`. Every candidate is derived and checked against the known public key,
+//! or any caller supplied check such as an address, on all the available cores.
+//!
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+use anyhow::{anyhow, ensure, Result};
+
+use super::{KeyPairSchema, PrivateKey, PublicKey, KEY_BYTES_SIZE};
+
+/// Maximum number of unknown or ambiguous characters of a pattern.
+pub const RECOVERY_MAX_UNKNOWN: usize = 8;
+
+const HEX_DIGITS: &[u8] = b"0123456789ABCDEF";
+
+/// A parsed private key recovery pattern.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecoveryPattern {
+    // the possible digits of every character, a single one when known.
+    chars: Vec<Vec<u8>>,
+}
+
+impl RecoveryPattern {
+    /// Parse a recovery pattern.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is a `RecoveryPattern` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn parse(pattern: &str) -> Result<Self> {
+        let mut chars = vec![];
+        let mut input = pattern.bytes();
+        while let Some(c) = input.next() {
+            match c.to_ascii_uppercase() {
+                b'?' => chars.push(HEX_DIGITS.to_vec()),
+                b'[' => {
+                    let mut group = vec![];
+                    loop {
+                        let c = input
+                            .next()
+                            .ok_or_else(|| anyhow!("unterminated [ group in pattern."))?
+                            .to_ascii_uppercase();
+                        if c == b']' {
+                            break;
+                        }
+                        ensure!(
+                            HEX_DIGITS.contains(&c),
+                            "invalid character {} in pattern group.",
+                            c as char
+                        );
+                        if !group.contains(&c) {
+                            group.push(c);
+                        }
+                    }
+                    ensure!(!group.is_empty(), "empty [] group in pattern.");
+                    chars.push(group);
+                }
+                c if HEX_DIGITS.contains(&c) => chars.push(vec![c]),
+                c => return Err(anyhow!("invalid character {} in pattern.", c as char)),
+            }
+        }
+
+        ensure!(
+            chars.len() == KEY_BYTES_SIZE * 2,
+            "pattern has {} characters, expected {}.",
+            chars.len(),
+            KEY_BYTES_SIZE * 2
+        );
+        let unknown = chars.iter().filter(|c| c.len() > 1).count();
+        ensure!(
+            unknown <= RECOVERY_MAX_UNKNOWN,
+            "pattern has {} unknown characters, at most {} are supported.",
+            unknown,
+            RECOVERY_MAX_UNKNOWN
+        );
+
+        Ok(Self { chars })
+    }
+
+    /// The number of candidate private keys.
+    ///
+    pub fn candidates(&self) -> u64 {
+        self.chars.iter().map(|c| c.len() as u64).product()
+    }
+
+    /// The candidate private key at `index`, in `0..candidates()`.
+    ///
+    pub fn candidate(&self, mut index: u64) -> PrivateKey {
+        let mut hex = [0u8; KEY_BYTES_SIZE * 2];
+        for (i, options) in self.chars.iter().enumerate().rev() {
+            let len = options.len() as u64;
+            hex[i] = options[(index % len) as usize];
+            index /= len;
+        }
+
+        let mut bytes = [0u8; KEY_BYTES_SIZE];
+        hex::decode_to_slice(hex, &mut bytes).expect("pattern characters are hex digits");
+        PrivateKey::from(bytes)
+    }
+}
+
+/// Search the private key of a pattern whose public key satisfies `check`.
+///
+/// # Inputs
+///
+/// * `pattern`: The recovery pattern.
+///
+/// * `check`: Called with every candidate public key, returns `true` for the expected key.
+///
+/// # Returns
+///
+/// A `Result` whose okay value is the recovered `PrivateKey`, or `None` when no candidate
+/// matches, or whose error value is an `failure::Error` describing the error that occurred.
+pub fn recover_private_key<Kp, F>(pattern: &str, check: F) -> Result<Option<PrivateKey>>
+where
+    Kp: KeyPairSchema,
+    F: Fn(&PublicKey) -> bool + Sync,
+{
+    let pattern = RecoveryPattern::parse(pattern)?;
+    let candidates = pattern.candidates();
+    let workers = thread::available_parallelism()
+        .map(|n| n.get() as u64)
+        .unwrap_or(1)
+        .min(candidates);
+
+    let found = AtomicBool::new(false);
+    let result = Mutex::new(None);
+
+    thread::scope(|scope| {
+        for worker in 0..workers {
+            let (pattern, check, found, result) = (&pattern, &check, &found, &result);
+            scope.spawn(move || {
+                let mut index = worker;
+                while index < candidates && !found.load(Ordering::Relaxed) {
+                    let private_key = pattern.candidate(index);
+                    if check(&Kp::from_private_key(private_key).public_key()) {
+                        found.store(true, Ordering::Relaxed);
+                        *result.lock().unwrap() = Some(private_key);
+                    }
+                    index += workers;
+                }
+            });
+        }
+    });
+
+    Ok(result.into_inner().unwrap())
+}
+
+/// Search the private key of a pattern matching a known public key.
+///
+/// # Returns
+///
+/// A `Result` whose okay value is the recovered `PrivateKey`, or `None` when no candidate
+/// matches, or whose error value is an `failure::Error` describing the error that occurred.
+pub fn recover_for_public_key<Kp: KeyPairSchema>(
+    pattern: &str,
+    public_key: &PublicKey,
+) -> Result<Option<PrivateKey>> {
+    recover_private_key::<Kp, _>(pattern, |candidate| candidate == public_key)
+}
@@ -0,0 +1,142 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use anyhow::{anyhow, Result};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+#[cfg(feature = "serde")]
+use super::SecretSerde;
+use super::{KeyPairSchema, Keypair, PublicKey, Signature};
+
+/// A signing key that can be rotated without breaking the verification of recent signatures.
+///
+/// New signatures are always made with `current`; signatures made with the `previous` key
+/// keep verifying until it is retired.
+///
+/// The serde implementation stores the current private key, like `SecretSerde`: treat the
+/// serialized form as secret.
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate", bound = "")
+)]
+pub struct RotatingKeypair<Kp: KeyPairSchema> {
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            serialize_with = "serialize_secret",
+            deserialize_with = "deserialize_secret"
+        )
+    )]
+    pub current: Keypair<Kp>,
+    pub previous: Option<PublicKey>,
+    /// Unix timestamp (seconds) from which `current` is used.
+    pub valid_from: u64,
+}
+
+impl<Kp: KeyPairSchema> RotatingKeypair<Kp> {
+    /// Start with a single key.
+    ///
+    pub fn new(current: Keypair<Kp>, valid_from: u64) -> Self {
+        Self {
+            current,
+            previous: None,
+            valid_from,
+        }
+    }
+
+    /// Replace the current key, which stays valid for verification as `previous`.
+    ///
+    /// # Inputs
+    ///
+    /// * `next`: The new signing `Keypair`.
+    ///
+    /// * `valid_from`: Unix timestamp (seconds) from which `next` is used.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the retired previous public key, if any, or whose error
+    /// value is an `failure::Error` describing the error that occurred.
+    pub fn rotate(&mut self, next: Keypair<Kp>, valid_from: u64) -> Result<Option<PublicKey>> {
+        if valid_from < self.valid_from {
+            return Err(anyhow!(
+                "rotation valid from {} is before the current key {}.",
+                valid_from,
+                self.valid_from
+            ));
+        }
+        if next.public_key() == self.current.public_key() {
+            return Err(anyhow!("rotation keeps the same key."));
+        }
+
+        let retired = self.previous.replace(self.current.public_key());
+        self.current = next;
+        self.valid_from = valid_from;
+        Ok(retired)
+    }
+
+    /// Stop accepting signatures of the previous key.
+    ///
+    pub fn retire_previous(&mut self) -> Option<PublicKey> {
+        self.previous.take()
+    }
+
+    /// The public keys accepted for verification, current first.
+    ///
+    pub fn public_keys(&self) -> Vec<PublicKey> {
+        std::iter::once(self.current.public_key())
+            .chain(self.previous)
+            .collect()
+    }
+
+    /// Signs data with the current key, panics as `Keypair::sign` for a key that cannot sign.
+    ///
+    #[allow(deprecated)]
+    pub fn sign(&self, data: &[u8]) -> Signature {
+        self.current.sign(data)
+    }
+
+    /// Verify a `Signature` with the current or the previous key.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the public key that verified the signature or whose
+    /// error value is an `failure::Error` describing the error that occurred.
+    pub fn verify(&self, data: &[u8], signature: Signature) -> Result<PublicKey> {
+        if self.current.verify(data, signature).is_ok() {
+            return Ok(self.current.public_key());
+        }
+
+        match self.previous {
+            Some(previous) => {
+                Kp::from_null_private_key(previous).verify(data, signature)?;
+                Ok(previous)
+            }
+            None => Err(anyhow!("signature is not valid for the current key.")),
+        }
+    }
+}
+
+// internal functions.
+#[cfg(feature = "serde")]
+fn serialize_secret<Kp: KeyPairSchema, S: Serializer>(
+    kp: &Keypair<Kp>,
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    SecretSerde(*kp).serialize(serializer)
+}
+
+#[cfg(feature = "serde")]
+fn deserialize_secret<'d, Kp: KeyPairSchema, D: Deserializer<'d>>(
+    deserializer: D,
+) -> std::result::Result<Keypair<Kp>, D::Error> {
+    SecretSerde::<Keypair<Kp>>::deserialize(deserializer).map(|secret| secret.0)
+}
@@ -0,0 +1,96 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use anyhow::{bail, ensure, Result};
+use curve25519::scalar::Scalar;
+
+use super::scalar_canonicality::{self, Canonicality};
+use super::{H256, KEY_BYTES_SIZE};
+
+/// Clamp the bits of an ed25519 secret scalar.
+///
+/// The lowest three bits are cleared, the highest bit is cleared and the second highest
+/// bit is set, as required by RFC 8032 for both Symbol and Nis1 keys.
+///
+pub fn clamp_scalar_bits(mut bits: [u8; KEY_BYTES_SIZE]) -> [u8; KEY_BYTES_SIZE] {
+    bits[0] &= 0xF8; // The lowest three bits must be 0
+    bits[31] &= 0x7F; // The highest bit must be 0
+    bits[31] |= 0x40; // The second highest bit must be 1
+    bits
+}
+
+/// Check that the bytes are a clamped ed25519 secret scalar, see `clamp_scalar_bits`.
+///
+/// # Returns
+///
+/// A `Result` whose okay value is the unreduced `Scalar` or whose error value
+/// is an `failure::Error` describing the error that occurred.
+pub fn check_clamped_scalar(bytes: [u8; KEY_BYTES_SIZE]) -> Result<Scalar> {
+    ensure!(
+        clamp_scalar_bits(bytes) == bytes,
+        "secret scalar is not clamped."
+    );
+    Ok(Scalar::from_bits(bytes))
+}
+
+/// Construct a secret `Scalar` from the clamped bits of a hash.
+///
+/// The result is not reduced modulo the group order, like the secret scalars of ed25519.
+///
+pub fn clamped_scalar(hash: &H256) -> Scalar {
+    Scalar::from_bits(clamp_scalar_bits(hash.to_fixed_bytes()))
+}
+
+/// Convert a `H256` to a `Scalar`.
+///
+/// # Inputs
+///
+/// * `hash`: The little endian scalar bytes.
+///
+/// * `reduce`: Reduce the value modulo the group order when `true`, otherwise reject
+///   non-canonical values.
+///
+/// # Returns
+///
+/// A `Result` whose okay value is a `Scalar` or whose error value
+/// is an `failure::Error` describing the error that occurred.
+pub fn scalar_from_h256(hash: &H256, reduce: bool) -> Result<Scalar> {
+    if reduce {
+        Ok(Scalar::from_bytes_mod_order(hash.to_fixed_bytes()))
+    } else {
+        check_scalar(hash.to_fixed_bytes())
+    }
+}
+
+/// Check that the bytes are a canonical (fully reduced) `Scalar`.
+///
+/// See `scalar_canonicality::check` for the rules.
+///
+/// # Returns
+///
+/// A `Result` whose okay value is a `Scalar` or whose error value
+/// is an `failure::Error` describing the error that occurred.
+#[inline(always)]
+pub fn check_scalar(bytes: [u8; KEY_BYTES_SIZE]) -> Result<Scalar> {
+    match scalar_canonicality::check(&bytes) {
+        Canonicality::ReducedFast | Canonicality::Canonical => Ok(Scalar::from_bits(bytes)),
+        Canonicality::NonCanonical => bail!("ScalarFormatError"),
+    }
+}
+
+impl From<Scalar> for H256 {
+    fn from(scalar: Scalar) -> Self {
+        H256(scalar.to_bytes())
+    }
+}
+
+impl<'a> From<&'a Scalar> for H256 {
+    fn from(scalar: &'a Scalar) -> Self {
+        H256(scalar.to_bytes())
+    }
+}
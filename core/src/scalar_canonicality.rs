@@ -0,0 +1,54 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Canonicality rules of the signature `s` scalar, shared by the Symbol and Nis1 verify paths.
+//!
+//! A signature whose `s` is not reduced modulo the group order is malleable: `s + l` verifies
+//! as well as `s`. Both schemas reject such signatures, downstream validators should apply the
+//! same rules.
+//!
+
+use curve25519::scalar::Scalar;
+
+use super::KEY_BYTES_SIZE;
+
+/// The outcome of a scalar canonicality check.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Canonicality {
+    /// The 4 most significant bits are unset, the scalar is reduced without further check.
+    ReducedFast,
+    /// The scalar passed the full reduction check.
+    Canonical,
+    /// The scalar is not reduced modulo the group order.
+    NonCanonical,
+}
+
+impl Canonicality {
+    /// Whether the scalar is accepted.
+    ///
+    pub fn is_canonical(&self) -> bool {
+        *self != Canonicality::NonCanonical
+    }
+}
+
+/// Check the canonicality of little endian scalar bytes.
+///
+/// Since the order of the basepoint is roughly a 2^(252.5) bit number, scalars whose 4 most
+/// significant bits are unset are guaranteed to be reduced. This succeed-fast path should
+/// succeed for roughly half of all scalars; the others need the full reduction check.
+///
+pub fn check(bytes: &[u8; KEY_BYTES_SIZE]) -> Canonicality {
+    if bytes[31] & 240 == 0 {
+        Canonicality::ReducedFast
+    } else if Scalar::from_canonical_bytes(*bytes).is_some() {
+        Canonicality::Canonical
+    } else {
+        Canonicality::NonCanonical
+    }
+}
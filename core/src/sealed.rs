@@ -0,0 +1,19 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Sealing of the public traits.
+//!
+//! `KeyPairSchema` and `BlockCipher` are only implemented by the Symbol and Nis1 schemas of
+//! this workspace, so that methods can be added to them without a semver breaking release.
+//! This module is public for the `sym-crypto` and `nis1-crypto` crates only, it is not part of
+//! the API.
+//!
+
+/// The supertrait of the sealed public traits.
+///
+pub trait Sealed {}
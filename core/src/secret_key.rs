@@ -0,0 +1,137 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Entry points taking the private key in a zeroizing secret wrapper, for applications that
+//! keep their keys in `secrecy`, `Zeroizing` or their own wrappers.
+//!
+//! Any `AsRef<[u8]> + Zeroize` secret is accepted, e.g. `secrecy::Secret<[u8; 32]>` exposed
+//! with `expose_secret()`, or the `SecretKey` of this module. The `Keypair` constructors and
+//! the `BlockCipher` methods below read the private key only for the duration of the call. The
+//! `Keypair` built from a secret still holds a copy of the private key, as any other `Keypair`.
+//!
+
+use std::fmt;
+
+use anyhow::{ensure, Result};
+use zeroize::Zeroize;
+
+use super::{BlockCipher, KeyPairSchema, Keypair, PrivateKey, PublicKey, KEY_BYTES_SIZE};
+
+/// A `PrivateKey` zeroed on drop and redacted from `Debug`.
+///
+#[derive(Clone)]
+pub struct SecretKey(PrivateKey);
+
+impl SecretKey {
+    /// Wrap a `PrivateKey`.
+    ///
+    pub fn new(private_key: PrivateKey) -> Self {
+        Self(private_key)
+    }
+
+    /// The wrapped `PrivateKey`, keep the exposed copy as short lived as possible.
+    ///
+    pub fn expose_secret(&self) -> &PrivateKey {
+        &self.0
+    }
+}
+
+impl AsRef<[u8]> for SecretKey {
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_bytes()
+    }
+}
+
+impl Zeroize for SecretKey {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl Drop for SecretKey {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl fmt::Debug for SecretKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SecretKey([REDACTED])")
+    }
+}
+
+impl<Kp: KeyPairSchema> Keypair<Kp> {
+    /// Construct a `Keypair` from a secret private key.
+    ///
+    /// # Inputs
+    ///
+    /// * `secret_key`: The private key bytes, in any `AsRef<[u8]> + Zeroize` wrapper.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the `Keypair` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn from_secret_key<S: AsRef<[u8]> + Zeroize>(secret_key: &S) -> Result<Self> {
+        Ok(Self::from_private_key(private_key(secret_key)?))
+    }
+
+    /// The private key of this keypair, in a `SecretKey`.
+    ///
+    pub fn secret_key(&self) -> SecretKey {
+        SecretKey::new(self.private_key())
+    }
+}
+
+/// The `BlockCipher` methods taking the secret key in an `AsRef<[u8]> + Zeroize` wrapper,
+/// implemented for every cipher.
+///
+pub trait SecretBlockCipher: BlockCipher {
+    /// Encrypt a message from the signer to the receiver, see `BlockCipher::encrypt_for`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is an encrypt message `Vec<u8>` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    fn encrypt_for_secret<S: AsRef<[u8]> + Zeroize>(
+        signer_sk: &S,
+        receiver_pk: &PublicKey,
+        msg: &[u8],
+    ) -> Result<Vec<u8>> {
+        let signer_sk = SecretKey::new(private_key(signer_sk)?);
+        Self::encrypt_for(signer_sk.expose_secret(), receiver_pk, msg)
+    }
+
+    /// Decrypt a message the signer encrypted for the receiver, see
+    /// `BlockCipher::decrypt_from`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is an decrypted message `Vec<u8>` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    fn decrypt_from_secret<S: AsRef<[u8]> + Zeroize>(
+        receiver_sk: &S,
+        signer_pk: &PublicKey,
+        enc_msg: &[u8],
+    ) -> Result<Vec<u8>> {
+        let receiver_sk = SecretKey::new(private_key(receiver_sk)?);
+        Self::decrypt_from(receiver_sk.expose_secret(), signer_pk, enc_msg)
+    }
+}
+
+impl<C: BlockCipher> SecretBlockCipher for C {}
+
+// internal functions.
+fn private_key<S: AsRef<[u8]> + Zeroize>(secret_key: &S) -> Result<PrivateKey> {
+    let bytes = secret_key.as_ref();
+    ensure!(
+        bytes.len() == KEY_BYTES_SIZE,
+        "secret key must be {} bytes.",
+        KEY_BYTES_SIZE
+    );
+    Ok(PrivateKey::from_slice(bytes))
+}
@@ -0,0 +1,57 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(feature = "serde")]
+use serde::de::Error as SerdeError;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+#[cfg(feature = "serde")]
+use serde_bytes::{ByteBuf as SerdeByteBuf, Bytes as SerdeBytes};
+
+#[cfg(feature = "serde")]
+use super::KeyPairSchema;
+
+/// Explicit opt-in wrapper serializing the full keypair, private key included.
+///
+/// `Serialize` for keypairs only emits the public key, so secrets never leak through an
+/// unrelated struct serialization. Wrap a keypair in `SecretSerde` to store and restore it,
+/// the encoding is the `to_bytes` layout: private key followed by public key, with serde or
+/// borsh. `Keypair<Kp>` being a `KeyPairSchema` too, `SecretSerde<Keypair<Kp>>` and
+/// `SecretSerde<Kp>` share the same encoding.
+///
+/// # Migration
+///
+/// Keypairs used to serialize their private key and implement `Deserialize`. Replace
+/// `Keypair` fields that must round-trip with `SecretSerde<Keypair<_>>`, data serialized by
+/// previous versions is read back unchanged.
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SecretSerde<T>(pub T);
+
+#[cfg(feature = "serde")]
+impl<C: KeyPairSchema> Serialize for SecretSerde<C> {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        SerdeBytes::new(&self.0.to_bytes()[..]).serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'d, C: KeyPairSchema> Deserialize<'d> for SecretSerde<C> {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'d>,
+    {
+        let bytes = <SerdeByteBuf>::deserialize(deserializer)?;
+        C::from_bytes(bytes.as_ref())
+            .map(SecretSerde)
+            .map_err(SerdeError::custom)
+    }
+}
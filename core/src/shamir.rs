@@ -0,0 +1,257 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Shamir secret sharing over GF(2^8), byte by byte.
+//!
+//! Every byte of the secret is the constant term of a random polynomial of degree
+//! `threshold - 1` over GF(2^8) (reduction polynomial `x^8 + x^4 + x^3 + x + 1`); the share of
+//! index `x` holds the evaluations of these polynomials at `x`.
+//!
+//! Shares are refreshed (proactive secret sharing) by adding the evaluations of random
+//! polynomials of constant term zero: the new shares give the same secret, and old and new
+//! shares cannot be mixed. `refresh_shares` does it for shares held in one place; across
+//! holders, each one sends the `zero_shares` of its own polynomial to the others, who `refresh`
+//! their share with every received delta, so the secret is never reconstructed.
+//!
+
+use anyhow::{ensure, Result};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::random_bytes;
+
+/// A share of a secret.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct Share {
+    /// The evaluation point, `1..=255`.
+    pub index: u8,
+    /// The evaluations, one per secret byte.
+    pub value: Vec<u8>,
+}
+
+/// Split a secret in `count` shares, any `threshold` of them recovering it.
+///
+/// # Returns
+///
+/// A `Result` whose okay value is the `Share`s, of index `1..=count`, or whose error value
+/// is an `failure::Error` describing the error that occurred.
+pub fn split(secret: &[u8], threshold: u8, count: u8) -> Result<Vec<Share>> {
+    ensure!(threshold >= 1, "threshold must be at least 1.");
+    ensure!(
+        threshold <= count,
+        "threshold {} is greater than the share count {}.",
+        threshold,
+        count
+    );
+
+    let mut shares: Vec<Share> = (1..=count)
+        .map(|index| Share {
+            index,
+            value: Vec::with_capacity(secret.len()),
+        })
+        .collect();
+
+    let mut coefficients = vec![0u8; threshold as usize];
+    for &byte in secret {
+        coefficients[0] = byte;
+        for coefficient in coefficients[1..].iter_mut() {
+            *coefficient = random_bytes::<1>()[0];
+        }
+        for share in shares.iter_mut() {
+            share.value.push(evaluate(&coefficients, share.index));
+        }
+    }
+    Ok(shares)
+}
+
+/// Split the zero secret of `len` bytes for the given indexes, the deltas of a refresh.
+///
+/// # Returns
+///
+/// A `Result` whose okay value is one delta `Share` per index or whose error value
+/// is an `failure::Error` describing the error that occurred.
+pub fn zero_shares(indexes: &[u8], threshold: u8, len: usize) -> Result<Vec<Share>> {
+    ensure!(threshold >= 1, "threshold must be at least 1.");
+    check_indexes(indexes.iter())?;
+
+    let mut shares: Vec<Share> = indexes
+        .iter()
+        .map(|&index| Share {
+            index,
+            value: Vec::with_capacity(len),
+        })
+        .collect();
+
+    let mut coefficients = vec![0u8; threshold as usize];
+    for _ in 0..len {
+        for coefficient in coefficients[1..].iter_mut() {
+            *coefficient = random_bytes::<1>()[0];
+        }
+        for share in shares.iter_mut() {
+            share.value.push(evaluate(&coefficients, share.index));
+        }
+    }
+    Ok(shares)
+}
+
+/// Refresh shares to a new random polynomial of the same secret, without combining them.
+///
+/// # Inputs
+///
+/// * `shares`: The shares to refresh, all of them should be given: a share left out can no
+///   longer be combined with the refreshed ones.
+///
+/// * `threshold`: The threshold of the shares.
+///
+/// # Returns
+///
+/// A `Result` whose okay value is the refreshed `Share`s, with the same indexes, or whose
+/// error value is an `failure::Error` describing the error that occurred.
+pub fn refresh_shares(shares: &[Share], threshold: u8) -> Result<Vec<Share>> {
+    let len = check_shares(shares)?;
+    let indexes: Vec<u8> = shares.iter().map(|share| share.index).collect();
+    let deltas = zero_shares(&indexes, threshold, len)?;
+
+    shares
+        .iter()
+        .zip(deltas.iter())
+        .map(|(share, delta)| {
+            let mut share = share.clone();
+            share.refresh(delta)?;
+            Ok(share)
+        })
+        .collect()
+}
+
+impl Share {
+    /// Add a refresh delta of the same index, see `zero_shares`.
+    ///
+    pub fn refresh(&mut self, delta: &Share) -> Result<()> {
+        ensure!(
+            delta.index == self.index,
+            "delta index {} does not match the share index {}.",
+            delta.index,
+            self.index
+        );
+        ensure!(
+            delta.value.len() == self.value.len(),
+            "delta and share have different lengths."
+        );
+        self.value
+            .iter_mut()
+            .zip(delta.value.iter())
+            .for_each(|(y, d)| *y ^= d);
+        Ok(())
+    }
+}
+
+/// Recover a secret from at least `threshold` distinct shares.
+///
+/// Fewer shares than the threshold give a wrong secret, not an error: check the result, e.g.
+/// against a known public key.
+///
+/// # Returns
+///
+/// A `Result` whose okay value is the secret `Vec<u8>` or whose error value
+/// is an `failure::Error` describing the error that occurred.
+pub fn combine(shares: &[Share]) -> Result<Vec<u8>> {
+    let len = check_shares(shares)?;
+
+    // Lagrange basis at x = 0: l_i = prod_{j != i} x_j / (x_j - x_i), subtraction is xor.
+    let basis: Vec<u8> = shares
+        .iter()
+        .map(|share| {
+            shares
+                .iter()
+                .filter(|other| other.index != share.index)
+                .fold(1, |acc, other| {
+                    gf_mul(acc, gf_div(other.index, other.index ^ share.index))
+                })
+        })
+        .collect();
+
+    Ok((0..len)
+        .map(|k| {
+            shares
+                .iter()
+                .zip(basis.iter())
+                .fold(0, |acc, (share, &l)| acc ^ gf_mul(share.value[k], l))
+        })
+        .collect())
+}
+
+// internal functions.
+fn check_shares(shares: &[Share]) -> Result<usize> {
+    ensure!(!shares.is_empty(), "no shares to combine.");
+    let len = shares[0].value.len();
+    ensure!(
+        shares.iter().all(|share| share.value.len() == len),
+        "shares have different lengths."
+    );
+    check_indexes(shares.iter().map(|share| &share.index))?;
+    Ok(len)
+}
+
+fn check_indexes<'a>(indexes: impl Iterator<Item = &'a u8>) -> Result<()> {
+    let mut seen = [false; 256];
+    for &index in indexes {
+        ensure!(index != 0, "share index 0 is invalid.");
+        ensure!(!seen[index as usize], "duplicate share index {}.", index);
+        seen[index as usize] = true;
+    }
+    Ok(())
+}
+
+fn evaluate(coefficients: &[u8], x: u8) -> u8 {
+    // Horner evaluation from the highest degree.
+    coefficients
+        .iter()
+        .rev()
+        .fold(0, |acc, &c| gf_mul(acc, x) ^ c)
+}
+
+fn gf_mul(mut a: u8, mut b: u8) -> u8 {
+    let mut product = 0;
+    while b != 0 {
+        if b & 1 != 0 {
+            product ^= a;
+        }
+        let carry = a & 0x80;
+        a <<= 1;
+        if carry != 0 {
+            a ^= 0x1B;
+        }
+        b >>= 1;
+    }
+    product
+}
+
+fn gf_inv(a: u8) -> u8 {
+    // a^254 = a^-1 in GF(2^8).
+    let mut result = 1;
+    let mut base = a;
+    let mut exponent = 254u8;
+    while exponent != 0 {
+        if exponent & 1 != 0 {
+            result = gf_mul(result, base);
+        }
+        base = gf_mul(base, base);
+        exponent >>= 1;
+    }
+    result
+}
+
+fn gf_div(a: u8, b: u8) -> u8 {
+    gf_mul(a, gf_inv(b))
+}
@@ -0,0 +1,201 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Signing of application structs over a canonical byte encoding.
+//!
+//! The signed bytes of a `Signable` value are
+//! `domain length (u8) || SIGNING_DOMAIN || fields`, every field in the order of its
+//! `signable!` declaration and encoded by `SigningBytes`:
+//!
+//! * integers: little endian, `bool` as one byte;
+//! * keys, signatures, hashes and byte arrays: their bytes;
+//! * byte strings, strings and vectors: `length (u32 little endian) || items`;
+//! * options: `0` or `1 || value`.
+//!
+//! Every variable size field is length prefixed, so two values never have the same encoding.
+//!
+
+use anyhow::Result;
+
+use super::{KeyPairSchema, Keypair, PublicKey, Signature, H256, H512};
+
+/// The canonical encoding of a field of a `Signable` struct.
+///
+pub trait SigningBytes {
+    /// Append the encoding of `self` to `out`.
+    ///
+    fn write_signing_bytes(&self, out: &mut Vec<u8>);
+}
+
+/// A struct signed over its canonical encoding, implemented with `signable!`.
+///
+pub trait Signable {
+    /// The domain of the signatures of this type, e.g. `"my-app/order/v1"`, so that they are
+    /// never valid for any other signed data.
+    const SIGNING_DOMAIN: &'static str;
+
+    /// Append the encoding of the fields to `out`.
+    ///
+    fn write_fields(&self, out: &mut Vec<u8>);
+
+    /// The signed bytes, `domain length (u8) || SIGNING_DOMAIN || fields`.
+    ///
+    fn signing_bytes(&self) -> Vec<u8> {
+        let domain = Self::SIGNING_DOMAIN.as_bytes();
+        assert!(domain.len() <= u8::MAX as usize, "signing domain too long");
+
+        let mut out = Vec::with_capacity(1 + domain.len());
+        out.push(domain.len() as u8);
+        out.extend_from_slice(domain);
+        self.write_fields(&mut out);
+        out
+    }
+
+    /// Sign the value.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the `Signature` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    fn sign_with<Kp: KeyPairSchema>(&self, keypair: &Keypair<Kp>) -> Result<Signature> {
+        keypair.try_sign(&self.signing_bytes())
+    }
+
+    /// Verify a signature of the value.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok` if the signature is valid.
+    fn verify<Kp: KeyPairSchema>(
+        &self,
+        public_key: &PublicKey,
+        signature: Signature,
+    ) -> Result<()> {
+        Kp::from_null_private_key(*public_key).verify(&self.signing_bytes(), signature)
+    }
+}
+
+/// Implement `Signable` for a struct from its signing domain and all its fields, in signing
+/// order. A field missing from the list does not compile.
+///
+/// ```
+/// use core_crypto::{signable, PublicKey};
+///
+/// struct Order {
+///     id: u64,
+///     recipient: PublicKey,
+///     memo: String,
+/// }
+///
+/// signable!(Order, "my-app/order/v1", { id, recipient, memo });
+/// ```
+#[macro_export]
+macro_rules! signable {
+    ($name:ident, $domain:expr, { $($field:ident),+ $(,)? }) => {
+        impl $crate::Signable for $name {
+            const SIGNING_DOMAIN: &'static str = $domain;
+
+            fn write_fields(&self, out: &mut Vec<u8>) {
+                let $name { $($field),+ } = self;
+                $( $crate::SigningBytes::write_signing_bytes($field, out); )+
+            }
+        }
+    };
+}
+
+macro_rules! impl_signing_bytes_int {
+    ($($int:ty),*) => {
+        $(
+            impl SigningBytes for $int {
+                fn write_signing_bytes(&self, out: &mut Vec<u8>) {
+                    out.extend_from_slice(&self.to_le_bytes());
+                }
+            }
+        )*
+    };
+}
+
+impl_signing_bytes_int!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128);
+
+macro_rules! impl_signing_bytes_fixed {
+    ($($fixed:ty),*) => {
+        $(
+            impl SigningBytes for $fixed {
+                fn write_signing_bytes(&self, out: &mut Vec<u8>) {
+                    out.extend_from_slice(self.as_bytes());
+                }
+            }
+        )*
+    };
+}
+
+impl_signing_bytes_fixed!(PublicKey, Signature, H256, H512);
+
+impl SigningBytes for bool {
+    fn write_signing_bytes(&self, out: &mut Vec<u8>) {
+        out.push(*self as u8);
+    }
+}
+
+impl<const N: usize> SigningBytes for [u8; N] {
+    fn write_signing_bytes(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(self);
+    }
+}
+
+impl SigningBytes for [u8] {
+    fn write_signing_bytes(&self, out: &mut Vec<u8>) {
+        write_len(self.len(), out);
+        out.extend_from_slice(self);
+    }
+}
+
+impl SigningBytes for str {
+    fn write_signing_bytes(&self, out: &mut Vec<u8>) {
+        self.as_bytes().write_signing_bytes(out);
+    }
+}
+
+impl SigningBytes for String {
+    fn write_signing_bytes(&self, out: &mut Vec<u8>) {
+        self.as_str().write_signing_bytes(out);
+    }
+}
+
+impl<T: SigningBytes> SigningBytes for Vec<T> {
+    fn write_signing_bytes(&self, out: &mut Vec<u8>) {
+        write_len(self.len(), out);
+        for item in self {
+            item.write_signing_bytes(out);
+        }
+    }
+}
+
+impl<T: SigningBytes> SigningBytes for Option<T> {
+    fn write_signing_bytes(&self, out: &mut Vec<u8>) {
+        match self {
+            None => out.push(0),
+            Some(value) => {
+                out.push(1);
+                value.write_signing_bytes(out);
+            }
+        }
+    }
+}
+
+impl<T: SigningBytes + ?Sized> SigningBytes for &T {
+    fn write_signing_bytes(&self, out: &mut Vec<u8>) {
+        (**self).write_signing_bytes(out);
+    }
+}
+
+// internal functions.
+fn write_len(len: usize, out: &mut Vec<u8>) {
+    let len = u32::try_from(len).expect("signed field longer than u32::MAX");
+    out.extend_from_slice(&len.to_le_bytes());
+}
@@ -6,6 +6,11 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+#[cfg(feature = "serde")]
+use serde_bytes::{ByteBuf as SerdeByteBuf, Bytes as SerdeBytes};
+
 use super::SIGNATURE_LENGTH;
 
 construct_fixed_hash! {
@@ -18,3 +23,41 @@ impl signature::Signature for Signature {
         Ok(Self::from_slice(bytes.into()))
     }
 }
+
+// `ed25519_dalek::Signature` is the `ed25519::Signature` wire type of the signature crates.
+impl From<ed25519::Signature> for Signature {
+    fn from(signature: ed25519::Signature) -> Self {
+        Self(signature.to_bytes())
+    }
+}
+
+impl TryFrom<Signature> for ed25519::Signature {
+    type Error = anyhow::Error;
+
+    /// Fails if the high bits of `s` are set, which no valid signature has.
+    fn try_from(signature: Signature) -> anyhow::Result<Self> {
+        Ok(ed25519::Signature::from_bytes(signature.as_bytes())?)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for Signature {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let bytes = self.as_bytes();
+        SerdeBytes::new(bytes).serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'d> Deserialize<'d> for Signature {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'d>,
+    {
+        let bytes = <SerdeByteBuf>::deserialize(deserializer)?;
+        Ok(Signature::from_slice(bytes.as_ref()))
+    }
+}
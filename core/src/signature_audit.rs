@@ -0,0 +1,65 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Audit of the signatures of a signing service.
+//!
+//! Ed25519 signatures are deterministic: signing the same data with the same key always gives
+//! the same signature. A signature that differs from its recomputation, or does not verify,
+//! reveals a fault (hardware glitch, fault injection, corrupted key in memory); releasing such a
+//! faulty deterministic signature can leak the private key, so `sign_audited` checks a
+//! signature before returning it. Every mismatch is counted process wide for monitoring, see
+//! `signature_mismatch_count`.
+//!
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use anyhow::{bail, Result};
+
+use super::{KeyPairSchema, Keypair, Signature};
+
+static SIGNATURE_MISMATCHES: AtomicU64 = AtomicU64::new(0);
+
+/// Check a signature made by `kp` by signing `data` again and verifying it.
+///
+/// # Returns
+///
+/// Returns `Ok` if the recomputed signature is `signature` and verifies with the `kp` public
+/// key, otherwise the mismatch is counted.
+pub fn verify_own_signature<Kp: KeyPairSchema>(
+    kp: &Keypair<Kp>,
+    data: &[u8],
+    signature: &Signature,
+) -> Result<()> {
+    if kp.try_sign(data)? != *signature {
+        SIGNATURE_MISMATCHES.fetch_add(1, Ordering::Relaxed);
+        bail!("signature does not match its deterministic recomputation.");
+    }
+    if kp.verify(data, *signature).is_err() {
+        SIGNATURE_MISMATCHES.fetch_add(1, Ordering::Relaxed);
+        bail!("signature does not verify with the keypair public key.");
+    }
+    Ok(())
+}
+
+/// Sign `data` and audit the signature with `verify_own_signature` before returning it.
+///
+/// # Returns
+///
+/// A `Result` whose okay value is the audited `Signature` or whose error value
+/// is an `failure::Error` describing the error that occurred.
+pub fn sign_audited<Kp: KeyPairSchema>(kp: &Keypair<Kp>, data: &[u8]) -> Result<Signature> {
+    let signature = kp.try_sign(data)?;
+    verify_own_signature(kp, data, &signature)?;
+    Ok(signature)
+}
+
+/// The number of signature mismatches found by `verify_own_signature` since the process start.
+///
+pub fn signature_mismatch_count() -> u64 {
+    SIGNATURE_MISMATCHES.load(Ordering::Relaxed)
+}
@@ -0,0 +1,38 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use anyhow::Result;
+
+use super::{KeyPairSchema, Keypair, PublicKey, Signature};
+
+/// A signing key, local or behind a service, e.g. for code that orchestrates signatures
+/// without depending on where the private key lives.
+///
+pub trait Signer {
+    /// The public key of the signatures.
+    ///
+    fn public_key(&self) -> PublicKey;
+
+    /// Signs a data bytes.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the `Signature` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    fn try_sign(&self, data: &[u8]) -> Result<Signature>;
+}
+
+impl<Kp: KeyPairSchema> Signer for Keypair<Kp> {
+    fn public_key(&self) -> PublicKey {
+        Keypair::public_key(self)
+    }
+
+    fn try_sign(&self, data: &[u8]) -> Result<Signature> {
+        Keypair::try_sign(self, data)
+    }
+}
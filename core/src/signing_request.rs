@@ -0,0 +1,335 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::collections::BTreeMap;
+use std::convert::TryInto;
+
+use anyhow::{anyhow, ensure, Result};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::{KeyPairSchema, Keypair, PublicKey, Signature, KEY_BYTES_SIZE, SIGNATURE_LENGTH};
+
+/// The offline signing protocol version.
+pub const SIGNING_PROTOCOL_VERSION: u8 = 1;
+
+/// Maximum payload size of a `SigningRequest`.
+pub const SIGNING_REQUEST_MAX_PAYLOAD: usize = 1024 * 1024;
+
+/// A request sent by an online coordinator to an air-gapped signer.
+///
+/// The binary encoding is:
+/// `version (u8) || schema length (u8) || schema || metadata count (u16) ||
+/// (key length (u16) || key || value length (u16) || value)* || payload length (u32) || payload`,
+/// all integers little endian. `to_hex` produces upper case hex, which fits the QR
+/// alphanumeric mode.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct SigningRequest {
+    pub version: u8,
+    /// The keypair schema name expected to sign, e.g. `symbol` or `nis1`.
+    pub schema: String,
+    /// The exact bytes to sign.
+    pub payload: Vec<u8>,
+    /// Free form information shown to the signer, e.g. a description or a network.
+    pub metadata: BTreeMap<String, String>,
+}
+
+/// The answer of an air-gapped signer to a `SigningRequest`.
+///
+/// The binary encoding is `version (u8) || public key || signature`.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct SignedResponse {
+    pub version: u8,
+    pub signature: Signature,
+    pub public_key: PublicKey,
+}
+
+impl SigningRequest {
+    /// Create a request for the `Kp` schema with the current protocol version.
+    ///
+    pub fn new<Kp: KeyPairSchema>(payload: Vec<u8>) -> Self {
+        Self {
+            version: SIGNING_PROTOCOL_VERSION,
+            schema: Kp::SCHEMA_NAME.to_string(),
+            payload,
+            metadata: BTreeMap::new(),
+        }
+    }
+
+    /// Add a metadata entry.
+    ///
+    pub fn with_metadata<K: Into<String>, V: Into<String>>(mut self, key: K, value: V) -> Self {
+        self.metadata.insert(key.into(), value.into());
+        self
+    }
+
+    /// Check the request is well formed.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok` if the version is supported, the schema is set and the payload is non empty
+    /// and at most `SIGNING_REQUEST_MAX_PAYLOAD` bytes.
+    pub fn validate(&self) -> Result<()> {
+        ensure!(
+            self.version == SIGNING_PROTOCOL_VERSION,
+            "unsupported signing request version {}.",
+            self.version
+        );
+        ensure!(
+            !self.schema.is_empty() && self.schema.len() <= u8::MAX as usize,
+            "signing request schema has unexpected size."
+        );
+        ensure!(
+            !self.payload.is_empty(),
+            "signing request payload is empty."
+        );
+        ensure!(
+            self.payload.len() <= SIGNING_REQUEST_MAX_PAYLOAD,
+            "signing request payload is larger than {} bytes.",
+            SIGNING_REQUEST_MAX_PAYLOAD
+        );
+        ensure!(
+            self.metadata.len() <= u16::MAX as usize,
+            "signing request has too many metadata entries."
+        );
+        for (key, value) in &self.metadata {
+            ensure!(
+                key.len() <= u16::MAX as usize && value.len() <= u16::MAX as usize,
+                "signing request metadata entry {} is too large.",
+                key
+            );
+        }
+        Ok(())
+    }
+
+    /// Validate the request and sign its payload.
+    ///
+    /// # Inputs
+    ///
+    /// * `kp`: The signer `Keypair`, its schema must match the request schema.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is a `SignedResponse` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn sign<Kp: KeyPairSchema>(&self, kp: &Keypair<Kp>) -> Result<SignedResponse> {
+        self.validate()?;
+        ensure!(
+            self.schema == Kp::SCHEMA_NAME,
+            "signing request expects a {} keypair.",
+            self.schema
+        );
+
+        Ok(SignedResponse {
+            version: SIGNING_PROTOCOL_VERSION,
+            signature: kp.try_sign(&self.payload)?,
+            public_key: kp.public_key(),
+        })
+    }
+
+    /// Encode the request to bytes.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the encoded request `Vec<u8>` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        self.validate()?;
+
+        let mut bytes = vec![self.version, self.schema.len() as u8];
+        bytes.extend_from_slice(self.schema.as_bytes());
+        bytes.extend_from_slice(&(self.metadata.len() as u16).to_le_bytes());
+        for (key, value) in &self.metadata {
+            bytes.extend_from_slice(&(key.len() as u16).to_le_bytes());
+            bytes.extend_from_slice(key.as_bytes());
+            bytes.extend_from_slice(&(value.len() as u16).to_le_bytes());
+            bytes.extend_from_slice(value.as_bytes());
+        }
+        bytes.extend_from_slice(&(self.payload.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(&self.payload);
+        Ok(bytes)
+    }
+
+    /// Decode and validate a request from bytes.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is a `SigningRequest` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let mut reader = Reader(bytes);
+
+        let version = reader.take(1)?[0];
+        ensure!(
+            version == SIGNING_PROTOCOL_VERSION,
+            "unsupported signing request version {}.",
+            version
+        );
+
+        let schema_len = reader.take(1)?[0] as usize;
+        let schema = reader.take_str(schema_len)?;
+
+        let mut metadata = BTreeMap::new();
+        for _ in 0..reader.take_u16()? {
+            let key_len = reader.take_u16()? as usize;
+            let key = reader.take_str(key_len)?;
+            let value_len = reader.take_u16()? as usize;
+            let value = reader.take_str(value_len)?;
+            metadata.insert(key, value);
+        }
+
+        let payload_len = u32::from_le_bytes(reader.take(4)?.try_into()?) as usize;
+        let payload = reader.take(payload_len)?.to_vec();
+        ensure!(reader.0.is_empty(), "signing request has trailing bytes.");
+
+        let request = Self {
+            version,
+            schema,
+            payload,
+            metadata,
+        };
+        request.validate()?;
+        Ok(request)
+    }
+
+    /// Encode the request to upper case hex.
+    ///
+    pub fn to_hex(&self) -> Result<String> {
+        Ok(hex::encode_upper(self.to_bytes()?))
+    }
+
+    /// Decode and validate a request from hex.
+    ///
+    pub fn from_hex<S: AsRef<str>>(hex: S) -> Result<Self> {
+        Self::from_bytes(&hex::decode(hex.as_ref())?)
+    }
+}
+
+impl SignedResponse {
+    /// Size of the encoded response.
+    pub const LENGTH: usize = 1 + KEY_BYTES_SIZE + SIGNATURE_LENGTH;
+
+    /// Verify the response answers a request.
+    ///
+    /// The signature is verified with the `Kp` schema, which must match the request schema.
+    ///
+    /// # Inputs
+    ///
+    /// * `request`: The request the response answers.
+    ///
+    /// * `expected_signer`: Optional public key the response must be signed by.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok` if the `Signature` is a valid signature of the request payload.
+    pub fn verify<Kp: KeyPairSchema>(
+        &self,
+        request: &SigningRequest,
+        expected_signer: Option<&PublicKey>,
+    ) -> Result<()> {
+        ensure!(
+            self.version == SIGNING_PROTOCOL_VERSION,
+            "unsupported signed response version {}.",
+            self.version
+        );
+        ensure!(
+            request.schema == Kp::SCHEMA_NAME,
+            "signing request expects a {} keypair.",
+            request.schema
+        );
+        if let Some(expected_signer) = expected_signer {
+            ensure!(
+                self.public_key == *expected_signer,
+                "response is signed by {:x}, expected {:x}.",
+                self.public_key,
+                expected_signer
+            );
+        }
+
+        Kp::from_null_private_key(self.public_key).verify(&request.payload, self.signature)
+    }
+
+    /// Encode the response to bytes.
+    ///
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(Self::LENGTH);
+        bytes.push(self.version);
+        bytes.extend_from_slice(self.public_key.as_bytes());
+        bytes.extend_from_slice(self.signature.as_bytes());
+        bytes
+    }
+
+    /// Decode a response from bytes.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is a `SignedResponse` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        ensure!(
+            bytes.len() == Self::LENGTH,
+            "signed response has unexpected size {}.",
+            bytes.len()
+        );
+        ensure!(
+            bytes[0] == SIGNING_PROTOCOL_VERSION,
+            "unsupported signed response version {}.",
+            bytes[0]
+        );
+
+        Ok(Self {
+            version: bytes[0],
+            public_key: PublicKey::from_slice(&bytes[1..1 + KEY_BYTES_SIZE]),
+            signature: Signature::from_slice(&bytes[1 + KEY_BYTES_SIZE..]),
+        })
+    }
+
+    /// Encode the response to upper case hex.
+    ///
+    pub fn to_hex(&self) -> String {
+        hex::encode_upper(self.to_bytes())
+    }
+
+    /// Decode a response from hex.
+    ///
+    pub fn from_hex<S: AsRef<str>>(hex: S) -> Result<Self> {
+        Self::from_bytes(&hex::decode(hex.as_ref())?)
+    }
+}
+
+// internal functions.
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        ensure!(self.0.len() >= len, "signing request is truncated.");
+        let (head, tail) = self.0.split_at(len);
+        self.0 = tail;
+        Ok(head)
+    }
+
+    fn take_u16(&mut self) -> Result<u16> {
+        Ok(u16::from_le_bytes(self.take(2)?.try_into()?))
+    }
+
+    fn take_str(&mut self, len: usize) -> Result<String> {
+        String::from_utf8(self.take(len)?.to_vec()).map_err(|e| anyhow!("{}", e))
+    }
+}
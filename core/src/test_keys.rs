@@ -0,0 +1,100 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Fixed, publicly known keypairs for examples, doctests and integration tests.
+//!
+//! **Never use these keys outside of tests**: their private keys are published here, anything
+//! they sign or receive on a real network is public.
+//!
+//! The private key of each name is `SHA3-256("symbol-crypto-core/test-keys/" || name)`, the
+//! same for both schemas; `alice::<KpSym>()` and `alice::<KpNis1>()` only differ by their public
+//! key. The Symbol public keys are:
+//!
+//! | name    | Symbol public key                                                  |
+//! |---------|--------------------------------------------------------------------|
+//! | `alice` | `F19FD1CD6B8495EA941CB58BB60D0106773638EBA44286F06F2835329E28A0A2` |
+//! | `bob`   | `3F4A7A992B63C05CE4E1A6431E3D07C44A5FC165326D12F518319E2F60DC4A70` |
+//! | `carol` | `6C2BE98C8321616815B5FF046A97C2C185508D69938C2D096B7A0394A15FDB19` |
+//! | `dave`  | `F292BC5A4277749AE7E8117FF29005F6F50C4E4DD10354CDAF030239B4750374` |
+//! | `eve`   | `FFB2D2F93B401ADA7A1CD7DEA536057DF1369DFCEE953529CE22DFAFFB7F84CC` |
+//!
+
+use super::{KeyPairSchema, Keypair};
+
+/// The private key of `alice`, for tests only.
+pub const ALICE_PRIVATE_KEY: &str =
+    "F3D5BDE1C4FAEC4EFE172D8C72934F3E850114120655165C39D65D5707458766";
+
+/// The private key of `bob`, for tests only.
+pub const BOB_PRIVATE_KEY: &str =
+    "1EDE57CED50C56F0E8D5CFACBED017B2D87BF05FC8690810FE2D5E7740D84912";
+
+/// The private key of `carol`, for tests only.
+pub const CAROL_PRIVATE_KEY: &str =
+    "27C43653E13045EFBE774A9F4967E756387D2F023446439231459A24EE58F23A";
+
+/// The private key of `dave`, for tests only.
+pub const DAVE_PRIVATE_KEY: &str =
+    "1ED5E54196763BE179D5E82233FDFF5AAA99D3BF9E7742AC7CEE389A5615D46E";
+
+/// The private key of `eve`, for tests only.
+pub const EVE_PRIVATE_KEY: &str =
+    "59570CD6B6ADCBD8BB1EE9AAEBF798CB6B4FAE007ED69759DF5C40176E1961AC";
+
+/// The test key names and private keys, in alphabetical order.
+pub const TEST_KEYS: [(&str, &str); 5] = [
+    ("alice", ALICE_PRIVATE_KEY),
+    ("bob", BOB_PRIVATE_KEY),
+    ("carol", CAROL_PRIVATE_KEY),
+    ("dave", DAVE_PRIVATE_KEY),
+    ("eve", EVE_PRIVATE_KEY),
+];
+
+/// The `alice` test keypair, never use it outside of tests.
+///
+pub fn alice<Kp: KeyPairSchema>() -> Keypair<Kp> {
+    keypair(ALICE_PRIVATE_KEY)
+}
+
+/// The `bob` test keypair, never use it outside of tests.
+///
+pub fn bob<Kp: KeyPairSchema>() -> Keypair<Kp> {
+    keypair(BOB_PRIVATE_KEY)
+}
+
+/// The `carol` test keypair, never use it outside of tests.
+///
+pub fn carol<Kp: KeyPairSchema>() -> Keypair<Kp> {
+    keypair(CAROL_PRIVATE_KEY)
+}
+
+/// The `dave` test keypair, never use it outside of tests.
+///
+pub fn dave<Kp: KeyPairSchema>() -> Keypair<Kp> {
+    keypair(DAVE_PRIVATE_KEY)
+}
+
+/// The `eve` test keypair, never use it outside of tests.
+///
+pub fn eve<Kp: KeyPairSchema>() -> Keypair<Kp> {
+    keypair(EVE_PRIVATE_KEY)
+}
+
+/// The test keypair of a name of `TEST_KEYS`.
+///
+pub fn by_name<Kp: KeyPairSchema>(name: &str) -> Option<Keypair<Kp>> {
+    TEST_KEYS
+        .iter()
+        .find(|(key_name, _)| *key_name == name)
+        .map(|(_, private_key)| keypair(private_key))
+}
+
+// internal functions.
+fn keypair<Kp: KeyPairSchema>(private_key: &str) -> Keypair<Kp> {
+    Keypair::from_hex_private_key(private_key).expect("test private keys are valid hex")
+}
@@ -6,7 +6,7 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-use rand::RngCore;
+use anyhow::{ensure, Result};
 use regex::Regex;
 
 pub fn is_hex(input: &str) -> bool {
@@ -26,9 +26,121 @@ pub fn hex_to_vec(data: &str) -> Vec<u8> {
         .unwrap()
 }
 
-pub fn random_bytes<const COUNT: usize>() -> [u8; COUNT] {
-    let mut rng = rand::thread_rng();
-    let mut buf = [0u8; COUNT];
-    rng.try_fill_bytes(&mut buf).unwrap();
-    buf
+/// Decodes a hex string of exactly `N` bytes, usable in const contexts.
+///
+/// # Panics
+///
+/// If the string is not `2 * N` hex characters; in a const context the panic is a compile
+/// error.
+pub const fn hex_to_bytes<const N: usize>(hex: &str) -> [u8; N] {
+    let hex = hex.as_bytes();
+    if hex.len() != 2 * N {
+        panic!("hex string has an unexpected length.");
+    }
+
+    let mut bytes = [0u8; N];
+    let mut i = 0;
+    while i < N {
+        bytes[i] = (hex_digit(hex[2 * i]) << 4) | hex_digit(hex[2 * i + 1]);
+        i += 1;
+    }
+    bytes
+}
+
+/// Encodes bytes as hex in constant time, for secrets such as private keys.
+///
+/// Public data should use the `hex` crate, which is faster.
+pub fn ct_hex_encode(bytes: &[u8], upper: bool) -> String {
+    let mut hex = vec![0u8; 2 * bytes.len()];
+    ct_hex_encode_into(bytes, upper, &mut hex).expect("hex buffer has the encoded size");
+    String::from_utf8(hex).expect("hex digits are ascii")
+}
+
+/// Encodes bytes as hex in constant time into `out`, of twice their length, without allocating.
+///
+/// # Returns
+///
+/// Returns `Ok` if `out` has the encoded size.
+pub fn ct_hex_encode_into(bytes: &[u8], upper: bool, out: &mut [u8]) -> Result<()> {
+    ensure!(
+        out.len() == 2 * bytes.len(),
+        "hex buffer has unexpected size {}.",
+        out.len()
+    );
+    // Offset from `'0' + 10` to `'a'` or `'A'`.
+    let alpha = if upper { 7 } else { 39 };
+    for (&byte, pair) in bytes.iter().zip(out.chunks_mut(2)) {
+        pair[0] = ct_hex_char(byte >> 4, alpha);
+        pair[1] = ct_hex_char(byte & 0x0F, alpha);
+    }
+    Ok(())
+}
+
+/// Decodes a hex string in constant time, for secrets such as private keys.
+///
+/// Only the length of the input may leak, the validity of every character is checked once
+/// all of them are decoded. Public data should use the `hex` crate, which is faster.
+///
+/// # Returns
+///
+/// A `Result` whose okay value is the decoded bytes or whose error value
+/// is an `failure::Error` describing the error that occurred.
+pub fn ct_hex_decode(hex: &str) -> Result<Vec<u8>> {
+    ensure!(hex.len().is_multiple_of(2), "hex string has an odd length.");
+    let mut bytes = vec![0u8; hex.len() / 2];
+    ct_hex_decode_into(hex, &mut bytes)?;
+    Ok(bytes)
+}
+
+/// Decodes a hex string in constant time into `out`, of half its length, without allocating.
+///
+/// # Returns
+///
+/// Returns `Ok` if `hex` is valid and `out` has the decoded size, `out` content is unspecified
+/// otherwise.
+pub fn ct_hex_decode_into(hex: &str, out: &mut [u8]) -> Result<()> {
+    let hex = hex.as_bytes();
+    ensure!(hex.len().is_multiple_of(2), "hex string has an odd length.");
+    ensure!(
+        out.len() == hex.len() / 2,
+        "hex buffer has unexpected size {}.",
+        out.len()
+    );
+
+    let mut invalid = 0u8;
+    for (pair, byte) in hex.chunks(2).zip(out.iter_mut()) {
+        let (high, high_valid) = ct_hex_nibble(pair[0]);
+        let (low, low_valid) = ct_hex_nibble(pair[1]);
+        invalid |= !(high_valid & low_valid);
+        *byte = (high << 4) | low;
+    }
+    ensure!(invalid == 0, "invalid hex character.");
+    Ok(())
+}
+
+// internal functions.
+fn ct_hex_char(nibble: u8, alpha: i16) -> u8 {
+    let n = nibble as i16;
+    // `(9 - n) >> 8` is all ones when `n > 9`.
+    (n + 0x30 + (((9 - n) >> 8) & alpha)) as u8
+}
+
+/// Returns the nibble value and an all ones mask when `c` is a hex digit.
+fn ct_hex_nibble(c: u8) -> (u8, u8) {
+    let c = c as i16;
+    // `(lo - 1 - c) & (c - hi - 1)` is negative, and `>> 8` all ones, iff `lo <= c <= hi`.
+    let digit_mask = ((0x2F - c) & (c - 0x3A)) >> 8;
+    let lower = c | 0x20;
+    let alpha_mask = ((0x60 - lower) & (lower - 0x67)) >> 8;
+    let value = ((c - 0x30) & digit_mask) | ((lower - 0x57) & alpha_mask);
+    (value as u8, (digit_mask | alpha_mask) as u8)
+}
+
+const fn hex_digit(c: u8) -> u8 {
+    match c {
+        b'0'..=b'9' => c - b'0',
+        b'a'..=b'f' => c - b'a' + 10,
+        b'A'..=b'F' => c - b'A' + 10,
+        _ => panic!("invalid hex character."),
+    }
 }
@@ -0,0 +1,250 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::marker::PhantomData;
+use std::sync::{Mutex, MutexGuard};
+
+use anyhow::{ensure, Result};
+use sha3::{Digest, Sha3_256};
+
+use super::{KeyPairSchema, PublicKey, Signature, VerifyItem, H256};
+
+type MetricsHook = Box<dyn Fn(VerifierCacheEvent) + Send + Sync>;
+
+/// What happened to a `VerifierCache`, given to its metrics hook.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum VerifierCacheEvent {
+    /// The verification was memoized, the signature was not checked again.
+    Hit,
+    /// The signature was checked.
+    Miss,
+    /// The least recently used verification was dropped to make room.
+    Eviction,
+}
+
+/// The counters of a `VerifierCache`.
+///
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct VerifierCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub evictions: u64,
+    /// The number of memoized verifications.
+    pub len: usize,
+}
+
+/// A size bounded, least recently used cache of the successful verifications of a schema.
+///
+/// A verification is memoized by `SHA3-256(public key || SHA3-256(data) || signature)`, so the
+/// cache never holds the signed data. Only valid signatures are memoized: an invalid one is
+/// checked again on every call. Services re-validating the same cosignatures, e.g. while
+/// assembling an aggregate transaction, skip the curve arithmetic of the repeated ones.
+///
+/// The cache is `Sync`, share it behind an `Arc`. The metrics hook is called outside of the
+/// cache lock.
+///
+pub struct VerifierCache<Kp: KeyPairSchema> {
+    capacity: usize,
+    inner: Mutex<CacheInner>,
+    metrics_hook: Option<MetricsHook>,
+    _schema: PhantomData<fn() -> Kp>,
+}
+
+#[derive(Default)]
+struct CacheInner {
+    // cache key to its last use, and last use to cache key: the first entry is the least
+    // recently used.
+    entries: HashMap<H256, u64>,
+    by_use: BTreeMap<u64, H256>,
+    tick: u64,
+    stats: VerifierCacheStats,
+}
+
+impl<Kp: KeyPairSchema> VerifierCache<Kp> {
+    /// A cache of at most `capacity` verifications.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the `VerifierCache` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn new(capacity: usize) -> Result<Self> {
+        ensure!(capacity > 0, "verifier cache capacity must be positive.");
+        Ok(Self {
+            capacity,
+            inner: Mutex::new(CacheInner::default()),
+            metrics_hook: None,
+            _schema: PhantomData,
+        })
+    }
+
+    /// Call `hook` on every hit, miss and eviction, e.g. to feed a metrics counter.
+    ///
+    pub fn with_metrics_hook<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(VerifierCacheEvent) + Send + Sync + 'static,
+    {
+        self.metrics_hook = Some(Box::new(hook));
+        self
+    }
+
+    /// Verify a `Signature` on a data, unless the same verification is memoized.
+    ///
+    /// # Inputs
+    ///
+    /// * `public_key`: The signer `PublicKey`.
+    ///
+    /// * `data`: The signed data.
+    ///
+    /// * `signature`: The `Signature` to verify.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok` if the signature is valid.
+    pub fn verify(&self, public_key: &PublicKey, data: &[u8], signature: Signature) -> Result<()> {
+        let key = cache_key(public_key, data, &signature);
+        if self.lock_inner().touch(&key) {
+            self.notify(VerifierCacheEvent::Hit);
+            return Ok(());
+        }
+
+        self.notify(VerifierCacheEvent::Miss);
+        Kp::from_null_private_key(*public_key).verify(data, signature)?;
+
+        let evicted = self.lock_inner().insert(key, self.capacity);
+        if evicted {
+            self.notify(VerifierCacheEvent::Eviction);
+        }
+        Ok(())
+    }
+
+    /// Verify a `VerifyItem`, see `verify`.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok` if the signature is valid.
+    pub fn verify_item(&self, item: &VerifyItem<'_>) -> Result<()> {
+        self.verify(&item.public_key, item.data, item.signature)
+    }
+
+    /// Whether the verification is memoized, without updating its use.
+    ///
+    pub fn contains(&self, public_key: &PublicKey, data: &[u8], signature: &Signature) -> bool {
+        let key = cache_key(public_key, data, signature);
+        self.lock_inner().entries.contains_key(&key)
+    }
+
+    /// The maximum number of memoized verifications.
+    ///
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// The number of memoized verifications.
+    ///
+    pub fn len(&self) -> usize {
+        self.lock_inner().entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The hit, miss and eviction counters since the cache was created.
+    ///
+    pub fn stats(&self) -> VerifierCacheStats {
+        let inner = self.lock_inner();
+        VerifierCacheStats {
+            len: inner.entries.len(),
+            ..inner.stats
+        }
+    }
+
+    /// Forget every memoized verification, e.g. after a key compromise, counters are kept.
+    ///
+    pub fn clear(&self) {
+        let mut inner = self.lock_inner();
+        inner.entries.clear();
+        inner.by_use.clear();
+    }
+
+    // internal functions.
+    fn lock_inner(&self) -> MutexGuard<'_, CacheInner> {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn notify(&self, event: VerifierCacheEvent) {
+        if let Some(hook) = &self.metrics_hook {
+            hook(event);
+        }
+    }
+}
+
+impl<Kp: KeyPairSchema> fmt::Debug for VerifierCache<Kp> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VerifierCache")
+            .field("capacity", &self.capacity)
+            .field("stats", &self.stats())
+            .field("metrics_hook", &self.metrics_hook.is_some())
+            .finish()
+    }
+}
+
+impl CacheInner {
+    // mark a memoized verification as the most recently used, counting the hit or the miss.
+    fn touch(&mut self, key: &H256) -> bool {
+        self.tick += 1;
+        let tick = self.tick;
+        match self.entries.get_mut(key) {
+            Some(last_use) => {
+                self.by_use.remove(last_use);
+                *last_use = tick;
+                self.by_use.insert(tick, *key);
+                self.stats.hits += 1;
+                true
+            }
+            None => {
+                self.stats.misses += 1;
+                false
+            }
+        }
+    }
+
+    // memoize a verification, returns whether another one was evicted.
+    fn insert(&mut self, key: H256, capacity: usize) -> bool {
+        self.tick += 1;
+        let tick = self.tick;
+        if let Some(last_use) = self.entries.insert(key, tick) {
+            // verified concurrently by another caller.
+            self.by_use.remove(&last_use);
+        }
+        self.by_use.insert(tick, key);
+
+        if self.entries.len() <= capacity {
+            return false;
+        }
+        let oldest = *self.by_use.keys().next().expect("cache over capacity is not empty");
+        if let Some(evicted) = self.by_use.remove(&oldest) {
+            self.entries.remove(&evicted);
+        }
+        self.stats.evictions += 1;
+        true
+    }
+}
+
+// internal functions.
+fn cache_key(public_key: &PublicKey, data: &[u8], signature: &Signature) -> H256 {
+    let data_hash = Sha3_256::digest(data);
+    let mut hasher = Sha3_256::new();
+    hasher.update(public_key.as_bytes());
+    hasher.update(data_hash);
+    hasher.update(signature.as_bytes());
+    H256::from_slice(hasher.finalize().as_slice())
+}
@@ -0,0 +1,73 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Verification of many signatures in one call, e.g. the transactions of a mempool.
+//!
+//! `verify_each` checks every item and returns one result per item, for diagnostics;
+//! `verify_all_fast` stops at the first invalid signature and returns its error only.
+//!
+
+use anyhow::{Context, Result};
+
+use super::{KeyPairSchema, Keypair, PublicKey, Signature};
+
+/// A signature to verify: the signer public key, the signed data and the signature.
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VerifyItem<'a> {
+    pub public_key: PublicKey,
+    pub data: &'a [u8],
+    pub signature: Signature,
+}
+
+impl<'a> VerifyItem<'a> {
+    pub fn new(public_key: PublicKey, data: &'a [u8], signature: Signature) -> Self {
+        Self {
+            public_key,
+            data,
+            signature,
+        }
+    }
+
+    // internal functions.
+    fn verify<Kp: KeyPairSchema>(&self) -> Result<()> {
+        Kp::from_null_private_key(self.public_key).verify(self.data, self.signature)
+    }
+}
+
+impl<Kp: KeyPairSchema> Keypair<Kp> {
+    /// Verify every item, without stopping at an invalid signature.
+    ///
+    /// # Inputs
+    ///
+    /// * `items`: The signatures to verify.
+    ///
+    /// # Returns
+    ///
+    /// One `Result` per item, in the order of the items.
+    pub fn verify_each(items: &[VerifyItem]) -> Vec<Result<()>> {
+        items.iter().map(|item| item.verify::<Kp>()).collect()
+    }
+
+    /// Verify every item, stopping at the first invalid signature.
+    ///
+    /// # Inputs
+    ///
+    /// * `items`: The signatures to verify.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok` if every signature is valid, otherwise the error of the first invalid
+    /// one, with its index.
+    pub fn verify_all_fast(items: &[VerifyItem]) -> Result<()> {
+        items.iter().enumerate().try_for_each(|(i, item)| {
+            item.verify::<Kp>()
+                .with_context(|| format!("item {} signature is not valid.", i))
+        })
+    }
+}
@@ -0,0 +1,155 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Nis1 addresses.
+//!
+//! An address is `network || RIPEMD-160(Keccak-256(public key)) || checksum`, the checksum being
+//! the first 4 bytes of `Keccak-256` of the first 21 bytes, and its text form is the base32 of
+//! these 25 bytes (40 characters).
+//!
+
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::{anyhow, ensure, Result};
+use ripemd::Ripemd160;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Keccak256};
+
+use crate::core::PublicKey;
+
+/// The size of an address.
+pub const NIS1_ADDRESS_SIZE: usize = 25;
+
+/// The size of an address text form.
+pub const NIS1_ADDRESS_STR_SIZE: usize = 40;
+
+const ADDRESS_CHECKSUM_OFFSET: usize = 21;
+const BASE32_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+/// A Nis1 address, with a valid checksum.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct Address([u8; NIS1_ADDRESS_SIZE]);
+
+impl Address {
+    /// The address of a public key.
+    ///
+    /// # Inputs
+    ///
+    /// * `network`: The network byte, e.g. `0x68` for mainnet or `0x98` for testnet.
+    ///
+    /// * `public_key`: The account public key.
+    ///
+    pub fn from_public_key(network: u8, public_key: &PublicKey) -> Self {
+        let mut address = [0u8; NIS1_ADDRESS_SIZE];
+        address[0] = network;
+        address[1..ADDRESS_CHECKSUM_OFFSET]
+            .copy_from_slice(&Ripemd160::digest(Keccak256::digest(public_key.as_bytes())));
+        let checksum = address_checksum(&address[..ADDRESS_CHECKSUM_OFFSET]);
+        address[ADDRESS_CHECKSUM_OFFSET..].copy_from_slice(&checksum);
+        Self(address)
+    }
+
+    /// Construct an `Address` from its 25 bytes.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the `Address` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        ensure!(
+            bytes.len() == NIS1_ADDRESS_SIZE,
+            "address has unexpected size {}.",
+            bytes.len()
+        );
+        ensure!(
+            address_checksum(&bytes[..ADDRESS_CHECKSUM_OFFSET]) == bytes[ADDRESS_CHECKSUM_OFFSET..],
+            "address has an invalid checksum."
+        );
+
+        let mut address = [0u8; NIS1_ADDRESS_SIZE];
+        address.copy_from_slice(bytes);
+        Ok(Self(address))
+    }
+
+    /// The network byte of this address.
+    ///
+    pub fn network(&self) -> u8 {
+        self.0[0]
+    }
+
+    /// View this address as a byte array.
+    ///
+    pub fn as_bytes(&self) -> &[u8; NIS1_ADDRESS_SIZE] {
+        &self.0
+    }
+}
+
+impl FromStr for Address {
+    type Err = anyhow::Error;
+
+    /// Parse the 40 character base32 text form, dashes allowed.
+    fn from_str(s: &str) -> Result<Self> {
+        let text: Vec<u8> = s.bytes().filter(|&c| c != b'-').collect();
+        ensure!(
+            text.len() == NIS1_ADDRESS_STR_SIZE,
+            "address {:?} has unexpected size.",
+            s
+        );
+
+        let mut bits = 0u64;
+        let mut count = 0;
+        let mut bytes = Vec::with_capacity(NIS1_ADDRESS_SIZE);
+        for &c in text.iter() {
+            let value = BASE32_ALPHABET
+                .iter()
+                .position(|&a| a == c.to_ascii_uppercase())
+                .ok_or_else(|| anyhow!("address {:?} is not base32.", s))?;
+            bits = (bits << 5) | value as u64;
+            count += 5;
+            if count >= 8 {
+                count -= 8;
+                bytes.push((bits >> count) as u8);
+            }
+        }
+
+        Address::from_bytes(&bytes)
+    }
+}
+
+impl fmt::Display for Address {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut bits = 0u64;
+        let mut count = 0;
+        let mut text = String::with_capacity(NIS1_ADDRESS_STR_SIZE);
+        for &byte in self.0.iter() {
+            bits = (bits << 8) | byte as u64;
+            count += 8;
+            while count >= 5 {
+                count -= 5;
+                text.push(BASE32_ALPHABET[((bits >> count) & 0x1F) as usize] as char);
+            }
+        }
+        f.write_str(&text)
+    }
+}
+
+// internal functions.
+fn address_checksum(network_and_hash: &[u8]) -> [u8; NIS1_ADDRESS_SIZE - ADDRESS_CHECKSUM_OFFSET] {
+    let hash = Keccak256::digest(network_and_hash);
+    let mut checksum = [0u8; NIS1_ADDRESS_SIZE - ADDRESS_CHECKSUM_OFFSET];
+    checksum.copy_from_slice(&hash[..NIS1_ADDRESS_SIZE - ADDRESS_CHECKSUM_OFFSET]);
+    checksum
+}
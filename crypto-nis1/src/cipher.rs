@@ -6,21 +6,42 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-use aes::cipher::{block_padding::Pkcs7, BlockDecryptMut, BlockEncryptMut, KeyIvInit};
+use aes::cipher::{
+    block_padding::{NoPadding, Pkcs7},
+    BlockDecryptMut, BlockEncryptMut, KeyIvInit,
+};
 use anyhow::{anyhow, ensure, Result};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
 use sha3::Keccak512;
 
+use super::envelope::{
+    Nis1Envelope, Nis1EnvelopeKind, Nis1ExtendedEnvelope, NIS1_EXTENDED_VERSION,
+    NIS_AES_BLOCK_SIZE,
+};
+use super::Keypair;
 use super::keccak_256::keccak256;
+use crate::core::kdf::{expand, KdfChoice};
 use crate::core::{
-    derive_shared_secret, random_bytes, BlockCipher, PrivateKey, PublicKey, H256, KEY_BYTES_SIZE,
-    NIS_AES_IV_LENGTH, NIS_SALT_LENGTH,
+    derive_shared_secret, random_bytes, BlockCipher, KeyPairSchema, PrivateKey, PublicKey,
+    SharedSecret, H256, KEY_BYTES_SIZE, NIS_AES_IV_LENGTH, NIS_SALT_LENGTH,
 };
 
 type Aes256CbcEnc = cbc::Encryptor<aes::Aes256>;
 type Aes2568CbcDec = cbc::Decryptor<aes::Aes256>;
+type HmacSha256 = Hmac<Sha256>;
+
+/// The HKDF info deriving the AES and MAC keys of `CryptoNis1::encrypt_then_mac` from the
+/// salted shared key.
+pub const NIS1_ETM_INFO: &[u8] = b"symbol-crypto-core/nis1-etm/v1";
+
+/// The HMAC-SHA256 tag of an encrypt-then-MAC message, see `CryptoNis1::encrypt_then_mac`.
+pub type Nis1Mac = H256;
 
 pub struct CryptoNis1;
 
+impl crate::core::sealed::Sealed for CryptoNis1 {}
+
 impl BlockCipher for CryptoNis1 {
     /// Encode a message text with AES algorithm using the signer's the PrivateKey and receiver's PublicKey.
     ///
@@ -36,28 +57,32 @@ impl BlockCipher for CryptoNis1 {
     ///
     /// A `Result` whose okay value is an encrypt message `Vec<u8>` or whose error value
     /// is an `failure::Error` describing the error that occurred.
-    fn encrypt_message(
-        signer_sk: &[u8; KEY_BYTES_SIZE],
-        receiver_pk: &[u8; KEY_BYTES_SIZE],
+    fn encrypt_for(
+        signer_sk: &PrivateKey,
+        receiver_pk: &PublicKey,
         msg: &[u8],
     ) -> Result<Vec<u8>> {
         let iv = random_bytes::<NIS_AES_IV_LENGTH>();
         let salt = random_bytes::<NIS_SALT_LENGTH>();
-        let signer_sk: PrivateKey = signer_sk.into();
-        let receiver_pk: PublicKey = receiver_pk.into();
 
-        let derive_key = derive_shared_key(salt, signer_sk, receiver_pk);
+        let derive_key = derive_shared_key(salt, *signer_sk, *receiver_pk);
 
-        let encrypted = encrypt(iv, derive_key, msg)?;
-        let mut enc = vec![];
-        enc.extend_from_slice(&salt);
-        enc.extend_from_slice(&iv);
-        enc.extend_from_slice(&encrypted);
-        Ok(enc)
+        let envelope = Nis1Envelope {
+            salt,
+            iv,
+            ciphertext: encrypt(iv, derive_key, msg)?,
+        };
+        Ok(envelope.to_bytes())
     }
 
     /// Decrypt a message text with AES algorithm using the receiver's the PrivateKey and signer's PublicKey.
     ///
+    /// Both the legacy and the extended envelopes of `encrypt_for_extended` are accepted.
+    ///
+    /// NIS1 messages are not authenticated: a tampered ciphertext decrypts to garbage or fails
+    /// the padding check. Padding is checked in constant time and every failure gives the same
+    /// error, but new protocols should use `encrypt_then_mac` and `decrypt_verified`.
+    ///
     /// # Inputs
     ///
     /// * `receiver_sk`: The receiver's private key.
@@ -84,7 +109,7 @@ impl BlockCipher for CryptoNis1 {
     ///     184, 66,
     /// ];
     ///
-    /// let decrypted_text = decrypt_message( receiver_sk.as_fixed_bytes(), signer_pk.as_fixed_bytes(), &encrypt_text_vec).unwrap();
+    /// let decrypted_text = decrypt_from(&receiver_sk, &signer_pk, &encrypt_text_vec).unwrap();
     /// # println!("{}", String::from_utf8(decrypted_text).unwrap());
     /// # }
     /// ```
@@ -93,43 +118,185 @@ impl BlockCipher for CryptoNis1 {
     ///
     /// A `Result` whose okay value is an decrypted message `Vec<u8>` or whose error value
     /// is an `failure::Error` describing the error that occurred.
-    fn decrypt_message(
-        receiver_sk: &[u8; KEY_BYTES_SIZE],
-        signer_pk: &[u8; KEY_BYTES_SIZE],
+    fn decrypt_from(
+        receiver_sk: &PrivateKey,
+        signer_pk: &PublicKey,
         enc_msg: &[u8],
     ) -> Result<Vec<u8>> {
         ensure!(!enc_msg.is_empty(), "msg cannot be empty");
 
-        let iv = &enc_msg[NIS_SALT_LENGTH..NIS_SALT_LENGTH + NIS_AES_IV_LENGTH];
-        let mut iv_bytes = [0u8; NIS_AES_IV_LENGTH];
-        iv_bytes.copy_from_slice(&iv[..]);
+        // `signer_pk` rather than the embedded sender key, so the signer can decrypt too.
+        match Nis1Envelope::detect(enc_msg) {
+            Nis1EnvelopeKind::Legacy => Nis1Envelope::parse(enc_msg)?,
+            Nis1EnvelopeKind::Extended => Nis1ExtendedEnvelope::parse(enc_msg)?.envelope,
+        }
+        .decrypt(receiver_sk, signer_pk)
+    }
+}
 
-        let mut salt_bytes = [0u8; NIS_SALT_LENGTH];
-        salt_bytes.clone_from_slice(&enc_msg[0..NIS_SALT_LENGTH]);
+impl CryptoNis1 {
+    /// Encrypt a message as `encrypt_for`, in an extended envelope embedding the signer's
+    /// public key.
+    ///
+    /// # Inputs
+    ///
+    /// * `signer_sk`: The signer's private key.
+    ///
+    /// * `receiver_pk`: The receiver's public key.
+    ///
+    /// * `msg`: Message to encrypt.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the extended envelope `Vec<u8>` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn encrypt_for_extended(
+        signer_sk: &PrivateKey,
+        receiver_pk: &PublicKey,
+        msg: &[u8],
+    ) -> Result<Vec<u8>> {
+        let envelope = Nis1Envelope::parse(&Self::encrypt_for(signer_sk, receiver_pk, msg)?)?;
+        let extended = Nis1ExtendedEnvelope {
+            version: NIS1_EXTENDED_VERSION,
+            sender_public_key: Keypair::from_private_key(*signer_sk).public_key,
+            envelope,
+        };
+        Ok(extended.to_bytes())
+    }
 
-        let mut enc_msg_bytes =
-            Vec::with_capacity(enc_msg.len() - (NIS_SALT_LENGTH + NIS_AES_IV_LENGTH));
-        enc_msg_bytes.extend_from_slice(&enc_msg[NIS_SALT_LENGTH + NIS_AES_IV_LENGTH..]);
+    /// Decrypt a legacy or an extended envelope as its receiver.
+    ///
+    /// The sender of an extended envelope is its embedded public key, a legacy envelope needs
+    /// the `signer_pk` from elsewhere.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the sender public key and the decrypted message or whose
+    /// error value is an `failure::Error` describing the error that occurred.
+    pub fn decrypt_with_sender(
+        receiver_sk: &PrivateKey,
+        signer_pk: Option<&PublicKey>,
+        enc_msg: &[u8],
+    ) -> Result<(PublicKey, Vec<u8>)> {
+        match Nis1Envelope::detect(enc_msg) {
+            Nis1EnvelopeKind::Extended => {
+                let extended = Nis1ExtendedEnvelope::parse(enc_msg)?;
+                if let Some(signer_pk) = signer_pk {
+                    ensure!(
+                        extended.sender_public_key == *signer_pk,
+                        "encrypted message is sent by unexpected sender {:X}.",
+                        extended.sender_public_key
+                    );
+                }
+                Ok((extended.sender_public_key, extended.decrypt(receiver_sk)?))
+            }
+            Nis1EnvelopeKind::Legacy => {
+                let signer_pk = signer_pk
+                    .ok_or_else(|| anyhow!("legacy envelope needs the signer public key."))?;
+                let msg = Nis1Envelope::parse(enc_msg)?.decrypt(receiver_sk, signer_pk)?;
+                Ok((*signer_pk, msg))
+            }
+        }
+    }
 
-        let recipient_sk: PrivateKey = receiver_sk.into();
-        let signer_pk: PublicKey = signer_pk.into();
+    /// Encrypt a message with AES-256-CBC, then authenticate it with HMAC-SHA256.
+    ///
+    /// The AES and MAC keys are derived together from the salted shared key, with HKDF-SHA256
+    /// and `NIS1_ETM_INFO`. The payload keeps the legacy layout, `salt || iv || ciphertext`,
+    /// and the `Nis1Mac` of the whole payload is returned apart, for protocols that carry it
+    /// next to the message. The legacy `decrypt_from` cannot read these messages.
+    ///
+    /// # Inputs
+    ///
+    /// * `signer_sk`: The signer's private key.
+    ///
+    /// * `receiver_pk`: The receiver's public key.
+    ///
+    /// * `msg`: Message to encrypt.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the payload `Vec<u8>` and its `Nis1Mac` or whose error
+    /// value is an `failure::Error` describing the error that occurred.
+    pub fn encrypt_then_mac(
+        signer_sk: &PrivateKey,
+        receiver_pk: &PublicKey,
+        msg: &[u8],
+    ) -> Result<(Vec<u8>, Nis1Mac)> {
+        let iv = random_bytes::<NIS_AES_IV_LENGTH>();
+        let salt = random_bytes::<NIS_SALT_LENGTH>();
 
-        let enc_key = derive_shared_key(salt_bytes, recipient_sk, signer_pk);
+        let (enc_key, mac_key) = derive_etm_keys(salt, *signer_sk, *receiver_pk)?;
+        let envelope = Nis1Envelope {
+            salt,
+            iv,
+            ciphertext: encrypt(iv, enc_key, msg)?,
+        };
+        let payload = envelope.to_bytes();
+        let mac = hmac_sha256(&mac_key)?.chain_update(&payload).finalize().into_bytes();
+        Ok((payload, H256::from_slice(&mac)))
+    }
 
-        let decrypt_vec = decrypt(iv_bytes, enc_key, &enc_msg_bytes)?;
+    /// Verify the `Nis1Mac` of an `encrypt_then_mac` message, then decrypt it.
+    ///
+    /// Nothing is decrypted before the MAC is verified, in constant time, so a tampered
+    /// message never reaches the padding check.
+    ///
+    /// # Inputs
+    ///
+    /// * `receiver_sk`: The receiver's private key.
+    ///
+    /// * `signer_pk`: The signer's public key.
+    ///
+    /// * `enc_msg`: The payload.
+    ///
+    /// * `mac`: The `Nis1Mac` received with the payload.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the decrypted message `Vec<u8>` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn decrypt_verified(
+        receiver_sk: &PrivateKey,
+        signer_pk: &PublicKey,
+        enc_msg: &[u8],
+        mac: &Nis1Mac,
+    ) -> Result<Vec<u8>> {
+        let envelope = Nis1Envelope::parse(enc_msg)?;
+        let (enc_key, mac_key) = derive_etm_keys(envelope.salt, *receiver_sk, *signer_pk)?;
+        hmac_sha256(&mac_key)?
+            .chain_update(enc_msg)
+            .verify_slice(mac.as_bytes())
+            .map_err(|_| anyhow!("encrypted message authentication failed."))?;
+        decrypt(envelope.iv, enc_key, &envelope.ciphertext)
+    }
 
-        Ok(decrypt_vec)
+    /// The raw ECDH shared secret of a private key and a public key, before the salt and the
+    /// Keccak-256 of the Nis1 cipher, to derive the keys of another protocol with
+    /// `kdf::expand`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the `SharedSecret` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn derive_raw_shared_secret(
+        secret_key: &PrivateKey,
+        public_key: &PublicKey,
+    ) -> Result<SharedSecret> {
+        public_key.to_point()?;
+        let nis1_order = PrivateKey::from(*secret_key.reverse_for_nis1());
+        Ok(derive_shared_secret::<Keccak512>(nis1_order, *public_key))
     }
 }
 
 // internal functions.
-fn derive_shared_key(
+pub(crate) fn derive_shared_key(
     salt: [u8; NIS_SALT_LENGTH],
-    mut secret_key: PrivateKey,
+    secret_key: PrivateKey,
     public_key: PublicKey,
 ) -> H256 {
-    secret_key.0.reverse();
-    let mut shared_secret = derive_shared_secret::<Keccak512>(secret_key, public_key);
+    let nis1_order = PrivateKey::from(*secret_key.reverse_for_nis1());
+    let mut shared_secret = derive_shared_secret::<Keccak512>(nis1_order, public_key);
     let mut i = 0;
     while i < shared_secret.0.len() {
         shared_secret.0[i] ^= salt[i];
@@ -154,15 +321,59 @@ fn encrypt(iv: [u8; NIS_AES_IV_LENGTH], derive_key: H256, msg: &[u8]) -> Result<
 
 /// Decrypt the given ciphertext slice with AES with a 256-bit key and 16-bytes nonce.
 ///
+/// The PKCS7 padding is checked in constant time, every invalid padding gives the same error.
+///
 /// # Returns
 ///
 /// A `Result` whose okay value is a plaintext as a vector of bytes or whose error value
 /// is an `Error` describing the error that occurred.
-fn decrypt(iv: [u8; NIS_AES_IV_LENGTH], derive_key: H256, enc_msg: &[u8]) -> Result<Vec<u8>> {
+pub(crate) fn decrypt(iv: [u8; NIS_AES_IV_LENGTH], derive_key: H256, enc_msg: &[u8]) -> Result<Vec<u8>> {
     let cipher = Aes2568CbcDec::new_from_slices(&derive_key.as_bytes(), &iv)?;
-    let decrypted = cipher
-        .decrypt_padded_vec_mut::<Pkcs7>(&enc_msg)
-        .map_err(|e| anyhow!("{}", e))?;
+    let mut decrypted = cipher
+        .decrypt_padded_vec_mut::<NoPadding>(enc_msg)
+        .map_err(|_| anyhow!("encrypted message could not be decrypted."))?;
 
+    let len = pkcs7_unpadded_len(&decrypted)
+        .ok_or_else(|| anyhow!("encrypted message could not be decrypted."))?;
+    decrypted.truncate(len);
     Ok(decrypted)
 }
+
+// the plaintext length of a PKCS7 padded buffer, without branching on its padding bytes.
+fn pkcs7_unpadded_len(padded: &[u8]) -> Option<usize> {
+    if padded.is_empty() || !padded.len().is_multiple_of(NIS_AES_BLOCK_SIZE) {
+        return None;
+    }
+    let last_block = &padded[padded.len() - NIS_AES_BLOCK_SIZE..];
+    let pad = last_block[NIS_AES_BLOCK_SIZE - 1];
+
+    // 0xFF if `a < b`, 0x00 otherwise.
+    let lt_mask = |a: u8, b: u8| ((a as u16).wrapping_sub(b as u16) >> 8) as u8;
+    let mut invalid = lt_mask(pad, 1) | lt_mask(NIS_AES_BLOCK_SIZE as u8, pad);
+    for (i, byte) in last_block.iter().rev().enumerate() {
+        invalid |= lt_mask(i as u8, pad) & (byte ^ pad);
+    }
+
+    if invalid == 0 {
+        Some(padded.len() - pad as usize)
+    } else {
+        None
+    }
+}
+
+fn derive_etm_keys(
+    salt: [u8; NIS_SALT_LENGTH],
+    secret_key: PrivateKey,
+    public_key: PublicKey,
+) -> Result<(H256, H256)> {
+    let shared_key = derive_shared_key(salt, secret_key, public_key);
+    let keys = expand(&shared_key, KdfChoice::HkdfSha256, NIS1_ETM_INFO, 2 * KEY_BYTES_SIZE)?;
+    Ok((
+        H256::from_slice(&keys[..KEY_BYTES_SIZE]),
+        H256::from_slice(&keys[KEY_BYTES_SIZE..]),
+    ))
+}
+
+fn hmac_sha256(key: &H256) -> Result<HmacSha256> {
+    HmacSha256::new_from_slice(key.as_bytes()).map_err(|_| anyhow!("invalid mac key length."))
+}
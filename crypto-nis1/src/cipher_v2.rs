@@ -0,0 +1,120 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use aes_gcm::{
+    aead::{generic_array::GenericArray, Aead, NewAead},
+    AeadInPlace, Aes256Gcm,
+};
+use anyhow::{anyhow, ensure, Result};
+
+use super::cipher::derive_shared_key;
+use crate::core::{
+    random_bytes, BlockCipher, PrivateKey, PublicKey, AES_TAG_LENGTH,
+    NIS_SALT_LENGTH, SYM_AES_IV_LENGTH,
+};
+
+/// Nis1 message cipher using AES-GCM instead of AES-CBC.
+///
+/// The shared key derivation is the same as `CryptoNis1` (salted Keccak-256 over the shared
+/// secret), the envelope is `salt || tag || iv || ciphertext`.
+///
+/// This cipher is **not** part of the NIS1 consensus rules: messages produced by it can not be
+/// read by NIS1 wallets and must only be used for off-chain messages between applications that
+/// opt in to it.
+pub struct CryptoNis1V2;
+
+impl crate::core::sealed::Sealed for CryptoNis1V2 {}
+
+impl BlockCipher for CryptoNis1V2 {
+    /// Encode a message text with AES-GCM algorithm using the signer's the PrivateKey and receiver's PublicKey.
+    ///
+    /// # Inputs
+    ///
+    /// * `signer_sk`: The signer's private key.
+    ///
+    /// * `receiver_pk`: The receiver's public key.
+    ///
+    /// * `msg`: Message to encrypt.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is an encrypt message `Vec<u8>` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    fn encrypt_for(
+        signer_sk: &PrivateKey,
+        receiver_pk: &PublicKey,
+        msg: &[u8],
+    ) -> Result<Vec<u8>> {
+        let iv = random_bytes::<SYM_AES_IV_LENGTH>();
+        let salt = random_bytes::<NIS_SALT_LENGTH>();
+
+        let derive_key = derive_shared_key(salt, *signer_sk, *receiver_pk);
+
+        let cipher = Aes256Gcm::new(derive_key.as_generic_array());
+
+        let mut encrypted = msg.to_vec();
+        let auth_tag = cipher
+            .encrypt_in_place_detached(GenericArray::from_slice(&iv), &[], &mut encrypted)
+            .map_err(|e| anyhow!("{}", e))?;
+
+        let mut enc = vec![];
+        enc.extend_from_slice(&salt);
+        enc.extend_from_slice(&auth_tag);
+        enc.extend_from_slice(&iv);
+        enc.extend_from_slice(&encrypted);
+        Ok(enc)
+    }
+
+    /// Decrypt a message text with AES-GCM algorithm using the receiver's the PrivateKey and signer's PublicKey.
+    ///
+    /// # Inputs
+    ///
+    /// * `receiver_sk`: The receiver's private key.
+    ///
+    /// * `signer_pk`: The signer's public key.
+    ///
+    /// * `enc_msg`: Message encrypted.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is an decrypted message `Vec<u8>` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    fn decrypt_from(
+        receiver_sk: &PrivateKey,
+        signer_pk: &PublicKey,
+        enc_msg: &[u8],
+    ) -> Result<Vec<u8>> {
+        ensure!(
+            enc_msg.len() >= NIS_SALT_LENGTH + AES_TAG_LENGTH + SYM_AES_IV_LENGTH,
+            "msg has unexpected size."
+        );
+
+        let mut salt_bytes = [0u8; NIS_SALT_LENGTH];
+        salt_bytes.copy_from_slice(&enc_msg[..NIS_SALT_LENGTH]);
+
+        let tag_offset = NIS_SALT_LENGTH;
+        let iv_offset = tag_offset + AES_TAG_LENGTH;
+        let msg_offset = iv_offset + SYM_AES_IV_LENGTH;
+
+        let mut msg_and_tag_bytes = Vec::with_capacity(enc_msg.len() - NIS_SALT_LENGTH);
+        msg_and_tag_bytes.extend_from_slice(&enc_msg[msg_offset..]);
+        msg_and_tag_bytes.extend_from_slice(&enc_msg[tag_offset..iv_offset]);
+
+        let enc_key = derive_shared_key(salt_bytes, *receiver_sk, *signer_pk);
+
+        let cipher = Aes256Gcm::new(enc_key.as_generic_array());
+        let decrypted = cipher
+            .decrypt(
+                GenericArray::from_slice(&enc_msg[iv_offset..msg_offset]),
+                msg_and_tag_bytes.as_ref(),
+            )
+            .map_err(|e| anyhow!("{}", e))?;
+
+        Ok(decrypted)
+    }
+}
@@ -0,0 +1,229 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The NIS1 legacy encrypted message layout, `salt || iv || AES-256-CBC(message)`.
+//!
+//! `Nis1Envelope::parse` checks every bound before slicing, so malformed historical messages,
+//! e.g. read by an explorer, give an error instead of a panic.
+//!
+//! The extended layout, `NIS1_EXTENDED_MAGIC || version || sender public key || ciphertext
+//! length (u32 little endian) || salt || iv || ciphertext`, also carries the signer public key,
+//! so a receiver can decrypt it without knowing the sender, and the ciphertext length, so a
+//! payload truncated on a block boundary is rejected. Its header is 41 bytes, so an extended
+//! envelope is never a multiple of the block size and cannot be mistaken for a legacy one:
+//! `Nis1Envelope::detect` tells them apart.
+//!
+
+use std::convert::TryInto;
+
+use anyhow::{anyhow, ensure, Result};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::cipher::{decrypt, derive_shared_key};
+use crate::core::{
+    PrivateKey, PublicKey, KEY_BYTES_SIZE, NIS_AES_IV_LENGTH, NIS_SALT_LENGTH,
+};
+
+/// The AES block size, the ciphertext is a non empty multiple of it.
+pub const NIS_AES_BLOCK_SIZE: usize = 16;
+
+/// The first bytes of an extended NIS1 envelope.
+pub const NIS1_EXTENDED_MAGIC: [u8; 4] = *b"NISX";
+
+/// The version byte of the extended envelopes written by this crate.
+pub const NIS1_EXTENDED_VERSION: u8 = 1;
+
+const EXTENDED_LENGTH_OFFSET: usize = NIS1_EXTENDED_MAGIC.len() + 1 + KEY_BYTES_SIZE;
+const EXTENDED_HEADER_SIZE: usize = EXTENDED_LENGTH_OFFSET + 4;
+
+/// The layout of an encrypted message payload, see `Nis1Envelope::detect`.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Nis1EnvelopeKind {
+    /// `salt || iv || ciphertext`.
+    Legacy,
+    /// `NIS1_EXTENDED_MAGIC || version || sender public key || ciphertext length || salt || iv ||
+    /// ciphertext`.
+    Extended,
+}
+
+/// A parsed NIS1 legacy encrypted message.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct Nis1Envelope {
+    pub salt: [u8; NIS_SALT_LENGTH],
+    pub iv: [u8; NIS_AES_IV_LENGTH],
+    /// The PKCS7 padded AES-256-CBC ciphertext.
+    pub ciphertext: Vec<u8>,
+}
+
+impl Nis1Envelope {
+    /// Detect the layout of an encrypted message payload, without validating it.
+    ///
+    /// A legacy payload is always a multiple of the block size, an extended one never is.
+    ///
+    pub fn detect(bytes: &[u8]) -> Nis1EnvelopeKind {
+        if !bytes.len().is_multiple_of(NIS_AES_BLOCK_SIZE)
+            && bytes.starts_with(&NIS1_EXTENDED_MAGIC)
+        {
+            Nis1EnvelopeKind::Extended
+        } else {
+            Nis1EnvelopeKind::Legacy
+        }
+    }
+
+    /// Parse an encrypted message payload.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is a `Nis1Envelope` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn parse(bytes: &[u8]) -> Result<Self> {
+        ensure!(
+            bytes.len() >= NIS_SALT_LENGTH + NIS_AES_IV_LENGTH + NIS_AES_BLOCK_SIZE,
+            "encrypted message is too short: {} bytes.",
+            bytes.len()
+        );
+
+        let (salt, rest) = bytes.split_at(NIS_SALT_LENGTH);
+        let (iv, ciphertext) = rest.split_at(NIS_AES_IV_LENGTH);
+        ensure!(
+            ciphertext.len() % NIS_AES_BLOCK_SIZE == 0,
+            "encrypted message ciphertext is not a multiple of the block size."
+        );
+
+        let mut envelope = Self {
+            salt: [0u8; NIS_SALT_LENGTH],
+            iv: [0u8; NIS_AES_IV_LENGTH],
+            ciphertext: ciphertext.to_vec(),
+        };
+        envelope.salt.copy_from_slice(salt);
+        envelope.iv.copy_from_slice(iv);
+        Ok(envelope)
+    }
+
+    /// The encrypted message payload, `salt || iv || ciphertext`.
+    ///
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes =
+            Vec::with_capacity(NIS_SALT_LENGTH + NIS_AES_IV_LENGTH + self.ciphertext.len());
+        bytes.extend_from_slice(&self.salt);
+        bytes.extend_from_slice(&self.iv);
+        bytes.extend_from_slice(&self.ciphertext);
+        bytes
+    }
+
+    /// Decrypt the message, as its receiver or its signer.
+    ///
+    /// # Inputs
+    ///
+    /// * `receiver_sk`: The receiver's private key.
+    ///
+    /// * `signer_pk`: The signer's public key.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the decrypted message `Vec<u8>` or whose error value
+    /// is an `failure::Error` describing the error that occurred, e.g. an invalid PKCS7
+    /// padding for a wrong key.
+    pub fn decrypt(&self, receiver_sk: &PrivateKey, signer_pk: &PublicKey) -> Result<Vec<u8>> {
+        let enc_key = derive_shared_key(self.salt, *receiver_sk, *signer_pk);
+        decrypt(self.iv, enc_key, &self.ciphertext)
+    }
+}
+
+/// A parsed NIS1 extended encrypted message, a legacy envelope and its sender public key.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct Nis1ExtendedEnvelope {
+    pub version: u8,
+    /// The public key of the signer that encrypted the message.
+    pub sender_public_key: PublicKey,
+    pub envelope: Nis1Envelope,
+}
+
+impl Nis1ExtendedEnvelope {
+    /// Parse an extended encrypted message payload.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is a `Nis1ExtendedEnvelope` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn parse(bytes: &[u8]) -> Result<Self> {
+        ensure!(
+            Nis1Envelope::detect(bytes) == Nis1EnvelopeKind::Extended
+                && bytes.len() >= EXTENDED_HEADER_SIZE,
+            "encrypted message is not an extended envelope."
+        );
+        let version = bytes[NIS1_EXTENDED_MAGIC.len()];
+        ensure!(
+            version == NIS1_EXTENDED_VERSION,
+            "extended envelope has unknown version {}.",
+            version
+        );
+
+        let envelope = Nis1Envelope::parse(&bytes[EXTENDED_HEADER_SIZE..])?;
+        let ciphertext_len =
+            u32::from_le_bytes(bytes[EXTENDED_LENGTH_OFFSET..EXTENDED_HEADER_SIZE].try_into()?);
+        ensure!(
+            envelope.ciphertext.len() == ciphertext_len as usize,
+            "extended envelope ciphertext is truncated or extended."
+        );
+
+        let sender_public_key =
+            PublicKey::from_slice(&bytes[NIS1_EXTENDED_MAGIC.len() + 1..EXTENDED_LENGTH_OFFSET]);
+        sender_public_key
+            .to_point()
+            .map_err(|e| anyhow!("extended envelope sender public key is invalid: {}", e))?;
+
+        Ok(Self {
+            version,
+            sender_public_key,
+            envelope,
+        })
+    }
+
+    /// The encrypted message payload, `NIS1_EXTENDED_MAGIC || version || sender public key ||
+    /// ciphertext length || salt || iv || ciphertext`.
+    ///
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let envelope = self.envelope.to_bytes();
+        let mut bytes = Vec::with_capacity(EXTENDED_HEADER_SIZE + envelope.len());
+        bytes.extend_from_slice(&NIS1_EXTENDED_MAGIC);
+        bytes.push(self.version);
+        bytes.extend_from_slice(self.sender_public_key.as_bytes());
+        bytes.extend_from_slice(&(self.envelope.ciphertext.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(&envelope);
+        bytes
+    }
+
+    /// Decrypt the message as its receiver, with the embedded sender public key.
+    ///
+    /// # Inputs
+    ///
+    /// * `receiver_sk`: The receiver's private key.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the decrypted message `Vec<u8>` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn decrypt(&self, receiver_sk: &PrivateKey) -> Result<Vec<u8>> {
+        self.envelope.decrypt(receiver_sk, &self.sender_public_key)
+    }
+}
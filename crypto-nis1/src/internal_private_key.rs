@@ -6,11 +6,14 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+use anyhow::{ensure, Result};
 use sha3::{Digest, Keccak512};
 
-use crate::core::{PrivateKey, PublicKey, Signature};
+use crate::core::{clamp_scalar_bits, PrivateKey, PublicKey, Signature};
 use crate::core::curve25519::{constants, edwards::CompressedEdwardsY, scalar::Scalar};
+use super::internal_public_key::InternalPublicKey;
 use super::internal_signature::InternalSignature;
+use super::nonce::nonce_scalar;
 
 pub struct ExpandedPrivateKey {
     pub key: Scalar,
@@ -24,47 +27,71 @@ impl<'a> From<&'a PrivateKey> for ExpandedPrivateKey {
         let mut lower: [u8; 32] = [0u8; 32];
         let mut upper: [u8; 32] = [0u8; 32];
 
-        let mut secret_key_bytes = secret_key.to_fixed_bytes();
-        secret_key_bytes.reverse();
-        h.update(secret_key_bytes);
+        h.update(&secret_key.reverse_for_nis1()[..]);
 
         hash.copy_from_slice(h.finalize().as_slice());
 
         lower.copy_from_slice(&hash[00..32]);
         upper.copy_from_slice(&hash[32..64]);
 
-        lower[0] &= 248;
-        lower[31] &= 63;
-        lower[31] |= 64;
-
         ExpandedPrivateKey {
-            key: Scalar::from_bits(lower),
+            key: Scalar::from_bits(clamp_scalar_bits(lower)),
             nonce: upper,
         }
     }
 }
 
 impl ExpandedPrivateKey {
+    /// Check the key can sign for `public_key`: a zeroed key, e.g. a wiped secret, or a key
+    /// whose public key is not `public_key` would produce signatures nobody can verify.
+    ///
+    pub fn check(&self, public_key: PublicKey) -> Result<()> {
+        ensure!(
+            self.key != Scalar::zero() && self.nonce != [0u8; 32],
+            "nis1 expanded private key is zeroed."
+        );
+        ensure!(
+            InternalPublicKey::from(self).to_bytes() == public_key.to_fixed_bytes(),
+            "nis1 expanded private key does not match the public key."
+        );
+        Ok(())
+    }
+
+    /// Sign a message with this `ExpandedKey`, see `check`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the `Signature` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn try_sign(&self, message: &[u8], public_key: PublicKey) -> Result<Signature> {
+        self.check(public_key)?;
+        Ok(self.sign(message, public_key))
+    }
+
     /// Sign a message with this `ExpandedKey`.
     ///
     #[allow(non_snake_case)]
     pub fn sign(&self, message: &[u8], public_key: PublicKey) -> Signature {
-        let mut h: Keccak512 = Keccak512::new();
+        self.sign_vectored(&[message], public_key)
+    }
+
+    /// Sign the concatenation of the message parts with this `ExpandedKey`.
+    ///
+    #[allow(non_snake_case)]
+    pub fn sign_vectored(&self, parts: &[&[u8]], public_key: PublicKey) -> Signature {
         let R: CompressedEdwardsY;
         let r: Scalar;
         let s: Scalar;
         let k: Scalar;
 
-        h.update(&self.nonce);
-        h.update(&message);
-        r = Scalar::from_hash(h);
+        r = nonce_scalar(&self.nonce, parts);
 
         R = (&r * &constants::ED25519_BASEPOINT_TABLE).compress();
 
-        h = Keccak512::new();
+        let mut h = Keccak512::new();
         h.update(R.as_bytes());
         h.update(public_key.as_bytes());
-        h.update(&message);
+        parts.iter().for_each(|part| h.update(part));
 
         k = Scalar::from_hash(h);
 
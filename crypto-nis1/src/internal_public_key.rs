@@ -20,7 +20,7 @@ use crate::core::curve25519::{
     edwards::{CompressedEdwardsY, EdwardsPoint},
     scalar::Scalar,
 };
-use crate::core::{PrivateKey, PublicKey, Signature, KEY_BYTES_SIZE};
+use crate::core::{clamp_scalar_bits, PrivateKey, PublicKey, Signature, KEY_BYTES_SIZE};
 
 #[derive(Copy, Clone, Default, Eq, PartialEq)]
 pub struct InternalPublicKey(pub(crate) CompressedEdwardsY, pub(crate) EdwardsPoint);
@@ -44,10 +44,7 @@ impl From<PrivateKey> for InternalPublicKey {
         let mut hash: [u8; 64] = [0u8; 64];
         let mut digest: [u8; 32] = [0u8; 32];
 
-        let mut secret_key = private_key.to_fixed_bytes();
-        secret_key.reverse();
-
-        h.update(&secret_key);
+        h.update(&private_key.reverse_for_nis1()[..]);
         hash.copy_from_slice(h.finalize().as_slice());
 
         digest.copy_from_slice(&hash[..32]);
@@ -116,9 +113,7 @@ impl InternalPublicKey {
     fn mangle_scalar_bits_and_multiply_by_basepoint_to_produce_public_key(
         bits: &mut [u8; 32],
     ) -> InternalPublicKey {
-        bits[0] &= 248;
-        bits[31] &= 127;
-        bits[31] |= 64;
+        *bits = clamp_scalar_bits(*bits);
 
         let point = &Scalar::from_bits(*bits) * &constants::ED25519_BASEPOINT_TABLE;
         let compressed = point.compress();
@@ -135,6 +130,19 @@ impl Verifier<Signature> for InternalPublicKey {
     /// Returns `Ok(())` if the signature is valid, and `Err` otherwise.
     #[allow(non_snake_case)]
     fn verify(&self, message: &[u8], signature: &Signature) -> Result<(), signature::Error> {
+        self.verify_vectored(&[message], signature)
+    }
+}
+
+impl InternalPublicKey {
+    /// Verify a signature on the concatenation of the message parts with this public key.
+    ///
+    #[allow(non_snake_case)]
+    pub fn verify_vectored(
+        &self,
+        parts: &[&[u8]],
+        signature: &Signature,
+    ) -> Result<(), signature::Error> {
         let signature = InternalSignature::from_bytes(signature.as_bytes())
             .map_err(|_| core::ed25519::SignatureError::new())?;
 
@@ -145,8 +153,7 @@ impl Verifier<Signature> for InternalPublicKey {
         let mut h: Keccak512 = Keccak512::new();
         h.update(signature.R.as_bytes());
         h.update(self.as_bytes());
-
-        h.update(&message);
+        parts.iter().for_each(|part| h.update(part));
 
         k = Scalar::from_hash(h);
         R = EdwardsPoint::vartime_double_scalar_mul_basepoint(&k, &(minus_A), &signature.s);
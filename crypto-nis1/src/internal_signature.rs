@@ -8,9 +8,8 @@
 
 use anyhow::{bail, ensure, Result};
 
-use super::check_scalar;
 use crate::core::curve25519::{edwards::CompressedEdwardsY, scalar::Scalar};
-use crate::core::{Signature, SIGNATURE_LENGTH};
+use crate::core::{check_scalar, Signature, SIGNATURE_LENGTH};
 
 #[derive(Clone, Copy, Eq, PartialEq)]
 #[allow(non_snake_case)]
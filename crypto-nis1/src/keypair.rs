@@ -14,42 +14,69 @@
 //! advice, please refer to the [Key pair](https://docs.nem.io/en/nem-sdk/private-key#6-2-create-key-pairs).
 //!
 use std::fmt;
-use std::str::FromStr;
+use std::hash::{Hash, Hasher};
 
-use anyhow::{ensure, Result};
+use anyhow::{anyhow, Result};
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
 #[cfg(feature = "serde")]
 use serde::{Serialize, Serializer};
 #[cfg(feature = "serde")]
 use serde_bytes::Bytes as SerdeBytes;
 
+use sha3::{Digest, Keccak512};
+
 use core::ed25519::Verifier;
 
 use super::{internal_private_key::ExpandedPrivateKey, internal_public_key::InternalPublicKey};
 use crate::cipher::CryptoNis1;
-use crate::core::{is_hex, KeyPairSchema, PrivateKey, PublicKey, Signature, KEY_STR_SIZE};
+use crate::core::curve25519::{
+    constants,
+    edwards::{CompressedEdwardsY, EdwardsPoint},
+    scalar::Scalar,
+};
+use crate::core::{
+    clamp_scalar_bits, KeyPairSchema, PrivateKey, PublicKey, Scalar32, Signature, KEY_BYTES_SIZE,
+};
 
 /// It represents an Nis1 asymmetric private/public encryption key.
 ///
-#[derive(Debug, Clone, Copy, PartialEq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Keypair {
     /// The private half of this keypair.
     pub private_key: PrivateKey,
     /// The public half of this keypair.
     pub public_key: PublicKey,
+    /// The expanded secret of a keypair imported without its private key.
+    expanded: Option<ExpandedScalar>,
 }
 
+/// The clamped secret scalar and nonce prefix of `Keypair::from_expanded_scalar`.
+///
+/// `Debug` redacts both and `Keypair` does not hash them.
+///
+#[derive(Clone, Copy, PartialEq)]
+struct ExpandedScalar {
+    scalar: [u8; KEY_BYTES_SIZE],
+    nonce: [u8; KEY_BYTES_SIZE],
+}
+
+impl crate::core::sealed::Sealed for Keypair {}
+
 impl KeyPairSchema for Keypair {
     type Crypto = CryptoNis1;
+    const SCHEMA_NAME: &'static str = "nis1";
 
     /// Create a new Nis1 `Keypair` with cryptographically random content.
     ///
     fn random() -> Self {
-        let private_key: PrivateKey = PrivateKey::random();
+        let private_key = PrivateKey::random();
         let public_key: PublicKey = InternalPublicKey::from(private_key).to_bytes().into();
 
         Self {
             private_key,
             public_key,
+            expanded: None,
         }
     }
 
@@ -60,6 +87,7 @@ impl KeyPairSchema for Keypair {
         Ok(Self {
             private_key: kp.secret.to_bytes().into(),
             public_key: kp.public.to_bytes().into(),
+            expanded: None,
         })
     }
 
@@ -74,21 +102,7 @@ impl KeyPairSchema for Keypair {
     /// A `Result` whose okay value is an Nis1 `Keypair` or whose error value
     /// is an `failure::Error` describing the error that occurred.
     fn from_hex_private_key<S: AsRef<str>>(hex: S) -> Result<Self> {
-        let hex = hex.as_ref();
-        ensure!(is_hex(hex), "private_key it's not hex.");
-        ensure!(
-            KEY_STR_SIZE == hex.len(),
-            format!("private key has unexpected size {}", hex.len())
-        );
-
-        let private_key: PrivateKey = PrivateKey::from_str(hex.as_ref())?;
-
-        let public_key: PublicKey = (InternalPublicKey::from(private_key).to_bytes()).into();
-
-        Ok(Self {
-            private_key,
-            public_key,
-        })
+        Ok(Self::from_private_key(PrivateKey::from_hex_ct(hex.as_ref())?))
     }
 
     /// Construct a Nis1 `Keypair` `PrivateKey` type.
@@ -106,6 +120,23 @@ impl KeyPairSchema for Keypair {
         Self {
             private_key: pk,
             public_key,
+            expanded: None,
+        }
+    }
+
+    /// Construct a signing only Nis1 `Keypair` from the clamped lower half of the Keccak-512
+    /// of a reversed private key and its upper half, the nonce prefix.
+    ///
+    fn from_expanded_scalar(scalar: &Scalar32, nonce: [u8; KEY_BYTES_SIZE]) -> Self {
+        let public_key = (&scalar.to_scalar() * &constants::ED25519_BASEPOINT_TABLE).compress();
+
+        Self {
+            private_key: PrivateKey::zero(),
+            public_key: PublicKey::from(public_key.to_bytes()),
+            expanded: Some(ExpandedScalar {
+                scalar: *scalar.as_bytes(),
+                nonce,
+            }),
         }
     }
 
@@ -126,10 +157,26 @@ impl KeyPairSchema for Keypair {
     /// # Returns
     ///
     /// A `Signature` the signature hash.
+    ///
+    /// # Panics
+    ///
+    /// If the keypair has no private key for its public key, e.g. from
+    /// `from_null_private_key`, see `try_sign`.
     fn sign(&self, data: &[u8]) -> Signature {
-        let kp = *self;
-        let expanded_sk: ExpandedPrivateKey = (&kp.private_key).into();
-        expanded_sk.sign(data, kp.public_key)
+        self.try_sign_unverified(data).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Signs a data bytes, see `sign`. Fails for a keypair without private key, e.g. from
+    /// `from_null_private_key`, or a zeroed expanded secret, instead of signing with it. With
+    /// the `verify_after_sign` feature the signature is verified before it is returned, against
+    /// faults injected while signing.
+    ///
+    fn try_sign(&self, data: &[u8]) -> Result<Signature> {
+        let signature = self.try_sign_unverified(data)?;
+        #[cfg(feature = "verify_after_sign")]
+        self.verify(data, signature)
+            .map_err(|_| anyhow!("signature failed verification after signing."))?;
+        Ok(signature)
     }
 
     /// Verify a `Signature` on a data with this Nis1 Keypair public key.
@@ -149,10 +196,80 @@ impl KeyPairSchema for Keypair {
         Ok(pk.verify(data, &signature)?)
     }
 
+    /// Signs the concatenation of the data parts with a Nis1 `Keypair`.
+    ///
+    /// # Panics
+    ///
+    /// If the keypair has no private key for its public key, see `sign`.
+    fn sign_vectored(&self, parts: &[&[u8]]) -> Signature {
+        let expanded = self.expanded_private_key();
+        expanded
+            .check(self.public_key)
+            .unwrap_or_else(|e| panic!("keypair cannot sign: {}", e));
+        expanded.sign_vectored(parts, self.public_key)
+    }
+
+    /// Verify a `Signature` on the concatenation of the data parts with this Nis1 Keypair
+    /// public key.
+    ///
+    fn verify_vectored(&self, parts: &[&[u8]], signature: Signature) -> Result<()> {
+        let pk = InternalPublicKey::from(self.public_key);
+        Ok(pk.verify_vectored(parts, &signature)?)
+    }
+
+    /// Verify a `Signature` on a data with the decompressed point of a Nis1 public key.
+    ///
+    fn verify_with_point(
+        public_key: &PublicKey,
+        point: &EdwardsPoint,
+        data: &[u8],
+        signature: Signature,
+    ) -> Result<()> {
+        let pk = InternalPublicKey(CompressedEdwardsY(public_key.to_fixed_bytes()), *point);
+        Ok(pk.verify_vectored(&[data], &signature)?)
+    }
+
+    fn derive_public_keys(private_keys: &[PrivateKey]) -> Vec<PublicKey> {
+        #[cfg(feature = "rayon")]
+        let public_keys = private_keys
+            .par_iter()
+            .map_init(Keccak512::new, derive_public_key)
+            .collect();
+        #[cfg(not(feature = "rayon"))]
+        let public_keys = {
+            let mut hasher = Keccak512::new();
+            private_keys
+                .iter()
+                .map(|private_key| derive_public_key(&mut hasher, private_key))
+                .collect()
+        };
+        public_keys
+    }
+
     fn from_null_private_key(pk: PublicKey) -> Self {
         Self {
             private_key: PrivateKey::zero(),
             public_key: pk,
+            expanded: None,
+        }
+    }
+}
+
+impl Keypair {
+    // internal functions.
+    fn try_sign_unverified(&self, data: &[u8]) -> Result<Signature> {
+        self.expanded_private_key()
+            .try_sign(data, self.public_key)
+            .map_err(|e| anyhow!("keypair cannot sign: {}", e))
+    }
+
+    fn expanded_private_key(&self) -> ExpandedPrivateKey {
+        match self.expanded {
+            Some(expanded) => ExpandedPrivateKey {
+                key: Scalar::from_bits(expanded.scalar),
+                nonce: expanded.nonce,
+            },
+            None => (&self.private_key).into(),
         }
     }
 }
@@ -163,13 +280,40 @@ impl fmt::Display for Keypair {
     }
 }
 
+impl Hash for Keypair {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.private_key.hash(state);
+        self.public_key.hash(state);
+    }
+}
+
+impl fmt::Debug for ExpandedScalar {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExpandedScalar")
+            .field("scalar", &"[redacted]")
+            .field("nonce", &"[redacted]")
+            .finish()
+    }
+}
+
 #[cfg(feature = "serde")]
 impl Serialize for Keypair {
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        let bytes = &self.to_bytes()[..];
-        SerdeBytes::new(bytes).serialize(serializer)
+        // only the public half, see `SecretSerde` for the full keypair.
+        SerdeBytes::new(self.public_key().as_bytes()).serialize(serializer)
     }
 }
+
+// internal functions.
+/// The public key of a private key, with a reused hasher.
+fn derive_public_key(hasher: &mut Keccak512, private_key: &PrivateKey) -> PublicKey {
+    hasher.update(&private_key.reverse_for_nis1()[..]);
+    let hash = hasher.finalize_reset();
+    let mut lower = [0u8; KEY_BYTES_SIZE];
+    lower.copy_from_slice(&hash[..KEY_BYTES_SIZE]);
+    let a = Scalar::from_bits(clamp_scalar_bits(lower));
+    PublicKey::from((&a * &constants::ED25519_BASEPOINT_TABLE).compress().to_bytes())
+}
@@ -0,0 +1,235 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::convert::TryInto;
+
+use anyhow::{anyhow, ensure, Result};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+
+use super::{GenerationHash, Keypair};
+use crate::core::{KeyPairSchema, PublicKey, Signature, H256, KEY_BYTES_SIZE, SIGNATURE_LENGTH};
+
+/// The `PartiallySignedAggregate` encoding version.
+pub const PSA_VERSION: u8 = 1;
+
+/// Size of the transaction header preceding the signed data:
+/// `size (u32) || reserved (u32) || signature || signer || reserved (u32)`.
+pub const TRANSACTION_HEADER_SIZE: usize = 4 + 4 + SIGNATURE_LENGTH + KEY_BYTES_SIZE + 4;
+
+/// Size of an encoded cosignature: `version (u64) || signer || signature`.
+pub const COSIGNATURE_SIZE: usize = 8 + KEY_BYTES_SIZE + SIGNATURE_LENGTH;
+
+/// A cosignature of an aggregate transaction hash.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct Cosignature {
+    pub version: u64,
+    pub signer_public_key: PublicKey,
+    pub signature: Signature,
+}
+
+/// A Symbol aggregate transaction collecting its signatures offline, analogous to Bitcoin's PSBT.
+///
+/// `payload` is the signed part of the aggregate: the transaction bytes following the
+/// `TRANSACTION_HEADER_SIZE` header, up to (excluding) the cosignatures. The initiator signs
+/// `generation_hash_seed || payload`, cosigners sign the resulting aggregate transaction hash.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct PartiallySignedAggregate {
+    pub version: u8,
+    pub generation_hash_seed: GenerationHash,
+    pub payload: Vec<u8>,
+    pub signer: Option<(PublicKey, Signature)>,
+    pub cosignatures: Vec<Cosignature>,
+}
+
+impl PartiallySignedAggregate {
+    /// Create an unsigned container.
+    ///
+    /// # Inputs
+    ///
+    /// * `generation_hash_seed`: The network generation hash seed.
+    ///
+    /// * `payload`: The aggregate bytes following the transaction header, without cosignatures.
+    ///
+    pub fn new(generation_hash_seed: GenerationHash, payload: Vec<u8>) -> Self {
+        Self {
+            version: PSA_VERSION,
+            generation_hash_seed,
+            payload,
+            signer: None,
+            cosignatures: vec![],
+        }
+    }
+
+    /// Sign the aggregate as initiator.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the aggregate transaction hash `H256` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn sign(&mut self, kp: &crate::core::Keypair<Keypair>) -> Result<H256> {
+        ensure!(self.signer.is_none(), "aggregate is already signed.");
+
+        self.signer = Some((kp.public_key(), kp.try_sign(&self.signing_data())?));
+        self.hash()
+    }
+
+    /// The aggregate transaction hash, available once the initiator signed.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the aggregate transaction hash `H256` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn hash(&self) -> Result<H256> {
+        let (signer, signature) = self
+            .signer
+            .ok_or_else(|| anyhow!("aggregate is not signed by the initiator."))?;
+
+        let mut h = Sha3_256::new();
+        h.update(&signature.as_bytes()[..SIGNATURE_LENGTH / 2]);
+        h.update(signer.as_bytes());
+        h.update(self.generation_hash_seed.as_bytes());
+        h.update(&self.payload);
+        Ok(H256::from_slice(h.finalize().as_slice()))
+    }
+
+    /// Add the cosignature of `kp`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the added `Cosignature` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn cosign(&mut self, kp: &crate::core::Keypair<Keypair>) -> Result<Cosignature> {
+        let cosignature = Cosignature {
+            version: 0,
+            signer_public_key: kp.public_key(),
+            signature: kp.try_sign(self.hash()?.as_bytes())?,
+        };
+        self.add_cosignature(cosignature)?;
+        Ok(cosignature)
+    }
+
+    /// Merge the signatures collected by another copy of the same aggregate.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok` if both containers carry the same aggregate and no conflicting signature.
+    pub fn merge(&mut self, other: &PartiallySignedAggregate) -> Result<()> {
+        ensure!(
+            self.generation_hash_seed == other.generation_hash_seed
+                && self.payload == other.payload,
+            "aggregates do not carry the same transaction."
+        );
+
+        match (self.signer, other.signer) {
+            (Some(signer), Some(other_signer)) => ensure!(
+                signer == other_signer,
+                "aggregates are signed by different initiators."
+            ),
+            (None, Some(other_signer)) => self.signer = Some(other_signer),
+            _ => (),
+        }
+
+        for cosignature in &other.cosignatures {
+            if !self.cosignatures.contains(cosignature) {
+                self.add_cosignature(*cosignature)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Verify the initiator signature and every cosignature.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok` if all the collected signatures are valid.
+    pub fn validate(&self) -> Result<()> {
+        ensure!(
+            self.version == PSA_VERSION,
+            "unsupported partially signed aggregate version {}.",
+            self.version
+        );
+
+        let (signer, signature) = self
+            .signer
+            .ok_or_else(|| anyhow!("aggregate is not signed by the initiator."))?;
+        Keypair::from_null_private_key(signer).verify(&self.signing_data(), signature)?;
+
+        let hash = self.hash()?;
+        for cosignature in &self.cosignatures {
+            Keypair::from_null_private_key(cosignature.signer_public_key)
+                .verify(hash.as_bytes(), cosignature.signature)?;
+        }
+        Ok(())
+    }
+
+    /// Validate and serialize the final transaction, ready to announce.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the transaction payload `Vec<u8>` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn finalize(&self) -> Result<Vec<u8>> {
+        self.validate()?;
+        let (signer, signature) = self
+            .signer
+            .ok_or_else(|| anyhow!("aggregate is not signed by the initiator."))?;
+
+        let size = TRANSACTION_HEADER_SIZE
+            + self.payload.len()
+            + COSIGNATURE_SIZE * self.cosignatures.len();
+        let size: u32 = size
+            .try_into()
+            .map_err(|_| anyhow!("aggregate transaction is too large."))?;
+
+        let mut bytes = Vec::with_capacity(size as usize);
+        bytes.extend_from_slice(&size.to_le_bytes());
+        bytes.extend_from_slice(&[0u8; 4]);
+        bytes.extend_from_slice(signature.as_bytes());
+        bytes.extend_from_slice(signer.as_bytes());
+        bytes.extend_from_slice(&[0u8; 4]);
+        bytes.extend_from_slice(&self.payload);
+        for cosignature in &self.cosignatures {
+            bytes.extend_from_slice(&cosignature.version.to_le_bytes());
+            bytes.extend_from_slice(cosignature.signer_public_key.as_bytes());
+            bytes.extend_from_slice(cosignature.signature.as_bytes());
+        }
+        Ok(bytes)
+    }
+
+    // internal functions.
+    fn signing_data(&self) -> Vec<u8> {
+        [self.generation_hash_seed.as_bytes(), &self.payload[..]].concat()
+    }
+
+    fn add_cosignature(&mut self, cosignature: Cosignature) -> Result<()> {
+        ensure!(
+            !self
+                .cosignatures
+                .iter()
+                .any(|c| c.signer_public_key == cosignature.signer_public_key),
+            "{:x} already cosigned.",
+            cosignature.signer_public_key
+        );
+        self.cosignatures.push(cosignature);
+        Ok(())
+    }
+}
@@ -0,0 +1,403 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Encrypted attachments with selective disclosure, for files exchanged off-chain whose
+//! on-chain message only carries the manifest hash.
+//!
+//! The attachment is split in chunks of `chunk_size` bytes (the last one shorter), each one
+//! encrypted with AES-256-GCM under its own key
+//! `HKDF-SHA256(salt = ATTACHMENT_KEY_SALT, ikm = content_key, info = id || index)`, with
+//! `index` as little endian `u32` and the chunk index as iv. Sharing the content key discloses
+//! the whole attachment, sharing a `chunk_key` discloses that chunk only.
+//!
+//! The `AttachmentManifest` lists the SHA3-256 of every encrypted chunk and is signed by the
+//! sender, so chunks can be checked, and their order and count, before any key is known.
+//!
+
+use std::convert::TryInto;
+use std::io::{self, Read, Write};
+
+use anyhow::{anyhow, ensure, Result};
+use hkdf::Hkdf;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use sha3::{Digest, Sha3_256};
+
+use super::cipher::{decrypt, encrypt};
+use super::Keypair;
+use crate::core::{
+    random_bytes, AesKey, KeyPairSchema, PublicKey, Signature, AES_TAG_LENGTH, H256,
+    SYM_AES_IV_LENGTH,
+};
+
+/// The HKDF salt of the chunk keys.
+pub const ATTACHMENT_KEY_SALT: &[u8] = b"symbol-attachment-v1";
+
+/// The prefix of every signed manifest.
+pub const ATTACHMENT_TAG: &[u8] = b"symbol-crypto-core/attachment/v1\0";
+
+/// The signed list of the encrypted chunks of an attachment.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct AttachmentManifest {
+    /// Random id of the attachment, part of every chunk key.
+    pub id: H256,
+    pub chunk_size: u32,
+    /// The plaintext size in bytes.
+    pub total_size: u64,
+    /// The SHA3-256 of every encrypted chunk, in order.
+    pub chunk_hashes: Vec<H256>,
+    pub signer: PublicKey,
+    pub signature: Signature,
+}
+
+impl AttachmentManifest {
+    /// The signed bytes, `ATTACHMENT_TAG || id || chunk_size || total_size || signer || hashes`.
+    ///
+    pub fn message(&self) -> Vec<u8> {
+        let mut message = ATTACHMENT_TAG.to_vec();
+        message.extend_from_slice(self.id.as_bytes());
+        message.extend_from_slice(&self.chunk_size.to_le_bytes());
+        message.extend_from_slice(&self.total_size.to_le_bytes());
+        message.extend_from_slice(self.signer.as_bytes());
+        self.chunk_hashes
+            .iter()
+            .for_each(|hash| message.extend_from_slice(hash.as_bytes()));
+        message
+    }
+
+    /// The SHA3-256 of the signed bytes, to reference the attachment on-chain.
+    ///
+    pub fn hash(&self) -> H256 {
+        H256::from_slice(Sha3_256::digest(self.message()).as_slice())
+    }
+
+    /// Verify the manifest was signed by `signer_pk` and is consistent.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok` if the manifest is valid.
+    ///
+    pub fn verify(&self, signer_pk: &PublicKey) -> Result<()> {
+        ensure!(self.signer == *signer_pk, "manifest signer does not match.");
+        ensure!(self.chunk_size > 0, "manifest chunk size is zero.");
+        ensure!(
+            self.chunk_hashes.len() as u64 == chunk_count(self.total_size, self.chunk_size),
+            "manifest chunk count does not match its size."
+        );
+        Keypair::from_null_private_key(self.signer).verify(&self.message(), self.signature)
+    }
+
+    /// The plaintext size of a chunk.
+    ///
+    pub fn chunk_len(&self, index: u32) -> usize {
+        let start = index as u64 * self.chunk_size as u64;
+        self.total_size
+            .saturating_sub(start)
+            .min(self.chunk_size as u64) as usize
+    }
+}
+
+/// An encrypted attachment held in memory.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct EncryptedAttachment {
+    pub manifest: AttachmentManifest,
+    /// The encrypted chunks, `ciphertext || tag`.
+    pub chunks: Vec<Vec<u8>>,
+}
+
+impl EncryptedAttachment {
+    /// Encrypt and sign an attachment.
+    ///
+    /// # Inputs
+    ///
+    /// * `kp`: The Symbol `Keypair` of the sender.
+    ///
+    /// * `content_key`: The attachment key, e.g. random and sent in a `CryptoSym` message.
+    ///
+    /// * `data`: The plaintext.
+    ///
+    /// * `chunk_size`: The plaintext size of the chunks.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the `EncryptedAttachment` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn encrypt(
+        kp: &crate::core::Keypair<Keypair>,
+        content_key: &AesKey,
+        data: &[u8],
+        chunk_size: u32,
+    ) -> Result<Self> {
+        let mut writer = AttachmentWriter::new(kp, content_key, chunk_size, vec![])?;
+        writer.write_all(data)?;
+        let (manifest, encrypted) = writer.finish()?;
+
+        let mut chunks = Vec::with_capacity(manifest.chunk_hashes.len());
+        let mut offset = 0;
+        for index in 0..manifest.chunk_hashes.len() as u32 {
+            let len = manifest.chunk_len(index) + AES_TAG_LENGTH;
+            chunks.push(encrypted[offset..offset + len].to_vec());
+            offset += len;
+        }
+        Ok(Self { manifest, chunks })
+    }
+
+    /// Decrypt the whole attachment.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the plaintext `Vec<u8>` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn decrypt(&self, signer_pk: &PublicKey, content_key: &AesKey) -> Result<Vec<u8>> {
+        self.manifest.verify(signer_pk)?;
+        ensure!(
+            self.chunks.len() == self.manifest.chunk_hashes.len(),
+            "attachment chunk count does not match the manifest."
+        );
+
+        let mut data = Vec::with_capacity(self.manifest.total_size as usize);
+        for (index, chunk) in self.chunks.iter().enumerate() {
+            let key = chunk_key(content_key, &self.manifest.id, index as u32);
+            data.extend(decrypt_chunk(&self.manifest, index as u32, &key, chunk)?);
+        }
+        Ok(data)
+    }
+}
+
+/// The key of one chunk, to disclose that chunk only.
+///
+pub fn chunk_key(content_key: &AesKey, id: &H256, index: u32) -> AesKey {
+    let mut info = id.as_bytes().to_vec();
+    info.extend_from_slice(&index.to_le_bytes());
+
+    let mut key = AesKey::zero();
+    Hkdf::<Sha256>::new(Some(ATTACHMENT_KEY_SALT), content_key.as_bytes())
+        .expand(&info, key.as_mut())
+        .expect("unexpected error in rust hkdf_sha256");
+    key
+}
+
+/// Check an encrypted chunk against a manifest, then decrypt it with its `chunk_key`.
+///
+/// The manifest signature is not checked, see `AttachmentManifest::verify`.
+///
+/// # Returns
+///
+/// A `Result` whose okay value is the plaintext chunk or whose error value
+/// is an `failure::Error` describing the error that occurred.
+pub fn decrypt_chunk(
+    manifest: &AttachmentManifest,
+    index: u32,
+    chunk_key: &AesKey,
+    chunk: &[u8],
+) -> Result<Vec<u8>> {
+    let hash = manifest
+        .chunk_hashes
+        .get(index as usize)
+        .ok_or_else(|| anyhow!("chunk {} is not in the manifest.", index))?;
+    ensure!(
+        chunk.len() == manifest.chunk_len(index) + AES_TAG_LENGTH,
+        "chunk {} has unexpected size.",
+        index
+    );
+    ensure!(
+        H256::from_slice(Sha3_256::digest(chunk).as_slice()) == *hash,
+        "chunk {} does not match the manifest.",
+        index
+    );
+    decrypt(chunk_iv(index), *chunk_key, chunk)
+}
+
+/// Streaming encryption of an attachment, the encrypted chunks are written to `inner` as they
+/// are complete.
+///
+pub struct AttachmentWriter<'a, W: Write> {
+    kp: &'a crate::core::Keypair<Keypair>,
+    content_key: AesKey,
+    id: H256,
+    chunk_size: u32,
+    total_size: u64,
+    chunk_hashes: Vec<H256>,
+    buffer: Vec<u8>,
+    inner: W,
+}
+
+impl<'a, W: Write> AttachmentWriter<'a, W> {
+    /// Start a new attachment with a random id.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the `AttachmentWriter` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn new(
+        kp: &'a crate::core::Keypair<Keypair>,
+        content_key: &AesKey,
+        chunk_size: u32,
+        inner: W,
+    ) -> Result<Self> {
+        ensure!(chunk_size > 0, "chunk size is zero.");
+        Ok(Self {
+            kp,
+            content_key: *content_key,
+            id: H256::from(random_bytes::<32>()),
+            chunk_size,
+            total_size: 0,
+            chunk_hashes: vec![],
+            buffer: Vec::with_capacity(chunk_size as usize),
+            inner,
+        })
+    }
+
+    /// Encrypt the last chunk and sign the manifest.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the signed `AttachmentManifest` and the inner writer, or
+    /// whose error value is an `failure::Error` describing the error that occurred.
+    pub fn finish(mut self) -> Result<(AttachmentManifest, W)> {
+        if !self.buffer.is_empty() {
+            self.write_chunk()?;
+        }
+        self.inner.flush()?;
+
+        let mut manifest = AttachmentManifest {
+            id: self.id,
+            chunk_size: self.chunk_size,
+            total_size: self.total_size,
+            chunk_hashes: self.chunk_hashes,
+            signer: self.kp.public_key(),
+            signature: Signature::zero(),
+        };
+        manifest.signature = self.kp.try_sign(&manifest.message())?;
+        Ok((manifest, self.inner))
+    }
+
+    // internal functions.
+    fn write_chunk(&mut self) -> Result<()> {
+        let index: u32 = self
+            .chunk_hashes
+            .len()
+            .try_into()
+            .map_err(|_| anyhow!("attachment has too many chunks."))?;
+        let key = chunk_key(&self.content_key, &self.id, index);
+        let (mut chunk, tag) = encrypt(chunk_iv(index), key, &self.buffer)?;
+        chunk.extend_from_slice(&tag);
+
+        self.inner.write_all(&chunk)?;
+        self.chunk_hashes
+            .push(H256::from_slice(Sha3_256::digest(&chunk).as_slice()));
+        self.total_size += self.buffer.len() as u64;
+        self.buffer.clear();
+        Ok(())
+    }
+}
+
+impl<'a, W: Write> Write for AttachmentWriter<'a, W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let len = buf.len().min(self.chunk_size as usize - self.buffer.len());
+        self.buffer.extend_from_slice(&buf[..len]);
+        if self.buffer.len() == self.chunk_size as usize {
+            self.write_chunk().map_err(io::Error::other)?;
+        }
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Streaming decryption of an attachment, every chunk read from `inner` is checked against
+/// the manifest before it is decrypted.
+///
+pub struct AttachmentReader<R: Read> {
+    manifest: AttachmentManifest,
+    content_key: AesKey,
+    next_index: u32,
+    buffer: Vec<u8>,
+    position: usize,
+    inner: R,
+}
+
+impl<R: Read> AttachmentReader<R> {
+    /// Read an attachment after verifying its manifest.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the `AttachmentReader` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn new(
+        manifest: &AttachmentManifest,
+        signer_pk: &PublicKey,
+        content_key: &AesKey,
+        inner: R,
+    ) -> Result<Self> {
+        manifest.verify(signer_pk)?;
+        Ok(Self {
+            manifest: manifest.clone(),
+            content_key: *content_key,
+            next_index: 0,
+            buffer: vec![],
+            position: 0,
+            inner,
+        })
+    }
+
+    // internal functions.
+    fn read_chunk(&mut self) -> Result<()> {
+        let index = self.next_index;
+        let mut chunk = vec![0u8; self.manifest.chunk_len(index) + AES_TAG_LENGTH];
+        self.inner.read_exact(&mut chunk)?;
+
+        let key = chunk_key(&self.content_key, &self.manifest.id, index);
+        self.buffer = decrypt_chunk(&self.manifest, index, &key, &chunk)?;
+        self.position = 0;
+        self.next_index += 1;
+        Ok(())
+    }
+}
+
+impl<R: Read> Read for AttachmentReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.position == self.buffer.len() {
+            if self.next_index as usize == self.manifest.chunk_hashes.len() {
+                return Ok(0);
+            }
+            self.read_chunk().map_err(io::Error::other)?;
+        }
+
+        let len = buf.len().min(self.buffer.len() - self.position);
+        buf[..len].copy_from_slice(&self.buffer[self.position..self.position + len]);
+        self.position += len;
+        Ok(len)
+    }
+}
+
+fn chunk_count(total_size: u64, chunk_size: u32) -> u64 {
+    total_size.div_ceil(chunk_size as u64)
+}
+
+fn chunk_iv(index: u32) -> [u8; SYM_AES_IV_LENGTH] {
+    // every chunk key is used once, the index is a unique iv.
+    let mut iv = [0u8; SYM_AES_IV_LENGTH];
+    iv[..4].copy_from_slice(&index.to_le_bytes());
+    iv
+}
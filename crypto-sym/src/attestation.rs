@@ -0,0 +1,191 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Attestation of a device key by its manufacturer.
+//!
+//! The statement `ATTESTATION_TAG || device public key || manufacturer public key ||
+//! metadata size || metadata` is signed by the device key, proving it is held by the device,
+//! and by the manufacturer key, binding the device to the metadata (e.g. model and serial
+//! number). An attestation is encoded as `device public key || manufacturer public key ||
+//! metadata size (u16 little endian) || metadata || device signature || manufacturer
+//! signature`.
+//!
+
+use std::convert::TryInto;
+
+use anyhow::{ensure, Result};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::Keypair;
+use crate::core::{KeyPairSchema, PublicKey, Signature, KEY_BYTES_SIZE, SIGNATURE_LENGTH};
+
+/// The prefix of every signed attestation statement.
+pub const ATTESTATION_TAG: &[u8] = b"symbol-crypto-core/attestation/v1\0";
+
+/// The maximum size of the attestation metadata.
+pub const ATTESTATION_MAX_METADATA_SIZE: usize = 1024;
+
+const METADATA_SIZE_LENGTH: usize = 2;
+const HEADER_SIZE: usize = 2 * KEY_BYTES_SIZE + METADATA_SIZE_LENGTH;
+
+/// A device public key and its metadata, signed by the device and its manufacturer.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct Attestation {
+    pub device_public_key: PublicKey,
+    pub manufacturer_public_key: PublicKey,
+    pub metadata: Vec<u8>,
+    pub device_signature: Signature,
+    pub manufacturer_signature: Signature,
+}
+
+/// Create the attestation of a device.
+///
+/// # Inputs
+///
+/// * `device_kp`: The device `Keypair`.
+///
+/// * `manufacturer_kp`: The manufacturer `Keypair`.
+///
+/// * `metadata`: The device metadata, at most `ATTESTATION_MAX_METADATA_SIZE` bytes.
+///
+/// # Returns
+///
+/// A `Result` whose okay value is the `Attestation` or whose error value
+/// is an `failure::Error` describing the error that occurred.
+pub fn create(
+    device_kp: &crate::core::Keypair<Keypair>,
+    manufacturer_kp: &crate::core::Keypair<Keypair>,
+    metadata: &[u8],
+) -> Result<Attestation> {
+    let device_public_key = device_kp.public_key();
+    let manufacturer_public_key = manufacturer_kp.public_key();
+    check(&device_public_key, &manufacturer_public_key, metadata)?;
+
+    let statement = statement(&device_public_key, &manufacturer_public_key, metadata);
+    Ok(Attestation {
+        device_public_key,
+        manufacturer_public_key,
+        metadata: metadata.to_vec(),
+        device_signature: device_kp.try_sign(&statement)?,
+        manufacturer_signature: manufacturer_kp.try_sign(&statement)?,
+    })
+}
+
+/// Verify an attestation issued by a trusted manufacturer.
+///
+/// # Inputs
+///
+/// * `attestation`: The `Attestation` to verify.
+///
+/// * `manufacturer_pk`: The public key of the trusted manufacturer.
+///
+/// # Returns
+///
+/// Returns `Ok` if the attestation is signed by its device and by `manufacturer_pk`.
+pub fn verify(attestation: &Attestation, manufacturer_pk: &PublicKey) -> Result<()> {
+    ensure!(
+        attestation.manufacturer_public_key == *manufacturer_pk,
+        "attestation is issued by unexpected manufacturer {:X}.",
+        attestation.manufacturer_public_key
+    );
+    check(
+        &attestation.device_public_key,
+        &attestation.manufacturer_public_key,
+        &attestation.metadata,
+    )?;
+
+    let statement = statement(
+        &attestation.device_public_key,
+        &attestation.manufacturer_public_key,
+        &attestation.metadata,
+    );
+    Keypair::from_null_private_key(attestation.device_public_key)
+        .verify(&statement, attestation.device_signature)?;
+    Keypair::from_null_private_key(attestation.manufacturer_public_key)
+        .verify(&statement, attestation.manufacturer_signature)
+}
+
+impl Attestation {
+    /// Encode the attestation.
+    ///
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let size = HEADER_SIZE + self.metadata.len() + 2 * SIGNATURE_LENGTH;
+        let mut bytes = Vec::with_capacity(size);
+        bytes.extend_from_slice(self.device_public_key.as_bytes());
+        bytes.extend_from_slice(self.manufacturer_public_key.as_bytes());
+        bytes.extend_from_slice(&(self.metadata.len() as u16).to_le_bytes());
+        bytes.extend_from_slice(&self.metadata);
+        bytes.extend_from_slice(self.device_signature.as_bytes());
+        bytes.extend_from_slice(self.manufacturer_signature.as_bytes());
+        bytes
+    }
+
+    /// Decode an attestation, without verifying it.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the `Attestation` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        ensure!(
+            bytes.len() >= HEADER_SIZE + 2 * SIGNATURE_LENGTH,
+            "attestation has unexpected size {}.",
+            bytes.len()
+        );
+        let metadata_size =
+            u16::from_le_bytes(bytes[2 * KEY_BYTES_SIZE..HEADER_SIZE].try_into()?) as usize;
+        ensure!(
+            bytes.len() == HEADER_SIZE + metadata_size + 2 * SIGNATURE_LENGTH,
+            "attestation has unexpected size {}.",
+            bytes.len()
+        );
+
+        let signatures = &bytes[HEADER_SIZE + metadata_size..];
+        Ok(Self {
+            device_public_key: PublicKey::from_slice(&bytes[..KEY_BYTES_SIZE]),
+            manufacturer_public_key: PublicKey::from_slice(
+                &bytes[KEY_BYTES_SIZE..2 * KEY_BYTES_SIZE],
+            ),
+            metadata: bytes[HEADER_SIZE..HEADER_SIZE + metadata_size].to_vec(),
+            device_signature: Signature::from_slice(&signatures[..SIGNATURE_LENGTH]),
+            manufacturer_signature: Signature::from_slice(&signatures[SIGNATURE_LENGTH..]),
+        })
+    }
+}
+
+// internal functions.
+fn check(device_pk: &PublicKey, manufacturer_pk: &PublicKey, metadata: &[u8]) -> Result<()> {
+    ensure!(
+        device_pk != manufacturer_pk,
+        "device key must differ from the manufacturer key."
+    );
+    ensure!(
+        metadata.len() <= ATTESTATION_MAX_METADATA_SIZE,
+        "attestation metadata is larger than {} bytes.",
+        ATTESTATION_MAX_METADATA_SIZE
+    );
+    Ok(())
+}
+
+fn statement(device_pk: &PublicKey, manufacturer_pk: &PublicKey, metadata: &[u8]) -> Vec<u8> {
+    [
+        ATTESTATION_TAG,
+        device_pk.as_bytes(),
+        manufacturer_pk.as_bytes(),
+        &(metadata.len() as u16).to_le_bytes(),
+        metadata,
+    ]
+    .concat()
+}
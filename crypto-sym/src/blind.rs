@@ -0,0 +1,179 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Blind Schnorr signatures on ed25519, for voucher or token issuance services that sign
+//! without seeing the message.
+//!
+//! The flow between the signer (public key `A = aB`) and a user is:
+//!
+//! 1. the signer calls `commit` and sends the commitment `R = kB`;
+//! 2. the user calls `blind`, picking `α, β` with `R' = R + αB + βA`, `c' = H(R' || A || m)`,
+//!    and sends the blinded challenge `c = c' + β`;
+//! 3. the signer calls `sign_blinded` and sends `s = k + ca`;
+//! 4. the user calls `unblind` to obtain the signature `(R', s + α)`.
+//!
+//! The result is a regular ed25519 signature of the Symbol `Keypair`, checked with `verify`,
+//! which the signer cannot link to the session it was issued in.
+//!
+//! This is off-consensus usage only, use a dedicated issuing key that signs nothing else:
+//! a blinded challenge is an arbitrary scalar, so the signer cannot tell what it signs.
+//! Blind Schnorr signatures are also vulnerable to the ROS attack when many sessions of the
+//! same key are open concurrently: a signer must finish (or drop) a session before calling
+//! `commit` again.
+//!
+
+use anyhow::{anyhow, ensure, Result};
+use sha2::{Digest, Sha512};
+
+use super::keypair::Keypair;
+use crate::core::curve25519::{constants, edwards::EdwardsPoint, scalar::Scalar};
+use crate::core::{
+    check_scalar, random_bytes, KeyPairSchema, PublicKey, Signature, H256, KEY_BYTES_SIZE,
+};
+
+/// The secret nonce of one signer session, consumed by `sign_blinded`.
+///
+pub struct BlindNonce {
+    k: Scalar,
+    commitment: H256,
+}
+
+impl BlindNonce {
+    /// The commitment `R` to send to the user.
+    ///
+    pub fn commitment(&self) -> H256 {
+        self.commitment
+    }
+}
+
+/// The user side state of one session, consumed by `unblind`.
+///
+pub struct BlindingFactors {
+    alpha: Scalar,
+    signer_pk: PublicKey,
+    r_prime: EdwardsPoint,
+    challenge: Scalar,
+}
+
+/// Start a signer session.
+///
+/// # Returns
+///
+/// The session `BlindNonce`, whose commitment is sent to the user.
+pub fn commit() -> BlindNonce {
+    let k = random_scalar();
+    let commitment = H256::from((&k * &constants::ED25519_BASEPOINT_TABLE).compress().0);
+    BlindNonce { k, commitment }
+}
+
+/// Blind a message for the signer commitment.
+///
+/// # Inputs
+///
+/// * `signer_pk`: The public key of the signer.
+///
+/// * `commitment`: The signer commitment `R`.
+///
+/// * `message`: The message to get signed.
+///
+/// # Returns
+///
+/// A `Result` whose okay value is the user `BlindingFactors` and the blinded challenge to send
+/// to the signer, or whose error value is an `failure::Error` describing the error that
+/// occurred.
+#[allow(non_snake_case)]
+pub fn blind(
+    signer_pk: &PublicKey,
+    commitment: &H256,
+    message: &[u8],
+) -> Result<(BlindingFactors, H256)> {
+    let A = signer_pk.to_point()?;
+    let R = PublicKey::from(commitment.to_fixed_bytes())
+        .to_point()
+        .map_err(|e| anyhow!("invalid commitment: {}", e))?;
+
+    let alpha = random_scalar();
+    let beta = random_scalar();
+    let r_prime = R + &alpha * &constants::ED25519_BASEPOINT_TABLE + beta * A;
+
+    let mut h = Sha512::new();
+    h.update(r_prime.compress().as_bytes());
+    h.update(signer_pk.as_bytes());
+    h.update(message);
+    let challenge = Scalar::from_hash(h);
+
+    let factors = BlindingFactors {
+        alpha,
+        signer_pk: *signer_pk,
+        r_prime,
+        challenge,
+    };
+    Ok((factors, H256::from(challenge + beta)))
+}
+
+/// Sign a blinded challenge, closing the signer session.
+///
+/// # Inputs
+///
+/// * `kp`: The Symbol issuing `Keypair`.
+///
+/// * `nonce`: The session `BlindNonce` returned by `commit`.
+///
+/// * `blinded_challenge`: The challenge returned by the user's `blind`.
+///
+/// # Returns
+///
+/// A `Result` whose okay value is the blinded `s` to send to the user or whose error value
+/// is an `failure::Error` describing the error that occurred.
+pub fn sign_blinded(
+    kp: &crate::core::Keypair<Keypair>,
+    nonce: BlindNonce,
+    blinded_challenge: &H256,
+) -> Result<H256> {
+    let c = check_scalar(blinded_challenge.to_fixed_bytes())?;
+    let (a, _) = kp.0.expanded_secret();
+    Ok(H256::from(nonce.k + c * a))
+}
+
+/// Unblind the signer response into a Symbol `Signature` of the message.
+///
+/// # Returns
+///
+/// A `Result` whose okay value is the unblinded `Signature` or whose error value
+/// is an `failure::Error` describing the error that occurred.
+#[allow(non_snake_case)]
+pub fn unblind(factors: BlindingFactors, blinded_s: &H256) -> Result<Signature> {
+    let s = check_scalar(blinded_s.to_fixed_bytes())? + factors.alpha;
+
+    let A = factors.signer_pk.to_point()?;
+    let expected = factors.r_prime + factors.challenge * A;
+    ensure!(
+        &s * &constants::ED25519_BASEPOINT_TABLE == expected,
+        "blinded signature is not valid for the signer public key."
+    );
+
+    let mut signature = [0u8; KEY_BYTES_SIZE * 2];
+    signature[..KEY_BYTES_SIZE].copy_from_slice(factors.r_prime.compress().as_bytes());
+    signature[KEY_BYTES_SIZE..].copy_from_slice(s.as_bytes());
+    Ok(Signature::from(signature))
+}
+
+/// Verify an unblinded `Signature` of a message.
+///
+/// # Returns
+///
+/// Returns `Ok` if the `Signature` is a valid signature of `signer_pk`.
+///
+pub fn verify(signer_pk: &PublicKey, message: &[u8], signature: Signature) -> Result<()> {
+    Keypair::from_null_private_key(*signer_pk).verify(message, signature)
+}
+
+// internal functions.
+fn random_scalar() -> Scalar {
+    Scalar::from_bytes_mod_order_wide(&random_bytes::<64>())
+}
@@ -0,0 +1,276 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The `addresses.yml` file of symbol-bootstrap, with the keys of the nodes of a target.
+//!
+//! Every node has its `main` (node owner and harvester), `transport` (peer TLS), `remote`
+//! (delegated harvesting), `vrf` and `voting` accounts, each with its public key and address
+//! and, unless bootstrap ran with private keys hidden, its private key. Keys are upper case hex,
+//! addresses are the 39 character base32 text form.
+//!
+//! `BootstrapAddresses::from_yaml` checks every account: the private key derives the public
+//! key, and the address is the address of the public key on `networkType`. Fields this module
+//! does not know (e.g. `mosaics`) are ignored, and not written back by `to_yaml`.
+//!
+
+use anyhow::{anyhow, ensure, Result};
+use serde::{Deserialize, Serialize};
+
+use super::recipient::Address;
+use super::Keypair;
+use crate::core::{KeyPairSchema, PrivateKey, PublicKey};
+
+/// The `addresses.yml` version of `BootstrapAddresses::new`.
+pub const BOOTSTRAP_ADDRESSES_VERSION: u32 = 1;
+
+/// The content of an `addresses.yml`.
+///
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(crate = "serde_crate", rename_all = "camelCase")]
+pub struct BootstrapAddresses {
+    pub version: u32,
+    /// The network byte, e.g. `152` for testnet.
+    pub network_type: u8,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nemesis_generation_hash_seed: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sink_address: Option<String>,
+    #[serde(default)]
+    pub nodes: Vec<BootstrapNode>,
+}
+
+/// The accounts of a node.
+///
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(crate = "serde_crate", rename_all = "camelCase")]
+pub struct BootstrapNode {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub friendly_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub main: Option<BootstrapAccount>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transport: Option<BootstrapAccount>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remote: Option<BootstrapAccount>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vrf: Option<BootstrapAccount>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub voting: Option<BootstrapAccount>,
+}
+
+/// An account of a node.
+///
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(crate = "serde_crate", rename_all = "camelCase")]
+pub struct BootstrapAccount {
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "hex_private_key"
+    )]
+    pub private_key: Option<PrivateKey>,
+    #[serde(with = "hex_public_key")]
+    pub public_key: PublicKey,
+    #[serde(with = "text_address")]
+    pub address: Address,
+}
+
+impl BootstrapAddresses {
+    /// An `addresses.yml` without nodes yet.
+    ///
+    pub fn new(network_type: u8) -> Self {
+        Self {
+            version: BOOTSTRAP_ADDRESSES_VERSION,
+            network_type,
+            nemesis_generation_hash_seed: None,
+            sink_address: None,
+            nodes: Vec::new(),
+        }
+    }
+
+    /// Parse and check an `addresses.yml`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the `BootstrapAddresses` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn from_yaml(yaml: &str) -> Result<Self> {
+        let addresses: Self = serde_yaml::from_str(yaml)?;
+        addresses.validate()?;
+        Ok(addresses)
+    }
+
+    /// Write the `addresses.yml`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the yaml `String` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn to_yaml(&self) -> Result<String> {
+        Ok(serde_yaml::to_string(self)?)
+    }
+
+    /// Check the keys and addresses of every account.
+    ///
+    pub fn validate(&self) -> Result<()> {
+        for node in &self.nodes {
+            for (role, account) in node.accounts() {
+                account
+                    .validate(self.network_type)
+                    .map_err(|e| anyhow!("node {} {} account: {}", node.name, role, e))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// The node named `name`.
+    ///
+    pub fn node(&self, name: &str) -> Option<&BootstrapNode> {
+        self.nodes.iter().find(|node| node.name == name)
+    }
+}
+
+impl BootstrapNode {
+    /// The accounts of the node, with their role names, in file order.
+    ///
+    pub fn accounts(&self) -> Vec<(&'static str, &BootstrapAccount)> {
+        vec![
+            ("main", &self.main),
+            ("transport", &self.transport),
+            ("remote", &self.remote),
+            ("vrf", &self.vrf),
+            ("voting", &self.voting),
+        ]
+        .into_iter()
+        .filter_map(|(role, account)| account.as_ref().map(|account| (role, account)))
+        .collect()
+    }
+}
+
+impl BootstrapAccount {
+    /// The account of a `Keypair` on a network, private key included.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the `BootstrapAccount` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn from_keypair(kp: &crate::core::Keypair<Keypair>, network_type: u8) -> Result<Self> {
+        Ok(Self {
+            private_key: Some(kp.private_key()),
+            public_key: kp.public_key(),
+            address: Address::from_public_key(network_type, &kp.public_key())?,
+        })
+    }
+
+    /// The `Keypair` of the account.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the `Keypair` or whose error value
+    /// is an `failure::Error` describing the error that occurred, e.g. a hidden private key.
+    pub fn keypair(&self) -> Result<crate::core::Keypair<Keypair>> {
+        let private_key = self
+            .private_key
+            .ok_or_else(|| anyhow!("account {} has no private key.", self.address))?;
+        Ok(crate::core::Keypair::from_private_key(private_key))
+    }
+
+    /// The account without its private key, e.g. to share the file.
+    ///
+    pub fn public_only(&self) -> Self {
+        Self {
+            private_key: None,
+            ..self.clone()
+        }
+    }
+
+    // internal functions.
+    fn validate(&self, network_type: u8) -> Result<()> {
+        if let Some(private_key) = self.private_key {
+            ensure!(
+                Keypair::from_private_key(private_key).public_key() == self.public_key,
+                "private key does not match the public key."
+            );
+        }
+        ensure!(
+            Address::from_public_key(network_type, &self.public_key)? == self.address,
+            "address does not match the public key."
+        );
+        Ok(())
+    }
+}
+
+impl std::fmt::Debug for BootstrapAccount {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BootstrapAccount")
+            .field("private_key", &self.private_key.map(|_| "[redacted]"))
+            .field("public_key", &self.public_key)
+            .field("address", &self.address)
+            .finish()
+    }
+}
+
+mod hex_private_key {
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    use crate::core::PrivateKey;
+
+    pub fn serialize<S: Serializer>(
+        private_key: &Option<PrivateKey>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match private_key {
+            Some(private_key) => serializer.serialize_str(&format!("{:X}", private_key)),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'d, D: Deserializer<'d>>(
+        deserializer: D,
+    ) -> Result<Option<PrivateKey>, D::Error> {
+        Option::<String>::deserialize(deserializer)?
+            .map(|hex| hex.parse().map_err(D::Error::custom))
+            .transpose()
+    }
+}
+
+mod hex_public_key {
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    use crate::core::PublicKey;
+
+    pub fn serialize<S: Serializer>(
+        public_key: &PublicKey,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&format!("{:X}", public_key))
+    }
+
+    pub fn deserialize<'d, D: Deserializer<'d>>(deserializer: D) -> Result<PublicKey, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(D::Error::custom)
+    }
+}
+
+mod text_address {
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    use super::Address;
+
+    pub fn serialize<S: Serializer>(address: &Address, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(address)
+    }
+
+    pub fn deserialize<'d, D: Deserializer<'d>>(deserializer: D) -> Result<Address, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(D::Error::custom)
+    }
+}
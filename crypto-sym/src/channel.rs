@@ -0,0 +1,191 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! An encrypted, mutually authenticated channel over any `Read + Write` transport.
+//!
+//! `EncryptedChannel::establish` runs the `handshake` over the transport, each end checking
+//! that the other signs with the expected public key, then frames the messages with the
+//! `peer_crypto` packets. Both ends call `establish` the same way: each end first writes its
+//! public key and checks the one it reads is the expected one, then the end with the lower
+//! public key (in byte order) takes the server role of the handshake. An end expecting another
+//! peer stops there, instead of both ends waiting for the other in the same role.
+//!
+
+use std::io::{Read, Write};
+
+use anyhow::{ensure, Result};
+
+use super::handshake::{
+    generate_client_challenge_response, generate_server_challenge_request,
+    generate_server_challenge_response, verify_client_challenge_response,
+    verify_server_challenge_response, ClientChallengeResponse, SecurityMode,
+    ServerChallengeRequest, ServerChallengeResponse, CLIENT_CHALLENGE_RESPONSE_SIZE,
+    SERVER_CHALLENGE_REQUEST_SIZE, SERVER_CHALLENGE_RESPONSE_SIZE,
+};
+use super::peer_crypto::{PeerCipher, PeerRole};
+use super::Keypair;
+use crate::core::{PublicKey, KEY_BYTES_SIZE};
+
+/// An established encrypted channel to a peer.
+///
+#[derive(Debug)]
+pub struct EncryptedChannel<T: Read + Write> {
+    transport: T,
+    cipher: PeerCipher,
+    peer_public_key: PublicKey,
+    role: PeerRole,
+}
+
+impl<T: Read + Write> EncryptedChannel<T> {
+    /// Authenticate the peer over `transport` and derive the channel keys.
+    ///
+    /// # Inputs
+    ///
+    /// * `local_kp`: The local Symbol `Keypair`.
+    ///
+    /// * `remote_pk`: The public key the peer must prove.
+    ///
+    /// * `transport`: The connection to the peer, e.g. a `TcpStream`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the `EncryptedChannel` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn establish(
+        local_kp: &crate::core::Keypair<Keypair>,
+        remote_pk: &PublicKey,
+        mut transport: T,
+    ) -> Result<Self> {
+        let local_pk = local_kp.public_key();
+        ensure!(local_pk != *remote_pk, "channel peer has the local public key.");
+
+        transport.write_all(local_pk.as_bytes())?;
+        transport.flush()?;
+        let announced_pk = PublicKey::from_slice(&read_packet(&mut transport, KEY_BYTES_SIZE)?);
+        ensure!(
+            announced_pk == *remote_pk,
+            "channel peer has unexpected public key {:X}.",
+            announced_pk
+        );
+
+        let role = if local_pk.as_bytes() < remote_pk.as_bytes() {
+            PeerRole::Server
+        } else {
+            PeerRole::Client
+        };
+        let (request, response) = match role {
+            PeerRole::Server => server_handshake(local_kp, remote_pk, &mut transport)?,
+            PeerRole::Client => client_handshake(local_kp, remote_pk, &mut transport)?,
+        };
+
+        Ok(Self {
+            transport,
+            cipher: PeerCipher::new(local_kp, remote_pk, &request, &response, role),
+            peer_public_key: *remote_pk,
+            role,
+        })
+    }
+
+    /// Set the maximum size of the encrypted packets, sent or received.
+    ///
+    pub fn with_max_packet_size(mut self, max_packet_size: usize) -> Self {
+        self.cipher = self.cipher.with_max_packet_size(max_packet_size);
+        self
+    }
+
+    /// Encrypt and write a message.
+    ///
+    pub fn send(&mut self, message: &[u8]) -> Result<()> {
+        let packet = self.cipher.encode(message)?;
+        self.transport.write_all(&packet)?;
+        self.transport.flush()?;
+        Ok(())
+    }
+
+    /// Read and decrypt the next message.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the message `Vec<u8>` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn recv(&mut self) -> Result<Vec<u8>> {
+        let mut header = [0u8; 4];
+        self.transport.read_exact(&mut header)?;
+        let size = self.cipher.packet_size(&header)?;
+
+        let mut packet = vec![0u8; size];
+        packet[..4].copy_from_slice(&header);
+        self.transport.read_exact(&mut packet[4..])?;
+        self.cipher.decode(&packet)
+    }
+
+    /// The public key the peer proved.
+    ///
+    pub fn peer_public_key(&self) -> PublicKey {
+        self.peer_public_key
+    }
+
+    /// The handshake role of the local end.
+    ///
+    pub fn role(&self) -> PeerRole {
+        self.role
+    }
+
+    /// The underlying transport, e.g. to close it.
+    ///
+    pub fn into_inner(self) -> T {
+        self.transport
+    }
+}
+
+// internal functions.
+fn server_handshake<T: Read + Write>(
+    kp: &crate::core::Keypair<Keypair>,
+    remote_pk: &PublicKey,
+    transport: &mut T,
+) -> Result<(ServerChallengeRequest, ServerChallengeResponse)> {
+    let request = generate_server_challenge_request();
+    transport.write_all(&request.to_bytes())?;
+    transport.flush()?;
+
+    let packet = read_packet(transport, SERVER_CHALLENGE_RESPONSE_SIZE)?;
+    let response = ServerChallengeResponse::from_bytes(&packet)?;
+    ensure!(
+        response.public_key == *remote_pk,
+        "channel peer has unexpected public key {:X}.",
+        response.public_key
+    );
+    verify_server_challenge_response(&response, &request)?;
+
+    transport.write_all(&generate_client_challenge_response(&response, kp).to_bytes())?;
+    transport.flush()?;
+    Ok((request, response))
+}
+
+fn client_handshake<T: Read + Write>(
+    kp: &crate::core::Keypair<Keypair>,
+    remote_pk: &PublicKey,
+    transport: &mut T,
+) -> Result<(ServerChallengeRequest, ServerChallengeResponse)> {
+    let packet = read_packet(transport, SERVER_CHALLENGE_REQUEST_SIZE)?;
+    let request = ServerChallengeRequest::from_bytes(&packet)?;
+    let response = generate_server_challenge_response(&request, kp, SecurityMode::Signed);
+    transport.write_all(&response.to_bytes())?;
+    transport.flush()?;
+
+    let packet = read_packet(transport, CLIENT_CHALLENGE_RESPONSE_SIZE)?;
+    let server_response = ClientChallengeResponse::from_bytes(&packet)?;
+    verify_client_challenge_response(&server_response, remote_pk, &response)?;
+    Ok((request, response))
+}
+
+fn read_packet<T: Read>(transport: &mut T, size: usize) -> Result<Vec<u8>> {
+    let mut bytes = vec![0u8; size];
+    transport.read_exact(&mut bytes)?;
+    Ok(bytes)
+}
@@ -6,22 +6,33 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+use aes::cipher::{BlockEncrypt, KeyInit as _};
+use aes::Aes256;
 use aes_gcm::{
-    aead::{generic_array::GenericArray, Aead, NewAead},
+    aead::{generic_array::GenericArray, Aead, NewAead, Payload},
     AeadInPlace, Aes256Gcm, Tag,
 };
 use anyhow::{anyhow, ensure, Result};
+use ghash::{universal_hash::UniversalHash, GHash};
 use sha2::Sha512;
+use subtle::ConstantTimeEq;
 
 use super::hkdf_sha256::hkdf_sha256;
 
 use crate::core::{
     derive_shared_secret, random_bytes, AesKey, BlockCipher, PrivateKey, PublicKey, AES_TAG_LENGTH,
-    H256, KEY_BYTES_SIZE, SYM_AES_IV_LENGTH,
+    Seed32, SharedSecret, H256, KEY_BYTES_SIZE, SYM_AES_IV_LENGTH,
 };
+#[cfg(feature = "heapless")]
+use crate::core::{StackBuf, SYM_ENCRYPT_OVERHEAD};
+
+/// The HKDF info label of the catapult (Symbol) networks.
+pub const CATAPULT_KDF_LABEL: &[u8] = b"catapult";
 
 pub struct CryptoSym;
 
+impl crate::core::sealed::Sealed for CryptoSym {}
+
 impl BlockCipher for CryptoSym {
     /// Encode a message text with AES-GCM algorithm using the signer's the PrivateKey and receiver's PublicKey.
     ///
@@ -37,25 +48,12 @@ impl BlockCipher for CryptoSym {
     ///
     /// A `Result` whose okay value is an encrypt message `Vec<u8>` or whose error value
     /// is an `failure::Error` describing the error that occurred.
-    fn encrypt_message(
-        signer_sk: &[u8; KEY_BYTES_SIZE],
-        receiver_pk: &[u8; KEY_BYTES_SIZE],
+    fn encrypt_for(
+        signer_sk: &PrivateKey,
+        receiver_pk: &PublicKey,
         msg: &[u8],
     ) -> Result<Vec<u8>> {
-        let iv = random_bytes::<SYM_AES_IV_LENGTH>();
-
-        let signer_sk = PrivateKey::from(signer_sk);
-        let receiver_pk = PublicKey::from(receiver_pk);
-
-        let derive_key = derive_shared_key(signer_sk, receiver_pk);
-
-        let (encrypted, auth_tag) = encrypt(iv, derive_key, msg)?;
-
-        let mut enc = vec![];
-        enc.extend_from_slice(&auth_tag);
-        enc.extend_from_slice(&iv);
-        enc.extend_from_slice(&encrypted);
-        Ok(enc)
+        encrypt_message_with_label(CATAPULT_KDF_LABEL, signer_sk, receiver_pk, msg)
     }
 
     /// Decrypt a message text with AES-GCM algorithm using the receiver's the PrivateKey and signer's PublicKey.
@@ -86,7 +84,7 @@ impl BlockCipher for CryptoSym {
     ///     184, 66,
     /// ];
     ///
-    /// let decrypted_text = decrypt_message( receiver_sk.as_fixed_bytes(), signer_pk.as_fixed_bytes(), &encrypt_text_vec).unwrap();
+    /// let decrypted_text = decrypt_from(&receiver_sk, &signer_pk, &encrypt_text_vec).unwrap();
     /// # println!("{}", String::from_utf8(decrypted_text).unwrap());
     /// # }
     /// ```
@@ -95,38 +93,306 @@ impl BlockCipher for CryptoSym {
     ///
     /// A `Result` whose okay value is an decrypted message `Vec<u8>` or whose error value
     /// is an `failure::Error` describing the error that occurred.
-    fn decrypt_message(
-        receiver_sk: &[u8; KEY_BYTES_SIZE],
-        signer_pk: &[u8; KEY_BYTES_SIZE],
+    fn decrypt_from(
+        receiver_sk: &PrivateKey,
+        signer_pk: &PublicKey,
         enc_msg: &[u8],
     ) -> Result<Vec<u8>> {
-        ensure!(!enc_msg.is_empty(), "msg cannot be empty");
+        decrypt_message_with_label(CATAPULT_KDF_LABEL, receiver_sk, signer_pk, enc_msg)
+    }
+}
 
-        let iv = &enc_msg[AES_TAG_LENGTH..AES_TAG_LENGTH + SYM_AES_IV_LENGTH];
-        let mut iv_bytes = [0u8; SYM_AES_IV_LENGTH];
-        iv_bytes.copy_from_slice(&iv[..]);
+impl CryptoSym {
+    /// The raw ECDH shared secret of a private key and a public key, before the `catapult`
+    /// HKDF, to derive the keys of another protocol with `kdf::expand`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the `SharedSecret` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn derive_raw_shared_secret(
+        secret_key: &PrivateKey,
+        public_key: &PublicKey,
+    ) -> Result<SharedSecret> {
+        public_key.to_point()?;
+        Ok(derive_shared_secret::<Sha512>(*secret_key, *public_key))
+    }
 
-        let tag = &enc_msg[0..AES_TAG_LENGTH];
+    /// Check the AES-GCM authentication tag of an encrypted message without returning the
+    /// plaintext.
+    ///
+    /// The tag is recomputed with GHASH over the ciphertext, which is never decrypted nor
+    /// copied, which allows services to discard corrupted or tampered messages before
+    /// scheduling the full decryption.
+    ///
+    /// # Inputs
+    ///
+    /// * `receiver_sk`: The receiver's private key.
+    ///
+    /// * `signer_pk`: The signer's public key.
+    ///
+    /// * `enc_msg`: Message encrypted.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok` if the message was encrypted by the signer for this receiver and was not
+    /// modified.
+    pub fn verify_ciphertext(
+        receiver_sk: &PrivateKey,
+        signer_pk: &PublicKey,
+        enc_msg: &[u8],
+    ) -> Result<()> {
+        verify_ciphertext_with_label(CATAPULT_KDF_LABEL, receiver_sk, signer_pk, enc_msg)
+    }
 
-        let mut msg_and_tag_bytes = Vec::with_capacity(enc_msg.len() - SYM_AES_IV_LENGTH);
-        msg_and_tag_bytes.extend_from_slice(&enc_msg[AES_TAG_LENGTH + SYM_AES_IV_LENGTH..]);
-        msg_and_tag_bytes.extend_from_slice(&tag[..]);
+    /// Encode a message text without allocating, see `encrypt_for`.
+    ///
+    /// The encrypted message has the `encrypt_for` layout and takes
+    /// `msg.len() + SYM_ENCRYPT_OVERHEAD` bytes of the `N` bytes buffer.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is an encrypt message `StackBuf<N>` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    #[cfg(feature = "heapless")]
+    pub fn encrypt_for_heapless<const N: usize>(
+        signer_sk: &PrivateKey,
+        receiver_pk: &PublicKey,
+        msg: &[u8],
+    ) -> Result<StackBuf<N>> {
+        let iv = random_bytes::<SYM_AES_IV_LENGTH>();
+        let derive_key = derive_shared_key(*signer_sk, *receiver_pk, CATAPULT_KDF_LABEL);
+        let cipher = Aes256Gcm::new(derive_key.as_generic_array());
 
-        let recipient_sk = PrivateKey::from(receiver_sk);
-        let signer_pk = PublicKey::from(signer_pk);
+        let mut enc = StackBuf::<N>::zeroed(SYM_ENCRYPT_OVERHEAD)?;
+        enc.extend_from_slice(msg)?;
+        let tag = cipher
+            .encrypt_in_place_detached(
+                GenericArray::from_slice(&iv),
+                &[],
+                &mut enc[SYM_ENCRYPT_OVERHEAD..],
+            )
+            .map_err(|e| anyhow!("{}", e))?;
+        enc[..AES_TAG_LENGTH].copy_from_slice(&tag);
+        enc[AES_TAG_LENGTH..SYM_ENCRYPT_OVERHEAD].copy_from_slice(&iv);
+        Ok(enc)
+    }
 
-        let enc_key = derive_shared_key(recipient_sk, signer_pk);
+    /// Decrypt a message text without allocating, see `decrypt_from`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is an decrypted message `StackBuf<N>` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    #[cfg(feature = "heapless")]
+    pub fn decrypt_from_heapless<const N: usize>(
+        receiver_sk: &PrivateKey,
+        signer_pk: &PublicKey,
+        enc_msg: &[u8],
+    ) -> Result<StackBuf<N>> {
+        ensure!(
+            enc_msg.len() >= SYM_ENCRYPT_OVERHEAD,
+            "msg has unexpected size."
+        );
 
-        let decrypt_vec = decrypt(iv_bytes, enc_key, &msg_and_tag_bytes)?;
+        let tag = Tag::from_slice(&enc_msg[..AES_TAG_LENGTH]);
+        let iv = GenericArray::from_slice(&enc_msg[AES_TAG_LENGTH..SYM_ENCRYPT_OVERHEAD]);
+        let enc_key = derive_shared_key(*receiver_sk, *signer_pk, CATAPULT_KDF_LABEL);
+        let cipher = Aes256Gcm::new(enc_key.as_generic_array());
 
-        Ok(decrypt_vec)
+        let mut msg = StackBuf::<N>::from_slice(&enc_msg[SYM_ENCRYPT_OVERHEAD..])?;
+        if let Err(e) = cipher.decrypt_in_place_detached(iv, &[], &mut msg, tag) {
+            msg.truncate(0);
+            return Err(anyhow!("{}", e));
+        }
+        Ok(msg)
+    }
+
+    /// The `CryptoSym` cipher with another HKDF info label.
+    ///
+    /// `CryptoSym::with_kdf_label(CATAPULT_KDF_LABEL)` is the same cipher as `CryptoSym`.
+    ///
+    pub fn with_kdf_label(label: &[u8]) -> CryptoSymLabeled<'_> {
+        CryptoSymLabeled { label }
+    }
+}
+
+/// A `CryptoSym` cipher whose HKDF info label is not `catapult`, for private networks forked
+/// with another label.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CryptoSymLabeled<'a> {
+    label: &'a [u8],
+}
+
+impl<'a> CryptoSymLabeled<'a> {
+    /// The HKDF info label.
+    ///
+    pub fn label(&self) -> &'a [u8] {
+        self.label
+    }
+
+    /// Encode a message text, see `CryptoSym::encrypt_for`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is an encrypt message `Vec<u8>` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn encrypt_for(
+        &self,
+        signer_sk: &PrivateKey,
+        receiver_pk: &PublicKey,
+        msg: &[u8],
+    ) -> Result<Vec<u8>> {
+        encrypt_message_with_label(self.label, signer_sk, receiver_pk, msg)
+    }
+
+    /// Decrypt a message text, see `CryptoSym::decrypt_from`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is an decrypted message `Vec<u8>` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn decrypt_from(
+        &self,
+        receiver_sk: &PrivateKey,
+        signer_pk: &PublicKey,
+        enc_msg: &[u8],
+    ) -> Result<Vec<u8>> {
+        decrypt_message_with_label(self.label, receiver_sk, signer_pk, enc_msg)
+    }
+
+    /// Encode a message text from the raw key bytes, see `encrypt_for`.
+    ///
+    #[deprecated(note = "use `encrypt_for`, which takes typed keys")]
+    pub fn encrypt_message(
+        &self,
+        signer_sk: &Seed32,
+        receiver_pk: &[u8; KEY_BYTES_SIZE],
+        msg: &[u8],
+    ) -> Result<Vec<u8>> {
+        self.encrypt_for(&signer_sk.into(), &receiver_pk.into(), msg)
+    }
+
+    /// Decrypt a message text from the raw key bytes, see `decrypt_from`.
+    ///
+    #[deprecated(note = "use `decrypt_from`, which takes typed keys")]
+    pub fn decrypt_message(
+        &self,
+        receiver_sk: &Seed32,
+        signer_pk: &[u8; KEY_BYTES_SIZE],
+        enc_msg: &[u8],
+    ) -> Result<Vec<u8>> {
+        self.decrypt_from(&receiver_sk.into(), &signer_pk.into(), enc_msg)
+    }
+
+    /// Check the authentication tag of an encrypted message, see `CryptoSym::verify_ciphertext`.
+    ///
+    pub fn verify_ciphertext(
+        &self,
+        receiver_sk: &PrivateKey,
+        signer_pk: &PublicKey,
+        enc_msg: &[u8],
+    ) -> Result<()> {
+        verify_ciphertext_with_label(self.label, receiver_sk, signer_pk, enc_msg)
     }
 }
 
 // internal functions.
-fn derive_shared_key(secret_key: PrivateKey, public_key: PublicKey) -> H256 {
+fn encrypt_message_with_label(
+    label: &[u8],
+    signer_sk: &PrivateKey,
+    receiver_pk: &PublicKey,
+    msg: &[u8],
+) -> Result<Vec<u8>> {
+    let iv = random_bytes::<SYM_AES_IV_LENGTH>();
+
+    let derive_key = derive_shared_key(*signer_sk, *receiver_pk, label);
+
+    let (encrypted, auth_tag) = encrypt(iv, derive_key, msg)?;
+
+    let mut enc = vec![];
+    enc.extend_from_slice(&auth_tag);
+    enc.extend_from_slice(&iv);
+    enc.extend_from_slice(&encrypted);
+    Ok(enc)
+}
+
+fn decrypt_message_with_label(
+    label: &[u8],
+    receiver_sk: &PrivateKey,
+    signer_pk: &PublicKey,
+    enc_msg: &[u8],
+) -> Result<Vec<u8>> {
+    ensure!(!enc_msg.is_empty(), "msg cannot be empty");
+    ensure!(
+        enc_msg.len() >= AES_TAG_LENGTH + SYM_AES_IV_LENGTH,
+        "msg has unexpected size."
+    );
+
+    let iv = &enc_msg[AES_TAG_LENGTH..AES_TAG_LENGTH + SYM_AES_IV_LENGTH];
+    let mut iv_bytes = [0u8; SYM_AES_IV_LENGTH];
+    iv_bytes.copy_from_slice(iv);
+
+    let tag = &enc_msg[0..AES_TAG_LENGTH];
+
+    let mut msg_and_tag_bytes = Vec::with_capacity(enc_msg.len() - SYM_AES_IV_LENGTH);
+    msg_and_tag_bytes.extend_from_slice(&enc_msg[AES_TAG_LENGTH + SYM_AES_IV_LENGTH..]);
+    msg_and_tag_bytes.extend_from_slice(tag);
+
+    let enc_key = derive_shared_key(*receiver_sk, *signer_pk, label);
+
+    let decrypt_vec = decrypt(iv_bytes, enc_key, &msg_and_tag_bytes)?;
+
+    Ok(decrypt_vec)
+}
+
+fn verify_ciphertext_with_label(
+    label: &[u8],
+    receiver_sk: &PrivateKey,
+    signer_pk: &PublicKey,
+    enc_msg: &[u8],
+) -> Result<()> {
+    ensure!(
+        enc_msg.len() >= AES_TAG_LENGTH + SYM_AES_IV_LENGTH,
+        "msg has unexpected size."
+    );
+
+    let tag = &enc_msg[..AES_TAG_LENGTH];
+    let iv = &enc_msg[AES_TAG_LENGTH..AES_TAG_LENGTH + SYM_AES_IV_LENGTH];
+
+    let enc_key = derive_shared_key(*receiver_sk, *signer_pk, label);
+    let expected_tag = gcm_tag(enc_key, iv, &enc_msg[AES_TAG_LENGTH + SYM_AES_IV_LENGTH..]);
+    ensure!(bool::from(expected_tag.ct_eq(tag)), "aead::Error");
+    Ok(())
+}
+
+/// The AES-GCM tag of a ciphertext without associated data, computed with GHASH only: the
+/// ciphertext is authenticated, not decrypted.
+fn gcm_tag(key: AesKey, iv: &[u8], ciphertext: &[u8]) -> Tag {
+    let cipher = Aes256::new(key.as_generic_array());
+
+    let mut hash_key = GenericArray::default();
+    cipher.encrypt_block(&mut hash_key);
+    let mut ghash = GHash::new(&hash_key);
+    ghash.update_padded(ciphertext);
+    let mut lengths = GenericArray::default();
+    lengths[8..].copy_from_slice(&((ciphertext.len() as u64) * 8).to_be_bytes());
+    ghash.update(&[lengths]);
+    let mut tag = ghash.finalize();
+
+    // the pre-counter block J0 = iv || 0^31 || 1 of a 96-bit iv.
+    let mut mask = GenericArray::default();
+    mask[..SYM_AES_IV_LENGTH].copy_from_slice(iv);
+    mask[15] = 1;
+    cipher.encrypt_block(&mut mask);
+    tag.iter_mut().zip(mask.iter()).for_each(|(byte, mask)| *byte ^= mask);
+    tag
+}
+
+fn derive_shared_key(secret_key: PrivateKey, public_key: PublicKey, label: &[u8]) -> H256 {
     let shared_secret = derive_shared_secret::<Sha512>(secret_key, public_key);
-    hkdf_sha256(shared_secret)
+    hkdf_sha256(shared_secret, label)
 }
 
 /// Encrypt the given plaintext slice with AES-GCM algorithm with a 256-bit key and 96-bit nonce.
@@ -136,8 +402,19 @@ fn derive_shared_key(secret_key: PrivateKey, public_key: PublicKey) -> H256 {
 /// A `Result` whose okay value is a ciphertext as a vector of bytes and auth_tag or whose error
 /// value
 /// is an `Error` describing the error that occurred.
-fn encrypt(iv: [u8; SYM_AES_IV_LENGTH], derive_key: AesKey, msg: &[u8]) -> Result<(Vec<u8>, Tag)> {
-    let key = GenericArray::from_slice(derive_key.as_fixed_bytes());
+pub(crate) fn encrypt(iv: [u8; SYM_AES_IV_LENGTH], derive_key: AesKey, msg: &[u8]) -> Result<(Vec<u8>, Tag)> {
+    encrypt_with_aad(iv, derive_key, &[], msg)
+}
+
+/// Encrypt as `encrypt`, authenticating the associated data `aad` along with the ciphertext.
+///
+pub(crate) fn encrypt_with_aad(
+    iv: [u8; SYM_AES_IV_LENGTH],
+    derive_key: AesKey,
+    aad: &[u8],
+    msg: &[u8],
+) -> Result<(Vec<u8>, Tag)> {
+    let key = derive_key.as_generic_array();
     let cipher = Aes256Gcm::new(key);
 
     let nonce = GenericArray::from_slice(&iv); // 96-bits; unique per message
@@ -146,7 +423,7 @@ fn encrypt(iv: [u8; SYM_AES_IV_LENGTH], derive_key: AesKey, msg: &[u8]) -> Resul
     msg_buf.extend_from_slice(msg);
 
     let auth_tag = cipher
-        .encrypt_in_place_detached(nonce, &vec![], &mut msg_buf)
+        .encrypt_in_place_detached(nonce, aad, &mut msg_buf)
         .map_err(|e| anyhow!("{}", e))?;
 
     Ok((msg_buf, auth_tag))
@@ -158,13 +435,25 @@ fn encrypt(iv: [u8; SYM_AES_IV_LENGTH], derive_key: AesKey, msg: &[u8]) -> Resul
 ///
 /// A `Result` whose okay value is a plaintext as a vector of bytes or whose error value
 /// is an `Error` describing the error that occurred.
-fn decrypt(iv: [u8; SYM_AES_IV_LENGTH], derive_key: AesKey, enc_msg: &[u8]) -> Result<Vec<u8>> {
-    let key = GenericArray::from_slice(derive_key.as_fixed_bytes());
+pub(crate) fn decrypt(iv: [u8; SYM_AES_IV_LENGTH], derive_key: AesKey, enc_msg: &[u8]) -> Result<Vec<u8>> {
+    decrypt_with_aad(iv, derive_key, &[], enc_msg)
+}
+
+/// Decrypt as `decrypt`, the associated data `aad` must be the one given to `encrypt_with_aad`.
+///
+pub(crate) fn decrypt_with_aad(
+    iv: [u8; SYM_AES_IV_LENGTH],
+    derive_key: AesKey,
+    aad: &[u8],
+    enc_msg: &[u8],
+) -> Result<Vec<u8>> {
+    let key = derive_key.as_generic_array();
     let cipher = Aes256Gcm::new(key);
 
     let iv = GenericArray::from_slice(&iv);
 
-    let decrypted = cipher.decrypt(iv, enc_msg).map_err(|e| anyhow!("{}", e))?;
+    let payload = Payload { msg: enc_msg, aad };
+    let decrypted = cipher.decrypt(iv, payload).map_err(|e| anyhow!("{}", e))?;
 
     Ok(decrypted)
 }
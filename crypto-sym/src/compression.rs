@@ -0,0 +1,153 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Compression of a message before its `CryptoSym` encryption, for the ~1KB on-chain message
+//! limit.
+//!
+//! The encrypted plaintext is `COMPRESSED_MESSAGE_VERSION || codec (u8) || payload`: the
+//! receiver reads the codec after decryption, and the sender stores the message uncompressed
+//! (`Codec::None`) when the codec does not make it smaller. Compressed messages are only
+//! readable with `decrypt_from_compressed`.
+//!
+//! Compression leaks the compressibility of the message through its size: do not compress
+//! messages that mix secrets with text an attacker chooses.
+//!
+
+use std::convert::TryFrom;
+
+use anyhow::{anyhow, bail, ensure, Result};
+
+use super::CryptoSym;
+use crate::core::{BlockCipher, PrivateKey, PublicKey};
+
+/// The version byte of the compressed message format.
+pub const COMPRESSED_MESSAGE_VERSION: u8 = 1;
+
+/// The maximum size of a decompressed message, against decompression bombs.
+pub const MAX_DECOMPRESSED_SIZE: usize = 64 * 1024;
+
+const DEFLATE_LEVEL: u8 = 9;
+#[cfg(feature = "zstd")]
+const ZSTD_LEVEL: i32 = 19;
+
+/// The compression codecs of a message.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum Codec {
+    /// Stored uncompressed.
+    None = 0,
+    /// Raw deflate (RFC 1951).
+    Deflate = 1,
+    /// Zstandard, with the `zstd` feature.
+    #[cfg(feature = "zstd")]
+    Zstd = 2,
+}
+
+impl TryFrom<u8> for Codec {
+    type Error = anyhow::Error;
+
+    fn try_from(codec: u8) -> Result<Self> {
+        match codec {
+            0 => Ok(Codec::None),
+            1 => Ok(Codec::Deflate),
+            #[cfg(feature = "zstd")]
+            2 => Ok(Codec::Zstd),
+            _ => bail!("unsupported message codec {}.", codec),
+        }
+    }
+}
+
+/// Compress a message with its header, falling back to `Codec::None` when the codec does not
+/// make it smaller.
+///
+/// # Returns
+///
+/// A `Result` whose okay value is the `version || codec || payload` message or whose error
+/// value is an `failure::Error` describing the error that occurred.
+pub fn compress(msg: &[u8], codec: Codec) -> Result<Vec<u8>> {
+    ensure!(
+        msg.len() <= MAX_DECOMPRESSED_SIZE,
+        "message has unexpected size {}.",
+        msg.len()
+    );
+
+    let compressed = match codec {
+        Codec::None => None,
+        Codec::Deflate => Some(miniz_oxide::deflate::compress_to_vec(msg, DEFLATE_LEVEL)),
+        #[cfg(feature = "zstd")]
+        Codec::Zstd => Some(zstd::bulk::compress(msg, ZSTD_LEVEL)?),
+    };
+    let (codec, payload) = match compressed {
+        Some(compressed) if compressed.len() < msg.len() => (codec, compressed),
+        _ => (Codec::None, msg.to_vec()),
+    };
+
+    let mut out = Vec::with_capacity(2 + payload.len());
+    out.push(COMPRESSED_MESSAGE_VERSION);
+    out.push(codec as u8);
+    out.extend_from_slice(&payload);
+    Ok(out)
+}
+
+/// Decompress a message made by `compress`.
+///
+/// # Returns
+///
+/// A `Result` whose okay value is the message or whose error value
+/// is an `failure::Error` describing the error that occurred.
+pub fn decompress(data: &[u8]) -> Result<Vec<u8>> {
+    ensure!(data.len() >= 2, "compressed message is truncated.");
+    ensure!(
+        data[0] == COMPRESSED_MESSAGE_VERSION,
+        "unsupported compressed message version {}.",
+        data[0]
+    );
+
+    let payload = &data[2..];
+    match Codec::try_from(data[1])? {
+        Codec::None => Ok(payload.to_vec()),
+        Codec::Deflate => {
+            miniz_oxide::inflate::decompress_to_vec_with_limit(payload, MAX_DECOMPRESSED_SIZE)
+                .map_err(|e| anyhow!("invalid deflate message: {:?}.", e.status))
+        }
+        #[cfg(feature = "zstd")]
+        Codec::Zstd => Ok(zstd::bulk::decompress(payload, MAX_DECOMPRESSED_SIZE)?),
+    }
+}
+
+impl CryptoSym {
+    /// Compress a message text with `codec`, then encode it, see `encrypt_for`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is an encrypt message `Vec<u8>` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn encrypt_for_compressed(
+        signer_sk: &PrivateKey,
+        receiver_pk: &PublicKey,
+        msg: &[u8],
+        codec: Codec,
+    ) -> Result<Vec<u8>> {
+        Self::encrypt_for(signer_sk, receiver_pk, &compress(msg, codec)?)
+    }
+
+    /// Decrypt a message of `encrypt_for_compressed` and decompress it with its codec.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is an decrypted message `Vec<u8>` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn decrypt_from_compressed(
+        receiver_sk: &PrivateKey,
+        signer_pk: &PublicKey,
+        enc_msg: &[u8],
+    ) -> Result<Vec<u8>> {
+        decompress(&Self::decrypt_from(receiver_sk, signer_pk, enc_msg)?)
+    }
+}
@@ -0,0 +1,93 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Per-thread message keys of a conversation between two accounts.
+//!
+//! The key of a thread is `HKDF-SHA256(salt = CONVERSATION_KEY_SALT, ikm = shared secret,
+//! info = thread id)`, the same for both accounts and stable over time. Sharing the key of a
+//! thread grants a third party access to the messages of that thread only: neither the shared
+//! secret nor the keys of the other threads can be derived from it.
+//!
+
+use anyhow::{ensure, Result};
+use hkdf::Hkdf;
+use sha2::{Sha256, Sha512};
+
+use super::cipher::{decrypt, encrypt};
+use super::Keypair;
+use crate::core::{
+    derive_shared_secret, random_bytes, AesKey, PublicKey, AES_TAG_LENGTH, SYM_AES_IV_LENGTH,
+};
+
+/// The HKDF salt separating conversation keys from any other use of the shared secret.
+pub const CONVERSATION_KEY_SALT: &[u8] = b"symbol-conversation-key";
+
+/// Derive the key of a conversation thread with a peer.
+///
+/// # Inputs
+///
+/// * `kp`: The local Symbol `Keypair`.
+///
+/// * `peer_pk`: The peer's public key.
+///
+/// * `thread_id`: The thread id, e.g. the hash of the transaction that started the thread.
+///
+/// # Returns
+///
+/// The thread `AesKey`, the same one the peer derives with the local public key.
+pub fn conversation_key(kp: &Keypair, peer_pk: &PublicKey, thread_id: &[u8]) -> AesKey {
+    let shared_secret = derive_shared_secret::<Sha512>(kp.private_key, *peer_pk);
+    let h = Hkdf::<Sha256>::new(Some(CONVERSATION_KEY_SALT), shared_secret.as_bytes());
+
+    let mut key = AesKey::zero();
+    h.expand(thread_id, key.as_mut())
+        .expect("unexpected error in rust hkdf_sha256");
+    key
+}
+
+/// Encrypt a message of a thread with its `conversation_key`.
+///
+/// The encrypted message is `tag || iv || ciphertext`, the layout of `CryptoSym`.
+///
+/// # Returns
+///
+/// A `Result` whose okay value is an encrypt message `Vec<u8>` or whose error value
+/// is an `failure::Error` describing the error that occurred.
+pub fn encrypt_in_thread(key: &AesKey, msg: &[u8]) -> Result<Vec<u8>> {
+    let iv = random_bytes::<SYM_AES_IV_LENGTH>();
+
+    let (encrypted, auth_tag) = encrypt(iv, *key, msg)?;
+
+    let mut enc = vec![];
+    enc.extend_from_slice(&auth_tag);
+    enc.extend_from_slice(&iv);
+    enc.extend_from_slice(&encrypted);
+    Ok(enc)
+}
+
+/// Decrypt a message of a thread with its `conversation_key`.
+///
+/// # Returns
+///
+/// A `Result` whose okay value is an decrypted message `Vec<u8>` or whose error value
+/// is an `failure::Error` describing the error that occurred.
+pub fn decrypt_in_thread(key: &AesKey, enc_msg: &[u8]) -> Result<Vec<u8>> {
+    ensure!(
+        enc_msg.len() >= AES_TAG_LENGTH + SYM_AES_IV_LENGTH,
+        "msg has unexpected size."
+    );
+
+    let mut iv = [0u8; SYM_AES_IV_LENGTH];
+    iv.copy_from_slice(&enc_msg[AES_TAG_LENGTH..AES_TAG_LENGTH + SYM_AES_IV_LENGTH]);
+
+    let mut msg_and_tag_bytes = Vec::with_capacity(enc_msg.len() - SYM_AES_IV_LENGTH);
+    msg_and_tag_bytes.extend_from_slice(&enc_msg[AES_TAG_LENGTH + SYM_AES_IV_LENGTH..]);
+    msg_and_tag_bytes.extend_from_slice(&enc_msg[..AES_TAG_LENGTH]);
+
+    decrypt(iv, *key, &msg_and_tag_bytes)
+}
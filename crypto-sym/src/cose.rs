@@ -0,0 +1,104 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! COSE_Sign1 ([RFC 9052](https://www.rfc-editor.org/rfc/rfc9052)) structures signed with
+//! Symbol keys.
+//!
+//! Symbol signatures are plain Ed25519, so the produced structures use the `EdDSA` algorithm
+//! and can be verified by any COSE toolchain. The signer public key is carried in the
+//! unprotected `kid` header.
+//!
+
+use anyhow::{anyhow, ensure, Result};
+use coset::{
+    iana, CborSerializable, CoseSign1, CoseSign1Builder, HeaderBuilder, RegisteredLabelWithPrivate,
+    TaggedCborSerializable,
+};
+
+use super::Keypair;
+use crate::core::{KeyPairSchema, PublicKey, Signature, SIGNATURE_LENGTH};
+
+/// Signs a payload into a tagged COSE_Sign1 structure.
+///
+/// # Inputs
+///
+/// * `kp`: The signer Symbol `Keypair`.
+///
+/// * `payload`: The payload to sign, embedded in the structure.
+///
+/// * `external_aad`: Externally supplied data authenticated with the payload.
+///
+/// # Returns
+///
+/// A `Result` whose okay value is the CBOR encoded COSE_Sign1 `Vec<u8>` or whose error value
+/// is an `failure::Error` describing the error that occurred.
+pub fn sign1(kp: &Keypair, payload: &[u8], external_aad: &[u8]) -> Result<Vec<u8>> {
+    let protected = HeaderBuilder::new()
+        .algorithm(iana::Algorithm::EdDSA)
+        .build();
+    let unprotected = HeaderBuilder::new()
+        .key_id(kp.public_key().as_bytes().to_vec())
+        .build();
+
+    CoseSign1Builder::new()
+        .protected(protected)
+        .unprotected(unprotected)
+        .payload(payload.to_vec())
+        .try_create_signature(external_aad, |data| {
+            kp.try_sign(data).map(|signature| signature.as_bytes().to_vec())
+        })?
+        .build()
+        .to_tagged_vec()
+        .map_err(|e| anyhow!("{}", e))
+}
+
+/// Verify a COSE_Sign1 structure with a Symbol public key.
+///
+/// Both the tagged and untagged encodings are accepted.
+///
+/// # Inputs
+///
+/// * `public_key`: The signer public key.
+///
+/// * `cose`: The CBOR encoded COSE_Sign1 structure.
+///
+/// * `external_aad`: Externally supplied data authenticated with the payload.
+///
+/// # Returns
+///
+/// A `Result` whose okay value is the verified payload `Vec<u8>` or whose error value
+/// is an `failure::Error` describing the error that occurred.
+pub fn verify1(public_key: &PublicKey, cose: &[u8], external_aad: &[u8]) -> Result<Vec<u8>> {
+    let sign1 = CoseSign1::from_tagged_slice(cose)
+        .or_else(|_| CoseSign1::from_slice(cose))
+        .map_err(|e| anyhow!("{}", e))?;
+
+    ensure!(
+        sign1.protected.header.alg
+            == Some(RegisteredLabelWithPrivate::Assigned(iana::Algorithm::EdDSA)),
+        "COSE_Sign1 algorithm is not EdDSA."
+    );
+    ensure!(
+        sign1.unprotected.key_id.is_empty()
+            || sign1.unprotected.key_id.as_slice() == public_key.as_bytes(),
+        "COSE_Sign1 key id does not match the public key."
+    );
+
+    let verifier = Keypair::from_null_private_key(*public_key);
+    sign1.verify_signature(external_aad, |signature, data| {
+        ensure!(
+            signature.len() == SIGNATURE_LENGTH,
+            "COSE_Sign1 signature has unexpected size."
+        );
+        verifier.verify(data, Signature::from_slice(signature))
+    })?;
+
+    sign1
+        .payload
+        .ok_or_else(|| anyhow!("COSE_Sign1 payload is detached."))
+}
@@ -0,0 +1,202 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Backup of an account private key to the cosigners of its multisig.
+//!
+//! The private key is split with Shamir secret sharing, every share is encrypted with
+//! `CryptoSym` from the account to one cosigner, and committed as
+//! `SHA3-256(account_public_key || index || share)`. A quorum of cosigners decrypts its shares
+//! with `decrypt_share`; `recover_from_cosigners` checks every share against its commitment
+//! before combining them, and the result against the account public key.
+//!
+
+use anyhow::{anyhow, ensure, Result};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+
+use super::{CryptoSym, Keypair};
+use crate::core::shamir::{self, Share};
+use crate::core::zeroize::Zeroizing;
+use crate::core::{
+    BlockCipher, KeyPairSchema, PrivateKey, PublicKey, H256, KEY_BYTES_SIZE,
+};
+
+/// A share encrypted to one cosigner.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct EncryptedShare {
+    pub cosigner_public_key: PublicKey,
+    pub index: u8,
+    /// `SHA3-256(account_public_key || index || share)`.
+    pub commitment: H256,
+    /// The `CryptoSym` encrypted share.
+    pub encrypted: Vec<u8>,
+}
+
+/// The cosigner backup of an account private key, safe to store publicly.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct CosignerBackup {
+    pub account_public_key: PublicKey,
+    pub threshold: u8,
+    pub shares: Vec<EncryptedShare>,
+}
+
+/// Split the account private key between its cosigners.
+///
+/// # Inputs
+///
+/// * `kp`: The Symbol `Keypair` of the account to back up.
+///
+/// * `cosigner_pks`: The public keys of the cosigners, one share each.
+///
+/// * `threshold`: The number of cosigners needed to recover the key.
+///
+/// # Returns
+///
+/// A `Result` whose okay value is the `CosignerBackup` or whose error value
+/// is an `failure::Error` describing the error that occurred.
+pub fn backup_for_cosigners(
+    kp: &crate::core::Keypair<Keypair>,
+    cosigner_pks: &[PublicKey],
+    threshold: u8,
+) -> Result<CosignerBackup> {
+    ensure!(
+        cosigner_pks.len() <= u8::MAX as usize,
+        "at most {} cosigners are supported.",
+        u8::MAX
+    );
+    for (i, pk) in cosigner_pks.iter().enumerate() {
+        ensure!(
+            !cosigner_pks[..i].contains(pk),
+            "duplicate cosigner {:X}.",
+            pk
+        );
+        ensure!(
+            *pk != kp.public_key(),
+            "the account cannot be its own cosigner."
+        );
+        pk.to_point()?;
+    }
+
+    let account_public_key = kp.public_key();
+    let shares = shamir::split(
+        kp.private_key().as_bytes(),
+        threshold,
+        cosigner_pks.len() as u8,
+    )?;
+
+    let shares = cosigner_pks
+        .iter()
+        .zip(shares)
+        .map(|(pk, share)| {
+            let value = Zeroizing::new(share.value);
+            Ok(EncryptedShare {
+                cosigner_public_key: *pk,
+                index: share.index,
+                commitment: share_commitment(&account_public_key, share.index, &value),
+                encrypted: kp.encrypt_for(pk, &value)?,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(CosignerBackup {
+        account_public_key,
+        threshold,
+        shares,
+    })
+}
+
+/// Decrypt the share of a cosigner.
+///
+/// # Returns
+///
+/// A `Result` whose okay value is the cosigner `Share` or whose error value
+/// is an `failure::Error` describing the error that occurred.
+pub fn decrypt_share(
+    cosigner: &crate::core::Keypair<Keypair>,
+    backup: &CosignerBackup,
+) -> Result<Share> {
+    let encrypted = backup
+        .shares
+        .iter()
+        .find(|share| share.cosigner_public_key == cosigner.public_key())
+        .ok_or_else(|| anyhow!("no share for cosigner {:X}.", cosigner.public_key()))?;
+
+    let value = CryptoSym::decrypt_from(
+        &cosigner.private_key(),
+        &backup.account_public_key,
+        &encrypted.encrypted,
+    )?;
+    Ok(Share {
+        index: encrypted.index,
+        value,
+    })
+}
+
+/// Recover the account private key from the shares of a quorum of cosigners.
+///
+/// # Returns
+///
+/// A `Result` whose okay value is the account `PrivateKey` or whose error value
+/// is an `failure::Error` describing the error that occurred.
+pub fn recover_from_cosigners(backup: &CosignerBackup, shares: &[Share]) -> Result<PrivateKey> {
+    ensure!(
+        shares.len() >= backup.threshold as usize,
+        "{} shares given, the threshold is {}.",
+        shares.len(),
+        backup.threshold
+    );
+
+    for share in shares {
+        let encrypted = backup
+            .shares
+            .iter()
+            .find(|encrypted| encrypted.index == share.index)
+            .ok_or_else(|| anyhow!("share {} is not part of the backup.", share.index))?;
+        ensure!(
+            share_commitment(&backup.account_public_key, share.index, &share.value)
+                == encrypted.commitment,
+            "share {} of cosigner {:X} does not match its commitment.",
+            share.index,
+            encrypted.cosigner_public_key
+        );
+    }
+
+    let secret = Zeroizing::new(shamir::combine(shares)?);
+    ensure!(
+        secret.len() == KEY_BYTES_SIZE,
+        "recovered secret has unexpected size."
+    );
+    let private_key = PrivateKey::from_slice(&secret);
+    ensure!(
+        Keypair::from_private_key(private_key).public_key() == backup.account_public_key,
+        "recovered key does not match the account public key."
+    );
+    Ok(private_key)
+}
+
+// internal functions.
+fn share_commitment(account_public_key: &PublicKey, index: u8, value: &[u8]) -> H256 {
+    let mut h = Sha3_256::new();
+    h.update(account_public_key.as_bytes());
+    h.update([index]);
+    h.update(value);
+    H256::from_slice(h.finalize().as_slice())
+}
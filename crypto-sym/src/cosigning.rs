@@ -0,0 +1,364 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Collection of the detached cosignatures of an aggregate transaction.
+//!
+//! A `CosigningSession` tracks an aggregate hash, the expected cosigners and how many of them
+//! are required. It verifies every `DetachedCosignature` when it arrives and rejects them once
+//! the session expired, e.g. at the deadline of an aggregate bonded transaction. Timestamps are
+//! unix seconds supplied by the caller.
+//!
+//! `CosigningSession::to_bytes` saves the partial state across restarts, as `version ||
+//! aggregate hash || expires_at (u64) || required (u16) || cosigner count (u16) || cosigners ||
+//! cosignature count (u16) || cosignatures`, integers little endian. `from_bytes` verifies the
+//! cosignatures again, so a tampered state file is rejected.
+//!
+
+use std::collections::BTreeSet;
+use std::convert::TryInto;
+
+use anyhow::{anyhow, ensure, Result};
+
+use super::{Cosignature, Keypair, COSIGNATURE_SIZE};
+use crate::core::{KeyPairSchema, PublicKey, Signature, H256, KEY_BYTES_SIZE};
+
+/// The `CosigningSession` encoding version.
+pub const COSIGNING_SESSION_VERSION: u8 = 1;
+
+/// Size of an encoded detached cosignature: `version (u64) || signer || signature || parent
+/// hash`.
+pub const DETACHED_COSIGNATURE_SIZE: usize = COSIGNATURE_SIZE + 32;
+
+const SESSION_HEADER_SIZE: usize = 1 + 32 + 8 + 2 + 2;
+
+/// A cosignature sent apart from its aggregate transaction, catapult's `DetachedCosignature`.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DetachedCosignature {
+    pub version: u64,
+    pub signer_public_key: PublicKey,
+    pub signature: Signature,
+    /// The hash of the cosigned aggregate transaction.
+    pub parent_hash: H256,
+}
+
+impl DetachedCosignature {
+    /// Cosign an aggregate transaction hash.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the `DetachedCosignature` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn sign(kp: &crate::core::Keypair<Keypair>, parent_hash: H256) -> Result<Self> {
+        Ok(Self {
+            version: 0,
+            signer_public_key: kp.public_key(),
+            signature: kp.try_sign(parent_hash.as_bytes())?,
+            parent_hash,
+        })
+    }
+
+    /// Verify the signature of the parent hash.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok` if the signature is valid.
+    pub fn verify(&self) -> Result<()> {
+        Keypair::from_null_private_key(self.signer_public_key)
+            .verify(self.parent_hash.as_bytes(), self.signature)
+    }
+
+    /// The cosignature to attach to the aggregate transaction.
+    ///
+    pub fn cosignature(&self) -> Cosignature {
+        Cosignature {
+            version: self.version,
+            signer_public_key: self.signer_public_key,
+            signature: self.signature,
+        }
+    }
+
+    /// Encode the detached cosignature.
+    ///
+    pub fn to_bytes(&self) -> [u8; DETACHED_COSIGNATURE_SIZE] {
+        let mut bytes = [0u8; DETACHED_COSIGNATURE_SIZE];
+        bytes[..COSIGNATURE_SIZE].copy_from_slice(&encode_cosignature(&self.cosignature()));
+        bytes[COSIGNATURE_SIZE..].copy_from_slice(self.parent_hash.as_bytes());
+        bytes
+    }
+
+    /// Decode a detached cosignature, without verifying it.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the `DetachedCosignature` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        ensure!(
+            bytes.len() == DETACHED_COSIGNATURE_SIZE,
+            "detached cosignature has unexpected size {}.",
+            bytes.len()
+        );
+        let cosignature = decode_cosignature(&bytes[..COSIGNATURE_SIZE])?;
+        Ok(Self {
+            version: cosignature.version,
+            signer_public_key: cosignature.signer_public_key,
+            signature: cosignature.signature,
+            parent_hash: H256::from_slice(&bytes[COSIGNATURE_SIZE..]),
+        })
+    }
+}
+
+/// The cosignatures of an aggregate transaction collected so far.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CosigningSession {
+    aggregate_hash: H256,
+    cosigners: BTreeSet<PublicKey>,
+    required: usize,
+    expires_at: u64,
+    cosignatures: Vec<Cosignature>,
+}
+
+impl CosigningSession {
+    /// Start collecting the cosignatures of an aggregate transaction.
+    ///
+    /// # Inputs
+    ///
+    /// * `aggregate_hash`: The aggregate transaction hash, see `PartiallySignedAggregate::hash`.
+    ///
+    /// * `cosigners`: The public keys expected to cosign.
+    ///
+    /// * `required`: How many of `cosigners` must cosign for the session to complete.
+    ///
+    /// * `expires_at`: Unix timestamp (seconds) after which cosignatures are rejected.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the `CosigningSession` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn new(
+        aggregate_hash: H256,
+        cosigners: BTreeSet<PublicKey>,
+        required: usize,
+        expires_at: u64,
+    ) -> Result<Self> {
+        ensure!(
+            required > 0 && required <= cosigners.len(),
+            "session requires {} of {} cosigners.",
+            required,
+            cosigners.len()
+        );
+        ensure!(
+            cosigners.len() <= u16::MAX as usize,
+            "session has too many cosigners."
+        );
+
+        Ok(Self {
+            aggregate_hash,
+            cosigners,
+            required,
+            expires_at,
+            cosignatures: vec![],
+        })
+    }
+
+    /// Verify and add a detached cosignature.
+    ///
+    /// # Inputs
+    ///
+    /// * `cosignature`: The received `DetachedCosignature`.
+    ///
+    /// * `now`: The current unix timestamp (seconds).
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is whether the session is complete or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn add(&mut self, cosignature: &DetachedCosignature, now: u64) -> Result<bool> {
+        ensure!(!self.is_expired(now), "cosigning session is expired.");
+        ensure!(
+            cosignature.parent_hash == self.aggregate_hash,
+            "cosignature is for another aggregate {:X}.",
+            cosignature.parent_hash
+        );
+        self.check(&cosignature.cosignature())?;
+
+        self.cosignatures.push(cosignature.cosignature());
+        Ok(self.is_complete())
+    }
+
+    /// Whether `required` cosigners cosigned.
+    ///
+    pub fn is_complete(&self) -> bool {
+        self.cosignatures.len() >= self.required
+    }
+
+    /// Whether the session expired at `now`, unix timestamp (seconds).
+    ///
+    pub fn is_expired(&self, now: u64) -> bool {
+        now > self.expires_at
+    }
+
+    /// The expected cosigners that did not cosign yet.
+    ///
+    pub fn missing(&self) -> BTreeSet<PublicKey> {
+        let signed: BTreeSet<PublicKey> =
+            self.cosignatures.iter().map(|c| c.signer_public_key).collect();
+        self.cosigners.difference(&signed).copied().collect()
+    }
+
+    /// The aggregate transaction hash.
+    ///
+    pub fn aggregate_hash(&self) -> H256 {
+        self.aggregate_hash
+    }
+
+    /// The expected cosigners.
+    ///
+    pub fn cosigners(&self) -> &BTreeSet<PublicKey> {
+        &self.cosigners
+    }
+
+    /// How many cosigners must cosign.
+    ///
+    pub fn required(&self) -> usize {
+        self.required
+    }
+
+    /// Unix timestamp (seconds) after which cosignatures are rejected.
+    ///
+    pub fn expires_at(&self) -> u64 {
+        self.expires_at
+    }
+
+    /// The verified cosignatures, in arrival order.
+    ///
+    pub fn cosignatures(&self) -> &[Cosignature] {
+        &self.cosignatures
+    }
+
+    /// Encode the session state.
+    ///
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let size = SESSION_HEADER_SIZE
+            + KEY_BYTES_SIZE * self.cosigners.len()
+            + 2
+            + COSIGNATURE_SIZE * self.cosignatures.len();
+        let mut bytes = Vec::with_capacity(size);
+        bytes.push(COSIGNING_SESSION_VERSION);
+        bytes.extend_from_slice(self.aggregate_hash.as_bytes());
+        bytes.extend_from_slice(&self.expires_at.to_le_bytes());
+        bytes.extend_from_slice(&(self.required as u16).to_le_bytes());
+        bytes.extend_from_slice(&(self.cosigners.len() as u16).to_le_bytes());
+        for cosigner in &self.cosigners {
+            bytes.extend_from_slice(cosigner.as_bytes());
+        }
+        bytes.extend_from_slice(&(self.cosignatures.len() as u16).to_le_bytes());
+        for cosignature in &self.cosignatures {
+            bytes.extend_from_slice(&encode_cosignature(cosignature));
+        }
+        bytes
+    }
+
+    /// Decode a session state, verifying its cosignatures again.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the `CosigningSession` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        ensure!(
+            bytes.len() >= SESSION_HEADER_SIZE,
+            "cosigning session has unexpected size {}.",
+            bytes.len()
+        );
+        ensure!(
+            bytes[0] == COSIGNING_SESSION_VERSION,
+            "unsupported cosigning session version {}.",
+            bytes[0]
+        );
+
+        let aggregate_hash = H256::from_slice(&bytes[1..33]);
+        let expires_at = u64::from_le_bytes(bytes[33..41].try_into()?);
+        let required = u16::from_le_bytes(bytes[41..43].try_into()?) as usize;
+        let cosigner_count = u16::from_le_bytes(bytes[43..45].try_into()?) as usize;
+
+        let cosigners_end = SESSION_HEADER_SIZE + KEY_BYTES_SIZE * cosigner_count;
+        ensure!(
+            bytes.len() >= cosigners_end + 2,
+            "cosigning session has unexpected size {}.",
+            bytes.len()
+        );
+        let cosigners: BTreeSet<PublicKey> = bytes[SESSION_HEADER_SIZE..cosigners_end]
+            .chunks(KEY_BYTES_SIZE)
+            .map(PublicKey::from_slice)
+            .collect();
+        ensure!(
+            cosigners.len() == cosigner_count,
+            "cosigning session has duplicated cosigners."
+        );
+
+        let cosignature_count =
+            u16::from_le_bytes(bytes[cosigners_end..cosigners_end + 2].try_into()?) as usize;
+        let cosignatures = &bytes[cosigners_end + 2..];
+        ensure!(
+            cosignatures.len() == COSIGNATURE_SIZE * cosignature_count,
+            "cosigning session has unexpected size {}.",
+            bytes.len()
+        );
+
+        let mut session = Self::new(aggregate_hash, cosigners, required, expires_at)?;
+        for cosignature in cosignatures.chunks(COSIGNATURE_SIZE) {
+            let cosignature = decode_cosignature(cosignature)?;
+            session.check(&cosignature)?;
+            session.cosignatures.push(cosignature);
+        }
+        Ok(session)
+    }
+
+    // internal functions.
+    fn check(&self, cosignature: &Cosignature) -> Result<()> {
+        let signer = cosignature.signer_public_key;
+        ensure!(
+            self.cosigners.contains(&signer),
+            "{:X} is not an expected cosigner.",
+            signer
+        );
+        ensure!(
+            !self.cosignatures.iter().any(|c| c.signer_public_key == signer),
+            "{:X} already cosigned.",
+            signer
+        );
+        Keypair::from_null_private_key(signer)
+            .verify(self.aggregate_hash.as_bytes(), cosignature.signature)
+            .map_err(|_| anyhow!("cosignature of {:X} is not valid.", signer))
+    }
+}
+
+// internal functions.
+fn encode_cosignature(cosignature: &Cosignature) -> [u8; COSIGNATURE_SIZE] {
+    let mut bytes = [0u8; COSIGNATURE_SIZE];
+    bytes[..8].copy_from_slice(&cosignature.version.to_le_bytes());
+    bytes[8..8 + KEY_BYTES_SIZE].copy_from_slice(cosignature.signer_public_key.as_bytes());
+    bytes[8 + KEY_BYTES_SIZE..].copy_from_slice(cosignature.signature.as_bytes());
+    bytes
+}
+
+fn decode_cosignature(bytes: &[u8]) -> Result<Cosignature> {
+    ensure!(
+        bytes.len() == COSIGNATURE_SIZE,
+        "cosignature has unexpected size {}.",
+        bytes.len()
+    );
+    Ok(Cosignature {
+        version: u64::from_le_bytes(bytes[..8].try_into()?),
+        signer_public_key: PublicKey::from_slice(&bytes[8..8 + KEY_BYTES_SIZE]),
+        signature: Signature::from_slice(&bytes[8 + KEY_BYTES_SIZE..]),
+    })
+}
@@ -0,0 +1,142 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Symbol persistent harvesting delegation messages.
+//!
+//! A delegation request is the message `marker || ephemeral_public_key || encrypted_keys`, where
+//! `encrypted_keys` is the `CryptoSym` encryption of `remote_private_key || vrf_private_key`
+//! from the ephemeral key to the node public key.
+//!
+//! For documentation on how persistent delegation works, please refer to the
+//! [Harvesting](https://docs.symbolplatform.com/concepts/harvesting.html#persistent-delegation) guide.
+//!
+
+use anyhow::{ensure, Result};
+
+use super::{CryptoSym, Keypair};
+use crate::core::curve25519::edwards::CompressedEdwardsY;
+use crate::core::zeroize::Zeroizing;
+use crate::core::{
+    BlockCipher, KeyPairSchema, PrivateKey, PublicKey, AES_TAG_LENGTH, KEY_BYTES_SIZE,
+    SYM_AES_IV_LENGTH,
+};
+
+/// The marker that prefixes every persistent harvesting delegation message.
+pub const DELEGATION_MARKER: [u8; 8] = [0xFE, 0x2A, 0x80, 0x61, 0x57, 0x73, 0x01, 0xE2];
+
+/// The length of a persistent harvesting delegation message in bytes.
+pub const DELEGATION_MESSAGE_LENGTH: usize = DELEGATION_MARKER.len()
+    + KEY_BYTES_SIZE
+    + AES_TAG_LENGTH
+    + SYM_AES_IV_LENGTH
+    + KEY_BYTES_SIZE
+    + KEY_BYTES_SIZE;
+
+/// The keys carried by a persistent harvesting delegation message.
+///
+pub struct DelegationRequest {
+    /// The ephemeral public key the message was encrypted with.
+    pub ephemeral_public_key: PublicKey,
+    /// The private key of the remote (linked) account.
+    pub remote_private_key: Zeroizing<PrivateKey>,
+    /// The private key of the VRF account.
+    pub vrf_private_key: Zeroizing<PrivateKey>,
+}
+
+/// Returns `true` if the payload starts with the persistent delegation marker.
+///
+pub fn is_delegation_request(payload: &[u8]) -> bool {
+    payload.starts_with(&DELEGATION_MARKER)
+}
+
+/// Create a persistent harvesting delegation message for a node.
+///
+/// # Inputs
+///
+/// * `remote_private_key`: The private key of the remote (linked) account.
+///
+/// * `vrf_private_key`: The private key of the VRF account.
+///
+/// * `node_public_key`: The public key of the node the delegation is sent to.
+///
+/// # Returns
+///
+/// A `Result` whose okay value is the message payload `Vec<u8>` or whose error value
+/// is an `failure::Error` describing the error that occurred.
+pub fn create_delegation_request(
+    remote_private_key: &PrivateKey,
+    vrf_private_key: &PrivateKey,
+    node_public_key: &PublicKey,
+) -> Result<Vec<u8>> {
+    let ephemeral_kp = Keypair::random();
+
+    let mut keys = Zeroizing::new(Vec::with_capacity(KEY_BYTES_SIZE * 2));
+    keys.extend_from_slice(remote_private_key.as_bytes());
+    keys.extend_from_slice(vrf_private_key.as_bytes());
+
+    let encrypted = CryptoSym::encrypt_for(&ephemeral_kp.private_key(), node_public_key, &keys)?;
+
+    let mut payload = Vec::with_capacity(DELEGATION_MESSAGE_LENGTH);
+    payload.extend_from_slice(&DELEGATION_MARKER);
+    payload.extend_from_slice(ephemeral_kp.public_key().as_bytes());
+    payload.extend_from_slice(&encrypted);
+    Ok(payload)
+}
+
+/// Parse a persistent harvesting delegation message with the node's private key.
+///
+/// # Inputs
+///
+/// * `node_private_key`: The private key of the node the delegation was sent to.
+///
+/// * `payload`: The message payload, including the delegation marker.
+///
+/// # Returns
+///
+/// A `Result` whose okay value is the `DelegationRequest` or whose error value
+/// is an `failure::Error` describing the error that occurred.
+pub fn parse_delegation_request(
+    node_private_key: &PrivateKey,
+    payload: &[u8],
+) -> Result<DelegationRequest> {
+    ensure!(
+        is_delegation_request(payload),
+        "payload is not a persistent delegation message."
+    );
+    ensure!(
+        payload.len() == DELEGATION_MESSAGE_LENGTH,
+        format!("delegation message has unexpected size {}", payload.len())
+    );
+
+    let ephemeral_offset = DELEGATION_MARKER.len();
+    let encrypted_offset = ephemeral_offset + KEY_BYTES_SIZE;
+
+    let ephemeral_public_key = PublicKey::from_slice(&payload[ephemeral_offset..encrypted_offset]);
+    ensure!(
+        CompressedEdwardsY(ephemeral_public_key.to_fixed_bytes())
+            .decompress()
+            .is_some(),
+        "delegation ephemeral public key is not a valid point."
+    );
+
+    let keys = Zeroizing::new(CryptoSym::decrypt_from(
+        node_private_key,
+        &ephemeral_public_key,
+        &payload[encrypted_offset..],
+    )?);
+    ensure!(
+        keys.len() == KEY_BYTES_SIZE * 2,
+        "delegation message has unexpected keys size."
+    );
+
+    Ok(DelegationRequest {
+        ephemeral_public_key,
+        remote_private_key: Zeroizing::new(PrivateKey::from_slice(&keys[..KEY_BYTES_SIZE])),
+        vrf_private_key: Zeroizing::new(PrivateKey::from_slice(&keys[KEY_BYTES_SIZE..])),
+    })
+}
@@ -0,0 +1,121 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Designated verifier signatures between two Symbol accounts.
+//!
+//! The signature is a Schnorr signature `(R, s)` whose challenge also hashes the ECDH shared
+//! secret of the signer and the verifier: `k = H(DOMAIN || shared || R || A || data)`. Only the
+//! verifier, who can compute the shared secret, can check it, e.g. for private receipts
+//! exchanged off-chain.
+//!
+//! The verifier cannot forge a signature, but can convince a third party of its validity by
+//! revealing the shared secret: this is not a strong (non-transferable) designated verifier
+//! scheme. The signatures are not valid Symbol transaction signatures.
+//!
+
+use anyhow::{ensure, Result};
+use sha2::{Digest, Sha512};
+
+use super::keypair::{expand_private_key, Keypair};
+use crate::core::curve25519::{constants, edwards::EdwardsPoint, scalar::Scalar};
+use crate::core::{
+    check_scalar, derive_shared_secret, PublicKey, SharedSecret, Signature, KEY_BYTES_SIZE,
+};
+
+const DESIGNATED_DOMAIN: &[u8] = b"symbol-designated-verifier";
+
+impl Keypair {
+    /// Signs data so that only `verifier_pk` can validate the signature.
+    ///
+    /// # Inputs
+    ///
+    /// * `verifier_pk`: The public key of the designated verifier.
+    ///
+    /// * `data`: an `&[u8]` representing the data to sign.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the designated `Signature` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    #[allow(non_snake_case)]
+    pub fn sign_for_verifier(&self, verifier_pk: &PublicKey, data: &[u8]) -> Result<Signature> {
+        verifier_pk.to_point()?;
+        let shared = derive_shared_secret::<Sha512>(self.private_key, *verifier_pk);
+        let (a, nonce) = expand_private_key(&self.private_key);
+
+        let mut h = Sha512::new();
+        h.update(nonce);
+        h.update(verifier_pk.as_bytes());
+        h.update(data);
+        let r = Scalar::from_hash(h);
+        let R = (&r * &constants::ED25519_BASEPOINT_TABLE).compress();
+
+        let k = designated_challenge(&shared, R.as_bytes(), &self.public_key, data);
+        let s = k * a + r;
+
+        let mut signature = [0u8; KEY_BYTES_SIZE * 2];
+        signature[..KEY_BYTES_SIZE].copy_from_slice(R.as_bytes());
+        signature[KEY_BYTES_SIZE..].copy_from_slice(s.as_bytes());
+        Ok(Signature::from(signature))
+    }
+
+    /// Verify a designated `Signature` of `signer_pk` with this verifier `Keypair`.
+    ///
+    /// # Inputs
+    ///
+    /// * `signer_pk`: The public key of the signer.
+    ///
+    /// * `data`: an `&[u8]` the signed data.
+    ///
+    /// * `signature`: The designated `Signature`.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok` if the `Signature` was created by `signer_pk` for this `Keypair`.
+    ///
+    #[allow(non_snake_case)]
+    pub fn verify_designated(
+        &self,
+        signer_pk: &PublicKey,
+        data: &[u8],
+        signature: Signature,
+    ) -> Result<()> {
+        let minus_A = -signer_pk.to_point()?;
+        let shared = derive_shared_secret::<Sha512>(self.private_key, *signer_pk);
+
+        let mut R = [0u8; KEY_BYTES_SIZE];
+        R.copy_from_slice(&signature.as_bytes()[..KEY_BYTES_SIZE]);
+        let mut s = [0u8; KEY_BYTES_SIZE];
+        s.copy_from_slice(&signature.as_bytes()[KEY_BYTES_SIZE..]);
+        let s = check_scalar(s)?;
+
+        let k = designated_challenge(&shared, &R, signer_pk, data);
+        let expected = EdwardsPoint::vartime_double_scalar_mul_basepoint(&k, &minus_A, &s);
+        ensure!(
+            expected.compress().as_bytes() == &R,
+            "designated signature is not valid."
+        );
+        Ok(())
+    }
+}
+
+// internal functions.
+fn designated_challenge(
+    shared: &SharedSecret,
+    r: &[u8],
+    signer_pk: &PublicKey,
+    data: &[u8],
+) -> Scalar {
+    let mut h = Sha512::new();
+    h.update(DESIGNATED_DOMAIN);
+    h.update(shared.as_bytes());
+    h.update(r);
+    h.update(signer_pk.as_bytes());
+    h.update(data);
+    Scalar::from_hash(h)
+}
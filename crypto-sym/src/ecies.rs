@@ -0,0 +1,151 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! ECIES style encryption with Symbol keys.
+//!
+//! This is a crate specific profile, built from the SEC1 / IEEE 1363a ECIES scheme but not
+//! interoperable with their implementations: the shared secret is the compressed Edwards point,
+//! not an x coordinate (nor the X25519 u coordinate), and the data is encrypted with
+//! AES-256-GCM. Only this crate decrypts it. The profile is:
+//!
+//! * key agreement: ephemeral Symbol keypair `r`, `R = r·G`, `Z = r·Q` encoded as the
+//!   compressed Edwards point (the same shared secret `CryptoSym` uses),
+//! * key derivation: ANSI X9.63 KDF with SHA-256, `K = SHA256(Z || counter || R || shared_info1)`,
+//! * data encryption and MAC: AES-256-GCM with `K`, an all-zero nonce (`K` is single use) and
+//!   `shared_info2` as associated data,
+//! * encoding: `R || ciphertext || tag`.
+//!
+
+use aes_gcm::{
+    aead::{generic_array::GenericArray, Aead, NewAead, Payload},
+    Aes256Gcm,
+};
+use anyhow::{anyhow, ensure, Result};
+use sha2::{Digest, Sha256, Sha512};
+
+use super::Keypair;
+use crate::core::curve25519::edwards::CompressedEdwardsY;
+use crate::core::{
+    derive_shared_secret, AesKey, KeyPairSchema, PrivateKey, PublicKey, SharedSecret,
+    AES_TAG_LENGTH, KEY_BYTES_SIZE, SYM_AES_IV_LENGTH,
+};
+
+/// Encrypt a message for a receiver with the ECIES profile.
+///
+/// # Inputs
+///
+/// * `receiver_pk`: The receiver's public key.
+///
+/// * `shared_info1`: Optional data bound into the key derivation.
+///
+/// * `shared_info2`: Optional data authenticated with the message.
+///
+/// * `msg`: Message to encrypt.
+///
+/// # Returns
+///
+/// A `Result` whose okay value is `R || ciphertext || tag` or whose error value
+/// is an `failure::Error` describing the error that occurred.
+pub fn encrypt(
+    receiver_pk: &PublicKey,
+    shared_info1: &[u8],
+    shared_info2: &[u8],
+    msg: &[u8],
+) -> Result<Vec<u8>> {
+    ensure!(
+        is_valid_point(receiver_pk),
+        "receiver public key is not a valid point."
+    );
+
+    let ephemeral_kp = Keypair::random();
+    let ephemeral_pk = ephemeral_kp.public_key();
+
+    let shared_secret = derive_shared_secret::<Sha512>(ephemeral_kp.private_key(), *receiver_pk);
+    let key = kdf_x963(shared_secret, &ephemeral_pk, shared_info1);
+
+    let cipher = Aes256Gcm::new(key.as_generic_array());
+    let encrypted = cipher
+        .encrypt(
+            GenericArray::from_slice(&[0u8; SYM_AES_IV_LENGTH]),
+            Payload {
+                msg,
+                aad: shared_info2,
+            },
+        )
+        .map_err(|e| anyhow!("{}", e))?;
+
+    let mut enc = Vec::with_capacity(KEY_BYTES_SIZE + encrypted.len());
+    enc.extend_from_slice(ephemeral_pk.as_bytes());
+    enc.extend_from_slice(&encrypted);
+    Ok(enc)
+}
+
+/// Decrypt a message encrypted with the ECIES profile.
+///
+/// # Inputs
+///
+/// * `receiver_sk`: The receiver's private key.
+///
+/// * `shared_info1`: The data bound into the key derivation by the sender.
+///
+/// * `shared_info2`: The data authenticated with the message by the sender.
+///
+/// * `enc_msg`: `R || ciphertext || tag`.
+///
+/// # Returns
+///
+/// A `Result` whose okay value is an decrypted message `Vec<u8>` or whose error value
+/// is an `failure::Error` describing the error that occurred.
+pub fn decrypt(
+    receiver_sk: &PrivateKey,
+    shared_info1: &[u8],
+    shared_info2: &[u8],
+    enc_msg: &[u8],
+) -> Result<Vec<u8>> {
+    ensure!(
+        enc_msg.len() >= KEY_BYTES_SIZE + AES_TAG_LENGTH,
+        "msg has unexpected size."
+    );
+
+    let ephemeral_pk = PublicKey::from_slice(&enc_msg[..KEY_BYTES_SIZE]);
+    ensure!(
+        is_valid_point(&ephemeral_pk),
+        "ephemeral public key is not a valid point."
+    );
+
+    let shared_secret = derive_shared_secret::<Sha512>(*receiver_sk, ephemeral_pk);
+    let key = kdf_x963(shared_secret, &ephemeral_pk, shared_info1);
+
+    let cipher = Aes256Gcm::new(key.as_generic_array());
+    cipher
+        .decrypt(
+            GenericArray::from_slice(&[0u8; SYM_AES_IV_LENGTH]),
+            Payload {
+                msg: &enc_msg[KEY_BYTES_SIZE..],
+                aad: shared_info2,
+            },
+        )
+        .map_err(|e| anyhow!("{}", e))
+}
+
+// internal functions.
+fn kdf_x963(shared_secret: SharedSecret, ephemeral_pk: &PublicKey, shared_info: &[u8]) -> AesKey {
+    // a single SHA-256 block is enough for an AES-256 key.
+    let mut h = Sha256::new();
+    h.update(shared_secret.as_bytes());
+    h.update(1u32.to_be_bytes());
+    h.update(ephemeral_pk.as_bytes());
+    h.update(shared_info);
+    AesKey::from_slice(h.finalize().as_slice())
+}
+
+fn is_valid_point(pk: &PublicKey) -> bool {
+    CompressedEdwardsY(pk.to_fixed_bytes())
+        .decompress()
+        .is_some()
+}
@@ -0,0 +1,257 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Short-lived signing keys derived from a master key, one per epoch.
+//!
+//! The epoch key of index `i` is
+//! `HKDF-SHA256(salt = EPOCH_KEY_SALT, ikm = master, info = duration (u64) || i (u64))`, and
+//! comes with a certificate signed by the master key over
+//! `EPOCH_CERTIFICATE_TAG || epoch public key || index || valid from || valid until`, the
+//! window being `[i * duration, (i + 1) * duration)`. A service keeps only the key of the
+//! current epoch: once leaked, it stops verifying at the end of its window.
+//!
+
+use anyhow::{anyhow, ensure, Result};
+use hkdf::Hkdf;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+
+use super::Keypair;
+use crate::core::{
+    KeyPairSchema, PrivateKey, PublicKey, Signature, KEY_BYTES_SIZE, SIGNATURE_LENGTH,
+};
+
+/// The HKDF salt separating epoch keys from any other use of the master key.
+pub const EPOCH_KEY_SALT: &[u8] = b"symbol-epoch-key";
+
+/// The prefix of the signed data of an epoch certificate.
+pub const EPOCH_CERTIFICATE_TAG: &[u8] = b"symbol-epoch-key/certificate/v1\0";
+
+/// The size of an encoded `EpochCertificate`.
+pub const EPOCH_CERTIFICATE_SIZE: usize = 2 * KEY_BYTES_SIZE + 3 * 8 + SIGNATURE_LENGTH;
+
+/// The master-signed statement of the validity window of an epoch key.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct EpochCertificate {
+    pub master_public_key: PublicKey,
+    pub epoch_public_key: PublicKey,
+    pub epoch_index: u64,
+    /// Start of the window, inclusive, in the time unit of the epoch duration.
+    pub valid_from: u64,
+    /// End of the window, exclusive.
+    pub valid_until: u64,
+    pub signature: Signature,
+}
+
+/// The signing key of an epoch and its certificate.
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EpochKeys {
+    keypair: crate::core::Keypair<Keypair>,
+    certificate: EpochCertificate,
+}
+
+impl EpochKeys {
+    /// Derive the key of an epoch and certify it with the master key.
+    ///
+    /// # Inputs
+    ///
+    /// * `master`: The master keypair.
+    ///
+    /// * `epoch_duration`: The epoch duration, e.g. `86_400` for daily keys on unix seconds.
+    ///
+    /// * `epoch_index`: The epoch, see `epoch_index_at`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the `EpochKeys` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn derive(
+        master: &crate::core::Keypair<Keypair>,
+        epoch_duration: u64,
+        epoch_index: u64,
+    ) -> Result<Self> {
+        ensure!(epoch_duration > 0, "epoch duration must not be zero.");
+        let valid_until = epoch_index
+            .checked_add(1)
+            .and_then(|next| next.checked_mul(epoch_duration))
+            .ok_or_else(|| anyhow!("epoch {} window overflows.", epoch_index))?;
+        let valid_from = valid_until - epoch_duration;
+
+        let keypair = crate::core::Keypair::<Keypair>::from_private_key(derive_epoch_private_key(
+            &master.private_key(),
+            epoch_duration,
+            epoch_index,
+        ));
+        let mut certificate = EpochCertificate {
+            master_public_key: master.public_key(),
+            epoch_public_key: keypair.public_key(),
+            epoch_index,
+            valid_from,
+            valid_until,
+            signature: Signature::zero(),
+        };
+        certificate.signature = master.try_sign(&certificate.signed_data())?;
+
+        Ok(Self {
+            keypair,
+            certificate,
+        })
+    }
+
+    /// The epoch signing keypair.
+    ///
+    pub fn keypair(&self) -> &crate::core::Keypair<Keypair> {
+        &self.keypair
+    }
+
+    /// The certificate of the epoch key.
+    ///
+    pub fn certificate(&self) -> &EpochCertificate {
+        &self.certificate
+    }
+
+    /// Sign data with the epoch key.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the `Signature` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn sign(&self, data: &[u8]) -> Result<Signature> {
+        self.keypair.try_sign(data)
+    }
+}
+
+impl EpochCertificate {
+    /// Verify the certificate against the expected master public key, at a time.
+    ///
+    /// # Inputs
+    ///
+    /// * `master_public_key`: The trusted master public key.
+    ///
+    /// * `now`: The current time, in the time unit of the epoch duration.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok` if the certificate is signed by the master key and valid at `now`.
+    pub fn verify(&self, master_public_key: &PublicKey, now: u64) -> Result<()> {
+        ensure!(
+            self.master_public_key == *master_public_key,
+            "epoch certificate is signed by {:X}, expected {:X}.",
+            self.master_public_key,
+            master_public_key
+        );
+        ensure!(
+            self.valid_from <= now && now < self.valid_until,
+            "epoch key {} is not valid at {}.",
+            self.epoch_index,
+            now
+        );
+        Keypair::from_null_private_key(self.master_public_key)
+            .verify(&self.signed_data(), self.signature)
+    }
+
+    /// Verify a signature of the epoch key, checking the certificate first.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok` if the certificate is valid at `now` and the signature is valid.
+    pub fn verify_signature(
+        &self,
+        master_public_key: &PublicKey,
+        now: u64,
+        data: &[u8],
+        signature: Signature,
+    ) -> Result<()> {
+        self.verify(master_public_key, now)?;
+        Keypair::from_null_private_key(self.epoch_public_key).verify(data, signature)
+    }
+
+    /// Encode the certificate to bytes.
+    ///
+    pub fn to_bytes(&self) -> [u8; EPOCH_CERTIFICATE_SIZE] {
+        let mut bytes = [0u8; EPOCH_CERTIFICATE_SIZE];
+        bytes[..KEY_BYTES_SIZE].copy_from_slice(self.master_public_key.as_bytes());
+        bytes[KEY_BYTES_SIZE..EPOCH_CERTIFICATE_SIZE - SIGNATURE_LENGTH]
+            .copy_from_slice(&self.signed_data()[EPOCH_CERTIFICATE_TAG.len()..]);
+        bytes[EPOCH_CERTIFICATE_SIZE - SIGNATURE_LENGTH..]
+            .copy_from_slice(self.signature.as_bytes());
+        bytes
+    }
+
+    /// Decode a certificate from bytes.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the `EpochCertificate` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        ensure!(
+            bytes.len() == EPOCH_CERTIFICATE_SIZE,
+            "epoch certificate has unexpected size {}.",
+            bytes.len()
+        );
+        let u64_at = |offset: usize| {
+            let mut value = [0u8; 8];
+            value.copy_from_slice(&bytes[offset..offset + 8]);
+            u64::from_le_bytes(value)
+        };
+
+        Ok(Self {
+            master_public_key: PublicKey::from_slice(&bytes[..KEY_BYTES_SIZE]),
+            epoch_public_key: PublicKey::from_slice(&bytes[KEY_BYTES_SIZE..2 * KEY_BYTES_SIZE]),
+            epoch_index: u64_at(2 * KEY_BYTES_SIZE),
+            valid_from: u64_at(2 * KEY_BYTES_SIZE + 8),
+            valid_until: u64_at(2 * KEY_BYTES_SIZE + 16),
+            signature: Signature::from_slice(&bytes[EPOCH_CERTIFICATE_SIZE - SIGNATURE_LENGTH..]),
+        })
+    }
+
+    // the signed data, without the master public key of the signature itself.
+    fn signed_data(&self) -> Vec<u8> {
+        let mut data = Vec::with_capacity(EPOCH_CERTIFICATE_TAG.len() + KEY_BYTES_SIZE + 3 * 8);
+        data.extend_from_slice(EPOCH_CERTIFICATE_TAG);
+        data.extend_from_slice(self.epoch_public_key.as_bytes());
+        data.extend_from_slice(&self.epoch_index.to_le_bytes());
+        data.extend_from_slice(&self.valid_from.to_le_bytes());
+        data.extend_from_slice(&self.valid_until.to_le_bytes());
+        data
+    }
+}
+
+/// The index of the epoch of a time.
+///
+/// # Panics
+///
+/// If `epoch_duration` is zero.
+pub fn epoch_index_at(epoch_duration: u64, time: u64) -> u64 {
+    time / epoch_duration
+}
+
+// internal functions.
+fn derive_epoch_private_key(
+    master_sk: &PrivateKey,
+    epoch_duration: u64,
+    epoch_index: u64,
+) -> PrivateKey {
+    let h = Hkdf::<Sha256>::new(Some(EPOCH_KEY_SALT), master_sk.as_bytes());
+    let mut info = [0u8; 16];
+    info[..8].copy_from_slice(&epoch_duration.to_le_bytes());
+    info[8..].copy_from_slice(&epoch_index.to_le_bytes());
+    let mut out = [0u8; KEY_BYTES_SIZE];
+    h.expand(&info, &mut out)
+        .expect("unexpected error in rust hkdf_sha256");
+    PrivateKey::from(out)
+}
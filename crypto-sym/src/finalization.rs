@@ -0,0 +1,296 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Signatures of the catapult finalization messages, for light clients checking the
+//! finalization proofs of a node.
+//!
+//! A message is signed with a two level `BmTreeSignature`. The root voting key signs the
+//! bottom key of an epoch, `bottom public key || epoch (u64)`, and the bottom key signs the
+//! message, `epoch (u32) || point (u32) || stage (u32) || height (u64) || hashes`, integers
+//! little endian.
+//!
+
+use std::convert::TryInto;
+
+use anyhow::{anyhow, bail, ensure, Result};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::voting_key::{voting_key_for_epoch, PinnedVotingKey};
+use super::Keypair;
+use crate::core::{KeyPairSchema, PublicKey, Signature, H256, KEY_BYTES_SIZE, SIGNATURE_LENGTH};
+
+/// The size of an encoded `ParentPublicKeySignaturePair`.
+pub const PARENT_SIGNATURE_PAIR_SIZE: usize = KEY_BYTES_SIZE + SIGNATURE_LENGTH;
+
+/// The size of an encoded `BmTreeSignature`.
+pub const BM_TREE_SIGNATURE_SIZE: usize = 2 * PARENT_SIGNATURE_PAIR_SIZE;
+
+/// The stage of a finalization round.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+#[repr(u32)]
+pub enum FinalizationStage {
+    Prevote = 0,
+    Precommit = 1,
+}
+
+/// The finalization round and stage a message is sent for.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct StepIdentifier {
+    pub epoch: u32,
+    pub point: u32,
+    pub stage: FinalizationStage,
+}
+
+/// A public key and its signature of the level below.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct ParentPublicKeySignaturePair {
+    pub parent_public_key: PublicKey,
+    pub signature: Signature,
+}
+
+/// The two level signature of a finalization message.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct BmTreeSignature {
+    /// The root voting key and its signature of the bottom key.
+    pub root: ParentPublicKeySignaturePair,
+    /// The bottom key of the epoch and its signature of the message.
+    pub bottom: ParentPublicKeySignaturePair,
+}
+
+impl FinalizationStage {
+    /// The stage of its numeric value.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the `FinalizationStage` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn from_u32(stage: u32) -> Result<Self> {
+        match stage {
+            0 => Ok(FinalizationStage::Prevote),
+            1 => Ok(FinalizationStage::Precommit),
+            _ => bail!("unknown finalization stage {}.", stage),
+        }
+    }
+}
+
+impl BmTreeSignature {
+    /// Sign a finalization message.
+    ///
+    /// # Inputs
+    ///
+    /// * `root_kp`: The root voting `Keypair`.
+    ///
+    /// * `bottom_kp`: The `Keypair` of the epoch.
+    ///
+    /// * `step`: The round and stage of the message.
+    ///
+    /// * `height`: The finalized height.
+    ///
+    /// * `hashes`: The block hashes from `height`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the `BmTreeSignature` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn sign(
+        root_kp: &crate::core::Keypair<Keypair>,
+        bottom_kp: &crate::core::Keypair<Keypair>,
+        step: &StepIdentifier,
+        height: u64,
+        hashes: &[H256],
+    ) -> Result<Self> {
+        let bottom_public_key = bottom_kp.public_key();
+        Ok(Self {
+            root: ParentPublicKeySignaturePair {
+                parent_public_key: root_kp.public_key(),
+                signature: root_kp.try_sign(&bound_key(&bottom_public_key, step.epoch))?,
+            },
+            bottom: ParentPublicKeySignaturePair {
+                parent_public_key: bottom_public_key,
+                signature: bottom_kp.try_sign(&message(step, height, hashes))?,
+            },
+        })
+    }
+
+    /// Encode the signature, `root public key || root signature || bottom public key ||
+    /// bottom signature`.
+    ///
+    pub fn to_bytes(&self) -> [u8; BM_TREE_SIGNATURE_SIZE] {
+        let mut bytes = [0u8; BM_TREE_SIGNATURE_SIZE];
+        for (chunk, pair) in bytes
+            .chunks_mut(PARENT_SIGNATURE_PAIR_SIZE)
+            .zip([&self.root, &self.bottom].iter())
+        {
+            chunk[..KEY_BYTES_SIZE].copy_from_slice(pair.parent_public_key.as_bytes());
+            chunk[KEY_BYTES_SIZE..].copy_from_slice(pair.signature.as_bytes());
+        }
+        bytes
+    }
+
+    /// Decode a signature.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the `BmTreeSignature` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        ensure!(
+            bytes.len() == BM_TREE_SIGNATURE_SIZE,
+            "bm tree signature has unexpected size {}.",
+            bytes.len()
+        );
+        let pair = |bytes: &[u8]| ParentPublicKeySignaturePair {
+            parent_public_key: PublicKey::from_slice(&bytes[..KEY_BYTES_SIZE]),
+            signature: Signature::from_slice(&bytes[KEY_BYTES_SIZE..PARENT_SIGNATURE_PAIR_SIZE]),
+        };
+        Ok(Self {
+            root: pair(&bytes[..PARENT_SIGNATURE_PAIR_SIZE]),
+            bottom: pair(&bytes[PARENT_SIGNATURE_PAIR_SIZE..]),
+        })
+    }
+}
+
+/// The signed bytes of a finalization message.
+///
+pub fn message(step: &StepIdentifier, height: u64, hashes: &[H256]) -> Vec<u8> {
+    let mut message = Vec::with_capacity(12 + 8 + 32 * hashes.len());
+    message.extend_from_slice(&step.epoch.to_le_bytes());
+    message.extend_from_slice(&step.point.to_le_bytes());
+    message.extend_from_slice(&(step.stage as u32).to_le_bytes());
+    message.extend_from_slice(&height.to_le_bytes());
+    for hash in hashes {
+        message.extend_from_slice(hash.as_bytes());
+    }
+    message
+}
+
+/// Decode the step identifier, height and hashes of signed message bytes, see `message`.
+///
+/// # Returns
+///
+/// A `Result` whose okay value is the step, height and hashes or whose error value
+/// is an `failure::Error` describing the error that occurred.
+pub fn parse_message(bytes: &[u8]) -> Result<(StepIdentifier, u64, Vec<H256>)> {
+    ensure!(
+        bytes.len() >= 20 && (bytes.len() - 20).is_multiple_of(32),
+        "finalization message has unexpected size {}.",
+        bytes.len()
+    );
+    let step = StepIdentifier {
+        epoch: u32::from_le_bytes(bytes[..4].try_into()?),
+        point: u32::from_le_bytes(bytes[4..8].try_into()?),
+        stage: FinalizationStage::from_u32(u32::from_le_bytes(bytes[8..12].try_into()?))?,
+    };
+    let height = u64::from_le_bytes(bytes[12..20].try_into()?);
+    let hashes = bytes[20..].chunks(32).map(H256::from_slice).collect();
+    Ok((step, height, hashes))
+}
+
+/// Verify the signature of a finalization message for a single hash.
+///
+/// # Inputs
+///
+/// * `signature`: The `BmTreeSignature` of the message.
+///
+/// * `voting_pk`: The root voting public key expected to sign `step.epoch`.
+///
+/// * `step`: The round and stage of the message.
+///
+/// * `height`: The finalized height.
+///
+/// * `hash`: The finalized block hash.
+///
+/// # Returns
+///
+/// Returns `Ok` if the message is signed by `voting_pk`.
+pub fn verify_message(
+    signature: &BmTreeSignature,
+    voting_pk: &PublicKey,
+    step: &StepIdentifier,
+    height: u64,
+    hash: &H256,
+) -> Result<()> {
+    verify_message_hashes(signature, voting_pk, step, height, &[*hash])
+}
+
+/// Verify the signature of a finalization message, see `verify_message`.
+///
+/// # Returns
+///
+/// Returns `Ok` if the message is signed by `voting_pk`.
+pub fn verify_message_hashes(
+    signature: &BmTreeSignature,
+    voting_pk: &PublicKey,
+    step: &StepIdentifier,
+    height: u64,
+    hashes: &[H256],
+) -> Result<()> {
+    ensure!(
+        signature.root.parent_public_key == *voting_pk,
+        "finalization message is signed by unexpected voting key {:X}.",
+        signature.root.parent_public_key
+    );
+
+    let bottom = &signature.bottom;
+    Keypair::from_null_private_key(*voting_pk)
+        .verify(
+            &bound_key(&bottom.parent_public_key, step.epoch),
+            signature.root.signature,
+        )
+        .map_err(|_| anyhow!("voting key did not sign the key of epoch {}.", step.epoch))?;
+    Keypair::from_null_private_key(bottom.parent_public_key)
+        .verify(&message(step, height, hashes), bottom.signature)
+        .map_err(|_| anyhow!("finalization message signature is not valid."))
+}
+
+/// Verify the signature of a finalization message with the voting keys of an account.
+///
+/// # Returns
+///
+/// Returns `Ok` if the message is signed by the voting key of `step.epoch`.
+pub fn verify_with_voting_keys(
+    signature: &BmTreeSignature,
+    voting_keys: &[PinnedVotingKey],
+    step: &StepIdentifier,
+    height: u64,
+    hashes: &[H256],
+) -> Result<()> {
+    let voting_key = voting_key_for_epoch(voting_keys, step.epoch)
+        .ok_or_else(|| anyhow!("account has no voting key for epoch {}.", step.epoch))?;
+    verify_message_hashes(signature, &voting_key.voting_key, step, height, hashes)
+}
+
+// internal functions.
+fn bound_key(bottom_pk: &PublicKey, epoch: u32) -> Vec<u8> {
+    [bottom_pk.as_bytes(), &(epoch as u64).to_le_bytes()].concat()
+}
@@ -0,0 +1,211 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! One-way messages encrypted from a fresh ephemeral key, for sender forward secrecy.
+//!
+//! Every message is the `CryptoSym` encryption from a new ephemeral keypair to the receiver,
+//! and the ephemeral private key is dropped once the message is encrypted: a later compromise
+//! of the sender's long-term key does not expose past messages. The receiver's key still
+//! decrypts every message sent to it.
+//!
+//! The envelope is `version || flags || ephemeral public key || [sender public key ||
+//! binding signature] || tag || iv || ciphertext`. With `FLAG_SENDER_BINDING`, the sender's
+//! long-term key signs `FORWARD_SECRET_BINDING_TAG || ephemeral public key || receiver public
+//! key`, so the receiver knows who sent the message.
+//!
+
+use anyhow::{anyhow, ensure, Result};
+
+use super::{CryptoSym, Keypair};
+use crate::core::curve25519::edwards::CompressedEdwardsY;
+use crate::core::{
+    BlockCipher, KeyPairSchema, PrivateKey, PublicKey, Signature, AES_TAG_LENGTH,
+    KEY_BYTES_SIZE, SIGNATURE_LENGTH, SYM_AES_IV_LENGTH,
+};
+
+/// The version byte of the envelopes written by this module.
+pub const FORWARD_SECRET_VERSION: u8 = 1;
+
+/// The flag of an envelope carrying the sender public key and binding signature.
+pub const FLAG_SENDER_BINDING: u8 = 0x01;
+
+/// The prefix of every signed sender binding.
+pub const FORWARD_SECRET_BINDING_TAG: &[u8] = b"symbol-crypto-core/forward-secret/v1\0";
+
+const HEADER_SIZE: usize = 2 + KEY_BYTES_SIZE;
+const BINDING_SIZE: usize = KEY_BYTES_SIZE + SIGNATURE_LENGTH;
+
+/// A decrypted forward secret message.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ForwardSecretMessage {
+    /// The ephemeral public key the message was encrypted with.
+    pub ephemeral_public_key: PublicKey,
+    /// The long-term public key of the sender, if the envelope has a verified binding.
+    pub sender_public_key: Option<PublicKey>,
+    pub message: Vec<u8>,
+}
+
+impl ForwardSecretMessage {
+    /// Check the message is bound to `sender_pk`.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok` if the envelope carried a valid binding signed by `sender_pk`.
+    pub fn verify_sender(&self, sender_pk: &PublicKey) -> Result<()> {
+        match self.sender_public_key {
+            Some(sender_public_key) => {
+                ensure!(
+                    sender_public_key == *sender_pk,
+                    "message is sent by unexpected sender {:X}.",
+                    sender_public_key
+                );
+                Ok(())
+            }
+            None => Err(anyhow!("message has no sender binding.")),
+        }
+    }
+}
+
+/// Encrypt an anonymous message from a fresh ephemeral key.
+///
+/// # Inputs
+///
+/// * `receiver_pk`: The receiver's public key.
+///
+/// * `msg`: Message to encrypt.
+///
+/// # Returns
+///
+/// A `Result` whose okay value is the envelope `Vec<u8>` or whose error value
+/// is an `failure::Error` describing the error that occurred.
+pub fn encrypt_forward_secret(receiver_pk: &PublicKey, msg: &[u8]) -> Result<Vec<u8>> {
+    encrypt_envelope(None, receiver_pk, msg)
+}
+
+/// Encrypt a message from a fresh ephemeral key signed by the sender's long-term key.
+///
+/// # Inputs
+///
+/// * `sender_kp`: The sender's long-term `Keypair`, it only signs the ephemeral public key.
+///
+/// * `receiver_pk`: The receiver's public key.
+///
+/// * `msg`: Message to encrypt.
+///
+/// # Returns
+///
+/// A `Result` whose okay value is the envelope `Vec<u8>` or whose error value
+/// is an `failure::Error` describing the error that occurred.
+pub fn encrypt_forward_secret_signed(
+    sender_kp: &crate::core::Keypair<Keypair>,
+    receiver_pk: &PublicKey,
+    msg: &[u8],
+) -> Result<Vec<u8>> {
+    encrypt_envelope(Some(sender_kp), receiver_pk, msg)
+}
+
+/// Decrypt a forward secret envelope, verifying its sender binding if it has one.
+///
+/// # Inputs
+///
+/// * `receiver_sk`: The receiver's private key.
+///
+/// * `envelope`: The envelope of `encrypt_forward_secret` or `encrypt_forward_secret_signed`.
+///
+/// # Returns
+///
+/// A `Result` whose okay value is the `ForwardSecretMessage` or whose error value
+/// is an `failure::Error` describing the error that occurred.
+pub fn decrypt_forward_secret(
+    receiver_sk: &PrivateKey,
+    envelope: &[u8],
+) -> Result<ForwardSecretMessage> {
+    ensure!(
+        envelope.len() >= HEADER_SIZE + AES_TAG_LENGTH + SYM_AES_IV_LENGTH,
+        "forward secret envelope has unexpected size {}.",
+        envelope.len()
+    );
+    ensure!(
+        envelope[0] == FORWARD_SECRET_VERSION,
+        "forward secret envelope has unknown version {}.",
+        envelope[0]
+    );
+    let flags = envelope[1];
+    ensure!(
+        flags & !FLAG_SENDER_BINDING == 0,
+        "forward secret envelope has unknown flags {:#04x}.",
+        flags
+    );
+
+    let ephemeral_public_key = PublicKey::from_slice(&envelope[2..HEADER_SIZE]);
+    ensure!(
+        CompressedEdwardsY(ephemeral_public_key.to_fixed_bytes())
+            .decompress()
+            .is_some(),
+        "forward secret ephemeral public key is not a valid point."
+    );
+
+    let mut offset = HEADER_SIZE;
+    let mut sender_public_key = None;
+    if flags & FLAG_SENDER_BINDING != 0 {
+        ensure!(
+            envelope.len() >= HEADER_SIZE + BINDING_SIZE + AES_TAG_LENGTH + SYM_AES_IV_LENGTH,
+            "forward secret envelope has unexpected size {}.",
+            envelope.len()
+        );
+        let sender_pk = PublicKey::from_slice(&envelope[offset..offset + KEY_BYTES_SIZE]);
+        let signature =
+            Signature::from_slice(&envelope[offset + KEY_BYTES_SIZE..offset + BINDING_SIZE]);
+        let receiver_pk = Keypair::from_private_key(*receiver_sk).public_key;
+        Keypair::from_null_private_key(sender_pk)
+            .verify(&binding(&ephemeral_public_key, &receiver_pk), signature)
+            .map_err(|_| anyhow!("forward secret sender binding is not valid."))?;
+
+        sender_public_key = Some(sender_pk);
+        offset += BINDING_SIZE;
+    }
+
+    let message = CryptoSym::decrypt_from(receiver_sk, &ephemeral_public_key, &envelope[offset..])?;
+    Ok(ForwardSecretMessage {
+        ephemeral_public_key,
+        sender_public_key,
+        message,
+    })
+}
+
+// internal functions.
+fn encrypt_envelope(
+    sender_kp: Option<&crate::core::Keypair<Keypair>>,
+    receiver_pk: &PublicKey,
+    msg: &[u8],
+) -> Result<Vec<u8>> {
+    let ephemeral_kp = Keypair::random();
+    let encrypted = CryptoSym::encrypt_for(&ephemeral_kp.private_key(), receiver_pk, msg)?;
+
+    let mut envelope = Vec::with_capacity(HEADER_SIZE + BINDING_SIZE + encrypted.len());
+    envelope.push(FORWARD_SECRET_VERSION);
+    envelope.push(sender_kp.map_or(0, |_| FLAG_SENDER_BINDING));
+    envelope.extend_from_slice(ephemeral_kp.public_key().as_bytes());
+    if let Some(sender_kp) = sender_kp {
+        let signature = sender_kp.try_sign(&binding(&ephemeral_kp.public_key(), receiver_pk))?;
+        envelope.extend_from_slice(sender_kp.public_key().as_bytes());
+        envelope.extend_from_slice(signature.as_bytes());
+    }
+    envelope.extend_from_slice(&encrypted);
+    Ok(envelope)
+}
+
+fn binding(ephemeral_pk: &PublicKey, receiver_pk: &PublicKey) -> Vec<u8> {
+    [
+        FORWARD_SECRET_BINDING_TAG,
+        ephemeral_pk.as_bytes(),
+        receiver_pk.as_bytes(),
+    ]
+    .concat()
+}
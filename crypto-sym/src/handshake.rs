@@ -0,0 +1,317 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The catapult peer handshake, authenticating both ends of a node connection.
+//!
+//! 1. the server sends a `ServerChallengeRequest` carrying a random challenge;
+//! 2. the client answers a `ServerChallengeResponse`: its own random challenge, its signature of
+//!    `server challenge || security mode`, its public key and the security mode;
+//! 3. the server checks it and answers a `ClientChallengeResponse`, its signature of the client
+//!    challenge, that the client checks against the public key of the node it connects to.
+//!
+//! Packets start with the catapult packet header `size (u32) || type (u32)`.
+//!
+
+use std::convert::TryInto;
+
+use anyhow::{anyhow, ensure, Result};
+
+use super::Keypair;
+use crate::core::{
+    random_bytes, KeyPairSchema, PublicKey, Signature, KEY_BYTES_SIZE, SIGNATURE_LENGTH,
+};
+
+/// Size of a handshake challenge.
+pub const CHALLENGE_SIZE: usize = 64;
+
+/// Size of the packet header: `size (u32) || type (u32)`.
+pub const PACKET_HEADER_SIZE: usize = 8;
+
+/// The packet type of `ServerChallengeRequest` and `ServerChallengeResponse`.
+pub const SERVER_CHALLENGE_PACKET_TYPE: u32 = 1;
+
+/// The packet type of `ClientChallengeResponse`.
+pub const CLIENT_CHALLENGE_PACKET_TYPE: u32 = 2;
+
+/// Size of an encoded `ServerChallengeRequest`.
+pub const SERVER_CHALLENGE_REQUEST_SIZE: usize = PACKET_HEADER_SIZE + CHALLENGE_SIZE;
+
+/// Size of an encoded `ServerChallengeResponse`.
+pub const SERVER_CHALLENGE_RESPONSE_SIZE: usize =
+    PACKET_HEADER_SIZE + CHALLENGE_SIZE + SIGNATURE_LENGTH + KEY_BYTES_SIZE + 1;
+
+/// Size of an encoded `ClientChallengeResponse`.
+pub const CLIENT_CHALLENGE_RESPONSE_SIZE: usize = PACKET_HEADER_SIZE + SIGNATURE_LENGTH;
+
+/// The security mode requested by the client for the connection.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum SecurityMode {
+    /// Packets are not signed.
+    None = 1,
+    /// Packets are signed.
+    Signed = 2,
+}
+
+/// The challenge sent by the server on a new connection.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ServerChallengeRequest {
+    pub challenge: [u8; CHALLENGE_SIZE],
+}
+
+/// The client answer to a `ServerChallengeRequest`.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ServerChallengeResponse {
+    /// The client challenge, for the server to sign.
+    pub challenge: [u8; CHALLENGE_SIZE],
+    /// The client signature of `server challenge || security mode`.
+    pub signature: Signature,
+    pub public_key: PublicKey,
+    pub security_mode: SecurityMode,
+}
+
+/// The server answer to a `ServerChallengeResponse`.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClientChallengeResponse {
+    /// The server signature of the client challenge.
+    pub signature: Signature,
+}
+
+/// Server side: create the challenge of a new connection.
+///
+pub fn generate_server_challenge_request() -> ServerChallengeRequest {
+    ServerChallengeRequest {
+        challenge: random_bytes::<CHALLENGE_SIZE>(),
+    }
+}
+
+/// Client side: answer the server challenge.
+///
+/// # Inputs
+///
+/// * `request`: The challenge received from the server.
+///
+/// * `kp`: The client node `Keypair`.
+///
+/// * `security_mode`: The requested connection security mode.
+///
+/// Panics as `Keypair::sign` for a keypair that cannot sign.
+#[allow(deprecated)]
+pub fn generate_server_challenge_response(
+    request: &ServerChallengeRequest,
+    kp: &crate::core::Keypair<Keypair>,
+    security_mode: SecurityMode,
+) -> ServerChallengeResponse {
+    ServerChallengeResponse {
+        challenge: random_bytes::<CHALLENGE_SIZE>(),
+        signature: kp.sign(&server_challenge_message(&request.challenge, security_mode)),
+        public_key: kp.public_key(),
+        security_mode,
+    }
+}
+
+/// Server side: verify the client answer to the challenge it sent.
+///
+/// # Returns
+///
+/// Returns `Ok` if the client signed the request challenge with its public key.
+pub fn verify_server_challenge_response(
+    response: &ServerChallengeResponse,
+    request: &ServerChallengeRequest,
+) -> Result<()> {
+    Keypair::from_null_private_key(response.public_key).verify(
+        &server_challenge_message(&request.challenge, response.security_mode),
+        response.signature,
+    )
+}
+
+/// Server side: answer the client challenge.
+///
+/// # Inputs
+///
+/// * `response`: The verified client answer.
+///
+/// * `kp`: The server node `Keypair`.
+///
+/// Panics as `Keypair::sign` for a keypair that cannot sign.
+#[allow(deprecated)]
+pub fn generate_client_challenge_response(
+    response: &ServerChallengeResponse,
+    kp: &crate::core::Keypair<Keypair>,
+) -> ClientChallengeResponse {
+    ClientChallengeResponse {
+        signature: kp.sign(&response.challenge),
+    }
+}
+
+/// Client side: verify the server answer to the client challenge.
+///
+/// # Inputs
+///
+/// * `response`: The server answer.
+///
+/// * `server_public_key`: The public key of the node the client connects to.
+///
+/// * `client_response`: The client answer, carrying the client challenge.
+///
+/// # Returns
+///
+/// Returns `Ok` if the server signed the client challenge with `server_public_key`.
+pub fn verify_client_challenge_response(
+    response: &ClientChallengeResponse,
+    server_public_key: &PublicKey,
+    client_response: &ServerChallengeResponse,
+) -> Result<()> {
+    Keypair::from_null_private_key(*server_public_key)
+        .verify(&client_response.challenge, response.signature)
+}
+
+impl SecurityMode {
+    /// Decode a security mode byte.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the `SecurityMode` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn from_u8(mode: u8) -> Result<Self> {
+        match mode {
+            1 => Ok(SecurityMode::None),
+            2 => Ok(SecurityMode::Signed),
+            _ => Err(anyhow!("unknown security mode {}.", mode)),
+        }
+    }
+}
+
+impl ServerChallengeRequest {
+    /// Encode the packet.
+    ///
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = packet_header(SERVER_CHALLENGE_REQUEST_SIZE, SERVER_CHALLENGE_PACKET_TYPE);
+        bytes.extend_from_slice(&self.challenge);
+        bytes
+    }
+
+    /// Decode a packet.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the `ServerChallengeRequest` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let body = check_packet(
+            bytes,
+            SERVER_CHALLENGE_REQUEST_SIZE,
+            SERVER_CHALLENGE_PACKET_TYPE,
+        )?;
+        Ok(Self {
+            challenge: body.try_into()?,
+        })
+    }
+}
+
+impl ServerChallengeResponse {
+    /// Encode the packet.
+    ///
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = packet_header(SERVER_CHALLENGE_RESPONSE_SIZE, SERVER_CHALLENGE_PACKET_TYPE);
+        bytes.extend_from_slice(&self.challenge);
+        bytes.extend_from_slice(self.signature.as_bytes());
+        bytes.extend_from_slice(self.public_key.as_bytes());
+        bytes.push(self.security_mode as u8);
+        bytes
+    }
+
+    /// Decode a packet.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the `ServerChallengeResponse` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let body = check_packet(
+            bytes,
+            SERVER_CHALLENGE_RESPONSE_SIZE,
+            SERVER_CHALLENGE_PACKET_TYPE,
+        )?;
+        let (challenge, rest) = body.split_at(CHALLENGE_SIZE);
+        let (signature, rest) = rest.split_at(SIGNATURE_LENGTH);
+        let (public_key, security_mode) = rest.split_at(KEY_BYTES_SIZE);
+        Ok(Self {
+            challenge: challenge.try_into()?,
+            signature: Signature::from_slice(signature),
+            public_key: PublicKey::from_slice(public_key),
+            security_mode: SecurityMode::from_u8(security_mode[0])?,
+        })
+    }
+}
+
+impl ClientChallengeResponse {
+    /// Encode the packet.
+    ///
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = packet_header(CLIENT_CHALLENGE_RESPONSE_SIZE, CLIENT_CHALLENGE_PACKET_TYPE);
+        bytes.extend_from_slice(self.signature.as_bytes());
+        bytes
+    }
+
+    /// Decode a packet.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the `ClientChallengeResponse` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let body = check_packet(
+            bytes,
+            CLIENT_CHALLENGE_RESPONSE_SIZE,
+            CLIENT_CHALLENGE_PACKET_TYPE,
+        )?;
+        Ok(Self {
+            signature: Signature::from_slice(body),
+        })
+    }
+}
+
+// internal functions.
+fn server_challenge_message(challenge: &[u8; CHALLENGE_SIZE], mode: SecurityMode) -> Vec<u8> {
+    let mut message = challenge.to_vec();
+    message.push(mode as u8);
+    message
+}
+
+fn packet_header(size: usize, packet_type: u32) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(size);
+    bytes.extend_from_slice(&(size as u32).to_le_bytes());
+    bytes.extend_from_slice(&packet_type.to_le_bytes());
+    bytes
+}
+
+fn check_packet(bytes: &[u8], size: usize, packet_type: u32) -> Result<&[u8]> {
+    ensure!(
+        bytes.len() == size,
+        "packet has unexpected size {}.",
+        bytes.len()
+    );
+    let header_size = u32::from_le_bytes(bytes[..4].try_into()?);
+    let header_type = u32::from_le_bytes(bytes[4..PACKET_HEADER_SIZE].try_into()?);
+    ensure!(
+        header_size as usize == size,
+        "packet header has unexpected size {}.",
+        header_size
+    );
+    ensure!(
+        header_type == packet_type,
+        "packet has unexpected type {}.",
+        header_type
+    );
+    Ok(&bytes[PACKET_HEADER_SIZE..])
+}
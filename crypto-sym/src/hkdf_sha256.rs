@@ -11,12 +11,10 @@ use sha2::Sha256;
 
 use crate::core::{H256, SharedSecret};
 
-const HKDF_INFO: &[u8; 8] = b"catapult";
-
-pub(crate) fn hkdf_sha256(master: SharedSecret) -> H256 {
+pub(crate) fn hkdf_sha256(master: SharedSecret, info: &[u8]) -> H256 {
     let h = Hkdf::<Sha256>::new(None, master.as_bytes());
     let mut out = H256::zero();
-    h.expand(HKDF_INFO, &mut out.as_mut())
+    h.expand(info, out.as_mut())
         .expect("unexpected error in rust hkdf_sha256");
     out
 }
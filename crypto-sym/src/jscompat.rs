@@ -0,0 +1,88 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The `Crypto.encode` / `Crypto.decode` functions of the symbol-sdk (JavaScript), with the
+//! same parameter order and hex string inputs and outputs, to port JS code mechanically before
+//! moving to `CryptoSym`.
+//!
+//! The payload is the `CryptoSym` message, `tag || iv || ciphertext`, as uppercase hex.
+//!
+
+use std::str::FromStr;
+
+use anyhow::{anyhow, Result};
+
+use super::CryptoSym;
+use crate::core::{is_hex, BlockCipher, PrivateKey, PublicKey};
+
+/// Encrypt a message, like `Crypto.encode(senderPriv, recipientPub, msg, isHexString)`.
+///
+/// # Inputs
+///
+/// * `sender_priv`: The sender private key, hex.
+///
+/// * `recipient_pub`: The recipient public key, hex.
+///
+/// * `msg`: The message, UTF-8 text or hex if `is_hex`.
+///
+/// * `is_hex`: Whether `msg` is a hex string.
+///
+/// # Returns
+///
+/// A `Result` whose okay value is the uppercase hex payload or whose error value
+/// is an `failure::Error` describing the error that occurred.
+pub fn encode(sender_priv: &str, recipient_pub: &str, msg: &str, is_hex: bool) -> Result<String> {
+    let sender_sk = PrivateKey::from_hex_ct(sender_priv)
+        .map_err(|e| anyhow!("invalid sender private key: {}", e))?;
+    let recipient_pk = PublicKey::from_str(recipient_pub)
+        .map_err(|e| anyhow!("invalid recipient public key: {}", e))?;
+
+    let msg = if is_hex {
+        decode_hex(msg)?
+    } else {
+        msg.as_bytes().to_vec()
+    };
+
+    let payload = CryptoSym::encrypt_for(&sender_sk, &recipient_pk, &msg)?;
+    Ok(hex::encode_upper(payload))
+}
+
+/// Decrypt a payload, like `Crypto.decode(recipientPrivate, senderPublic, payload)`.
+///
+/// # Inputs
+///
+/// * `recipient_priv`: The recipient private key, hex.
+///
+/// * `sender_pub`: The sender public key, hex.
+///
+/// * `payload`: The encrypted payload, hex.
+///
+/// # Returns
+///
+/// A `Result` whose okay value is the message as uppercase hex or whose error value
+/// is an `failure::Error` describing the error that occurred.
+pub fn decode(recipient_priv: &str, sender_pub: &str, payload: &str) -> Result<String> {
+    let recipient_sk = PrivateKey::from_hex_ct(recipient_priv)
+        .map_err(|e| anyhow!("invalid recipient private key: {}", e))?;
+    let sender_pk =
+        PublicKey::from_str(sender_pub).map_err(|e| anyhow!("invalid sender public key: {}", e))?;
+
+    let msg = CryptoSym::decrypt_from(&recipient_sk, &sender_pk, &decode_hex(payload)?)?;
+    Ok(hex::encode_upper(msg))
+}
+
+// internal functions.
+fn decode_hex(data: &str) -> Result<Vec<u8>> {
+    if data.is_empty() {
+        return Ok(vec![]);
+    }
+    if !is_hex(data) {
+        return Err(anyhow!("{} is not a hex string.", data));
+    }
+    hex::decode(data).map_err(|e| anyhow!("{}", e))
+}
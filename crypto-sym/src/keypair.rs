@@ -15,47 +15,64 @@
 //!
 use ::std::convert::TryInto;
 use ::std::fmt;
+use ::std::hash::{Hash, Hasher};
 
-use anyhow::{ensure, Result};
-use rand::thread_rng;
+use anyhow::{anyhow, ensure, Result};
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+use sha2::{Digest, Sha512};
 #[cfg(feature = "serde")]
-use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde::{Serialize, Serializer};
 #[cfg(feature = "serde")]
-use serde_bytes::{ByteBuf as SerdeByteBuf, Bytes as SerdeBytes};
-#[cfg(feature = "serde")]
-use serde::de::Error as SerdeError;
+use serde_bytes::Bytes as SerdeBytes;
 
 use core::ed25519::{self, Verifier};
 
+use crate::core::curve25519::{
+    constants,
+    edwards::{CompressedEdwardsY, EdwardsPoint},
+    scalar::Scalar,
+};
+
+use super::nonce::nonce_scalar;
 use super::CryptoSym;
 use crate::core::{
-    hex_to_vec, is_hex, KeyPairSchema, PrivateKey, PublicKey, Signature, KEY_STR_SIZE,
+    check_scalar, clamp_scalar_bits, scalar_canonicality, KeyPairSchema, PrivateKey, PublicKey, Scalar32, Signature,
+    KEY_BYTES_SIZE,
 };
 
 /// It represents an Symbol asymmetric private/public encryption key.
 ///
-#[derive(Debug, Clone, Copy, PartialEq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Keypair {
     /// The private half of this keypair.
     pub private_key: PrivateKey,
     /// The public half of this keypair.
     pub public_key: PublicKey,
+    /// The expanded secret of a keypair imported without its private key.
+    expanded: Option<ExpandedScalar>,
+}
+
+/// The clamped secret scalar and nonce prefix of `Keypair::from_expanded_scalar`.
+///
+/// `Debug` redacts both and `Keypair` does not hash them.
+///
+#[derive(Clone, Copy, PartialEq)]
+struct ExpandedScalar {
+    scalar: [u8; KEY_BYTES_SIZE],
+    nonce: [u8; KEY_BYTES_SIZE],
 }
 
+impl crate::core::sealed::Sealed for Keypair {}
+
 impl KeyPairSchema for Keypair {
     type Crypto = CryptoSym;
+    const SCHEMA_NAME: &'static str = "symbol";
 
     /// Create a new Symbol `Keypair` with cryptographically random content.
     ///
     fn random() -> Self {
-        let mut csprng = thread_rng();
-        let sk = ed25519::SecretKey::generate(&mut csprng);
-        let pk: ed25519::PublicKey = (&sk).into();
-
-        Self {
-            private_key: PrivateKey::from(sk.to_bytes()),
-            public_key: PublicKey::from(pk.to_bytes()),
-        }
+        Self::from_private_key(PrivateKey::random())
     }
 
     /// Construct a Symbol `Keypair` from the bytes of a `PublicKey` and `PrivateKey`.
@@ -76,22 +93,7 @@ impl KeyPairSchema for Keypair {
     /// A `Result` whose okay value is an Symbol `Keypair` or whose error value
     /// is an `failure::Error` describing the error that occurred.
     fn from_hex_private_key<S: AsRef<str>>(hex: S) -> Result<Self> {
-        let hex = hex.as_ref();
-        ensure!(is_hex(hex), "private_key it's not hex.");
-
-        ensure!(
-            KEY_STR_SIZE == hex.len(),
-            format!("private key has unexpected size {}", hex.len())
-        );
-
-        let sk = ed25519::SecretKey::from_bytes(&hex_to_vec(hex))?;
-
-        let pk: ed25519::PublicKey = (&sk).into();
-
-        Ok(Self {
-            private_key: PrivateKey::from(sk.to_bytes()),
-            public_key: PublicKey::from(pk.to_bytes()),
-        })
+        Ok(Self::from_private_key(PrivateKey::from_hex_ct(hex.as_ref())?))
     }
 
     /// Construct a Symbol `Keypair` `PrivateKey` type.
@@ -111,6 +113,23 @@ impl KeyPairSchema for Keypair {
         Self {
             private_key: PrivateKey::from(sk.to_bytes()),
             public_key: PublicKey::from(pk.to_bytes()),
+            expanded: None,
+        }
+    }
+
+    /// Construct a signing only Symbol `Keypair` from the clamped lower half of the SHA-512 of
+    /// a private key and its upper half, the nonce prefix.
+    ///
+    fn from_expanded_scalar(scalar: &Scalar32, nonce: [u8; KEY_BYTES_SIZE]) -> Self {
+        let public_key = (&scalar.to_scalar() * &constants::ED25519_BASEPOINT_TABLE).compress();
+
+        Self {
+            private_key: PrivateKey::zero(),
+            public_key: PublicKey::from(public_key.to_bytes()),
+            expanded: Some(ExpandedScalar {
+                scalar: *scalar.as_bytes(),
+                nonce,
+            }),
         }
     }
 
@@ -131,11 +150,25 @@ impl KeyPairSchema for Keypair {
     /// # Returns
     ///
     /// A `Signature` the signature hash.
+    ///
+    /// # Panics
+    ///
+    /// If the keypair has no private key for its public key, e.g. from
+    /// `from_null_private_key`, see `try_sign`.
     fn sign(&self, data: &[u8]) -> Signature {
-        let kp = ed25519::Keypair::from_bytes(&self.to_bytes()).unwrap();
+        self.checked_sign(&[data]).unwrap_or_else(|e| panic!("{}", e))
+    }
 
-        let expanded_sk: ed25519::ExpandedSecretKey = (&kp.secret).into();
-        (expanded_sk.sign(data, &kp.public).to_bytes()).into()
+    /// Signs a data bytes, see `sign`. Fails for a keypair without private key, e.g. from
+    /// `from_null_private_key`, instead of panicking. With the `verify_after_sign` feature the
+    /// signature is verified before it is returned, against faults injected while signing.
+    ///
+    fn try_sign(&self, data: &[u8]) -> Result<Signature> {
+        let signature = self.checked_sign(&[data])?;
+        #[cfg(feature = "verify_after_sign")]
+        self.verify(data, signature)
+            .map_err(|_| anyhow!("signature failed verification after signing."))?;
+        Ok(signature)
     }
 
     /// Verify a `Signature` on a data with this Symbol Keypair public key.
@@ -151,28 +184,121 @@ impl KeyPairSchema for Keypair {
     /// Returns `Ok` if the `Signature` was a valid signature created by this Symbol `Keypair`
     ///
     fn verify(&self, data: &[u8], signature: Signature) -> Result<()> {
+        let mut s = [0u8; KEY_BYTES_SIZE];
+        s.copy_from_slice(&signature.as_bytes()[KEY_BYTES_SIZE..]);
+        ensure!(
+            scalar_canonicality::check(&s).is_canonical(),
+            "ScalarFormatError"
+        );
+
         let pk = ed25519::PublicKey::from_bytes(self.public_key.as_bytes())?;
         let signature: ed25519::Signature = (signature.as_bytes()).try_into()?;
         Ok(pk.verify(data, &signature)?)
     }
 
+    /// Signs the concatenation of the data parts with a Symbol `Keypair`.
+    ///
+    /// The signature is the ed25519 signature of the joined data.
+    ///
+    /// # Panics
+    ///
+    /// If the keypair has no private key for its public key, see `sign`.
+    fn sign_vectored(&self, parts: &[&[u8]]) -> Signature {
+        self.checked_sign(parts).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Verify a `Signature` on the concatenation of the data parts with this Symbol Keypair
+    /// public key.
+    ///
+    fn verify_vectored(&self, parts: &[&[u8]], signature: Signature) -> Result<()> {
+        let point = CompressedEdwardsY(self.public_key.to_fixed_bytes())
+            .decompress()
+            .ok_or_else(|| anyhow!("PointDecompressionError"))?;
+        verify_parts(&self.public_key, &point, parts, signature)
+    }
+
+    /// Verify a `Signature` on a data with the decompressed point of a Symbol public key, as
+    /// `verify_vectored`.
+    ///
+    fn verify_with_point(
+        public_key: &PublicKey,
+        point: &EdwardsPoint,
+        data: &[u8],
+        signature: Signature,
+    ) -> Result<()> {
+        verify_parts(public_key, point, &[data], signature)
+    }
+
+    fn derive_public_keys(private_keys: &[PrivateKey]) -> Vec<PublicKey> {
+        #[cfg(feature = "rayon")]
+        let public_keys = private_keys
+            .par_iter()
+            .map_init(Sha512::new, derive_public_key)
+            .collect();
+        #[cfg(not(feature = "rayon"))]
+        let public_keys = {
+            let mut hasher = Sha512::new();
+            private_keys
+                .iter()
+                .map(|private_key| derive_public_key(&mut hasher, private_key))
+                .collect()
+        };
+        public_keys
+    }
+
     fn from_null_private_key(pk: PublicKey) -> Self {
         Self {
             private_key: PrivateKey::zero(),
             public_key: pk,
+            expanded: None,
         }
     }
 }
 
+impl Keypair {
+    /// The ed25519 secret scalar and nonce prefix of this keypair.
+    ///
+    pub(crate) fn expanded_secret(&self) -> (Scalar, [u8; KEY_BYTES_SIZE]) {
+        match self.expanded {
+            Some(expanded) => (Scalar::from_bits(expanded.scalar), expanded.nonce),
+            None => expand_private_key(&self.private_key),
+        }
+    }
+
+    // internal functions.
+    /// Signs the concatenation of the data parts, once the secret scalar is checked against
+    /// the public key.
+    #[allow(non_snake_case)]
+    fn checked_sign(&self, parts: &[&[u8]]) -> Result<Signature> {
+        let (a, nonce) = self.expanded_secret();
+        let A = (&a * &constants::ED25519_BASEPOINT_TABLE).compress();
+        ensure!(
+            A.as_bytes() == self.public_key.as_bytes(),
+            "keypair cannot sign, its private key does not match the public key."
+        );
+
+        let r = nonce_scalar(&nonce, parts);
+        let R = (&r * &constants::ED25519_BASEPOINT_TABLE).compress();
+
+        let k = challenge(R.as_bytes(), &self.public_key, parts);
+        let s = k * a + r;
+
+        let mut signature = [0u8; KEY_BYTES_SIZE * 2];
+        signature[..KEY_BYTES_SIZE].copy_from_slice(R.as_bytes());
+        signature[KEY_BYTES_SIZE..].copy_from_slice(s.as_bytes());
+        Ok(Signature::from(signature))
+    }
+}
+
 impl<'a> From<&'a PrivateKey> for Keypair {
     fn from(sk: &'a PrivateKey) -> Self {
-        Self::from_hex_private_key(format!("{:x}", sk)).unwrap()
+        Self::from_private_key(*sk)
     }
 }
 
 impl From<PrivateKey> for Keypair {
     fn from(sk: PrivateKey) -> Self {
-        Self::from_hex_private_key(format!("{:x}", sk)).unwrap()
+        Self::from_private_key(sk)
     }
 }
 
@@ -182,24 +308,81 @@ impl fmt::Display for Keypair {
     }
 }
 
+impl Hash for Keypair {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.private_key.hash(state);
+        self.public_key.hash(state);
+    }
+}
+
+impl fmt::Debug for ExpandedScalar {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExpandedScalar")
+            .field("scalar", &"[redacted]")
+            .field("nonce", &"[redacted]")
+            .finish()
+    }
+}
+
 #[cfg(feature = "serde")]
 impl Serialize for Keypair {
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        let bytes = &self.to_bytes()[..];
-        SerdeBytes::new(bytes).serialize(serializer)
+        // only the public half, see `SecretSerde` for the full keypair.
+        SerdeBytes::new(self.public_key().as_bytes()).serialize(serializer)
     }
 }
 
-#[cfg(feature = "serde")]
-impl<'d> Deserialize<'d> for Keypair {
-    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
-        where
-            D: Deserializer<'d>,
-    {
-        let bytes = <SerdeByteBuf>::deserialize(deserializer)?;
-        Keypair::from_bytes(bytes.as_ref()).map_err(SerdeError::custom)
-    }
-}
\ No newline at end of file
+// internal functions.
+/// The public key of a private key, with a reused hasher.
+fn derive_public_key(hasher: &mut Sha512, private_key: &PrivateKey) -> PublicKey {
+    hasher.update(private_key.as_bytes());
+    let hash = hasher.finalize_reset();
+    let mut lower = [0u8; KEY_BYTES_SIZE];
+    lower.copy_from_slice(&hash[..KEY_BYTES_SIZE]);
+    let a = Scalar::from_bits(clamp_scalar_bits(lower));
+    PublicKey::from((&a * &constants::ED25519_BASEPOINT_TABLE).compress().to_bytes())
+}
+
+/// The ed25519 secret scalar and nonce prefix of a private key.
+pub(crate) fn expand_private_key(private_key: &PrivateKey) -> (Scalar, [u8; KEY_BYTES_SIZE]) {
+    let hash = Sha512::digest(private_key.as_bytes());
+    let mut lower = [0u8; KEY_BYTES_SIZE];
+    let mut upper = [0u8; KEY_BYTES_SIZE];
+    lower.copy_from_slice(&hash[..KEY_BYTES_SIZE]);
+    upper.copy_from_slice(&hash[KEY_BYTES_SIZE..]);
+    (Scalar::from_bits(clamp_scalar_bits(lower)), upper)
+}
+
+#[allow(non_snake_case)]
+fn verify_parts(
+    public_key: &PublicKey,
+    point: &EdwardsPoint,
+    parts: &[&[u8]],
+    signature: Signature,
+) -> Result<()> {
+    let mut R = [0u8; KEY_BYTES_SIZE];
+    R.copy_from_slice(&signature.as_bytes()[..KEY_BYTES_SIZE]);
+    let mut s = [0u8; KEY_BYTES_SIZE];
+    s.copy_from_slice(&signature.as_bytes()[KEY_BYTES_SIZE..]);
+    let s = check_scalar(s)?;
+
+    let minus_A = -point;
+    let k = challenge(&R, public_key, parts);
+    let expected = EdwardsPoint::vartime_double_scalar_mul_basepoint(&k, &minus_A, &s);
+    ensure!(
+        expected.compress().as_bytes() == &R,
+        "signature is not valid."
+    );
+    Ok(())
+}
+
+fn challenge(r: &[u8], public_key: &PublicKey, parts: &[&[u8]]) -> Scalar {
+    let mut h = Sha512::new();
+    h.update(r);
+    h.update(public_key.as_bytes());
+    parts.iter().for_each(|part| h.update(part));
+    Scalar::from_hash(h)
+}
@@ -10,9 +10,58 @@ extern crate core_crypto as core;
 #[cfg(feature = "serde")]
 extern crate serde_crate as serde;
 
+pub use aggregate::*;
 pub use cipher::*;
+pub use conversation::*;
+pub use cosigner_backup::*;
+pub use delegation::*;
+pub use epoch_key::*;
 pub use keypair::*;
+pub use metadata::*;
+pub use multisig::*;
+pub use purpose_key::*;
+pub use session::*;
+pub use transaction::*;
 
+mod aggregate;
+pub mod attachment;
+pub mod attestation;
+pub mod blind;
+#[cfg(feature = "bootstrap")]
+pub mod bootstrap;
+pub mod channel;
 mod cipher;
+#[cfg(feature = "compression")]
+pub mod compression;
+mod conversation;
+#[cfg(feature = "cose")]
+pub mod cose;
+mod cosigner_backup;
+pub mod cosigning;
+mod delegation;
+mod designated;
+mod epoch_key;
+#[cfg(feature = "ecies")]
+pub mod ecies;
+pub mod finalization;
+pub mod forward_secret;
+pub mod handshake;
 mod hkdf_sha256;
+#[cfg(feature = "jscompat")]
+pub mod jscompat;
 pub mod keypair;
+pub mod link_proof;
+mod metadata;
+mod multisig;
+pub mod namespace;
+pub mod network_gen;
+pub mod nonce;
+pub mod peer_crypto;
+mod purpose_key;
+pub mod recipient;
+pub mod request_auth;
+mod session;
+mod transaction;
+#[cfg(feature = "vc")]
+pub mod vc;
+pub mod voting_key;
@@ -0,0 +1,121 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Off-chain proof that a remote harvesting key is delegated by a main account.
+//!
+//! The main account signs `LINK_PROOF_TAG || main public key || remote public key`, so a pool
+//! operator can check a harvester claim before accepting it, without waiting for the account
+//! key link transaction. The tag keeps the proof from being mistaken for a transaction
+//! signature. A proof is encoded as `main public key || remote public key || signature`.
+//!
+
+use anyhow::{ensure, Result};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::Keypair;
+use crate::core::{KeyPairSchema, PublicKey, Signature, KEY_BYTES_SIZE, SIGNATURE_LENGTH};
+
+/// The prefix of every signed link proof.
+pub const LINK_PROOF_TAG: &[u8] = b"symbol-crypto-core/link-proof/v1\0";
+
+/// Size of an encoded `LinkProof`.
+pub const LINK_PROOF_SIZE: usize = 2 * KEY_BYTES_SIZE + SIGNATURE_LENGTH;
+
+/// A signed statement of a main account delegating harvesting to a remote key.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct LinkProof {
+    pub main_public_key: PublicKey,
+    pub remote_public_key: PublicKey,
+    pub signature: Signature,
+}
+
+/// Create the proof linking `remote_pk` to the main account.
+///
+/// # Inputs
+///
+/// * `main_kp`: The main account `Keypair`.
+///
+/// * `remote_pk`: The remote harvesting public key.
+///
+/// # Returns
+///
+/// A `Result` whose okay value is the `LinkProof` or whose error value
+/// is an `failure::Error` describing the error that occurred.
+pub fn create(main_kp: &crate::core::Keypair<Keypair>, remote_pk: &PublicKey) -> Result<LinkProof> {
+    let main_public_key = main_kp.public_key();
+    ensure!(
+        main_public_key != *remote_pk,
+        "remote key must differ from the main key."
+    );
+
+    Ok(LinkProof {
+        main_public_key,
+        remote_public_key: *remote_pk,
+        signature: main_kp.try_sign(&message(&main_public_key, remote_pk))?,
+    })
+}
+
+/// Verify a proof.
+///
+/// # Returns
+///
+/// Returns `Ok` if the proof is signed by its main account.
+pub fn verify(proof: &LinkProof) -> Result<()> {
+    ensure!(
+        proof.main_public_key != proof.remote_public_key,
+        "remote key must differ from the main key."
+    );
+    Keypair::from_null_private_key(proof.main_public_key).verify(
+        &message(&proof.main_public_key, &proof.remote_public_key),
+        proof.signature,
+    )
+}
+
+impl LinkProof {
+    /// Encode the proof.
+    ///
+    pub fn to_bytes(&self) -> [u8; LINK_PROOF_SIZE] {
+        let mut bytes = [0u8; LINK_PROOF_SIZE];
+        bytes[..KEY_BYTES_SIZE].copy_from_slice(self.main_public_key.as_bytes());
+        bytes[KEY_BYTES_SIZE..2 * KEY_BYTES_SIZE]
+            .copy_from_slice(self.remote_public_key.as_bytes());
+        bytes[2 * KEY_BYTES_SIZE..].copy_from_slice(self.signature.as_bytes());
+        bytes
+    }
+
+    /// Decode a proof, without verifying it.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the `LinkProof` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        ensure!(
+            bytes.len() == LINK_PROOF_SIZE,
+            "link proof has unexpected size {}.",
+            bytes.len()
+        );
+        Ok(Self {
+            main_public_key: PublicKey::from_slice(&bytes[..KEY_BYTES_SIZE]),
+            remote_public_key: PublicKey::from_slice(&bytes[KEY_BYTES_SIZE..2 * KEY_BYTES_SIZE]),
+            signature: Signature::from_slice(&bytes[2 * KEY_BYTES_SIZE..]),
+        })
+    }
+}
+
+// internal functions.
+fn message(main_pk: &PublicKey, remote_pk: &PublicKey) -> Vec<u8> {
+    [LINK_PROOF_TAG, main_pk.as_bytes(), remote_pk.as_bytes()].concat()
+}
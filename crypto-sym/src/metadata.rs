@@ -0,0 +1,147 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Deterministic encryption of Symbol key-value metadata values.
+//!
+//! Every `(scoped_key, target)` pair uses its own key, so equal values stored under different
+//! scoped keys or targets never produce relatable ciphertexts, while updating an entry with an
+//! unchanged value gives the same ciphertext. The format, which a companion library can
+//! implement, is:
+//!
+//! * `shared = compress(a * T)`, the `CryptoSym` ECDH of the source private key `a` (the clamped
+//!   lower half of its SHA-512) and the target public key `T`;
+//! * `key = HKDF-SHA256(salt = METADATA_KEY_SALT, ikm = shared, info = scoped_key || T)`, with
+//!   `scoped_key` as little endian `u64`;
+//! * `iv = HKDF-SHA256(salt = key, ikm = value, info = METADATA_IV_INFO)`, its first 12 bytes;
+//! * `output = tag || iv || AES-256-GCM(key, iv, value)`, the `CryptoSym` message layout.
+//!
+
+use anyhow::{anyhow, ensure, Result};
+use hkdf::Hkdf;
+use sha2::{Sha256, Sha512};
+
+use super::cipher::{decrypt, encrypt};
+use super::Keypair;
+use crate::core::{
+    derive_shared_secret, AesKey, PrivateKey, PublicKey, AES_TAG_LENGTH, SYM_AES_IV_LENGTH,
+};
+
+/// The HKDF salt of the metadata value keys.
+pub const METADATA_KEY_SALT: &[u8] = b"symbol-metadata-v1";
+
+/// The HKDF info of the metadata value IVs.
+pub const METADATA_IV_INFO: &[u8] = b"iv";
+
+/// Encrypt a metadata value deterministically for its scoped key and target.
+///
+/// # Inputs
+///
+/// * `kp`: The Symbol `Keypair` of the metadata source account.
+///
+/// * `scoped_key`: The metadata scoped key.
+///
+/// * `target_pk`: The public key of the metadata target account.
+///
+/// * `value`: The plaintext value.
+///
+/// # Returns
+///
+/// A `Result` whose okay value is the encrypted value `Vec<u8>` or whose error value
+/// is an `failure::Error` describing the error that occurred.
+pub fn encrypt_metadata(
+    kp: &crate::core::Keypair<Keypair>,
+    scoped_key: u64,
+    target_pk: &PublicKey,
+    value: &[u8],
+) -> Result<Vec<u8>> {
+    let key = metadata_key(kp.private_key(), target_pk, scoped_key, target_pk)?;
+
+    let mut iv = [0u8; SYM_AES_IV_LENGTH];
+    Hkdf::<Sha256>::new(Some(key.as_bytes()), value)
+        .expand(METADATA_IV_INFO, &mut iv)
+        .expect("unexpected error in rust hkdf_sha256");
+
+    let (encrypted, auth_tag) = encrypt(iv, key, value)?;
+
+    let mut enc = vec![];
+    enc.extend_from_slice(&auth_tag);
+    enc.extend_from_slice(&iv);
+    enc.extend_from_slice(&encrypted);
+    Ok(enc)
+}
+
+/// Decrypt a metadata value, as its source or its target account.
+///
+/// # Inputs
+///
+/// * `kp`: The Symbol `Keypair` of the metadata source or target account.
+///
+/// * `scoped_key`: The metadata scoped key.
+///
+/// * `source_pk`: The public key of the metadata source account.
+///
+/// * `target_pk`: The public key of the metadata target account.
+///
+/// * `enc_value`: The encrypted value.
+///
+/// # Returns
+///
+/// A `Result` whose okay value is the plaintext value `Vec<u8>` or whose error value
+/// is an `failure::Error` describing the error that occurred.
+pub fn decrypt_metadata(
+    kp: &crate::core::Keypair<Keypair>,
+    scoped_key: u64,
+    source_pk: &PublicKey,
+    target_pk: &PublicKey,
+    enc_value: &[u8],
+) -> Result<Vec<u8>> {
+    ensure!(
+        enc_value.len() >= AES_TAG_LENGTH + SYM_AES_IV_LENGTH,
+        "encrypted value has unexpected size."
+    );
+
+    let peer_pk = if kp.public_key() == *source_pk {
+        target_pk
+    } else if kp.public_key() == *target_pk {
+        source_pk
+    } else {
+        return Err(anyhow!(
+            "keypair is neither the metadata source nor target."
+        ));
+    };
+    let key = metadata_key(kp.private_key(), peer_pk, scoped_key, target_pk)?;
+
+    let mut iv = [0u8; SYM_AES_IV_LENGTH];
+    iv.copy_from_slice(&enc_value[AES_TAG_LENGTH..AES_TAG_LENGTH + SYM_AES_IV_LENGTH]);
+
+    let mut msg_and_tag = Vec::with_capacity(enc_value.len() - SYM_AES_IV_LENGTH);
+    msg_and_tag.extend_from_slice(&enc_value[AES_TAG_LENGTH + SYM_AES_IV_LENGTH..]);
+    msg_and_tag.extend_from_slice(&enc_value[..AES_TAG_LENGTH]);
+
+    decrypt(iv, key, &msg_and_tag)
+}
+
+// internal functions.
+fn metadata_key(
+    secret_key: PrivateKey,
+    peer_pk: &PublicKey,
+    scoped_key: u64,
+    target_pk: &PublicKey,
+) -> Result<AesKey> {
+    peer_pk.to_point()?;
+    let shared = derive_shared_secret::<Sha512>(secret_key, *peer_pk);
+
+    let mut info = scoped_key.to_le_bytes().to_vec();
+    info.extend_from_slice(target_pk.as_bytes());
+
+    let mut key = AesKey::zero();
+    Hkdf::<Sha256>::new(Some(METADATA_KEY_SALT), shared.as_bytes())
+        .expand(&info, key.as_mut())
+        .expect("unexpected error in rust hkdf_sha256");
+    Ok(key)
+}
@@ -0,0 +1,146 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use anyhow::{ensure, Result};
+use sha3::{Digest, Sha3_256};
+
+use crate::core::{PublicKey, H256};
+
+/// Maximum depth of a multisig hierarchy accepted by catapult.
+pub const MULTISIG_MAX_DEPTH: usize = 3;
+
+/// The multisig settings of an account.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MultisigEntry {
+    pub min_approval: u32,
+    pub min_removal: u32,
+    pub cosignatories: Vec<PublicKey>,
+}
+
+impl MultisigEntry {
+    /// Hash of the cosigner set, independent of the cosignatories order.
+    ///
+    /// `SHA3-256(min_approval || min_removal || sorted cosignatories)`, with the numbers
+    /// little endian encoded.
+    ///
+    pub fn hash(&self) -> H256 {
+        let cosignatories: BTreeSet<&PublicKey> = self.cosignatories.iter().collect();
+
+        let mut h = Sha3_256::new();
+        h.update(self.min_approval.to_le_bytes());
+        h.update(self.min_removal.to_le_bytes());
+        for cosignatory in cosignatories {
+            h.update(cosignatory.as_bytes());
+        }
+        H256::from_slice(h.finalize().as_slice())
+    }
+}
+
+/// The kind of operation the cosignatures are collected for.
+///
+/// Removing a cosignatory from an account requires `min_removal` of its cosignatories,
+/// anything else requires `min_approval`.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MultisigOperation {
+    Approval,
+    Removal,
+}
+
+/// The outcome of a cosignature eligibility check.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CosignatureCheck {
+    /// Whether the provided signers satisfy the multisig rules.
+    pub satisfied: bool,
+    /// The (leaf) signers that did not sign yet and can still contribute to an unsatisfied
+    /// level, empty when `satisfied`.
+    pub missing: BTreeSet<PublicKey>,
+}
+
+/// Check whether a set of signers satisfies the multisig rules of an account.
+///
+/// Cosignatories that are multisig accounts themselves are satisfied by their own
+/// cosignatories, following catapult rules, up to `MULTISIG_MAX_DEPTH` levels. The
+/// `operation` threshold only applies to `account`, nested accounts always use `min_approval`.
+///
+/// # Inputs
+///
+/// * `account`: The account the transaction is issued for.
+///
+/// * `multisig`: The multisig settings of `account` and of every nested multisig cosignatory.
+///
+/// * `signers`: The public keys of the provided signatures (signer and cosignatures).
+///
+/// * `operation`: The kind of operation the cosignatures are collected for.
+///
+/// # Returns
+///
+/// A `Result` whose okay value is a `CosignatureCheck` or whose error value
+/// is an `failure::Error` describing the error that occurred.
+pub fn check_cosignatures(
+    account: &PublicKey,
+    multisig: &BTreeMap<PublicKey, MultisigEntry>,
+    signers: &BTreeSet<PublicKey>,
+    operation: MultisigOperation,
+) -> Result<CosignatureCheck> {
+    let (satisfied, missing) = check_level(account, multisig, signers, operation, 0)?;
+
+    Ok(CosignatureCheck { satisfied, missing })
+}
+
+// internal functions.
+fn check_level(
+    account: &PublicKey,
+    multisig: &BTreeMap<PublicKey, MultisigEntry>,
+    signers: &BTreeSet<PublicKey>,
+    operation: MultisigOperation,
+    depth: usize,
+) -> Result<(bool, BTreeSet<PublicKey>)> {
+    let entry = match multisig.get(account) {
+        Some(entry) => entry,
+        None if signers.contains(account) => return Ok((true, BTreeSet::new())),
+        None => return Ok((false, std::iter::once(*account).collect())),
+    };
+
+    ensure!(
+        depth < MULTISIG_MAX_DEPTH,
+        "multisig hierarchy is deeper than {}.",
+        MULTISIG_MAX_DEPTH
+    );
+
+    let min = match operation {
+        MultisigOperation::Approval => entry.min_approval,
+        MultisigOperation::Removal => entry.min_removal,
+    };
+
+    // a cosignatory listed twice approves once.
+    let cosignatories: BTreeSet<&PublicKey> = entry.cosignatories.iter().collect();
+    let mut approvals = 0u32;
+    let mut missing = BTreeSet::new();
+    for cosignatory in cosignatories {
+        let (satisfied, cosignatory_missing) = check_level(
+            cosignatory,
+            multisig,
+            signers,
+            MultisigOperation::Approval,
+            depth + 1,
+        )?;
+        if satisfied {
+            approvals += 1;
+        } else {
+            missing.extend(cosignatory_missing);
+        }
+    }
+
+    let satisfied = approvals >= min;
+    Ok((satisfied, if satisfied { BTreeSet::new() } else { missing }))
+}
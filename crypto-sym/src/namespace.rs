@@ -0,0 +1,115 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Symbol namespace id generation and name validation, following the catapult rules.
+//!
+//! The id of a namespace is the first 8 bytes (little endian) of
+//! `SHA3-256(parent_id || name)`, with the parent id as little endian `u64` (`0` for a root
+//! namespace) and the most significant bit set.
+//!
+//! For documentation on namespaces, please refer to the
+//! [Namespace](https://docs.symbolplatform.com/concepts/namespace.html) guide.
+//!
+
+use std::convert::TryInto;
+
+use anyhow::{ensure, Result};
+use sha3::{Digest, Sha3_256};
+
+/// The maximum number of levels of a namespace full name.
+pub const NAMESPACE_MAX_DEPTH: usize = 3;
+
+/// The maximum size of a namespace level name.
+pub const NAMESPACE_MAX_NAME_SIZE: usize = 64;
+
+/// The bit set in every namespace id.
+pub const NAMESPACE_FLAG: u64 = 1 << 63;
+
+/// Returns `true` if `name` is a valid namespace level name: 1 to `NAMESPACE_MAX_NAME_SIZE`
+/// characters among `a-z`, `0-9`, `-` and `_`, starting with a letter or a digit.
+///
+pub fn is_valid_name(name: &str) -> bool {
+    let bytes = name.as_bytes();
+    !bytes.is_empty()
+        && bytes.len() <= NAMESPACE_MAX_NAME_SIZE
+        && is_alphanumeric(bytes[0])
+        && bytes[1..]
+            .iter()
+            .all(|&c| is_alphanumeric(c) || c == b'-' || c == b'_')
+}
+
+/// Generate the id of a namespace level.
+///
+/// # Inputs
+///
+/// * `parent_id`: The parent namespace id, `0` for a root namespace.
+///
+/// * `name`: The level name.
+///
+/// # Returns
+///
+/// A `Result` whose okay value is the namespace id or whose error value
+/// is an `failure::Error` describing the error that occurred.
+pub fn generate_namespace_id(parent_id: u64, name: &str) -> Result<u64> {
+    ensure!(is_valid_name(name), "invalid namespace name {:?}.", name);
+
+    let mut h = Sha3_256::new();
+    h.update(parent_id.to_le_bytes());
+    h.update(name.as_bytes());
+    let hash = h.finalize();
+
+    Ok(u64::from_le_bytes(hash[..8].try_into().unwrap()) | NAMESPACE_FLAG)
+}
+
+/// Compute the ids of every level of a namespace full name, root first.
+///
+/// # Inputs
+///
+/// * `full_name`: The namespace full name, e.g. `symbol.xym`.
+///
+/// # Returns
+///
+/// A `Result` whose okay value is the ids `Vec<u64>` of the parent chain or whose error value
+/// is an `failure::Error` describing the error that occurred.
+pub fn parent_chain(full_name: &str) -> Result<Vec<u64>> {
+    let names: Vec<&str> = full_name.split('.').collect();
+    ensure!(
+        names.len() <= NAMESPACE_MAX_DEPTH,
+        "namespace {:?} has {} levels, at most {} are allowed.",
+        full_name,
+        names.len(),
+        NAMESPACE_MAX_DEPTH
+    );
+
+    let mut ids = Vec::with_capacity(names.len());
+    let mut parent_id = 0;
+    for name in names {
+        parent_id = generate_namespace_id(parent_id, name)?;
+        ids.push(parent_id);
+    }
+    Ok(ids)
+}
+
+/// Compute the id of a namespace full name.
+///
+/// # Inputs
+///
+/// * `full_name`: The namespace full name, e.g. `symbol.xym`.
+///
+/// # Returns
+///
+/// A `Result` whose okay value is the namespace id or whose error value
+/// is an `failure::Error` describing the error that occurred.
+pub fn id_from_full_name(full_name: &str) -> Result<u64> {
+    Ok(*parent_chain(full_name)?.last().unwrap())
+}
+
+// internal functions.
+fn is_alphanumeric(c: u8) -> bool {
+    c.is_ascii_lowercase() || c.is_ascii_digit()
+}
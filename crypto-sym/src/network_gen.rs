@@ -0,0 +1,196 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Nemesis block signing, for private network bootstrap tooling.
+//!
+//! A catapult block header starts with the verifiable entity header
+//! `size (u32) || reserved (u32) || signature || signer || reserved (u32)`, followed by
+//! `version (u8) || network (u8) || type (u16) || height (u64) || ...`. The signer signs the
+//! header from `version` to the end of the block footer (the transactions are committed by the
+//! transactions hash), and the block hash is
+//! `SHA3-256(signature[..32] || signer || signed data)`.
+//!
+//! catapult takes any 32 bytes as generation hash seed; `NemesisSigner` derives it from the
+//! nemesis configuration so that running the tooling again gives the same network.
+//!
+
+use anyhow::{bail, ensure, Result};
+use sha3::{Digest, Sha3_256};
+
+use super::{GenerationHash, Keypair};
+use crate::core::{KeyPairSchema, PublicKey, Signature, H256, KEY_BYTES_SIZE, SIGNATURE_LENGTH};
+
+/// Size of the verifiable entity header preceding the signed data of a block.
+pub const VERIFIABLE_ENTITY_HEADER_SIZE: usize = 4 + 4 + SIGNATURE_LENGTH + KEY_BYTES_SIZE + 4;
+
+/// Size of the header of a normal block, with its padding footer.
+pub const NORMAL_BLOCK_HEADER_SIZE: usize = 376;
+
+/// Size of the header of a nemesis or importance block, with its importance footer.
+pub const IMPORTANCE_BLOCK_HEADER_SIZE: usize = 424;
+
+/// The nemesis block type.
+pub const NEMESIS_BLOCK_TYPE: u16 = 0x8043;
+
+/// The normal block type.
+pub const NORMAL_BLOCK_TYPE: u16 = 0x8143;
+
+/// The importance block type.
+pub const IMPORTANCE_BLOCK_TYPE: u16 = 0x8243;
+
+const SIGNATURE_OFFSET: usize = 8;
+const SIGNER_OFFSET: usize = SIGNATURE_OFFSET + SIGNATURE_LENGTH;
+const NETWORK_OFFSET: usize = VERIFIABLE_ENTITY_HEADER_SIZE + 1;
+const TYPE_OFFSET: usize = VERIFIABLE_ENTITY_HEADER_SIZE + 2;
+const HEIGHT_OFFSET: usize = VERIFIABLE_ENTITY_HEADER_SIZE + 4;
+
+/// Signs the nemesis block of a private network.
+///
+pub struct NemesisSigner<'a> {
+    keypair: &'a crate::core::Keypair<Keypair>,
+    network: u8,
+    epoch_adjustment: u64,
+}
+
+impl<'a> NemesisSigner<'a> {
+    /// Create a nemesis signer.
+    ///
+    /// # Inputs
+    ///
+    /// * `keypair`: The nemesis signer keypair.
+    ///
+    /// * `network`: The network byte, e.g. `0x98` for a testnet.
+    ///
+    /// * `epoch_adjustment`: The network epoch, in seconds since the unix epoch.
+    ///
+    pub fn new(
+        keypair: &'a crate::core::Keypair<Keypair>,
+        network: u8,
+        epoch_adjustment: u64,
+    ) -> Self {
+        Self {
+            keypair,
+            network,
+            epoch_adjustment,
+        }
+    }
+
+    /// The nemesis signer public key.
+    ///
+    pub fn public_key(&self) -> PublicKey {
+        self.keypair.public_key()
+    }
+
+    /// The generation hash seed of the network:
+    /// `SHA3-256(signer || network || epoch adjustment (u64))`.
+    ///
+    pub fn generation_hash_seed(&self) -> GenerationHash {
+        let mut h = Sha3_256::new();
+        h.update(self.keypair.public_key().as_bytes());
+        h.update([self.network]);
+        h.update(self.epoch_adjustment.to_le_bytes());
+        GenerationHash::new(H256::from_slice(h.finalize().as_slice()))
+    }
+
+    /// Sign a nemesis block, writing the signature and the signer public key in its header.
+    ///
+    /// # Inputs
+    ///
+    /// * `block`: The serialized nemesis block, its transactions included or not.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the block hash `H256` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn sign_block_header(&self, block: &mut [u8]) -> Result<H256> {
+        let header_size = block_header_size(block)?;
+        ensure!(
+            block_type(block) == NEMESIS_BLOCK_TYPE,
+            "block type {:#06X} is not a nemesis block.",
+            block_type(block)
+        );
+        ensure!(
+            block[NETWORK_OFFSET] == self.network,
+            "block network {:#04X} does not match the nemesis network {:#04X}.",
+            block[NETWORK_OFFSET],
+            self.network
+        );
+        ensure!(block_height(block) == 1, "nemesis block height must be 1.");
+
+        let signature = self
+            .keypair
+            .try_sign(&block[VERIFIABLE_ENTITY_HEADER_SIZE..header_size])?;
+        block[SIGNATURE_OFFSET..SIGNER_OFFSET].copy_from_slice(signature.as_bytes());
+        block[SIGNER_OFFSET..SIGNER_OFFSET + KEY_BYTES_SIZE]
+            .copy_from_slice(self.keypair.public_key().as_bytes());
+
+        block_hash(block)
+    }
+}
+
+/// The hash of a signed block.
+///
+/// # Returns
+///
+/// A `Result` whose okay value is the block hash `H256` or whose error value
+/// is an `failure::Error` describing the error that occurred.
+pub fn block_hash(block: &[u8]) -> Result<H256> {
+    let header_size = block_header_size(block)?;
+
+    let mut h = Sha3_256::new();
+    h.update(&block[SIGNATURE_OFFSET..SIGNATURE_OFFSET + SIGNATURE_LENGTH / 2]);
+    h.update(&block[SIGNER_OFFSET..SIGNER_OFFSET + KEY_BYTES_SIZE]);
+    h.update(&block[VERIFIABLE_ENTITY_HEADER_SIZE..header_size]);
+    Ok(H256::from_slice(h.finalize().as_slice()))
+}
+
+/// Verify the signature of a block header against its signer public key.
+///
+/// # Returns
+///
+/// Returns `Ok` if the signature is valid.
+pub fn verify_block_header(block: &[u8]) -> Result<()> {
+    let header_size = block_header_size(block)?;
+    let signer = PublicKey::from_slice(&block[SIGNER_OFFSET..SIGNER_OFFSET + KEY_BYTES_SIZE]);
+    let signature = Signature::from_slice(&block[SIGNATURE_OFFSET..SIGNER_OFFSET]);
+
+    Keypair::from_null_private_key(signer).verify(
+        &block[VERIFIABLE_ENTITY_HEADER_SIZE..header_size],
+        signature,
+    )
+}
+
+// internal functions.
+fn block_header_size(block: &[u8]) -> Result<usize> {
+    ensure!(
+        block.len() >= HEIGHT_OFFSET,
+        "block has unexpected size {}.",
+        block.len()
+    );
+    let header_size = match block_type(block) {
+        NORMAL_BLOCK_TYPE => NORMAL_BLOCK_HEADER_SIZE,
+        NEMESIS_BLOCK_TYPE | IMPORTANCE_BLOCK_TYPE => IMPORTANCE_BLOCK_HEADER_SIZE,
+        other => bail!("unknown block type {:#06X}.", other),
+    };
+    ensure!(
+        block.len() >= header_size,
+        "block has unexpected size {}.",
+        block.len()
+    );
+    Ok(header_size)
+}
+
+fn block_type(block: &[u8]) -> u16 {
+    u16::from_le_bytes([block[TYPE_OFFSET], block[TYPE_OFFSET + 1]])
+}
+
+fn block_height(block: &[u8]) -> u64 {
+    let mut height = [0u8; 8];
+    height.copy_from_slice(&block[HEIGHT_OFFSET..HEIGHT_OFFSET + 8]);
+    u64::from_le_bytes(height)
+}
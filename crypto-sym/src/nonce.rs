@@ -0,0 +1,59 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The deterministic nonce of Symbol signatures, as standalone functions for audits and fuzzing.
+//!
+//! The nonce prefix is the upper half of `SHA-512(private key)`, and the nonce of a message is
+//! `r = SHA-512(nonce prefix || message) mod l`. The first half of a signature is the nonce
+//! commitment `R = r * B`. `Keypair::sign` derives its nonce with these functions.
+//!
+
+use sha2::{Digest, Sha512};
+
+use crate::core::curve25519::{constants, scalar::Scalar};
+use crate::core::{PrivateKey, KEY_BYTES_SIZE};
+
+/// The nonce prefix of a private key, the upper half of its SHA-512 hash.
+///
+pub fn nonce_prefix(private_key: &PrivateKey) -> [u8; KEY_BYTES_SIZE] {
+    let hash = Sha512::digest(private_key.as_bytes());
+    let mut prefix = [0u8; KEY_BYTES_SIZE];
+    prefix.copy_from_slice(&hash[KEY_BYTES_SIZE..]);
+    prefix
+}
+
+/// The nonce `r` of a message, reduced modulo the group order, little endian.
+///
+pub fn derive_nonce(nonce_prefix: &[u8; KEY_BYTES_SIZE], message: &[u8]) -> [u8; KEY_BYTES_SIZE] {
+    derive_nonce_vectored(nonce_prefix, &[message])
+}
+
+/// The nonce `r` of the concatenation of the message parts, see `derive_nonce`.
+///
+pub fn derive_nonce_vectored(
+    nonce_prefix: &[u8; KEY_BYTES_SIZE],
+    parts: &[&[u8]],
+) -> [u8; KEY_BYTES_SIZE] {
+    nonce_scalar(nonce_prefix, parts).to_bytes()
+}
+
+/// The nonce commitment `R = r * B`, compressed, the first half of the signature.
+///
+pub fn nonce_commitment(nonce: &[u8; KEY_BYTES_SIZE]) -> [u8; KEY_BYTES_SIZE] {
+    (&Scalar::from_bytes_mod_order(*nonce) * &constants::ED25519_BASEPOINT_TABLE)
+        .compress()
+        .to_bytes()
+}
+
+// internal functions.
+pub(crate) fn nonce_scalar(nonce_prefix: &[u8; KEY_BYTES_SIZE], parts: &[&[u8]]) -> Scalar {
+    let mut h = Sha512::new();
+    h.update(nonce_prefix);
+    parts.iter().for_each(|part| h.update(part));
+    Scalar::from_hash(h)
+}
@@ -0,0 +1,223 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! AES-GCM packet encryption for peer connections, keyed by the `handshake`.
+//!
+//! Both ends derive one key per direction with HKDF-SHA256 from the shared secret of the node
+//! keys, salted with `PEER_CRYPTO_SALT || server challenge || client challenge`, so every
+//! connection gets fresh keys. Packets of a direction are numbered from zero and the nonce of a
+//! packet is its number (`u64` little endian, zero padded to 12 bytes): it is never sent, so
+//! a replayed, dropped or reordered packet fails to decrypt.
+//!
+//! An encrypted packet is `size (u32) || tag || ciphertext`, with `size` the length of the whole
+//! encrypted packet, bounded by the maximum packet size so that a reader never allocates more
+//! than that before authenticating a packet.
+//!
+//! catapult nodes protect their connections with TLS: this framing is for connections between
+//! peers built on this crate.
+//!
+
+use std::convert::TryInto;
+use std::fmt;
+
+use anyhow::{anyhow, ensure, Result};
+use hkdf::Hkdf;
+use sha2::{Sha256, Sha512};
+
+use super::cipher::{decrypt, encrypt};
+use super::handshake::{ServerChallengeRequest, ServerChallengeResponse};
+use super::Keypair;
+use crate::core::{derive_shared_secret, AesKey, PublicKey, AES_TAG_LENGTH, SYM_AES_IV_LENGTH};
+
+/// The default maximum size of an encrypted packet.
+pub const PEER_MAX_PACKET_SIZE: usize = 150 * 1024 * 1024;
+
+/// Size of the header of an encrypted packet: `size (u32) || tag`.
+pub const PEER_PACKET_HEADER_SIZE: usize = 4 + AES_TAG_LENGTH;
+
+const PEER_CRYPTO_SALT: &[u8] = b"symbol-peer-crypto";
+const CLIENT_TO_SERVER_INFO: &[u8] = b"client-to-server";
+const SERVER_TO_CLIENT_INFO: &[u8] = b"server-to-client";
+
+/// The side of the connection.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PeerRole {
+    /// The end that connected and answered the server challenge.
+    Client,
+    /// The end that accepted the connection and sent the server challenge.
+    Server,
+}
+
+/// The packet encryption state of one end of a connection.
+///
+/// It is neither `Clone` nor `PartialEq`: two copies would encrypt with the same key and
+/// counter, reusing AES-GCM nonces. `Debug` prints the counters only.
+///
+pub struct PeerCipher {
+    send_key: AesKey,
+    send_counter: u64,
+    recv_key: AesKey,
+    recv_counter: u64,
+    max_packet_size: usize,
+}
+
+impl PeerCipher {
+    /// Derive the packet keys of a connection after a successful handshake.
+    ///
+    /// # Inputs
+    ///
+    /// * `kp`: The local node `Keypair`.
+    ///
+    /// * `peer_pk`: The peer node public key, verified by the handshake.
+    ///
+    /// * `request`: The server challenge of the handshake.
+    ///
+    /// * `response`: The client answer of the handshake.
+    ///
+    /// * `role`: The side of the connection of `kp`.
+    ///
+    pub fn new(
+        kp: &crate::core::Keypair<Keypair>,
+        peer_pk: &PublicKey,
+        request: &ServerChallengeRequest,
+        response: &ServerChallengeResponse,
+        role: PeerRole,
+    ) -> Self {
+        let shared_secret = derive_shared_secret::<Sha512>(kp.private_key(), *peer_pk);
+
+        let salt = [PEER_CRYPTO_SALT, &request.challenge, &response.challenge].concat();
+        let h = Hkdf::<Sha256>::new(Some(&salt), shared_secret.as_bytes());
+        let client_to_server = hkdf_expand(&h, CLIENT_TO_SERVER_INFO);
+        let server_to_client = hkdf_expand(&h, SERVER_TO_CLIENT_INFO);
+
+        let (send_key, recv_key) = match role {
+            PeerRole::Client => (client_to_server, server_to_client),
+            PeerRole::Server => (server_to_client, client_to_server),
+        };
+
+        Self {
+            send_key,
+            send_counter: 0,
+            recv_key,
+            recv_counter: 0,
+            max_packet_size: PEER_MAX_PACKET_SIZE,
+        }
+    }
+
+    /// Set the maximum size of the encrypted packets, sent or received.
+    ///
+    pub fn with_max_packet_size(mut self, max_packet_size: usize) -> Self {
+        self.max_packet_size = max_packet_size;
+        self
+    }
+
+    /// Encrypt the next packet to send.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the encrypted packet `Vec<u8>` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn encode(&mut self, packet: &[u8]) -> Result<Vec<u8>> {
+        let size = PEER_PACKET_HEADER_SIZE + packet.len();
+        self.check_size(size)?;
+
+        let nonce = counter_nonce(self.send_counter);
+        self.send_counter = self
+            .send_counter
+            .checked_add(1)
+            .ok_or_else(|| anyhow!("peer sending counter is exhausted."))?;
+        let (encrypted, tag) = encrypt(nonce, self.send_key, packet)?;
+
+        let mut bytes = Vec::with_capacity(size);
+        bytes.extend_from_slice(&(size as u32).to_le_bytes());
+        bytes.extend_from_slice(&tag);
+        bytes.extend_from_slice(&encrypted);
+        Ok(bytes)
+    }
+
+    /// The size of an incoming encrypted packet from its first 4 bytes, to read it from a
+    /// stream.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the size of the whole encrypted packet or whose error
+    /// value is an `failure::Error` describing the error that occurred.
+    pub fn packet_size(&self, header: &[u8]) -> Result<usize> {
+        ensure!(header.len() >= 4, "packet header has unexpected size.");
+        let size = u32::from_le_bytes(header[..4].try_into()?) as usize;
+        self.check_size(size)?;
+        Ok(size)
+    }
+
+    /// Decrypt the next received packet.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the decrypted packet `Vec<u8>` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn decode(&mut self, bytes: &[u8]) -> Result<Vec<u8>> {
+        let size = self.packet_size(bytes)?;
+        ensure!(
+            bytes.len() == size,
+            "packet has unexpected size {}.",
+            bytes.len()
+        );
+
+        let mut encrypted = Vec::with_capacity(size - 4);
+        encrypted.extend_from_slice(&bytes[PEER_PACKET_HEADER_SIZE..]);
+        encrypted.extend_from_slice(&bytes[4..PEER_PACKET_HEADER_SIZE]);
+
+        let packet = decrypt(counter_nonce(self.recv_counter), self.recv_key, &encrypted)?;
+        self.recv_counter = self
+            .recv_counter
+            .checked_add(1)
+            .ok_or_else(|| anyhow!("peer receiving counter is exhausted."))?;
+        Ok(packet)
+    }
+
+    // internal functions.
+    fn check_size(&self, size: usize) -> Result<()> {
+        ensure!(
+            size >= PEER_PACKET_HEADER_SIZE,
+            "packet has unexpected size {}.",
+            size
+        );
+        ensure!(
+            size <= self.max_packet_size && size <= u32::MAX as usize,
+            "packet size {} exceeds the maximum packet size {}.",
+            size,
+            self.max_packet_size
+        );
+        Ok(())
+    }
+}
+
+impl fmt::Debug for PeerCipher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PeerCipher")
+            .field("send_counter", &self.send_counter)
+            .field("recv_counter", &self.recv_counter)
+            .field("max_packet_size", &self.max_packet_size)
+            .finish()
+    }
+}
+
+// internal functions.
+fn counter_nonce(counter: u64) -> [u8; SYM_AES_IV_LENGTH] {
+    let mut nonce = [0u8; SYM_AES_IV_LENGTH];
+    nonce[..8].copy_from_slice(&counter.to_le_bytes());
+    nonce
+}
+
+fn hkdf_expand(h: &Hkdf<Sha256>, info: &[u8]) -> AesKey {
+    let mut out = AesKey::zero();
+    h.expand(info, out.as_mut())
+        .expect("unexpected error in rust hkdf_sha256");
+    out
+}
@@ -0,0 +1,50 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Per-purpose keys derived from a single master private key.
+//!
+//! Every purpose key is `HKDF-SHA256(salt = PURPOSE_KEY_SALT, ikm = master, info = purpose)`,
+//! so only the master key needs a backup while the derived keys, and the accounts of their
+//! public keys, cannot be linked to each other without it.
+//!
+
+use hkdf::Hkdf;
+use sha2::Sha256;
+
+use crate::core::{PrivateKey, KEY_BYTES_SIZE};
+
+/// The HKDF salt separating purpose keys from any other use of the master key.
+pub const PURPOSE_KEY_SALT: &[u8] = b"symbol-purpose-key";
+
+/// The purpose label of harvesting (remote) keys.
+pub const PURPOSE_HARVEST: &str = "harvest";
+
+/// The purpose label of message encryption keys.
+pub const PURPOSE_MESSAGING: &str = "messaging";
+
+/// The purpose label of API request signing keys.
+pub const PURPOSE_API: &str = "api";
+
+/// Derive the deterministic private key of a purpose from a master private key.
+///
+/// # Inputs
+///
+/// * `master_sk`: The master private key.
+///
+/// * `purpose`: The purpose label, e.g. `PURPOSE_HARVEST`; any label is accepted.
+///
+/// # Returns
+///
+/// The purpose `PrivateKey`.
+pub fn derive_purpose_key(master_sk: &PrivateKey, purpose: &str) -> PrivateKey {
+    let h = Hkdf::<Sha256>::new(Some(PURPOSE_KEY_SALT), master_sk.as_bytes());
+    let mut out = [0u8; KEY_BYTES_SIZE];
+    h.expand(purpose.as_bytes(), &mut out)
+        .expect("unexpected error in rust hkdf_sha256");
+    PrivateKey::from(out)
+}
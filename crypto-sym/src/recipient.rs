@@ -0,0 +1,296 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The 24 byte recipient field of catapult transactions (`UnresolvedAddress`).
+//!
+//! A recipient is either an address or a namespace alias resolved by the network:
+//!
+//! * address: `network || RIPEMD-160(SHA3-256(public key)) || checksum`, the checksum being the
+//!   first 3 bytes of `SHA3-256` of the first 21 bytes. Its text form is the base32 of the
+//!   address padded with a zero byte to 25 bytes, without the last character (39 characters);
+//! * alias: `network | RECIPIENT_ALIAS_FLAG || namespace id (little endian) || 15 zero bytes`.
+//!
+//! Network bytes are even (e.g. `0x68` for mainnet, `0x98` for testnet), so the lowest bit of
+//! the first byte tells the forms apart.
+//!
+
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::{anyhow, ensure, Result};
+use ripemd::Ripemd160;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+
+use super::namespace::{id_from_full_name, NAMESPACE_FLAG};
+use crate::core::PublicKey;
+
+/// The size of a recipient field.
+pub const RECIPIENT_SIZE: usize = 24;
+
+/// The size of an address text form.
+pub const ADDRESS_STR_SIZE: usize = 39;
+
+/// The bit set in the first byte of a namespace alias recipient.
+pub const RECIPIENT_ALIAS_FLAG: u8 = 0x01;
+
+const ADDRESS_CHECKSUM_OFFSET: usize = 21;
+const ALIAS_ID_END: usize = 9;
+const BASE32_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+/// A Symbol address, with a valid checksum.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct Address([u8; RECIPIENT_SIZE]);
+
+/// The decoded recipient field of a transaction.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub enum Recipient {
+    Address(Address),
+    /// A namespace linked to an address by an alias transaction.
+    Alias {
+        network: u8,
+        namespace_id: u64,
+    },
+}
+
+impl Address {
+    /// The address of a public key.
+    ///
+    /// # Inputs
+    ///
+    /// * `network`: The network byte, e.g. `0x68` for mainnet.
+    ///
+    /// * `public_key`: The account public key.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the `Address` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn from_public_key(network: u8, public_key: &PublicKey) -> Result<Self> {
+        ensure!(
+            network & RECIPIENT_ALIAS_FLAG == 0,
+            "network byte {:#04X} has the namespace alias flag set.",
+            network
+        );
+
+        let mut address = [0u8; RECIPIENT_SIZE];
+        address[0] = network;
+        address[1..ADDRESS_CHECKSUM_OFFSET]
+            .copy_from_slice(&Ripemd160::digest(Sha3_256::digest(public_key.as_bytes())));
+        let checksum = address_checksum(&address[..ADDRESS_CHECKSUM_OFFSET]);
+        address[ADDRESS_CHECKSUM_OFFSET..].copy_from_slice(&checksum);
+        Ok(Self(address))
+    }
+
+    /// Construct an `Address` from its 24 bytes.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the `Address` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        ensure!(
+            bytes.len() == RECIPIENT_SIZE,
+            "address has unexpected size {}.",
+            bytes.len()
+        );
+        ensure!(
+            bytes[0] & RECIPIENT_ALIAS_FLAG == 0,
+            "address has the namespace alias flag set."
+        );
+        ensure!(
+            address_checksum(&bytes[..ADDRESS_CHECKSUM_OFFSET]) == bytes[ADDRESS_CHECKSUM_OFFSET..],
+            "address has an invalid checksum."
+        );
+
+        let mut address = [0u8; RECIPIENT_SIZE];
+        address.copy_from_slice(bytes);
+        Ok(Self(address))
+    }
+
+    /// The network byte of this address.
+    ///
+    pub fn network(&self) -> u8 {
+        self.0[0]
+    }
+
+    /// View this address as a byte array.
+    ///
+    pub fn as_bytes(&self) -> &[u8; RECIPIENT_SIZE] {
+        &self.0
+    }
+}
+
+impl Recipient {
+    /// The namespace alias recipient of a namespace full name.
+    ///
+    /// # Inputs
+    ///
+    /// * `network`: The network byte, e.g. `0x68` for mainnet.
+    ///
+    /// * `full_name`: The namespace full name, e.g. `symbol.xym`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the alias `Recipient` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn alias(network: u8, full_name: &str) -> Result<Self> {
+        ensure!(
+            network & RECIPIENT_ALIAS_FLAG == 0,
+            "network byte {:#04X} has the namespace alias flag set.",
+            network
+        );
+        Ok(Recipient::Alias {
+            network,
+            namespace_id: id_from_full_name(full_name)?,
+        })
+    }
+
+    /// Decode a recipient field.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the `Recipient` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        ensure!(
+            bytes.len() == RECIPIENT_SIZE,
+            "recipient has unexpected size {}.",
+            bytes.len()
+        );
+        if bytes[0] & RECIPIENT_ALIAS_FLAG == 0 {
+            return Ok(Recipient::Address(Address::from_bytes(bytes)?));
+        }
+
+        ensure!(
+            bytes[ALIAS_ID_END..].iter().all(|&b| b == 0),
+            "namespace alias recipient has a non zero padding."
+        );
+        let mut id = [0u8; 8];
+        id.copy_from_slice(&bytes[1..ALIAS_ID_END]);
+        let namespace_id = u64::from_le_bytes(id);
+        ensure!(
+            namespace_id & NAMESPACE_FLAG != 0,
+            "namespace alias recipient has an invalid namespace id {:016X}.",
+            namespace_id
+        );
+
+        Ok(Recipient::Alias {
+            network: bytes[0] & !RECIPIENT_ALIAS_FLAG,
+            namespace_id,
+        })
+    }
+
+    /// Encode the recipient field.
+    ///
+    pub fn to_bytes(&self) -> [u8; RECIPIENT_SIZE] {
+        match self {
+            Recipient::Address(address) => address.0,
+            Recipient::Alias {
+                network,
+                namespace_id,
+            } => {
+                let mut bytes = [0u8; RECIPIENT_SIZE];
+                bytes[0] = network | RECIPIENT_ALIAS_FLAG;
+                bytes[1..ALIAS_ID_END].copy_from_slice(&namespace_id.to_le_bytes());
+                bytes
+            }
+        }
+    }
+
+    /// The network byte of the recipient.
+    ///
+    pub fn network(&self) -> u8 {
+        match self {
+            Recipient::Address(address) => address.network(),
+            Recipient::Alias { network, .. } => *network,
+        }
+    }
+}
+
+impl From<Address> for Recipient {
+    fn from(address: Address) -> Self {
+        Recipient::Address(address)
+    }
+}
+
+impl FromStr for Address {
+    type Err = anyhow::Error;
+
+    /// Parse the 39 character base32 text form, dashes allowed.
+    fn from_str(s: &str) -> Result<Self> {
+        let text: Vec<u8> = s.bytes().filter(|&c| c != b'-').collect();
+        ensure!(
+            text.len() == ADDRESS_STR_SIZE,
+            "address {:?} has unexpected size.",
+            s
+        );
+
+        // 39 characters and a padding `A` are the 25 bytes of the zero padded address.
+        let mut bits = 0u64;
+        let mut count = 0;
+        let mut bytes = Vec::with_capacity(RECIPIENT_SIZE + 1);
+        for &c in text.iter().chain(b"A") {
+            let value = BASE32_ALPHABET
+                .iter()
+                .position(|&a| a == c.to_ascii_uppercase())
+                .ok_or_else(|| anyhow!("address {:?} is not base32.", s))?;
+            bits = (bits << 5) | value as u64;
+            count += 5;
+            if count >= 8 {
+                count -= 8;
+                bytes.push((bits >> count) as u8);
+            }
+        }
+        ensure!(
+            bytes[RECIPIENT_SIZE] == 0,
+            "address {:?} is not canonical.",
+            s
+        );
+
+        Address::from_bytes(&bytes[..RECIPIENT_SIZE])
+    }
+}
+
+impl fmt::Display for Address {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut bits = 0u64;
+        let mut count = 0;
+        let mut text = String::with_capacity(ADDRESS_STR_SIZE + 1);
+        for &byte in self.0.iter().chain(&[0]) {
+            bits = (bits << 8) | byte as u64;
+            count += 8;
+            while count >= 5 {
+                count -= 5;
+                text.push(BASE32_ALPHABET[((bits >> count) & 0x1F) as usize] as char);
+            }
+        }
+        f.write_str(&text[..ADDRESS_STR_SIZE])
+    }
+}
+
+// internal functions.
+fn address_checksum(network_and_hash: &[u8]) -> [u8; RECIPIENT_SIZE - ADDRESS_CHECKSUM_OFFSET] {
+    let hash = Sha3_256::digest(network_and_hash);
+    let mut checksum = [0u8; RECIPIENT_SIZE - ADDRESS_CHECKSUM_OFFSET];
+    checksum.copy_from_slice(&hash[..RECIPIENT_SIZE - ADDRESS_CHECKSUM_OFFSET]);
+    checksum
+}
@@ -0,0 +1,248 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Signed key value strings, e.g. to authenticate the admin REST calls of a node.
+//!
+//! A `KeyValueSigner` holds ordered `key: value` entries, `for_request` those of a REST call
+//! (`method` and `path`). Signing appends the `timestamp` entry and signs
+//! `REQUEST_AUTH_TAG || canonical string`, the entries joined as `key:value` lines. The
+//! `Authorization` header carries the signer, the timestamp and the signature:
+//!
+//! `Symbol-Ed25519 publicKey=<hex>, timestamp=<unix seconds>, signature=<hex>`
+//!
+//! The verifier rebuilds the entries from the request it received and accepts timestamps
+//! within `max_skew` seconds of its own clock, in both directions.
+//!
+
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail, ensure, Result};
+
+use super::Keypair;
+use crate::core::{KeyPairSchema, PublicKey, Signature, KEY_BYTES_SIZE, SIGNATURE_LENGTH};
+
+/// The prefix of every signed key value string.
+pub const REQUEST_AUTH_TAG: &[u8] = b"symbol-crypto-core/request-auth/v1\0";
+
+/// The scheme of the `Authorization` header.
+pub const REQUEST_AUTH_SCHEME: &str = "Symbol-Ed25519";
+
+/// Ordered key value entries to sign.
+///
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct KeyValueSigner {
+    entries: Vec<(String, String)>,
+}
+
+/// The parsed `Authorization` header of a signed request.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Authorization {
+    pub public_key: PublicKey,
+    /// Unix timestamp (seconds) of the signature.
+    pub timestamp: u64,
+    pub signature: Signature,
+}
+
+impl KeyValueSigner {
+    /// An empty set of entries.
+    ///
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The entries of a REST call, `method` (upper case) and `path`.
+    ///
+    /// # Inputs
+    ///
+    /// * `method`: The HTTP method, e.g. `POST`.
+    ///
+    /// * `path`: The request path with its query string, e.g. `/node/unlockedaccount`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the `KeyValueSigner` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn for_request(method: &str, path: &str) -> Result<Self> {
+        let mut signer = Self::new();
+        signer.push("method", &method.to_ascii_uppercase())?;
+        signer.push("path", path)?;
+        Ok(signer)
+    }
+
+    /// Append an entry.
+    ///
+    /// Keys are lower case, non empty and without `:`, values without line breaks, so the
+    /// canonical string has a single reading. `timestamp` is reserved for the signature.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok` if the entry is valid.
+    pub fn push(&mut self, key: &str, value: &str) -> Result<()> {
+        check_entry(key, value)?;
+        ensure!(key != "timestamp", "key timestamp is reserved.");
+        self.entries.push((key.to_string(), value.to_string()));
+        Ok(())
+    }
+
+    /// The canonical string signed at `timestamp`, `key:value` lines ending with the
+    /// `timestamp` entry.
+    ///
+    pub fn canonical_string(&self, timestamp: u64) -> String {
+        let mut canonical = String::new();
+        for (key, value) in &self.entries {
+            canonical.push_str(key);
+            canonical.push(':');
+            canonical.push_str(value);
+            canonical.push('\n');
+        }
+        canonical.push_str("timestamp:");
+        canonical.push_str(&timestamp.to_string());
+        canonical
+    }
+
+    /// Sign the entries.
+    ///
+    /// # Inputs
+    ///
+    /// * `kp`: The signer `Keypair`.
+    ///
+    /// * `timestamp`: The current unix timestamp (seconds).
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the `Authorization` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn sign(
+        &self,
+        kp: &crate::core::Keypair<Keypair>,
+        timestamp: u64,
+    ) -> Result<Authorization> {
+        Ok(Authorization {
+            public_key: kp.public_key(),
+            timestamp,
+            signature: kp.try_sign(&self.message(timestamp))?,
+        })
+    }
+
+    /// Verify an authorization of the entries.
+    ///
+    /// # Inputs
+    ///
+    /// * `authorization`: The `Authorization` received with the request.
+    ///
+    /// * `now`: The current unix timestamp (seconds).
+    ///
+    /// * `max_skew`: The largest accepted difference, in seconds, between `now` and the
+    ///   signature timestamp.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the signer `PublicKey` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn verify(
+        &self,
+        authorization: &Authorization,
+        now: u64,
+        max_skew: u64,
+    ) -> Result<PublicKey> {
+        let timestamp = authorization.timestamp;
+        ensure!(
+            timestamp <= now.saturating_add(max_skew) && now <= timestamp.saturating_add(max_skew),
+            "authorization timestamp {} is out of the accepted window.",
+            timestamp
+        );
+        Keypair::from_null_private_key(authorization.public_key)
+            .verify(&self.message(timestamp), authorization.signature)
+            .map_err(|_| anyhow!("authorization signature is not valid."))?;
+        Ok(authorization.public_key)
+    }
+
+    // internal functions.
+    fn message(&self, timestamp: u64) -> Vec<u8> {
+        [REQUEST_AUTH_TAG, self.canonical_string(timestamp).as_bytes()].concat()
+    }
+}
+
+impl fmt::Display for Authorization {
+    /// The `Authorization` header value.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} publicKey={:X}, timestamp={}, signature={:X}",
+            REQUEST_AUTH_SCHEME, self.public_key, self.timestamp, self.signature
+        )
+    }
+}
+
+impl FromStr for Authorization {
+    type Err = anyhow::Error;
+
+    /// Parse an `Authorization` header value.
+    fn from_str(s: &str) -> Result<Self> {
+        let params = s
+            .trim()
+            .strip_prefix(REQUEST_AUTH_SCHEME)
+            .filter(|params| params.starts_with(' '))
+            .ok_or_else(|| anyhow!("authorization scheme is not {}.", REQUEST_AUTH_SCHEME))?;
+
+        let (mut public_key, mut timestamp, mut signature) = (None, None, None);
+        for param in params.split(',') {
+            let (name, value) = param
+                .trim()
+                .split_once('=')
+                .ok_or_else(|| anyhow!("authorization parameter {:?} has no value.", param))?;
+            let slot = match name {
+                "publicKey" => &mut public_key,
+                "timestamp" => &mut timestamp,
+                "signature" => &mut signature,
+                _ => bail!("unknown authorization parameter {:?}.", name),
+            };
+            ensure!(slot.is_none(), "duplicated authorization parameter {:?}.", name);
+            *slot = Some(value);
+        }
+
+        let missing = |name: &str| anyhow!("authorization has no {} parameter.", name);
+        let public_key = public_key.ok_or_else(|| missing("publicKey"))?;
+        let timestamp = timestamp.ok_or_else(|| missing("timestamp"))?;
+        let signature = signature.ok_or_else(|| missing("signature"))?;
+        ensure!(
+            public_key.len() == 2 * KEY_BYTES_SIZE && signature.len() == 2 * SIGNATURE_LENGTH,
+            "authorization key or signature has unexpected size."
+        );
+
+        Ok(Self {
+            public_key: PublicKey::from_str(public_key)
+                .map_err(|_| anyhow!("authorization public key is not hex."))?,
+            timestamp: timestamp
+                .parse()
+                .map_err(|_| anyhow!("authorization timestamp is not a number."))?,
+            signature: Signature::from_str(signature)
+                .map_err(|_| anyhow!("authorization signature is not hex."))?,
+        })
+    }
+}
+
+// internal functions.
+fn check_entry(key: &str, value: &str) -> Result<()> {
+    ensure!(
+        !key.is_empty()
+            && key
+                .bytes()
+                .all(|c| c.is_ascii_graphic() && !c.is_ascii_uppercase() && c != b':'),
+        "key {:?} is not a lower case token.",
+        key
+    );
+    ensure!(
+        !value.contains(['\n', '\r']),
+        "value of {:?} has a line break.",
+        key
+    );
+    Ok(())
+}
@@ -0,0 +1,292 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Messaging sessions with ratcheting message keys.
+//!
+//! A `Session` derives two HKDF chains (one per direction) from the static shared secret of
+//! both accounts and a random session id. Every message advances the sending chain, and the
+//! chain key used for a message is discarded afterwards, so compromising the current session
+//! state does not expose previously exchanged messages.
+//!
+//! This is a symmetric-key ratchet only: unlike the full double ratchet it does not heal after
+//! a compromise of the session state.
+//!
+
+use std::collections::BTreeMap;
+use std::convert::TryInto;
+use std::fmt;
+
+use anyhow::{anyhow, ensure, Result};
+use hkdf::Hkdf;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use sha2::{Sha256, Sha512};
+
+use super::cipher::{decrypt_with_aad, encrypt_with_aad};
+use super::Keypair;
+use crate::core::{
+    derive_shared_secret, random_bytes, AesKey, PublicKey, AES_TAG_LENGTH, H256, SYM_AES_IV_LENGTH,
+};
+
+/// The maximum number of skipped message keys a `Session` keeps for out-of-order messages.
+pub const SESSION_MAX_SKIP: u32 = 1000;
+
+const SESSION_SALT: &[u8] = b"symbol-session";
+const INITIATOR_CHAIN_INFO: &[u8] = b"initiator";
+const RESPONDER_CHAIN_INFO: &[u8] = b"responder";
+const CHAIN_KEY_INFO: &[u8] = b"chain";
+const MESSAGE_KEY_INFO: &[u8] = b"message";
+
+const INDEX_LENGTH: usize = 4;
+
+/// It represents a messaging session between two Symbol accounts.
+///
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+#[derive(Clone, PartialEq)]
+pub struct Session {
+    id: H256,
+    send_chain: H256,
+    send_index: u32,
+    recv_chain: H256,
+    recv_index: u32,
+    skipped: BTreeMap<u32, AesKey>,
+}
+
+// the receiving chain key and index after a message, and the keys it skipped.
+type ChainAdvance = (H256, u32, Vec<(u32, AesKey)>);
+
+// the receiving chain state after a message, committed once the message is authenticated.
+struct ReceiveStep {
+    message_key: AesKey,
+    advance: Option<ChainAdvance>,
+}
+
+impl Session {
+    /// Start a new `Session` with a peer.
+    ///
+    /// The session id must be sent to the peer, who joins the session with `Session::accept`.
+    ///
+    /// # Inputs
+    ///
+    /// * `kp`: The local Symbol `Keypair`.
+    ///
+    /// * `peer_pk`: The peer's public key.
+    ///
+    /// # Returns
+    ///
+    /// A `Session` with a random id.
+    pub fn initiate(kp: &Keypair, peer_pk: &PublicKey) -> Self {
+        let id = H256::from(random_bytes::<32>());
+        Self::new(kp, peer_pk, id, true)
+    }
+
+    /// Join a `Session` started by a peer.
+    ///
+    /// # Inputs
+    ///
+    /// * `kp`: The local Symbol `Keypair`.
+    ///
+    /// * `peer_pk`: The public key of the peer who initiated the session.
+    ///
+    /// * `id`: The session id received from the peer.
+    ///
+    /// # Returns
+    ///
+    /// A `Session`
+    pub fn accept(kp: &Keypair, peer_pk: &PublicKey, id: H256) -> Self {
+        Self::new(kp, peer_pk, id, false)
+    }
+
+    /// The session id.
+    pub fn id(&self) -> H256 {
+        self.id
+    }
+
+    /// Advance the sending chain.
+    ///
+    /// # Returns
+    ///
+    /// The index and the key of the next message to send.
+    pub fn ratchet(&mut self) -> Result<(u32, AesKey)> {
+        let index = self.send_index;
+        self.send_index = index
+            .checked_add(1)
+            .ok_or_else(|| anyhow!("session sending chain is exhausted."))?;
+
+        let (chain, message_key) = chain_step(&self.send_chain);
+        self.send_chain = chain;
+        Ok((index, message_key))
+    }
+
+    /// Encrypt a message with the next sending key.
+    ///
+    /// The encrypted message is `index || tag || iv || ciphertext`, the index is authenticated
+    /// as associated data.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is an encrypt message `Vec<u8>` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn encrypt(&mut self, msg: &[u8]) -> Result<Vec<u8>> {
+        let (index, message_key) = self.ratchet()?;
+        let iv = random_bytes::<SYM_AES_IV_LENGTH>();
+
+        let index_bytes = index.to_le_bytes();
+        let (encrypted, auth_tag) = encrypt_with_aad(iv, message_key, &index_bytes, msg)?;
+
+        let mut enc = vec![];
+        enc.extend_from_slice(&index_bytes);
+        enc.extend_from_slice(&auth_tag);
+        enc.extend_from_slice(&iv);
+        enc.extend_from_slice(&encrypted);
+        Ok(enc)
+    }
+
+    /// Decrypt a message received from the peer.
+    ///
+    /// Messages can be received out of order, up to `SESSION_MAX_SKIP` messages ahead of the
+    /// last one received. Every message can only be decrypted once. The session state only
+    /// changes once the message is authenticated, forged messages leave it untouched.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is an decrypted message `Vec<u8>` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn decrypt(&mut self, enc_msg: &[u8]) -> Result<Vec<u8>> {
+        ensure!(
+            enc_msg.len() >= INDEX_LENGTH + AES_TAG_LENGTH + SYM_AES_IV_LENGTH,
+            "msg has unexpected size."
+        );
+
+        let index = u32::from_le_bytes(enc_msg[..INDEX_LENGTH].try_into()?);
+        let tag = &enc_msg[INDEX_LENGTH..INDEX_LENGTH + AES_TAG_LENGTH];
+        let iv_offset = INDEX_LENGTH + AES_TAG_LENGTH;
+
+        let mut iv = [0u8; SYM_AES_IV_LENGTH];
+        iv.copy_from_slice(&enc_msg[iv_offset..iv_offset + SYM_AES_IV_LENGTH]);
+
+        let mut msg_and_tag_bytes = Vec::with_capacity(enc_msg.len() - INDEX_LENGTH);
+        msg_and_tag_bytes.extend_from_slice(&enc_msg[iv_offset + SYM_AES_IV_LENGTH..]);
+        msg_and_tag_bytes.extend_from_slice(tag);
+
+        let step = self.receive_key(index)?;
+        let msg = decrypt_with_aad(
+            iv,
+            step.message_key,
+            &enc_msg[..INDEX_LENGTH],
+            &msg_and_tag_bytes,
+        )?;
+        self.commit(index, step);
+        Ok(msg)
+    }
+
+    fn new(kp: &Keypair, peer_pk: &PublicKey, id: H256, initiator: bool) -> Self {
+        let shared_secret = derive_shared_secret::<Sha512>(kp.private_key, *peer_pk);
+
+        let mut salt = SESSION_SALT.to_vec();
+        salt.extend_from_slice(id.as_bytes());
+        let root = Hkdf::<Sha256>::new(Some(&salt), shared_secret.as_bytes());
+
+        let initiator_chain = hkdf_expand(&root, INITIATOR_CHAIN_INFO);
+        let responder_chain = hkdf_expand(&root, RESPONDER_CHAIN_INFO);
+
+        let (send_chain, recv_chain) = if initiator {
+            (initiator_chain, responder_chain)
+        } else {
+            (responder_chain, initiator_chain)
+        };
+
+        Self {
+            id,
+            send_chain,
+            send_index: 0,
+            recv_chain,
+            recv_index: 0,
+            skipped: BTreeMap::new(),
+        }
+    }
+
+    // the key of a received message, derived on a copy of the receiving chain.
+    fn receive_key(&self, index: u32) -> Result<ReceiveStep> {
+        if let Some(message_key) = self.skipped.get(&index) {
+            return Ok(ReceiveStep {
+                message_key: *message_key,
+                advance: None,
+            });
+        }
+
+        ensure!(
+            index >= self.recv_index,
+            "message key was already used or discarded."
+        );
+        ensure!(
+            index - self.recv_index <= SESSION_MAX_SKIP
+                && self.skipped.len() as u32 + (index - self.recv_index) <= SESSION_MAX_SKIP,
+            "too many skipped messages in session."
+        );
+
+        let mut chain = self.recv_chain;
+        let mut skipped = Vec::with_capacity((index - self.recv_index) as usize);
+        for skipped_index in self.recv_index..index {
+            let (next_chain, message_key) = chain_step(&chain);
+            skipped.push((skipped_index, message_key));
+            chain = next_chain;
+        }
+
+        let (next_chain, message_key) = chain_step(&chain);
+        let next_index = index
+            .checked_add(1)
+            .ok_or_else(|| anyhow!("session receiving chain is exhausted."))?;
+        Ok(ReceiveStep {
+            message_key,
+            advance: Some((next_chain, next_index, skipped)),
+        })
+    }
+
+    // apply the receiving chain state of an authenticated message, its key is discarded.
+    fn commit(&mut self, index: u32, step: ReceiveStep) {
+        match step.advance {
+            None => {
+                self.skipped.remove(&index);
+            }
+            Some((chain, next_index, skipped)) => {
+                self.recv_chain = chain;
+                self.recv_index = next_index;
+                self.skipped.extend(skipped);
+            }
+        }
+    }
+}
+
+impl fmt::Debug for Session {
+    /// Only the session id, the chain and message keys are secret.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Session").field("id", &self.id).finish()
+    }
+}
+
+// internal functions.
+fn chain_step(chain_key: &H256) -> (H256, AesKey) {
+    let h = Hkdf::<Sha256>::from_prk(chain_key.as_bytes())
+        .expect("unexpected error in rust hkdf_sha256");
+    (
+        hkdf_expand(&h, CHAIN_KEY_INFO),
+        hkdf_expand(&h, MESSAGE_KEY_INFO),
+    )
+}
+
+fn hkdf_expand(h: &Hkdf<Sha256>, info: &[u8]) -> H256 {
+    let mut out = H256::zero();
+    h.expand(info, out.as_mut())
+        .expect("unexpected error in rust hkdf_sha256");
+    out
+}
@@ -0,0 +1,185 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Transaction signing for a network, identified by its generation hash seed.
+//!
+//! A transaction signature covers `generation hash seed || transaction data`, the data being
+//! the bytes following the `TRANSACTION_HEADER_SIZE` header, so it is only valid on its
+//! network. The transaction hash is
+//! `SHA3-256(signature[..32] || signer || generation hash seed || transaction data)`.
+//!
+
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::{ensure, Result};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+
+use super::{Keypair, TRANSACTION_HEADER_SIZE};
+use crate::core::{
+    hex_to_bytes, KeyPairSchema, PublicKey, Signature, H256, KEY_BYTES_SIZE, SIGNATURE_LENGTH,
+};
+
+/// The generation hash seed of the Symbol mainnet.
+pub const MAINNET_GENERATION_HASH: GenerationHash = GenerationHash(H256(hex_to_bytes(
+    "57F7DA205008026C776CB6AED843393F04CD458E0AA2D9F1D5F31A402072B2D6",
+)));
+
+/// The generation hash seed of the Symbol testnet.
+pub const TESTNET_GENERATION_HASH: GenerationHash = GenerationHash(H256(hex_to_bytes(
+    "49D6E1CE276A85B70EAFE52349AACCA389302E7A9754BCF1221E79494FC665A4",
+)));
+
+const SIGNATURE_OFFSET: usize = 8;
+const SIGNER_OFFSET: usize = SIGNATURE_OFFSET + SIGNATURE_LENGTH;
+
+/// The generation hash seed of a network, a distinct type from the payload hashes it signs
+/// with.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate", transparent)
+)]
+pub struct GenerationHash(H256);
+
+impl GenerationHash {
+    /// Wrap the generation hash seed of a network, e.g. from its `/network/properties`.
+    ///
+    pub const fn new(hash: H256) -> Self {
+        Self(hash)
+    }
+
+    /// The generation hash seed as a hash.
+    ///
+    pub fn as_h256(&self) -> &H256 {
+        &self.0
+    }
+
+    /// View the generation hash seed as a byte array.
+    ///
+    pub fn as_bytes(&self) -> &[u8] {
+        self.0.as_bytes()
+    }
+}
+
+impl FromStr for GenerationHash {
+    type Err = anyhow::Error;
+
+    /// Parse the 64 hex characters of a generation hash seed.
+    fn from_str(s: &str) -> Result<Self> {
+        ensure!(
+            s.len() == 2 * KEY_BYTES_SIZE,
+            "generation hash {:?} has unexpected size.",
+            s
+        );
+        Ok(Self(H256::from_str(s).map_err(|_| {
+            anyhow::anyhow!("generation hash {:?} is not hex.", s)
+        })?))
+    }
+}
+
+impl fmt::Display for GenerationHash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:X}", self.0)
+    }
+}
+
+/// Sign a transaction for a network, writing the signature and the signer public key in its
+/// header.
+///
+/// # Inputs
+///
+/// * `kp`: The signer keypair.
+///
+/// * `generation_hash`: The generation hash seed of the network.
+///
+/// * `transaction`: The serialized transaction, its size field set.
+///
+/// # Returns
+///
+/// A `Result` whose okay value is the transaction hash `H256` or whose error value
+/// is an `failure::Error` describing the error that occurred.
+pub fn sign_transaction(
+    kp: &crate::core::Keypair<Keypair>,
+    generation_hash: &GenerationHash,
+    transaction: &mut [u8],
+) -> Result<H256> {
+    check_transaction_size(transaction)?;
+
+    let signature = kp.try_sign(&signing_data(generation_hash, transaction))?;
+    transaction[SIGNATURE_OFFSET..SIGNER_OFFSET].copy_from_slice(signature.as_bytes());
+    transaction[SIGNER_OFFSET..SIGNER_OFFSET + KEY_BYTES_SIZE]
+        .copy_from_slice(kp.public_key().as_bytes());
+
+    transaction_hash(generation_hash, transaction)
+}
+
+/// Verify the signature of a transaction against its signer public key.
+///
+/// # Returns
+///
+/// Returns `Ok` if the transaction is signed for the network of `generation_hash`.
+pub fn verify_transaction(generation_hash: &GenerationHash, transaction: &[u8]) -> Result<()> {
+    check_transaction_size(transaction)?;
+    let signer = PublicKey::from_slice(&transaction[SIGNER_OFFSET..SIGNER_OFFSET + KEY_BYTES_SIZE]);
+    let signature = Signature::from_slice(&transaction[SIGNATURE_OFFSET..SIGNER_OFFSET]);
+
+    Keypair::from_null_private_key(signer)
+        .verify(&signing_data(generation_hash, transaction), signature)
+}
+
+/// The hash of a signed transaction.
+///
+/// # Returns
+///
+/// A `Result` whose okay value is the transaction hash `H256` or whose error value
+/// is an `failure::Error` describing the error that occurred.
+pub fn transaction_hash(generation_hash: &GenerationHash, transaction: &[u8]) -> Result<H256> {
+    check_transaction_size(transaction)?;
+
+    let mut h = Sha3_256::new();
+    h.update(&transaction[SIGNATURE_OFFSET..SIGNATURE_OFFSET + SIGNATURE_LENGTH / 2]);
+    h.update(&transaction[SIGNER_OFFSET..SIGNER_OFFSET + KEY_BYTES_SIZE]);
+    h.update(generation_hash.as_bytes());
+    h.update(&transaction[TRANSACTION_HEADER_SIZE..]);
+    Ok(H256::from_slice(h.finalize().as_slice()))
+}
+
+// internal functions.
+fn check_transaction_size(transaction: &[u8]) -> Result<()> {
+    ensure!(
+        transaction.len() > TRANSACTION_HEADER_SIZE,
+        "transaction has unexpected size {}.",
+        transaction.len()
+    );
+    let size = u32::from_le_bytes([
+        transaction[0],
+        transaction[1],
+        transaction[2],
+        transaction[3],
+    ]);
+    ensure!(
+        size as usize == transaction.len(),
+        "transaction size field {} does not match its size {}.",
+        size,
+        transaction.len()
+    );
+    Ok(())
+}
+
+fn signing_data(generation_hash: &GenerationHash, transaction: &[u8]) -> Vec<u8> {
+    [
+        generation_hash.as_bytes(),
+        &transaction[TRANSACTION_HEADER_SIZE..],
+    ]
+    .concat()
+}
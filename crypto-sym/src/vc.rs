@@ -0,0 +1,195 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Verifiable credential proofs ([VC Data Integrity EdDSA](https://www.w3.org/TR/vc-di-eddsa/))
+//! with Symbol keys.
+//!
+//! Proofs use the `eddsa-jcs-2022` cryptosuite: the proof configuration and the credential are
+//! canonicalized with JCS (RFC 8785), hashed with SHA-256, and the concatenated hashes are signed
+//! with the issuer Symbol key. The credential `issuer` and the verification method are the
+//! issuer did:key, and `verify_proof` checks both against the issuer the caller expects.
+//!
+//! The `Ed25519Signature2020` suite signs the same way but canonicalizes with RDF Dataset
+//! Canonicalization, which needs a JSON-LD processor; it is not implemented here.
+//!
+
+use anyhow::{anyhow, ensure, Result};
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256};
+
+use super::Keypair;
+use crate::core::canonical_json::to_signing_bytes;
+use crate::core::{did, KeyPairSchema, PublicKey, Signature, SIGNATURE_LENGTH};
+
+/// The Data Integrity proof type.
+pub const DATA_INTEGRITY_PROOF: &str = "DataIntegrityProof";
+
+/// The JCS EdDSA cryptosuite identifier.
+pub const EDDSA_JCS_2022: &str = "eddsa-jcs-2022";
+
+/// The proof purpose of credentials issued by the signer.
+pub const ASSERTION_METHOD: &str = "assertionMethod";
+
+const MULTIBASE_BASE58BTC: char = 'z';
+
+/// Adds an `eddsa-jcs-2022` proof to a credential.
+///
+/// # Inputs
+///
+/// * `kp`: The issuer Symbol `Keypair`.
+///
+/// * `credential`: The unsecured credential JSON object, its `issuer` is the did:key of `kp`.
+///
+/// * `created`: Optional XML Schema date time of the proof creation, e.g. `2021-06-01T00:00:00Z`.
+///
+/// # Returns
+///
+/// A `Result` whose okay value is the secured credential `Value` or whose error value
+/// is an `failure::Error` describing the error that occurred.
+pub fn add_proof(
+    kp: &crate::core::Keypair<Keypair>,
+    credential: &Value,
+    created: Option<&str>,
+) -> Result<Value> {
+    let document = credential
+        .as_object()
+        .ok_or_else(|| anyhow!("credential is not a JSON object."))?;
+    ensure!(
+        !document.contains_key("proof"),
+        "credential already has a proof."
+    );
+    ensure!(
+        issuer_public_key(document)? == kp.public_key(),
+        "credential issuer is not the did:key of the signer."
+    );
+
+    let multibase = did::to_public_key_multibase(&kp.public_key());
+    let mut proof = Map::new();
+    proof.insert("type".into(), DATA_INTEGRITY_PROOF.into());
+    proof.insert("cryptosuite".into(), EDDSA_JCS_2022.into());
+    if let Some(created) = created {
+        proof.insert("created".into(), created.into());
+    }
+    proof.insert(
+        "verificationMethod".into(),
+        format!("{}{}#{}", did::DID_KEY_PREFIX, multibase, multibase).into(),
+    );
+    proof.insert("proofPurpose".into(), ASSERTION_METHOD.into());
+    if let Some(context) = document.get("@context") {
+        proof.insert("@context".into(), context.clone());
+    }
+
+    let signature = kp.try_sign(&hash_data(&proof, document))?;
+    proof.insert(
+        "proofValue".into(),
+        format!(
+            "{}{}",
+            MULTIBASE_BASE58BTC,
+            bs58::encode(signature.as_bytes()).into_string()
+        )
+        .into(),
+    );
+
+    let mut secured = document.clone();
+    secured.insert("proof".into(), Value::Object(proof));
+    Ok(Value::Object(secured))
+}
+
+/// Verify the `eddsa-jcs-2022` proof of a credential issued by an expected issuer.
+///
+/// A valid proof only shows the credential was signed by the key its proof names, so the
+/// credential `issuer` and the did:key verification method must both be `expected_issuer`.
+///
+/// # Inputs
+///
+/// * `secured`: The secured credential JSON object.
+///
+/// * `expected_issuer`: The public key of the trusted issuer.
+///
+/// # Returns
+///
+/// Returns `Ok` if the proof is a valid signature of the credential created by
+/// `expected_issuer`.
+pub fn verify_proof(secured: &Value, expected_issuer: &PublicKey) -> Result<()> {
+    let mut document = secured
+        .as_object()
+        .cloned()
+        .ok_or_else(|| anyhow!("credential is not a JSON object."))?;
+    let mut proof = match document.remove("proof") {
+        Some(Value::Object(proof)) => proof,
+        _ => return Err(anyhow!("credential has no proof.")),
+    };
+
+    ensure!(
+        proof.get("type").and_then(Value::as_str) == Some(DATA_INTEGRITY_PROOF),
+        "proof type is not DataIntegrityProof."
+    );
+    ensure!(
+        proof.get("cryptosuite").and_then(Value::as_str) == Some(EDDSA_JCS_2022),
+        "proof cryptosuite is not eddsa-jcs-2022."
+    );
+    ensure!(
+        proof.get("@context") == document.get("@context"),
+        "proof context does not match the credential context."
+    );
+
+    let proof_value = match proof.remove("proofValue") {
+        Some(Value::String(proof_value)) => proof_value,
+        _ => return Err(anyhow!("proof has no proofValue.")),
+    };
+    let encoded = proof_value
+        .strip_prefix(MULTIBASE_BASE58BTC)
+        .ok_or_else(|| anyhow!("proofValue is not base58btc."))?;
+    let signature = bs58::decode(encoded).into_vec()?;
+    ensure!(
+        signature.len() == SIGNATURE_LENGTH,
+        "proofValue has unexpected size."
+    );
+
+    let verification_method = proof
+        .get("verificationMethod")
+        .and_then(Value::as_str)
+        .ok_or_else(|| anyhow!("proof has no verificationMethod."))?;
+    let public_key = did::from_did_key(verification_method)?;
+    ensure!(
+        public_key == *expected_issuer,
+        "proof verification method is not the expected issuer."
+    );
+    ensure!(
+        issuer_public_key(&document)? == *expected_issuer,
+        "credential issuer is not the expected issuer."
+    );
+
+    Keypair::from_null_private_key(public_key).verify(
+        &hash_data(&proof, &document),
+        Signature::from_slice(&signature),
+    )
+}
+
+// internal functions.
+fn issuer_public_key(document: &Map<String, Value>) -> Result<PublicKey> {
+    // the issuer is a URL or an object with an `id`.
+    let issuer = match document.get("issuer") {
+        Some(Value::Object(issuer)) => issuer.get("id").and_then(Value::as_str),
+        Some(issuer) => issuer.as_str(),
+        None => None,
+    }
+    .ok_or_else(|| anyhow!("credential has no issuer."))?;
+    did::from_did_key(issuer)
+}
+
+fn hash_data(proof_config: &Map<String, Value>, document: &Map<String, Value>) -> Vec<u8> {
+    let mut data = Vec::with_capacity(64);
+    data.extend_from_slice(&Sha256::digest(to_signing_bytes(&Value::Object(
+        proof_config.clone(),
+    ))));
+    data.extend_from_slice(&Sha256::digest(to_signing_bytes(&Value::Object(
+        document.clone(),
+    ))));
+    data
+}
@@ -0,0 +1,124 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Epoch ranges of the voting public keys linked to an account.
+//!
+//! Catapult stores a linked voting key as a `PinnedVotingKey`, `voting public key || start
+//! epoch (u32) || end epoch (u32)` little endian, in the account state and in the voting key
+//! link transaction. Voting keys are ed25519 keys: the 48 bytes BLS keys of the first
+//! testnets are not supported. The keys of an account cover disjoint epoch ranges, so at most
+//! one of them signs the finalization messages of an epoch.
+//!
+
+use std::convert::TryInto;
+
+use anyhow::{ensure, Result};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::core::{PublicKey, KEY_BYTES_SIZE};
+
+/// The size of an encoded `PinnedVotingKey`.
+pub const PINNED_VOTING_KEY_SIZE: usize = KEY_BYTES_SIZE + 4 + 4;
+
+/// A voting public key and the finalization epochs it is valid for, bounds included.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct PinnedVotingKey {
+    pub voting_key: PublicKey,
+    pub start_epoch: u32,
+    pub end_epoch: u32,
+}
+
+impl PinnedVotingKey {
+    /// Whether the key signs the finalization messages of `epoch`.
+    ///
+    pub fn is_active(&self, epoch: u32) -> bool {
+        self.start_epoch <= epoch && epoch <= self.end_epoch
+    }
+
+    /// Encode the pinned voting key.
+    ///
+    pub fn to_bytes(&self) -> [u8; PINNED_VOTING_KEY_SIZE] {
+        let mut bytes = [0u8; PINNED_VOTING_KEY_SIZE];
+        bytes[..KEY_BYTES_SIZE].copy_from_slice(self.voting_key.as_bytes());
+        bytes[KEY_BYTES_SIZE..KEY_BYTES_SIZE + 4].copy_from_slice(&self.start_epoch.to_le_bytes());
+        bytes[KEY_BYTES_SIZE + 4..].copy_from_slice(&self.end_epoch.to_le_bytes());
+        bytes
+    }
+
+    /// Decode a pinned voting key.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the `PinnedVotingKey` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        ensure!(
+            bytes.len() == PINNED_VOTING_KEY_SIZE,
+            "pinned voting key has unexpected size {}.",
+            bytes.len()
+        );
+
+        let key = Self {
+            voting_key: PublicKey::from_slice(&bytes[..KEY_BYTES_SIZE]),
+            start_epoch: u32::from_le_bytes(bytes[KEY_BYTES_SIZE..KEY_BYTES_SIZE + 4].try_into()?),
+            end_epoch: u32::from_le_bytes(bytes[KEY_BYTES_SIZE + 4..].try_into()?),
+        };
+        ensure!(
+            key.start_epoch <= key.end_epoch,
+            "voting key ends at epoch {} before it starts at epoch {}.",
+            key.end_epoch,
+            key.start_epoch
+        );
+        Ok(key)
+    }
+}
+
+/// Decode the voting keys of an account, consecutive `PinnedVotingKey`s.
+///
+/// # Returns
+///
+/// A `Result` whose okay value is the `Vec<PinnedVotingKey>` or whose error value
+/// is an `failure::Error` describing the error that occurred, e.g. overlapping epoch ranges.
+pub fn parse_voting_keys(bytes: &[u8]) -> Result<Vec<PinnedVotingKey>> {
+    ensure!(
+        bytes.len().is_multiple_of(PINNED_VOTING_KEY_SIZE),
+        "voting keys have unexpected size {}.",
+        bytes.len()
+    );
+
+    let keys = bytes
+        .chunks(PINNED_VOTING_KEY_SIZE)
+        .map(PinnedVotingKey::from_bytes)
+        .collect::<Result<Vec<_>>>()?;
+    let mut ranges: Vec<(u32, u32)> = keys.iter().map(|k| (k.start_epoch, k.end_epoch)).collect();
+    ranges.sort_unstable();
+    ensure!(
+        ranges.windows(2).all(|w| w[0].1 < w[1].0),
+        "voting keys have overlapping epochs."
+    );
+    Ok(keys)
+}
+
+/// Encode the voting keys of an account, see `parse_voting_keys`.
+///
+pub fn voting_keys_to_bytes(keys: &[PinnedVotingKey]) -> Vec<u8> {
+    keys.iter().flat_map(|key| key.to_bytes()).collect()
+}
+
+/// The voting key of an account for a finalization epoch.
+///
+pub fn voting_key_for_epoch(keys: &[PinnedVotingKey], epoch: u32) -> Option<&PinnedVotingKey> {
+    keys.iter().find(|key| key.is_active(epoch))
+}
@@ -21,13 +21,13 @@ fn encrypt_decrypt<S: KeyPairSchema>(sender_pk: &str, recipient_key: &str, msg:
     println!("recipient_keypair: {}", recipient_keypair);
 
     let encrypt_msg = sender_keypair
-        .encrypt_message(&recipient_keypair.public_key().to_fixed_bytes(), msg)
+        .encrypt_for(&recipient_keypair.public_key(), msg)
         .unwrap();
 
     println!("encrypt_text: {}", hex::encode(&encrypt_msg));
 
     let decrypt_msg = recipient_keypair
-        .decrypt_message(&sender_keypair.public_key().to_fixed_bytes(), &encrypt_msg)
+        .decrypt_from(&sender_keypair.public_key(), &encrypt_msg)
         .unwrap();
 
     println!(
@@ -15,7 +15,7 @@ fn sign_verify<S: KeyPairSchema>(private_key: &str, data: &[u8]) {
     let keypair = Keypair::<S>::from_hex_private_key(private_key).unwrap();
     println!("keypair: {}", keypair);
 
-    let sign_sym = keypair.sign(data);
+    let sign_sym = keypair.try_sign(data).unwrap();
     println!("signature: {:x}", sign_sym);
 
     let verify_sym = keypair.verify(data, sign_sym);
@@ -0,0 +1,181 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Symbol and Nis1 addresses behind one type, for explorers showing both chains.
+//!
+//! Both chains use the same network bytes (`0x68` mainnet, `0x98` testnet) and base32 text
+//! forms, so an address text is told apart by its size: 39 characters for Symbol, 40 for Nis1,
+//! dashes excluded. The same public key has a different address on each chain, Nis1 hashing
+//! it with Keccak-256 and Symbol with SHA3-256: `AnyAddress::counterpart` converts an address
+//! given the public key of its account.
+//!
+
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail, ensure, Result};
+
+use crate::nis1::address::{Address as Nis1Address, NIS1_ADDRESS_SIZE, NIS1_ADDRESS_STR_SIZE};
+use crate::prelude::PublicKey;
+use crate::sym::recipient::{Address as SymAddress, ADDRESS_STR_SIZE, RECIPIENT_SIZE};
+
+/// A Symbol or Nis1 address.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AnyAddress {
+    /// A Symbol address, 24 bytes and 39 characters.
+    Symbol(SymAddress),
+    /// A Nis1 address, 25 bytes and 40 characters.
+    Nis1(Nis1Address),
+}
+
+impl AnyAddress {
+    /// Decode an address from its bytes, Symbol for 24 bytes and Nis1 for 25.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the `AnyAddress` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        match bytes.len() {
+            RECIPIENT_SIZE => Ok(AnyAddress::Symbol(SymAddress::from_bytes(bytes)?)),
+            NIS1_ADDRESS_SIZE => Ok(AnyAddress::Nis1(Nis1Address::from_bytes(bytes)?)),
+            size => bail!("address has unexpected size {}.", size),
+        }
+    }
+
+    /// The network byte of this address.
+    ///
+    pub fn network(&self) -> u8 {
+        match self {
+            AnyAddress::Symbol(address) => address.network(),
+            AnyAddress::Nis1(address) => address.network(),
+        }
+    }
+
+    /// The bytes of this address.
+    ///
+    pub fn as_bytes(&self) -> &[u8] {
+        match self {
+            AnyAddress::Symbol(address) => address.as_bytes(),
+            AnyAddress::Nis1(address) => address.as_bytes(),
+        }
+    }
+
+    /// Whether this is a Symbol address.
+    ///
+    pub fn is_symbol(&self) -> bool {
+        matches!(self, AnyAddress::Symbol(_))
+    }
+
+    /// Whether this is a Nis1 address.
+    ///
+    pub fn is_nis1(&self) -> bool {
+        matches!(self, AnyAddress::Nis1(_))
+    }
+
+    /// Whether this address belongs to a public key.
+    ///
+    pub fn is_of(&self, public_key: &PublicKey) -> bool {
+        match self {
+            AnyAddress::Symbol(address) => {
+                SymAddress::from_public_key(address.network(), public_key)
+                    .is_ok_and(|a| a == *address)
+            }
+            AnyAddress::Nis1(address) => {
+                Nis1Address::from_public_key(address.network(), public_key) == *address
+            }
+        }
+    }
+
+    /// The address of the same account on the other chain, with the same network byte.
+    ///
+    /// # Inputs
+    ///
+    /// * `public_key`: The public key of the account, checked against this address.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the `AnyAddress` of the other chain or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn counterpart(&self, public_key: &PublicKey) -> Result<Self> {
+        ensure!(
+            self.is_of(public_key),
+            "address {} does not belong to public key {:X}.",
+            self,
+            public_key
+        );
+        match self {
+            AnyAddress::Symbol(address) => Ok(AnyAddress::Nis1(Nis1Address::from_public_key(
+                address.network(),
+                public_key,
+            ))),
+            AnyAddress::Nis1(address) => Ok(AnyAddress::Symbol(SymAddress::from_public_key(
+                address.network(),
+                public_key,
+            )?)),
+        }
+    }
+}
+
+impl From<SymAddress> for AnyAddress {
+    fn from(address: SymAddress) -> Self {
+        AnyAddress::Symbol(address)
+    }
+}
+
+impl From<Nis1Address> for AnyAddress {
+    fn from(address: Nis1Address) -> Self {
+        AnyAddress::Nis1(address)
+    }
+}
+
+impl TryFrom<AnyAddress> for SymAddress {
+    type Error = anyhow::Error;
+
+    fn try_from(address: AnyAddress) -> Result<Self> {
+        match address {
+            AnyAddress::Symbol(address) => Ok(address),
+            AnyAddress::Nis1(address) => Err(anyhow!("{} is a nis1 address.", address)),
+        }
+    }
+}
+
+impl TryFrom<AnyAddress> for Nis1Address {
+    type Error = anyhow::Error;
+
+    fn try_from(address: AnyAddress) -> Result<Self> {
+        match address {
+            AnyAddress::Nis1(address) => Ok(address),
+            AnyAddress::Symbol(address) => Err(anyhow!("{} is a symbol address.", address)),
+        }
+    }
+}
+
+impl FromStr for AnyAddress {
+    type Err = anyhow::Error;
+
+    /// Parse a Symbol or Nis1 base32 text form by its size, dashes allowed.
+    fn from_str(s: &str) -> Result<Self> {
+        match s.bytes().filter(|&c| c != b'-').count() {
+            ADDRESS_STR_SIZE => Ok(AnyAddress::Symbol(SymAddress::from_str(s)?)),
+            NIS1_ADDRESS_STR_SIZE => Ok(AnyAddress::Nis1(Nis1Address::from_str(s)?)),
+            _ => bail!("address {:?} has unexpected size.", s),
+        }
+    }
+}
+
+impl fmt::Display for AnyAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AnyAddress::Symbol(address) => fmt::Display::fmt(address, f),
+            AnyAddress::Nis1(address) => fmt::Display::fmt(address, f),
+        }
+    }
+}
@@ -0,0 +1,193 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! `symbol-crypto` command line companion for key operations.
+//!
+//! Every value argument is hex, or `@path` to read it from a file: key and signature files
+//! contain hex, data files are read as raw bytes. Results are printed as upper case hex, or
+//! written as raw bytes with `--out <path>`.
+//!
+
+use std::str::FromStr;
+use std::{env, fs, process};
+
+use anyhow::{anyhow, bail, ensure, Result};
+use symbol_crypto_core::prelude::{
+    create_with_mnemonic, from_mnemonic, KeyPairSchema, Keypair, KpNis1, KpSym, PrivateKey,
+    PublicKey, Signature,
+};
+use symbol_crypto_core::prelude::zeroize::Zeroizing;
+
+const USAGE: &str = "\
+Usage: symbol-crypto [--nis1] [--out <path>] <command> [args]
+
+Commands:
+  keygen                                    generate a random keypair
+  derive    <private-key>                   derive the public key
+  sign      <private-key> <data>            sign data
+  verify    <public-key> <data> <signature> verify a signature
+  encrypt   <private-key> <receiver-public-key> <data>
+                                            encrypt a message
+  decrypt   <private-key> <signer-public-key> <data>
+                                            decrypt a message
+  mnemonic  [--password <password>] [<phrase>]
+                                            create a mnemonic, or recover its private key
+
+Options:
+  --nis1    use the Nis1 schema instead of Symbol
+  --out     write the result as raw bytes to a file
+
+Values are hex, or @path to read them from a file.";
+
+struct Options {
+    nis1: bool,
+    out: Option<String>,
+    password: String,
+    args: Vec<String>,
+}
+
+fn main() {
+    let result = parse_options(env::args().skip(1).collect()).and_then(|options| {
+        if options.nis1 {
+            run::<KpNis1>(&options)
+        } else {
+            run::<KpSym>(&options)
+        }
+    });
+
+    if let Err(e) = result {
+        eprintln!("error: {}\n\n{}", e, USAGE);
+        process::exit(1);
+    }
+}
+
+fn parse_options(raw: Vec<String>) -> Result<Options> {
+    let mut options = Options {
+        nis1: false,
+        out: None,
+        password: String::new(),
+        args: vec![],
+    };
+
+    let mut raw = raw.into_iter();
+    while let Some(arg) = raw.next() {
+        match arg.as_str() {
+            "--nis1" => options.nis1 = true,
+            "--out" => {
+                options.out = Some(raw.next().ok_or_else(|| anyhow!("--out needs a path."))?)
+            }
+            "--password" => {
+                options.password = raw
+                    .next()
+                    .ok_or_else(|| anyhow!("--password needs a value."))?
+            }
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                process::exit(0);
+            }
+            _ => options.args.push(arg),
+        }
+    }
+    ensure!(!options.args.is_empty(), "missing command.");
+    Ok(options)
+}
+
+fn run<Kp: KeyPairSchema>(options: &Options) -> Result<()> {
+    let args = &options.args[1..];
+    let arg = |i: usize, name: &str| -> Result<&str> {
+        args.get(i)
+            .map(String::as_str)
+            .ok_or_else(|| anyhow!("missing <{}> argument.", name))
+    };
+
+    match options.args[0].as_str() {
+        "keygen" => {
+            let kp = Keypair::<Kp>::random();
+            print_keypair(&kp);
+        }
+        "derive" => {
+            let kp = Keypair::<Kp>::from_private_key(read_private_key(arg(0, "private-key")?)?);
+            output(options, kp.public_key().as_bytes())?;
+        }
+        "sign" => {
+            let kp = Keypair::<Kp>::from_private_key(read_private_key(arg(0, "private-key")?)?);
+            let signature = kp.try_sign(&read_data(arg(1, "data")?)?)?;
+            output(options, signature.as_bytes())?;
+        }
+        "verify" => {
+            let public_key = PublicKey::from_str(&read_text(arg(0, "public-key")?)?)
+                .map_err(|e| anyhow!("invalid public key: {}", e))?;
+            let data = read_data(arg(1, "data")?)?;
+            let signature = Signature::from_str(&read_text(arg(2, "signature")?)?)
+                .map_err(|e| anyhow!("invalid signature: {}", e))?;
+
+            Kp::from_null_private_key(public_key).verify(&data, signature)?;
+            println!("valid");
+        }
+        "encrypt" | "decrypt" => {
+            let kp = Keypair::<Kp>::from_private_key(read_private_key(arg(0, "private-key")?)?);
+            let public_key = PublicKey::from_str(&read_text(arg(1, "public-key")?)?)
+                .map_err(|e| anyhow!("invalid public key: {}", e))?;
+            let data = read_data(arg(2, "data")?)?;
+
+            let result = if options.args[0] == "encrypt" {
+                kp.encrypt_for(&public_key, &data)?
+            } else {
+                kp.decrypt_from(&public_key, &data)?
+            };
+            output(options, &result)?;
+        }
+        "mnemonic" => {
+            let private_key = match args.first() {
+                Some(_) => from_mnemonic(&args.join(" "), &options.password)?,
+                None => {
+                    let (private_key, mnemonic) = create_with_mnemonic(&options.password)?;
+                    println!("mnemonic: {}", mnemonic);
+                    private_key
+                }
+            };
+            print_keypair(&Keypair::<Kp>::from_private_key(private_key));
+        }
+        command => bail!("unknown command {}.", command),
+    }
+    Ok(())
+}
+
+fn print_keypair<Kp: KeyPairSchema>(kp: &Keypair<Kp>) {
+    println!("private_key: {}", *kp.private_key().to_hex_ct());
+    println!("public_key: {:X}", kp.public_key());
+}
+
+fn output(options: &Options, bytes: &[u8]) -> Result<()> {
+    match &options.out {
+        Some(path) => fs::write(path, bytes)?,
+        None => println!("{}", hex::encode_upper(bytes)),
+    }
+    Ok(())
+}
+
+/// Reads a hex value, or the hex content of a `@path` file.
+fn read_text(value: &str) -> Result<String> {
+    match value.strip_prefix('@') {
+        Some(path) => Ok(fs::read_to_string(path)?.trim().to_string()),
+        None => Ok(value.to_string()),
+    }
+}
+
+/// Reads hex data, or the raw bytes of a `@path` file.
+fn read_data(value: &str) -> Result<Vec<u8>> {
+    match value.strip_prefix('@') {
+        Some(path) => Ok(fs::read(path)?),
+        None => Ok(hex::decode(value)?),
+    }
+}
+
+fn read_private_key(value: &str) -> Result<PrivateKey> {
+    let text = Zeroizing::new(read_text(value)?);
+    PrivateKey::from_hex_ct(&text).map_err(|e| anyhow!("invalid private key: {}", e))
+}
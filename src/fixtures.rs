@@ -0,0 +1,96 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Deterministic test network accounts.
+//!
+//! The private key of the account of index `i` is
+//! `SHA3-256(FIXTURES_TAG || seed || i (u32 little endian))`, and every account comes with its
+//! Symbol and Nis1 public keys and addresses, so test environments sharing a seed share the
+//! same accounts. The seed is public: never fund these accounts on a public network.
+//!
+
+use anyhow::Result;
+#[cfg(feature = "serde")]
+use serde_crate::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+
+use crate::nis1::address::Address as Nis1Address;
+use crate::prelude::{Keypair, KpNis1, KpSym, PrivateKey, PublicKey};
+use crate::sym::recipient::Address as SymAddress;
+
+/// The prefix of the hashed seed of every test account.
+pub const FIXTURES_TAG: &[u8] = b"symbol-crypto-core/fixtures/v1\0";
+
+/// A test account, with its keys for both schemas.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct TestAccount {
+    /// The index of the account for its seed.
+    pub index: u32,
+    /// The private key, shared by both schemas.
+    pub private_key: PrivateKey,
+    /// The Symbol public key.
+    pub sym_public_key: PublicKey,
+    /// The Symbol address.
+    pub sym_address: SymAddress,
+    /// The Nis1 public key.
+    pub nis1_public_key: PublicKey,
+    /// The Nis1 address.
+    pub nis1_address: Nis1Address,
+}
+
+/// Generate the test accounts of a seed.
+///
+/// # Inputs
+///
+/// * `seed`: The seed of the account set, e.g. the name of the test environment.
+///
+/// * `count`: The number of accounts, of index `0..count`.
+///
+/// * `network`: The network byte of the addresses, e.g. `0x98` for testnet.
+///
+/// # Returns
+///
+/// A `Result` whose okay value is the `TestAccount`s or whose error value
+/// is an `failure::Error` describing the error that occurred.
+pub fn test_accounts(seed: &[u8], count: u32, network: u8) -> Result<Vec<TestAccount>> {
+    (0..count)
+        .map(|index| test_account(seed, index, network))
+        .collect()
+}
+
+/// Generate the test account of index `index` of a seed, see `test_accounts`.
+///
+/// # Returns
+///
+/// A `Result` whose okay value is the `TestAccount` or whose error value
+/// is an `failure::Error` describing the error that occurred.
+pub fn test_account(seed: &[u8], index: u32, network: u8) -> Result<TestAccount> {
+    let mut h = Sha3_256::new();
+    h.update(FIXTURES_TAG);
+    h.update(seed);
+    h.update(index.to_le_bytes());
+    let private_key = PrivateKey::from_slice(h.finalize().as_slice());
+
+    let sym_public_key = Keypair::<KpSym>::from_private_key(private_key).public_key();
+    let nis1_public_key = Keypair::<KpNis1>::from_private_key(private_key).public_key();
+
+    Ok(TestAccount {
+        index,
+        private_key,
+        sym_public_key,
+        sym_address: SymAddress::from_public_key(network, &sym_public_key)?,
+        nis1_public_key,
+        nis1_address: Nis1Address::from_public_key(network, &nis1_public_key),
+    })
+}
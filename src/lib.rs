@@ -50,10 +50,24 @@
 //! Nis1 Bockchain crypto library, along with cryptographic utilities for signing and
 //! verifying Edwards Digital Signature Algorithm (EdDSA) over Curve25519.
 //!
+//! The `sym` (default) and `nis1` features select the chains, at least one of them is
+//! required: a Nis1 only build, `default-features = false, features = ['nis1']`, does not
+//! compile the Symbol crate and its dependencies, nor `KpSym` and `CryptoSym`.
+//!
+
+#[cfg(not(any(feature = "sym", feature = "nis1")))]
+compile_error!("symbol-crypto-core needs at least one of the `sym` and `nis1` features.");
 
+#[cfg(feature = "any_address")]
+pub mod any_address;
+#[cfg(feature = "fixtures")]
+pub mod fixtures;
 #[cfg(feature = "nis1")]
 pub use nis1_crypto as nis1;
+#[cfg(feature = "sym")]
 pub use sym_crypto as sym;
+#[cfg(feature = "tagged_signature")]
+pub mod tagged_signature;
 
 /// Easy imports of frequently used type definitions and traits
 ///
@@ -62,10 +76,12 @@ pub mod prelude {
     pub use core_crypto::*;
 
     #[cfg(feature = "nis1")]
-    pub use nis1_crypto::CryptoNis1;
+    pub use nis1_crypto::{CryptoNis1, CryptoNis1V2};
     #[cfg(feature = "nis1")]
     pub type KpNis1 = nis1_crypto::keypair::Keypair;
 
+    #[cfg(feature = "sym")]
     pub use sym_crypto::CryptoSym;
+    #[cfg(feature = "sym")]
     pub type KpSym = sym_crypto::keypair::Keypair;
 }
@@ -0,0 +1,209 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Signatures tagged with the schema of their signer, for APIs accepting proofs from both
+//! Symbol and Nis1 accounts.
+//!
+//! The binary form is `schema (u8) || signature || signer`, the serde form a struct whose
+//! `schema` is the `KeyPairSchema::SCHEMA_NAME` of the signer.
+//!
+//! `verify_any` finds the schema of an untagged signature among candidate signers, e.g. for
+//! snapshot audits of historical datasets mixing both chains.
+//!
+
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+use anyhow::{bail, ensure, Result};
+#[cfg(feature = "serde")]
+use serde_crate::{Deserialize, Serialize};
+
+use core_crypto::curve25519::edwards::{CompressedEdwardsY, EdwardsPoint};
+
+use crate::prelude::{
+    KeyPairSchema, Keypair, KpNis1, KpSym, PublicKey, Signature, KEY_BYTES_SIZE, SIGNATURE_LENGTH,
+};
+
+/// The size of an encoded `TaggedSignature`.
+pub const TAGGED_SIGNATURE_SIZE: usize = 1 + SIGNATURE_LENGTH + KEY_BYTES_SIZE;
+
+/// The signature schemas of the library.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+#[repr(u8)]
+pub enum SchemaKind {
+    /// Symbol, Ed25519 with SHA-512.
+    #[cfg_attr(feature = "serde", serde(rename = "symbol"))]
+    Symbol = 1,
+    /// Nis1, Ed25519 with Keccak-512 and reversed private keys.
+    #[cfg_attr(feature = "serde", serde(rename = "nis1"))]
+    Nis1 = 2,
+}
+
+/// A signature with its signer and the schema it was made with.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct TaggedSignature {
+    /// The schema of the signer.
+    pub schema: SchemaKind,
+    /// The signature.
+    pub signature: Signature,
+    /// The signer public key.
+    pub signer: PublicKey,
+}
+
+impl SchemaKind {
+    /// The schema of a `KeyPairSchema` by its `SCHEMA_NAME`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the `SchemaKind` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn from_name(name: &str) -> Result<Self> {
+        match name {
+            n if n == KpSym::SCHEMA_NAME => Ok(SchemaKind::Symbol),
+            n if n == KpNis1::SCHEMA_NAME => Ok(SchemaKind::Nis1),
+            _ => bail!("unknown signature schema {:?}.", name),
+        }
+    }
+
+    /// The `KeyPairSchema::SCHEMA_NAME` of this schema.
+    ///
+    pub fn name(&self) -> &'static str {
+        match self {
+            SchemaKind::Symbol => KpSym::SCHEMA_NAME,
+            SchemaKind::Nis1 => KpNis1::SCHEMA_NAME,
+        }
+    }
+}
+
+impl TryFrom<u8> for SchemaKind {
+    type Error = anyhow::Error;
+
+    fn try_from(tag: u8) -> Result<Self> {
+        match tag {
+            1 => Ok(SchemaKind::Symbol),
+            2 => Ok(SchemaKind::Nis1),
+            _ => bail!("unknown signature schema tag {}.", tag),
+        }
+    }
+}
+
+impl TaggedSignature {
+    /// Sign data with a keypair of either schema.
+    ///
+    /// # Inputs
+    ///
+    /// * `keypair`: The signer keypair.
+    ///
+    /// * `data`: The signed data.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the `TaggedSignature` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn sign<Kp: KeyPairSchema>(keypair: &Keypair<Kp>, data: &[u8]) -> Result<Self> {
+        Ok(Self {
+            schema: SchemaKind::from_name(Kp::SCHEMA_NAME)?,
+            signature: keypair.try_sign(data)?,
+            signer: keypair.public_key(),
+        })
+    }
+
+    /// Verify the signature of data with the schema of the signer.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok` if the signature is valid.
+    pub fn verify(&self, data: &[u8]) -> Result<()> {
+        match self.schema {
+            SchemaKind::Symbol => {
+                KpSym::from_null_private_key(self.signer).verify(data, self.signature)
+            }
+            SchemaKind::Nis1 => {
+                KpNis1::from_null_private_key(self.signer).verify(data, self.signature)
+            }
+        }
+    }
+
+    /// Encode the tagged signature to bytes.
+    ///
+    pub fn to_bytes(&self) -> [u8; TAGGED_SIGNATURE_SIZE] {
+        let mut bytes = [0u8; TAGGED_SIGNATURE_SIZE];
+        bytes[0] = self.schema as u8;
+        bytes[1..1 + SIGNATURE_LENGTH].copy_from_slice(self.signature.as_bytes());
+        bytes[1 + SIGNATURE_LENGTH..].copy_from_slice(self.signer.as_bytes());
+        bytes
+    }
+
+    /// Decode a tagged signature from bytes.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose okay value is the `TaggedSignature` or whose error value
+    /// is an `failure::Error` describing the error that occurred.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        ensure!(
+            bytes.len() == TAGGED_SIGNATURE_SIZE,
+            "tagged signature has unexpected size {}.",
+            bytes.len()
+        );
+
+        Ok(Self {
+            schema: SchemaKind::try_from(bytes[0])?,
+            signature: Signature::from_slice(&bytes[1..1 + SIGNATURE_LENGTH]),
+            signer: PublicKey::from_slice(&bytes[1 + SIGNATURE_LENGTH..]),
+        })
+    }
+}
+
+/// Find the schema a signature verifies with, among candidate signers.
+///
+/// Candidates are tried in order. The point of each distinct public key is decompressed once
+/// and shared by its schemas; a key that is not a curve point matches no schema.
+///
+/// # Inputs
+///
+/// * `pk_candidates`: The schemas and public keys the signer may be.
+///
+/// * `data`: The signed data.
+///
+/// * `signature`: The untagged `Signature`.
+///
+/// # Returns
+///
+/// The `SchemaKind` of the first candidate the signature is valid for, `None` if there is
+/// none.
+pub fn verify_any(
+    pk_candidates: &[(SchemaKind, PublicKey)],
+    data: &[u8],
+    signature: Signature,
+) -> Option<SchemaKind> {
+    let mut points: HashMap<PublicKey, Option<EdwardsPoint>> = HashMap::new();
+    pk_candidates.iter().find_map(|(schema, public_key)| {
+        let point = points
+            .entry(*public_key)
+            .or_insert_with(|| CompressedEdwardsY(public_key.to_fixed_bytes()).decompress())
+            .as_ref()?;
+        let verified = match schema {
+            SchemaKind::Symbol => KpSym::verify_with_point(public_key, point, data, signature),
+            SchemaKind::Nis1 => KpNis1::verify_with_point(public_key, point, data, signature),
+        };
+        verified.ok().map(|_| *schema)
+    })
+}
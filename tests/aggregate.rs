@@ -0,0 +1,94 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(test)]
+pub mod tests {
+    use symbol_crypto_core::prelude::{Keypair, KpSym};
+    use symbol_crypto_core::sym::{
+        PartiallySignedAggregate, COSIGNATURE_SIZE, MAINNET_GENERATION_HASH,
+        TRANSACTION_HEADER_SIZE,
+    };
+
+    fn aggregate() -> PartiallySignedAggregate {
+        PartiallySignedAggregate::new(
+            MAINNET_GENERATION_HASH,
+            vec![0x01, 0x98, 0x41, 0x41, 0xAA, 0xBB],
+        )
+    }
+
+    #[test]
+    fn test_sign_cosign_finalize() {
+        let initiator = Keypair::<KpSym>::random();
+        let cosigner = Keypair::<KpSym>::random();
+
+        let mut psa = aggregate();
+        assert!(psa.hash().is_err());
+        assert!(psa.cosign(&cosigner).is_err());
+
+        let hash = psa.sign(&initiator).unwrap();
+        assert_eq!(psa.hash().unwrap(), hash);
+        psa.cosign(&cosigner).unwrap();
+        assert!(psa.cosign(&cosigner).is_err());
+
+        psa.validate().unwrap();
+        let payload = psa.finalize().unwrap();
+        assert_eq!(
+            payload.len(),
+            TRANSACTION_HEADER_SIZE + psa.payload.len() + COSIGNATURE_SIZE
+        );
+        assert_eq!(
+            u32::from_le_bytes([payload[0], payload[1], payload[2], payload[3]]) as usize,
+            payload.len()
+        );
+        assert_eq!(&payload[72..104], initiator.public_key().as_bytes());
+    }
+
+    #[test]
+    fn test_merge() {
+        let initiator = Keypair::<KpSym>::random();
+        let cosigners: Vec<_> = (0..2).map(|_| Keypair::<KpSym>::random()).collect();
+
+        let mut psa = aggregate();
+        psa.sign(&initiator).unwrap();
+
+        let mut first = psa.clone();
+        first.cosign(&cosigners[0]).unwrap();
+        let mut second = psa.clone();
+        second.cosign(&cosigners[1]).unwrap();
+
+        let mut unsigned = aggregate();
+        unsigned.merge(&first).unwrap();
+        unsigned.merge(&second).unwrap();
+        unsigned.merge(&second).unwrap();
+        assert_eq!(unsigned.cosignatures.len(), 2);
+        unsigned.validate().unwrap();
+
+        let mut other = aggregate();
+        other.sign(&Keypair::<KpSym>::random()).unwrap();
+        assert!(unsigned.merge(&other).is_err());
+
+        let mut different = aggregate();
+        different.payload.push(0);
+        assert!(unsigned.merge(&different).is_err());
+    }
+
+    #[test]
+    fn test_validate_tampered() {
+        let mut psa = aggregate();
+        psa.sign(&Keypair::<KpSym>::random()).unwrap();
+        psa.cosign(&Keypair::<KpSym>::random()).unwrap();
+
+        let json = serde_json::to_string(&psa).unwrap();
+        let mut restored: PartiallySignedAggregate = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, psa);
+
+        restored.payload[0] ^= 1;
+        assert!(restored.validate().is_err());
+        assert!(restored.finalize().is_err());
+    }
+}
@@ -0,0 +1,74 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(test)]
+pub mod tests {
+    use std::convert::TryFrom;
+    use std::str::FromStr;
+
+    use symbol_crypto_core::any_address::AnyAddress;
+    use symbol_crypto_core::nis1::address::Address as Nis1Address;
+    use symbol_crypto_core::prelude::PublicKey;
+    use symbol_crypto_core::sym::recipient::Address as SymAddress;
+
+    const PUBLIC_KEY: &str = "C5FB65CB902623D93DF2E682FFB13F99D50FAC24D5FF2A42F68C7CA1772FE8A0";
+    const SYM_ADDRESS: &str = "TBLYH55IHPS5QCCMNWR3GZWKV6WMCKPTNI7KSDA";
+    const NIS1_ADDRESS: &str = "TDR4IXOGFXC4DLEEVXM54F3W2DOUPLC6O3NMXM7T";
+
+    #[test]
+    fn test_parse_detects_chain() {
+        let address = AnyAddress::from_str(SYM_ADDRESS).unwrap();
+        assert!(address.is_symbol());
+        assert_eq!(address.network(), 0x98);
+        assert_eq!(address.to_string(), SYM_ADDRESS);
+        assert_eq!(address.as_bytes().len(), 24);
+
+        let dashed = "TDR4IX-OGFXC4-DLEEVX-M54F3W-2DOUPL-C6O3NM-XM7T";
+        let address = AnyAddress::from_str(dashed).unwrap();
+        assert!(address.is_nis1());
+        assert_eq!(address.network(), 0x98);
+        assert_eq!(address.to_string(), NIS1_ADDRESS);
+        assert_eq!(AnyAddress::from_bytes(address.as_bytes()).unwrap(), address);
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert!(AnyAddress::from_str("").is_err());
+        assert!(AnyAddress::from_str(&SYM_ADDRESS[..38]).is_err());
+        assert!(AnyAddress::from_str(&format!("{}A", NIS1_ADDRESS)).is_err());
+        assert!(AnyAddress::from_str("TDR4IXOGFXC4DLEEVXM54F3W2DOUPLC6O3NMXM7A").is_err());
+        assert!(AnyAddress::from_bytes(&[0x98; 23]).is_err());
+    }
+
+    #[test]
+    fn test_conversions() {
+        let sym = SymAddress::from_str(SYM_ADDRESS).unwrap();
+        let nis1 = Nis1Address::from_str(NIS1_ADDRESS).unwrap();
+
+        assert_eq!(SymAddress::try_from(AnyAddress::from(sym)).unwrap(), sym);
+        assert_eq!(Nis1Address::try_from(AnyAddress::from(nis1)).unwrap(), nis1);
+        assert!(SymAddress::try_from(AnyAddress::from(nis1)).is_err());
+        assert!(Nis1Address::try_from(AnyAddress::from(sym)).is_err());
+    }
+
+    #[test]
+    fn test_counterpart() {
+        let public_key = PublicKey::from_str(PUBLIC_KEY).unwrap();
+        let sym = AnyAddress::from_str(SYM_ADDRESS).unwrap();
+        let nis1 = AnyAddress::from_str(NIS1_ADDRESS).unwrap();
+
+        assert!(sym.is_of(&public_key));
+        assert!(nis1.is_of(&public_key));
+        assert_eq!(sym.counterpart(&public_key).unwrap(), nis1);
+        assert_eq!(nis1.counterpart(&public_key).unwrap(), sym);
+
+        let other = PublicKey::from_slice(&[1u8; 32]);
+        assert!(!sym.is_of(&other));
+        assert!(sym.counterpart(&other).is_err());
+    }
+}
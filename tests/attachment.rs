@@ -0,0 +1,103 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(test)]
+pub mod tests {
+    use std::io::{Read, Write};
+
+    use symbol_crypto_core::prelude::{random_bytes, AesKey, Keypair, KpSym};
+    use symbol_crypto_core::sym::attachment::{
+        chunk_key, decrypt_chunk, AttachmentReader, AttachmentWriter, EncryptedAttachment,
+    };
+
+    fn data(len: usize) -> Vec<u8> {
+        (0..len).map(|i| (i * 7) as u8).collect()
+    }
+
+    #[test]
+    fn test_encrypt_decrypt() {
+        let kp = Keypair::<KpSym>::random();
+        let key = AesKey::from(random_bytes::<32>());
+
+        for &len in [0usize, 1, 99, 100, 101, 1000].iter() {
+            let attachment = EncryptedAttachment::encrypt(&kp, &key, &data(len), 100).unwrap();
+            assert_eq!(attachment.chunks.len(), len.div_ceil(100));
+            assert!(attachment.manifest.verify(&kp.public_key()).is_ok());
+            assert_eq!(
+                attachment.decrypt(&kp.public_key(), &key).unwrap(),
+                data(len)
+            );
+        }
+    }
+
+    #[test]
+    fn test_streaming() {
+        let kp = Keypair::<KpSym>::random();
+        let key = AesKey::from(random_bytes::<32>());
+
+        let mut writer = AttachmentWriter::new(&kp, &key, 64, vec![]).unwrap();
+        for part in data(1000).chunks(30) {
+            writer.write_all(part).unwrap();
+        }
+        let (manifest, encrypted) = writer.finish().unwrap();
+        assert_eq!(manifest.total_size, 1000);
+        assert_eq!(manifest.chunk_hashes.len(), 16);
+
+        let mut reader =
+            AttachmentReader::new(&manifest, &kp.public_key(), &key, &encrypted[..]).unwrap();
+        let mut decrypted = vec![];
+        reader.read_to_end(&mut decrypted).unwrap();
+        assert_eq!(decrypted, data(1000));
+
+        // a corrupted chunk fails the read.
+        let mut corrupted = encrypted.clone();
+        corrupted[200] ^= 1;
+        let mut reader =
+            AttachmentReader::new(&manifest, &kp.public_key(), &key, &corrupted[..]).unwrap();
+        assert!(reader.read_to_end(&mut vec![]).is_err());
+    }
+
+    #[test]
+    fn test_selective_disclosure() {
+        let kp = Keypair::<KpSym>::random();
+        let key = AesKey::from(random_bytes::<32>());
+        let attachment = EncryptedAttachment::encrypt(&kp, &key, &data(300), 100).unwrap();
+        let manifest = &attachment.manifest;
+
+        // only the key of chunk 1 is disclosed.
+        let disclosed = chunk_key(&key, &manifest.id, 1);
+        assert_eq!(
+            decrypt_chunk(manifest, 1, &disclosed, &attachment.chunks[1]).unwrap(),
+            &data(300)[100..200]
+        );
+        assert!(decrypt_chunk(manifest, 2, &disclosed, &attachment.chunks[2]).is_err());
+        assert!(decrypt_chunk(manifest, 0, &disclosed, &attachment.chunks[1]).is_err());
+    }
+
+    #[test]
+    fn test_manifest_tampering() {
+        let kp = Keypair::<KpSym>::random();
+        let key = AesKey::from(random_bytes::<32>());
+        let attachment = EncryptedAttachment::encrypt(&kp, &key, &data(300), 100).unwrap();
+
+        let hash = attachment.manifest.hash();
+        let mut truncated = attachment.clone();
+        truncated.manifest.chunk_hashes.pop();
+        truncated.manifest.total_size = 200;
+        truncated.chunks.pop();
+        assert_ne!(truncated.manifest.hash(), hash);
+        assert!(truncated.decrypt(&kp.public_key(), &key).is_err());
+
+        let other = Keypair::<KpSym>::random();
+        assert!(attachment.decrypt(&other.public_key(), &key).is_err());
+
+        let mut swapped = attachment.clone();
+        swapped.chunks.swap(0, 1);
+        assert!(swapped.decrypt(&kp.public_key(), &key).is_err());
+    }
+}
@@ -0,0 +1,73 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(test)]
+pub mod tests {
+    use symbol_crypto_core::prelude::{Keypair, KpSym};
+    use symbol_crypto_core::sym::attestation::{
+        self, Attestation, ATTESTATION_MAX_METADATA_SIZE,
+    };
+
+    const METADATA: &[u8] = b"model=TX-1;serial=0042";
+
+    #[test]
+    fn test_create_verify() {
+        let device = Keypair::<KpSym>::random();
+        let manufacturer = Keypair::<KpSym>::random();
+
+        let attestation = attestation::create(&device, &manufacturer, METADATA).unwrap();
+        assert_eq!(attestation.device_public_key, device.public_key());
+        assert_eq!(attestation.manufacturer_public_key, manufacturer.public_key());
+        assert_eq!(attestation.metadata, METADATA.to_vec());
+        assert!(attestation::verify(&attestation, &manufacturer.public_key()).is_ok());
+
+        let bytes = attestation.to_bytes();
+        assert_eq!(bytes.len(), 2 * 32 + 2 + METADATA.len() + 2 * 64);
+        assert_eq!(Attestation::from_bytes(&bytes).unwrap(), attestation);
+    }
+
+    #[test]
+    fn test_verify_rejects_untrusted_or_tampered() {
+        let device = Keypair::<KpSym>::random();
+        let manufacturer = Keypair::<KpSym>::random();
+        let other = Keypair::<KpSym>::random();
+        let attestation = attestation::create(&device, &manufacturer, METADATA).unwrap();
+
+        assert!(attestation::verify(&attestation, &other.public_key()).is_err());
+
+        let mut tampered = attestation.clone();
+        tampered.metadata = b"model=TX-1;serial=0043".to_vec();
+        assert!(attestation::verify(&tampered, &manufacturer.public_key()).is_err());
+
+        let mut tampered = attestation.clone();
+        tampered.device_public_key = other.public_key();
+        assert!(attestation::verify(&tampered, &manufacturer.public_key()).is_err());
+
+        // a manufacturer cannot attest a device key without the device signature.
+        let forged = attestation::create(&other, &manufacturer, METADATA).unwrap();
+        let mut tampered = forged;
+        tampered.device_public_key = device.public_key();
+        assert!(attestation::verify(&tampered, &manufacturer.public_key()).is_err());
+    }
+
+    #[test]
+    fn test_invalid() {
+        let device = Keypair::<KpSym>::random();
+        let manufacturer = Keypair::<KpSym>::random();
+
+        assert!(attestation::create(&device, &device, METADATA).is_err());
+        let metadata = vec![0u8; ATTESTATION_MAX_METADATA_SIZE + 1];
+        assert!(attestation::create(&device, &manufacturer, &metadata).is_err());
+
+        let bytes = attestation::create(&device, &manufacturer, METADATA)
+            .unwrap()
+            .to_bytes();
+        assert!(Attestation::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+        assert!(Attestation::from_bytes(&bytes[..10]).is_err());
+    }
+}
@@ -0,0 +1,98 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(test)]
+pub mod tests {
+    use std::str::FromStr;
+
+    use symbol_crypto_core::prelude::binary::{from_bincode, to_bincode};
+    use symbol_crypto_core::prelude::borsh::{self, BorshDeserialize};
+    use symbol_crypto_core::prelude::{
+        Keypair, KpSym, PrivateKey, PublicKey, SecretSerde, Signature, H256, H512,
+    };
+
+    const PRIVATE_KEY: &str = "575DBB3062267EFF57C970A336EBBC8FBCFE12C5BD3ED7BC11EB0481D7704CED";
+
+    fn keypair() -> Keypair<KpSym> {
+        Keypair::from_private_key(PrivateKey::from_str(PRIVATE_KEY).unwrap())
+    }
+
+    #[test]
+    fn test_borsh_round_trip() {
+        let kp = keypair();
+        let signature = kp.try_sign(b"borsh").unwrap();
+
+        let bytes = borsh::to_vec(&kp.private_key()).unwrap();
+        assert_eq!(bytes, kp.private_key().as_bytes().to_vec());
+        assert_eq!(PrivateKey::try_from_slice(&bytes).unwrap(), kp.private_key());
+
+        let bytes = borsh::to_vec(&kp.public_key()).unwrap();
+        assert_eq!(bytes.len(), 32);
+        assert_eq!(PublicKey::try_from_slice(&bytes).unwrap(), kp.public_key());
+
+        let bytes = borsh::to_vec(&signature).unwrap();
+        assert_eq!(bytes.len(), 64);
+        assert_eq!(Signature::try_from_slice(&bytes).unwrap(), signature);
+
+        let hash = H256::repeat_byte(0xab);
+        assert_eq!(H256::try_from_slice(&borsh::to_vec(&hash).unwrap()).unwrap(), hash);
+        let hash = H512::repeat_byte(0xcd);
+        assert_eq!(H512::try_from_slice(&borsh::to_vec(&hash).unwrap()).unwrap(), hash);
+    }
+
+    #[test]
+    fn test_borsh_keypair_is_public_only() {
+        let kp = keypair();
+
+        assert_eq!(
+            borsh::to_vec(&kp).unwrap(),
+            kp.public_key().as_bytes().to_vec()
+        );
+
+        let bytes = borsh::to_vec(&SecretSerde(kp)).unwrap();
+        assert_eq!(bytes, kp.to_bytes().to_vec());
+        let restored = SecretSerde::<Keypair<KpSym>>::try_from_slice(&bytes).unwrap();
+        assert_eq!(restored.0.private_key(), kp.private_key());
+        assert_eq!(restored.0.public_key(), kp.public_key());
+    }
+
+    #[test]
+    fn test_borsh_rejects_invalid() {
+        assert!(PublicKey::try_from_slice(&[0u8; 31]).is_err());
+        assert!(PublicKey::try_from_slice(&[0u8; 33]).is_err());
+        assert!(Signature::try_from_slice(&[0u8; 63]).is_err());
+
+        let bytes = keypair().to_bytes();
+        assert!(SecretSerde::<Keypair<KpSym>>::try_from_slice(&bytes[..63]).is_err());
+    }
+
+    #[test]
+    fn test_bincode_round_trip() {
+        let kp = keypair();
+        let signature = kp.try_sign(b"bincode").unwrap();
+
+        let bytes = to_bincode(&kp.private_key()).unwrap();
+        assert_eq!(from_bincode::<PrivateKey>(&bytes).unwrap(), kp.private_key());
+        let bytes = to_bincode(&kp.public_key()).unwrap();
+        assert_eq!(from_bincode::<PublicKey>(&bytes).unwrap(), kp.public_key());
+        let bytes = to_bincode(&signature).unwrap();
+        assert_eq!(from_bincode::<Signature>(&bytes).unwrap(), signature);
+        let hash = H256::repeat_byte(0xab);
+        assert_eq!(from_bincode::<H256>(&to_bincode(&hash).unwrap()).unwrap(), hash);
+        let hash = H512::repeat_byte(0xcd);
+        assert_eq!(from_bincode::<H512>(&to_bincode(&hash).unwrap()).unwrap(), hash);
+
+        assert_eq!(
+            to_bincode(&kp).unwrap(),
+            to_bincode(&kp.public_key()).unwrap()
+        );
+        let bytes = to_bincode(&SecretSerde(kp)).unwrap();
+        let restored = from_bincode::<SecretSerde<Keypair<KpSym>>>(&bytes).unwrap();
+        assert_eq!(restored.0.private_key(), kp.private_key());
+    }
+}
@@ -0,0 +1,54 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(test)]
+pub mod tests {
+    use symbol_crypto_core::prelude::{Keypair, KpSym, H256};
+    use symbol_crypto_core::sym::blind::{blind, commit, sign_blinded, unblind, verify};
+
+    const VOUCHER: &[u8] = b"voucher 7f3a: 10 coffees";
+
+    #[test]
+    fn test_blind_signature() {
+        let issuer = Keypair::<KpSym>::random();
+
+        let nonce = commit();
+        let commitment = nonce.commitment();
+        let (factors, challenge) = blind(&issuer.public_key(), &commitment, VOUCHER).unwrap();
+        let blinded_s = sign_blinded(&issuer, nonce, &challenge).unwrap();
+        let signature = unblind(factors, &blinded_s).unwrap();
+
+        // a regular Symbol signature, unlinkable to the session values.
+        assert!(verify(&issuer.public_key(), VOUCHER, signature).is_ok());
+        assert!(issuer.verify(VOUCHER, signature).is_ok());
+        assert_ne!(&signature.as_bytes()[..32], commitment.as_bytes());
+        assert_ne!(&signature.as_bytes()[32..], blinded_s.as_bytes());
+
+        assert!(verify(&issuer.public_key(), b"voucher 7f3a: 99 coffees", signature).is_err());
+        let other = Keypair::<KpSym>::random();
+        assert!(verify(&other.public_key(), VOUCHER, signature).is_err());
+    }
+
+    #[test]
+    fn test_unblind_wrong_signer() {
+        let issuer = Keypair::<KpSym>::random();
+        let other = Keypair::<KpSym>::random();
+
+        let nonce = commit();
+        let (factors, challenge) =
+            blind(&issuer.public_key(), &nonce.commitment(), VOUCHER).unwrap();
+        let blinded_s = sign_blinded(&other, nonce, &challenge).unwrap();
+        assert!(unblind(factors, &blinded_s).is_err());
+    }
+
+    #[test]
+    fn test_blind_invalid_commitment() {
+        let issuer = Keypair::<KpSym>::random();
+        assert!(blind(&issuer.public_key(), &H256::zero(), VOUCHER).is_err());
+    }
+}
@@ -0,0 +1,96 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(test)]
+pub mod tests {
+    use symbol_crypto_core::prelude::{Keypair, KpSym};
+    use symbol_crypto_core::sym::bootstrap::{BootstrapAccount, BootstrapAddresses, BootstrapNode};
+
+    const PRIVATE_KEY: &str = "575DBB3062267EFF57C970A336EBBC8FBCFE12C5BD3ED7BC11EB0481D7704CED";
+    const PUBLIC_KEY: &str = "2E834140FD66CF87B254A693A2C7862C819217B676D3943267156625E816EC6F";
+    const ADDRESS: &str = "TATNE7Q5BITMUTRRN6IB4I7FLSDRDWZA37JGO5Q";
+
+    fn addresses_yml(private_key: &str, address: &str) -> String {
+        format!(
+            "version: 1
+networkType: 152
+nemesisGenerationHashSeed: 3B5E1FA6445653C971A50687E75E6D09FB30481055E3990C84B25E9222DC1155
+mosaics:
+  - name: currency
+    id: 3A8416DB2D53B6C8
+nodes:
+  - name: peer-node
+    friendlyName: my node
+    main:
+      privateKey: {}
+      publicKey: {}
+      address: {}
+    transport:
+      publicKey: {}
+      address: {}
+",
+            private_key, PUBLIC_KEY, address, PUBLIC_KEY, address
+        )
+    }
+
+    #[test]
+    fn test_parse_addresses_yml() {
+        let addresses =
+            BootstrapAddresses::from_yaml(&addresses_yml(PRIVATE_KEY, ADDRESS)).unwrap();
+
+        assert_eq!(addresses.network_type, 152);
+        let node = addresses.node("peer-node").unwrap();
+        assert_eq!(node.friendly_name.as_deref(), Some("my node"));
+        assert_eq!(node.accounts().len(), 2);
+
+        let main = node.main.as_ref().unwrap();
+        assert_eq!(format!("{:X}", main.keypair().unwrap().public_key()), PUBLIC_KEY);
+        assert_eq!(main.address.to_string(), ADDRESS);
+        assert!(node.transport.as_ref().unwrap().keypair().is_err());
+        assert!(node.remote.is_none());
+    }
+
+    #[test]
+    fn test_parse_rejects_mismatched_keys() {
+        let other = format!("{:X}", Keypair::<KpSym>::random().private_key());
+        assert!(BootstrapAddresses::from_yaml(&addresses_yml(&other, ADDRESS)).is_err());
+
+        // the mainnet address of the public key.
+        let other_network = "NATNE7Q5BITMUTRRN6IB4I7FLSDRDWZA34SQ33Y";
+        assert!(BootstrapAddresses::from_yaml(&addresses_yml(PRIVATE_KEY, other_network)).is_err());
+    }
+
+    #[test]
+    fn test_emit_round_trip() {
+        let mut addresses = BootstrapAddresses::new(152);
+        addresses.nodes.push(BootstrapNode {
+            name: "api-node".to_string(),
+            friendly_name: None,
+            main: Some(BootstrapAccount::from_keypair(&Keypair::<KpSym>::random(), 152).unwrap()),
+            transport: None,
+            remote: None,
+            vrf: Some(BootstrapAccount::from_keypair(&Keypair::<KpSym>::random(), 152).unwrap()),
+            voting: None,
+        });
+
+        let yaml = addresses.to_yaml().unwrap();
+        assert!(yaml.contains("networkType: 152"));
+        assert_eq!(BootstrapAddresses::from_yaml(&yaml).unwrap(), addresses);
+    }
+
+    #[test]
+    fn test_public_only_hides_private_key() {
+        let account = BootstrapAccount::from_keypair(&Keypair::<KpSym>::random(), 152).unwrap();
+        let public = account.public_only();
+
+        assert!(public.private_key.is_none());
+        assert_eq!(public.public_key, account.public_key);
+        let private_key = format!("{:X}", account.keypair().unwrap().private_key());
+        assert!(!format!("{:?}", account).contains(&private_key));
+    }
+}
@@ -0,0 +1,63 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(test)]
+pub mod tests {
+    use serde_json::{json, Value};
+
+    use symbol_crypto_core::prelude::canonical_json::{sign_json, to_signing_bytes, verify_json};
+    use symbol_crypto_core::prelude::{Keypair, KpNis1, KpSym};
+
+    #[test]
+    fn test_rfc8785_sample() {
+        let value: Value = serde_json::from_str(
+            r#"{
+                "numbers": [333333333.33333329, 1E30, 4.50, 2e-3, 0.000000000000000000000000001],
+                "string": "\u20ac$\u000F\u000aA'\u0042\u0022\u005c\\\"\/",
+                "literals": [null, true, false]
+            }"#,
+        )
+        .unwrap();
+
+        let expected = r#"{"literals":[null,true,false],"numbers":[333333333.3333333,1e+30,4.5,0.002,1e-27],"string":"€$\u000f\nA'B\"\\\\\"/"}"#;
+
+        assert_eq!(
+            String::from_utf8(to_signing_bytes(&value)).unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_utf16_member_ordering() {
+        let value =
+            json!({ "\u{20ac}": 1, "\r": 2, "\u{1f600}": 3, "1": 4, "\u{0080}": 5, "\u{fb33}": 6 });
+
+        let expected =
+            "{\"\\r\":2,\"1\":4,\"\u{0080}\":5,\"\u{20ac}\":1,\"\u{1f600}\":3,\"\u{fb33}\":6}";
+
+        assert_eq!(
+            String::from_utf8(to_signing_bytes(&value)).unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_sign_verify_json() {
+        let kp = Keypair::<KpSym>::random();
+        let value = json!({ "price": 1.25, "pair": "XYM/USD" });
+        let reordered: Value = serde_json::from_str(r#"{"pair":"XYM/USD","price":1.250}"#).unwrap();
+
+        let signature = sign_json(&kp, &value);
+
+        assert!(verify_json::<KpSym>(&kp.public_key(), &reordered, signature).is_ok());
+        assert!(
+            verify_json::<KpSym>(&kp.public_key(), &json!({ "price": 1.26 }), signature).is_err()
+        );
+        assert!(verify_json::<KpNis1>(&kp.public_key(), &value, signature).is_err());
+    }
+}
@@ -0,0 +1,222 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(test)]
+pub mod tests {
+    use symbol_crypto_core::prelude::ceremony::{
+        verify_transcript, verify_transcript_with_contributions, Ceremony, CeremonyTranscript,
+        TranscriptFailure, TranscriptReport,
+    };
+    use symbol_crypto_core::prelude::{Keypair, KpNis1, KpSym};
+
+    fn participants() -> Vec<Keypair<KpSym>> {
+        (0..3).map(|_| Keypair::<KpSym>::random()).collect()
+    }
+
+    fn run(participants: &[Keypair<KpSym>]) -> (Keypair<KpNis1>, CeremonyTranscript) {
+        let mut ceremony = Ceremony::<KpNis1>::new(
+            "treasury",
+            participants.iter().map(|kp| kp.public_key()).collect(),
+        )
+        .unwrap();
+        for (i, kp) in participants.iter().enumerate() {
+            assert!(!ceremony.is_complete());
+            ceremony
+                .contribute(&kp.public_key(), &[i as u8; 32])
+                .unwrap();
+        }
+        assert!(ceremony.is_complete());
+        ceremony.finalize().unwrap()
+    }
+
+    #[test]
+    fn test_ceremony_is_reproducible() {
+        let participants = participants();
+
+        let (kp, transcript) = run(&participants);
+        let (kp_again, transcript_again) = run(&participants);
+
+        assert_eq!(kp, kp_again);
+        assert_eq!(transcript.public_key, kp.public_key());
+        assert_eq!(transcript.schema, "nis1");
+        assert_eq!(transcript.hash(), transcript_again.hash());
+
+        for (i, participant) in participants.iter().enumerate() {
+            transcript
+                .verify_contribution(&participant.public_key(), &[i as u8; 32])
+                .unwrap();
+        }
+        assert!(transcript
+            .verify_contribution(&participants[0].public_key(), &[9u8; 32])
+            .is_err());
+    }
+
+    #[test]
+    fn test_transcript_signed_by_all() {
+        let participants = participants();
+        let (_, mut transcript) = run(&participants);
+
+        transcript.sign(&participants[0]).unwrap();
+        transcript.sign(&participants[1]).unwrap();
+        assert!(transcript.verify::<KpSym>().is_err());
+        assert!(transcript.sign(&participants[1]).is_err());
+        assert!(transcript.sign(&Keypair::<KpSym>::random()).is_err());
+
+        transcript.sign(&participants[2]).unwrap();
+        assert!(transcript.verify::<KpSym>().is_ok());
+
+        let json = serde_json::to_string(&transcript).unwrap();
+        let mut restored: CeremonyTranscript = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, transcript);
+
+        restored.label = "other".into();
+        assert!(restored.verify::<KpSym>().is_err());
+    }
+
+    #[test]
+    fn test_ceremony_invalid() {
+        let participants = participants();
+        let keys: Vec<_> = participants.iter().map(|kp| kp.public_key()).collect();
+
+        assert!(Ceremony::<KpSym>::new("empty", vec![]).is_err());
+        assert!(Ceremony::<KpSym>::new("duplicated", vec![keys[0], keys[0]]).is_err());
+
+        let mut ceremony = Ceremony::<KpSym>::new("invalid", keys.clone()).unwrap();
+        assert!(ceremony.contribute(&keys[0], &[1u8; 16]).is_err());
+        assert!(ceremony
+            .contribute(&Keypair::<KpSym>::random().public_key(), &[1u8; 32])
+            .is_err());
+        ceremony.contribute(&keys[0], &[1u8; 32]).unwrap();
+        assert!(ceremony.contribute(&keys[0], &[2u8; 32]).is_err());
+        assert!(ceremony.finalize().is_err());
+    }
+
+    fn signed_transcript(participants: &[Keypair<KpSym>]) -> CeremonyTranscript {
+        let (_, mut transcript) = run(participants);
+        for kp in participants {
+            transcript.sign(kp).unwrap();
+        }
+        transcript
+    }
+
+    #[test]
+    fn test_verify_transcript_valid() {
+        let participants = participants();
+        let transcript = signed_transcript(&participants);
+
+        let report = verify_transcript::<KpSym>(&transcript);
+        assert!(report.is_valid());
+        assert_eq!(report.transcript_hash, transcript.hash());
+        assert_eq!(report.valid_signatures, 3);
+        assert!(!report.key_checked);
+
+        let contributions = [[0u8; 32], [1u8; 32], [2u8; 32]];
+        let revealed: Vec<&[u8]> = contributions.iter().map(|c| &c[..]).collect();
+        let report =
+            verify_transcript_with_contributions::<KpSym, KpNis1>(&transcript, &revealed);
+        assert!(report.is_valid());
+        assert!(report.key_checked);
+        assert!(report.into_result().is_ok());
+    }
+
+    #[test]
+    fn test_verify_transcript_locates_failures() {
+        let participants = participants();
+        let mut transcript = signed_transcript(&participants);
+
+        // participant #1 signature is replaced by the one of participant #2.
+        transcript.signatures[1].1 = transcript.signatures[2].1;
+        let stranger = Keypair::<KpSym>::random();
+        let signature = stranger.try_sign(transcript.hash().as_bytes()).unwrap();
+        transcript.signatures.push((stranger.public_key(), signature));
+        transcript.signatures.push(transcript.signatures[0]);
+
+        let report = verify_transcript::<KpSym>(&transcript);
+        assert_eq!(report.valid_signatures, 2);
+        assert_eq!(
+            report.failures,
+            vec![
+                TranscriptFailure::UnknownSigner {
+                    position: 3,
+                    signer: stranger.public_key(),
+                },
+                TranscriptFailure::DuplicateSignature {
+                    position: 4,
+                    first: 0,
+                    signer: participants[0].public_key(),
+                },
+                TranscriptFailure::InvalidSignature {
+                    index: 1,
+                    participant: participants[1].public_key(),
+                },
+            ]
+        );
+        assert!(report.to_string().contains("signature of participant #1"));
+        assert!(transcript.verify::<KpSym>().is_err());
+
+        transcript.commitments[2] = transcript.commitments[0];
+        transcript.signatures.truncate(2);
+        let failures = verify_transcript::<KpSym>(&transcript).failures;
+        assert!(failures.contains(&TranscriptFailure::DuplicateCommitment { index: 2, first: 0 }));
+        assert!(failures.contains(&TranscriptFailure::MissingSignature {
+            index: 2,
+            participant: participants[2].public_key(),
+        }));
+    }
+
+    #[test]
+    fn test_verify_transcript_contributions() {
+        let participants = participants();
+        let transcript = signed_transcript(&participants);
+
+        let contributions = [[0u8; 32], [7u8; 32], [2u8; 32]];
+        let revealed: Vec<&[u8]> = contributions.iter().map(|c| &c[..]).collect();
+        let report =
+            verify_transcript_with_contributions::<KpSym, KpNis1>(&transcript, &revealed);
+        assert_eq!(report.failures.len(), 2);
+        assert_eq!(
+            report.failures[0],
+            TranscriptFailure::CommitmentMismatch {
+                index: 1,
+                participant: participants[1].public_key(),
+            }
+        );
+        assert!(matches!(
+            report.failures[1],
+            TranscriptFailure::PublicKeyMismatch { .. }
+        ));
+
+        let report =
+            verify_transcript_with_contributions::<KpSym, KpSym>(&transcript, &revealed[..2]);
+        assert!(report.failures.contains(&TranscriptFailure::SchemaMismatch {
+            transcript: "nis1".into(),
+            checked: "symbol".into(),
+        }));
+        assert!(report.failures.contains(&TranscriptFailure::ContributionCount {
+            participants: 3,
+            contributions: 2,
+        }));
+    }
+
+    #[test]
+    fn test_transcript_report_serde() {
+        let participants = participants();
+        let mut transcript = signed_transcript(&participants);
+        transcript.signatures.pop();
+
+        let json = serde_json::to_string(&transcript).unwrap();
+        let restored: CeremonyTranscript = serde_json::from_str(&json).unwrap();
+        let report = verify_transcript::<KpSym>(&restored);
+        assert!(!report.is_valid());
+
+        let json = serde_json::to_string(&report).unwrap();
+        assert!(json.contains(r#""kind":"missingSignature""#));
+        let restored: TranscriptReport = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, report);
+    }
+}
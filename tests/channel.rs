@@ -0,0 +1,80 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(test)]
+pub mod tests {
+    use std::net::{TcpListener, TcpStream};
+    use std::thread;
+
+    use anyhow::Result;
+
+    use symbol_crypto_core::prelude::{Keypair, KpSym, PublicKey};
+    use symbol_crypto_core::sym::channel::EncryptedChannel;
+
+    type Channel = Result<EncryptedChannel<TcpStream>>;
+
+    // establish both ends over a local tcp connection, each end expecting a public key.
+    fn connect(
+        a: Keypair<KpSym>,
+        a_expects: PublicKey,
+        b: Keypair<KpSym>,
+        b_expects: PublicKey,
+    ) -> (Channel, Channel) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let remote = thread::spawn(move || {
+            EncryptedChannel::establish(&b, &b_expects, TcpStream::connect(addr).unwrap())
+        });
+        let (stream, _) = listener.accept().unwrap();
+        let local = EncryptedChannel::establish(&a, &a_expects, stream);
+        (local, remote.join().unwrap())
+    }
+
+    #[test]
+    fn test_channel_exchanges_messages() {
+        let a = Keypair::<KpSym>::random();
+        let b = Keypair::<KpSym>::random();
+        let (a_channel, b_channel) = connect(a, b.public_key(), b, a.public_key());
+        let mut a_channel = a_channel.unwrap();
+        let mut b_channel = b_channel.unwrap();
+
+        assert_ne!(a_channel.role(), b_channel.role());
+        assert_eq!(a_channel.peer_public_key(), b.public_key());
+        assert_eq!(b_channel.peer_public_key(), a.public_key());
+
+        a_channel.send(b"ping").unwrap();
+        a_channel.send(b"").unwrap();
+        assert_eq!(b_channel.recv().unwrap(), b"ping");
+        assert_eq!(b_channel.recv().unwrap(), b"");
+
+        b_channel.send(b"pong").unwrap();
+        assert_eq!(a_channel.recv().unwrap(), b"pong");
+    }
+
+    #[test]
+    fn test_channel_rejects_unexpected_peer() {
+        let a = Keypair::<KpSym>::random();
+        let b = Keypair::<KpSym>::random();
+        let other = Keypair::<KpSym>::random();
+
+        let (a_channel, b_channel) = connect(a, other.public_key(), b, a.public_key());
+
+        assert!(a_channel.is_err());
+        drop(b_channel);
+    }
+
+    #[test]
+    fn test_channel_rejects_own_public_key() {
+        let a = Keypair::<KpSym>::random();
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+
+        assert!(EncryptedChannel::establish(&a, &a.public_key(), stream).is_err());
+    }
+}
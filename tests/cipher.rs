@@ -0,0 +1,611 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(test)]
+pub mod tests {
+    use symbol_crypto_core::prelude::{BlockCipher, Keypair};
+
+    const MESSAGE: &[u8] = b"Nem is awesome from Rust!";
+
+    pub mod tests_nis1 {
+        use std::str::FromStr;
+
+        use symbol_crypto_core::nis1::{
+            Nis1Envelope, Nis1EnvelopeKind, Nis1ExtendedEnvelope, Nis1Mac, NIS1_EXTENDED_MAGIC,
+            NIS1_EXTENDED_VERSION,
+        };
+        use symbol_crypto_core::prelude::{CryptoNis1, KpNis1, PrivateKey};
+
+        use super::*;
+
+        #[test]
+        fn test_envelope_round_trip() {
+            let signer_kp = Keypair::<KpNis1>::random();
+            let receiver_kp = Keypair::<KpNis1>::random();
+
+            let enc_msg = CryptoNis1::encrypt_for(
+                &signer_kp.private_key(),
+                &receiver_kp.public_key(),
+                MESSAGE,
+            )
+            .unwrap();
+
+            let envelope = Nis1Envelope::parse(&enc_msg).unwrap();
+            assert_eq!(envelope.to_bytes(), enc_msg);
+            assert_eq!(envelope.ciphertext.len(), 32);
+
+            let dec_msg = envelope
+                .decrypt(&receiver_kp.private_key(), &signer_kp.public_key())
+                .unwrap();
+            assert_eq!(dec_msg, MESSAGE);
+        }
+
+        #[test]
+        fn test_envelope_malformed() {
+            let receiver_kp = Keypair::<KpNis1>::random();
+            let signer_kp = Keypair::<KpNis1>::random();
+
+            // every truncation of a one block message, and unaligned ciphertexts.
+            let bytes = [0x5Au8; 32 + 16 + 16 + 15];
+            for len in (0..64).chain(65..bytes.len()) {
+                assert!(Nis1Envelope::parse(&bytes[..len]).is_err());
+                assert!(CryptoNis1::decrypt_from(
+                    &receiver_kp.private_key(),
+                    &signer_kp.public_key(),
+                    &bytes[..len],
+                )
+                .is_err());
+            }
+            assert!(Nis1Envelope::parse(&bytes[..64]).is_ok());
+        }
+
+        #[test]
+        fn test_envelope_invalid_padding() {
+            let receiver_sk = PrivateKey::from_str(
+                "A22A4BBF126A2D7D7ECE823174DFD184C5DE0FDE4CB2075D30CFA409F7EF8908",
+            )
+            .unwrap();
+            let signer_kp = Keypair::<KpNis1>::from_private_key(receiver_sk);
+
+            let envelope = Nis1Envelope::parse(&[0x5Au8; 64]).unwrap();
+            let dec_msg = envelope.decrypt(&receiver_sk, &signer_kp.public_key());
+            assert!(dec_msg.is_err());
+        }
+
+        #[test]
+        fn test_extended_envelope_round_trip() {
+            let signer_kp = Keypair::<KpNis1>::random();
+            let receiver_kp = Keypair::<KpNis1>::random();
+
+            let enc_msg = CryptoNis1::encrypt_for_extended(
+                &signer_kp.private_key(),
+                &receiver_kp.public_key(),
+                MESSAGE,
+            )
+            .unwrap();
+            assert_eq!(Nis1Envelope::detect(&enc_msg), Nis1EnvelopeKind::Extended);
+
+            let extended = Nis1ExtendedEnvelope::parse(&enc_msg).unwrap();
+            assert_eq!(extended.to_bytes(), enc_msg);
+            assert_eq!(extended.version, NIS1_EXTENDED_VERSION);
+            assert_eq!(extended.sender_public_key, signer_kp.public_key());
+            assert_eq!(extended.decrypt(&receiver_kp.private_key()).unwrap(), MESSAGE);
+
+            // `decrypt_from` handles it transparently, for the receiver and the signer.
+            let dec_msg = CryptoNis1::decrypt_from(
+                &receiver_kp.private_key(),
+                &signer_kp.public_key(),
+                &enc_msg,
+            )
+            .unwrap();
+            assert_eq!(dec_msg, MESSAGE);
+            let dec_msg = CryptoNis1::decrypt_from(
+                &signer_kp.private_key(),
+                &receiver_kp.public_key(),
+                &enc_msg,
+            )
+            .unwrap();
+            assert_eq!(dec_msg, MESSAGE);
+        }
+
+        #[test]
+        fn test_decrypt_with_sender() {
+            let signer_kp = Keypair::<KpNis1>::random();
+            let receiver_kp = Keypair::<KpNis1>::random();
+            let receiver_sk = receiver_kp.private_key();
+
+            let extended = CryptoNis1::encrypt_for_extended(
+                &signer_kp.private_key(),
+                &receiver_kp.public_key(),
+                MESSAGE,
+            )
+            .unwrap();
+            let (sender_pk, dec_msg) =
+                CryptoNis1::decrypt_with_sender(&receiver_sk, None, &extended).unwrap();
+            assert_eq!(sender_pk, signer_kp.public_key());
+            assert_eq!(dec_msg, MESSAGE);
+            assert!(CryptoNis1::decrypt_with_sender(
+                &receiver_sk,
+                Some(&receiver_kp.public_key()),
+                &extended
+            )
+            .is_err());
+
+            let legacy = CryptoNis1::encrypt_for(
+                &signer_kp.private_key(),
+                &receiver_kp.public_key(),
+                MESSAGE,
+            )
+            .unwrap();
+            assert_eq!(Nis1Envelope::detect(&legacy), Nis1EnvelopeKind::Legacy);
+            assert!(CryptoNis1::decrypt_with_sender(&receiver_sk, None, &legacy).is_err());
+            let (sender_pk, dec_msg) = CryptoNis1::decrypt_with_sender(
+                &receiver_sk,
+                Some(&signer_kp.public_key()),
+                &legacy,
+            )
+            .unwrap();
+            assert_eq!(sender_pk, signer_kp.public_key());
+            assert_eq!(dec_msg, MESSAGE);
+        }
+
+        #[test]
+        fn test_extended_envelope_malformed() {
+            let signer_kp = Keypair::<KpNis1>::random();
+            let receiver_kp = Keypair::<KpNis1>::random();
+            let enc_msg = CryptoNis1::encrypt_for_extended(
+                &signer_kp.private_key(),
+                &receiver_kp.public_key(),
+                MESSAGE,
+            )
+            .unwrap();
+
+            for len in 0..enc_msg.len() {
+                assert!(Nis1ExtendedEnvelope::parse(&enc_msg[..len]).is_err());
+            }
+
+            let mut unknown_version = enc_msg.clone();
+            unknown_version[NIS1_EXTENDED_MAGIC.len()] = NIS1_EXTENDED_VERSION + 1;
+            assert!(Nis1ExtendedEnvelope::parse(&unknown_version).is_err());
+
+            // a legacy payload starting with the magic is still a legacy payload.
+            let mut legacy = [0x5Au8; 64];
+            legacy[..NIS1_EXTENDED_MAGIC.len()].copy_from_slice(&NIS1_EXTENDED_MAGIC);
+            assert_eq!(Nis1Envelope::detect(&legacy), Nis1EnvelopeKind::Legacy);
+            assert!(Nis1ExtendedEnvelope::parse(&legacy).is_err());
+        }
+
+        #[test]
+        fn test_extended_envelope_invalid_sender_key() {
+            let signer_kp = Keypair::<KpNis1>::random();
+            let receiver_kp = Keypair::<KpNis1>::random();
+            let mut enc_msg = CryptoNis1::encrypt_for_extended(
+                &signer_kp.private_key(),
+                &receiver_kp.public_key(),
+                MESSAGE,
+            )
+            .unwrap();
+
+            // y = 2 is not on the curve.
+            let offset = NIS1_EXTENDED_MAGIC.len() + 1;
+            enc_msg[offset..offset + 32].copy_from_slice(&[0u8; 32]);
+            enc_msg[offset] = 2;
+
+            assert!(Nis1ExtendedEnvelope::parse(&enc_msg).is_err());
+            assert!(
+                CryptoNis1::decrypt_with_sender(&receiver_kp.private_key(), None, &enc_msg)
+                    .is_err()
+            );
+        }
+
+        #[test]
+        fn test_encrypt_then_mac_round_trip() {
+            let signer_kp = Keypair::<KpNis1>::random();
+            let receiver_kp = Keypair::<KpNis1>::random();
+
+            let (enc_msg, mac) = CryptoNis1::encrypt_then_mac(
+                &signer_kp.private_key(),
+                &receiver_kp.public_key(),
+                MESSAGE,
+            )
+            .unwrap();
+            assert_eq!(Nis1Envelope::detect(&enc_msg), Nis1EnvelopeKind::Legacy);
+
+            let dec_msg = CryptoNis1::decrypt_verified(
+                &receiver_kp.private_key(),
+                &signer_kp.public_key(),
+                &enc_msg,
+                &mac,
+            )
+            .unwrap();
+            assert_eq!(dec_msg, MESSAGE);
+            let dec_msg = CryptoNis1::decrypt_verified(
+                &signer_kp.private_key(),
+                &receiver_kp.public_key(),
+                &enc_msg,
+                &mac,
+            )
+            .unwrap();
+            assert_eq!(dec_msg, MESSAGE);
+
+            // the keys differ from the legacy ones.
+            let legacy = CryptoNis1::decrypt_from(
+                &receiver_kp.private_key(),
+                &signer_kp.public_key(),
+                &enc_msg,
+            );
+            assert!(legacy.map_or(true, |msg| msg != MESSAGE));
+        }
+
+        #[test]
+        fn test_decrypt_verified_rejects_tampering() {
+            let signer_kp = Keypair::<KpNis1>::random();
+            let receiver_kp = Keypair::<KpNis1>::random();
+            let (enc_msg, mac) = CryptoNis1::encrypt_then_mac(
+                &signer_kp.private_key(),
+                &receiver_kp.public_key(),
+                MESSAGE,
+            )
+            .unwrap();
+            let decrypt = |enc_msg: &[u8], mac: &Nis1Mac| {
+                CryptoNis1::decrypt_verified(
+                    &receiver_kp.private_key(),
+                    &signer_kp.public_key(),
+                    enc_msg,
+                    mac,
+                )
+            };
+
+            for i in 0..enc_msg.len() {
+                let mut tampered = enc_msg.clone();
+                tampered[i] ^= 1;
+                assert!(decrypt(&tampered, &mac).is_err());
+            }
+            let mut tampered_mac = mac;
+            tampered_mac.0[0] ^= 1;
+            assert!(decrypt(&enc_msg, &tampered_mac).is_err());
+            assert!(decrypt(&enc_msg[..enc_msg.len() - 16], &mac).is_err());
+        }
+
+        #[test]
+        fn test_padding_errors_are_uniform() {
+            let receiver_kp = Keypair::<KpNis1>::random();
+            let signer_kp = Keypair::<KpNis1>::random();
+
+            let errors: Vec<String> = (0u8..8)
+                .map(|i| {
+                    Nis1Envelope::parse(&[i; 64])
+                        .unwrap()
+                        .decrypt(&receiver_kp.private_key(), &signer_kp.public_key())
+                })
+                .filter_map(|result| result.err().map(|e| e.to_string()))
+                .collect();
+            assert!(!errors.is_empty());
+            assert!(errors.iter().all(|e| e == "encrypted message could not be decrypted."));
+        }
+    }
+
+    pub mod tests_nis1_v2 {
+        use symbol_crypto_core::prelude::{CryptoNis1V2, KpNis1};
+
+        use super::*;
+
+        #[test]
+        fn test_encrypt_decrypt_round_trip() {
+            let signer_kp = Keypair::<KpNis1>::random();
+            let receiver_kp = Keypair::<KpNis1>::random();
+
+            let enc_msg = CryptoNis1V2::encrypt_for(
+                &signer_kp.private_key(),
+                &receiver_kp.public_key(),
+                MESSAGE,
+            )
+            .unwrap();
+
+            let dec_msg = CryptoNis1V2::decrypt_from(
+                &receiver_kp.private_key(),
+                &signer_kp.public_key(),
+                &enc_msg,
+            )
+            .unwrap();
+
+            assert_eq!(dec_msg, MESSAGE);
+        }
+
+        #[test]
+        fn test_decrypt_tampered_message() {
+            let signer_kp = Keypair::<KpNis1>::random();
+            let receiver_kp = Keypair::<KpNis1>::random();
+
+            let mut enc_msg = CryptoNis1V2::encrypt_for(
+                &signer_kp.private_key(),
+                &receiver_kp.public_key(),
+                MESSAGE,
+            )
+            .unwrap();
+
+            let last = enc_msg.len() - 1;
+            enc_msg[last] ^= 0xff;
+
+            let dec_msg = CryptoNis1V2::decrypt_from(
+                &receiver_kp.private_key(),
+                &signer_kp.public_key(),
+                &enc_msg,
+            );
+
+            assert!(dec_msg.is_err());
+            assert!(CryptoNis1V2::decrypt_from(
+                &receiver_kp.private_key(),
+                &signer_kp.public_key(),
+                &enc_msg[..10],
+            )
+            .is_err());
+        }
+    }
+
+    pub mod tests_sym {
+        use symbol_crypto_core::prelude::{CryptoSym, KpSym};
+        use symbol_crypto_core::sym::CATAPULT_KDF_LABEL;
+
+        use super::*;
+
+        #[test]
+        fn test_typed_helpers_round_trip() {
+            let signer_kp = Keypair::<KpSym>::random();
+            let receiver_kp = Keypair::<KpSym>::random();
+
+            let enc_msg = signer_kp
+                .private_key()
+                .encrypt_for::<CryptoSym>(&receiver_kp.public_key(), MESSAGE)
+                .unwrap();
+            let dec_msg = receiver_kp
+                .decrypt_from(&signer_kp.public_key(), &enc_msg)
+                .unwrap();
+            assert_eq!(dec_msg, MESSAGE);
+
+            let dec_msg = receiver_kp
+                .private_key()
+                .decrypt_from::<CryptoSym>(&signer_kp.public_key(), &enc_msg)
+                .unwrap();
+            assert_eq!(dec_msg, MESSAGE);
+
+            // the shared key is symmetric: the signer decrypts too, not a wrong peer key.
+            assert!(signer_kp
+                .decrypt_from(&receiver_kp.public_key(), &enc_msg)
+                .is_ok());
+            assert!(receiver_kp
+                .decrypt_from(&receiver_kp.public_key(), &enc_msg)
+                .is_err());
+        }
+
+        #[test]
+        fn test_verify_ciphertext() {
+            let signer_kp = Keypair::<KpSym>::random();
+            let receiver_kp = Keypair::<KpSym>::random();
+
+            let mut enc_msg = signer_kp
+                .encrypt_for(&receiver_kp.public_key(), MESSAGE)
+                .unwrap();
+
+            assert!(CryptoSym::verify_ciphertext(
+                &receiver_kp.private_key(),
+                &signer_kp.public_key(),
+                &enc_msg,
+            )
+            .is_ok());
+
+            enc_msg[0] ^= 0xff;
+
+            assert!(CryptoSym::verify_ciphertext(
+                &receiver_kp.private_key(),
+                &signer_kp.public_key(),
+                &enc_msg,
+            )
+            .is_err());
+            assert!(CryptoSym::verify_ciphertext(
+                &receiver_kp.private_key(),
+                &signer_kp.public_key(),
+                &enc_msg[..8],
+            )
+            .is_err());
+        }
+
+        #[test]
+        fn test_kdf_label() {
+            let signer_kp = Keypair::<KpSym>::random();
+            let receiver_kp = Keypair::<KpSym>::random();
+            let forked = CryptoSym::with_kdf_label(b"private-chain");
+            assert_eq!(forked.label(), b"private-chain");
+
+            let enc_msg = forked
+                .encrypt_for(&signer_kp.private_key(), &receiver_kp.public_key(), MESSAGE)
+                .unwrap();
+            let dec_msg = forked
+                .decrypt_from(
+                    &receiver_kp.private_key(),
+                    &signer_kp.public_key(),
+                    &enc_msg,
+                )
+                .unwrap();
+            assert_eq!(dec_msg, MESSAGE);
+            assert!(forked
+                .verify_ciphertext(
+                    &receiver_kp.private_key(),
+                    &signer_kp.public_key(),
+                    &enc_msg,
+                )
+                .is_ok());
+
+            // the catapult label does not decrypt it, and is the default cipher.
+            assert!(CryptoSym::decrypt_from(
+                &receiver_kp.private_key(),
+                &signer_kp.public_key(),
+                &enc_msg,
+            )
+            .is_err());
+
+            let enc_msg = signer_kp
+                .encrypt_for(&receiver_kp.public_key(), MESSAGE)
+                .unwrap();
+            let dec_msg = CryptoSym::with_kdf_label(CATAPULT_KDF_LABEL)
+                .decrypt_from(
+                    &receiver_kp.private_key(),
+                    &signer_kp.public_key(),
+                    &enc_msg,
+                )
+                .unwrap();
+            assert_eq!(dec_msg, MESSAGE);
+        }
+    }
+
+    pub mod tests_ecies {
+        use symbol_crypto_core::prelude::KpSym;
+        use symbol_crypto_core::sym::ecies;
+
+        use super::*;
+
+        #[test]
+        fn test_ecies_round_trip() {
+            let receiver_kp = Keypair::<KpSym>::random();
+
+            let enc_msg =
+                ecies::encrypt(&receiver_kp.public_key(), b"info1", b"info2", MESSAGE).unwrap();
+
+            let dec_msg =
+                ecies::decrypt(&receiver_kp.private_key(), b"info1", b"info2", &enc_msg).unwrap();
+            assert_eq!(dec_msg, MESSAGE);
+
+            assert!(
+                ecies::decrypt(&receiver_kp.private_key(), b"other", b"info2", &enc_msg).is_err()
+            );
+            assert!(
+                ecies::decrypt(&receiver_kp.private_key(), b"info1", b"other", &enc_msg).is_err()
+            );
+            assert!(ecies::decrypt(
+                &receiver_kp.private_key(),
+                b"info1",
+                b"info2",
+                &enc_msg[..40]
+            )
+            .is_err());
+        }
+    }
+
+    pub mod tests_envelope_mode {
+        use symbol_crypto_core::prelude::envelope_mode::{
+            envelope_bucket, pad_to_bucket, unpad_bucket, ENVELOPE_BUCKETS,
+        };
+        use symbol_crypto_core::prelude::{
+            CryptoNis1, CryptoNis1V2, CryptoSym, EnvelopeMode, KeyPairSchema, KpNis1, KpSym,
+        };
+
+        use super::*;
+
+        fn ciphertext_sizes<C: BlockCipher, Kp: KeyPairSchema>(
+            msg_sizes: &[usize],
+            mode: EnvelopeMode,
+        ) -> Vec<usize> {
+            let signer_kp = Keypair::<Kp>::random();
+            let receiver_kp = Keypair::<Kp>::random();
+
+            msg_sizes
+                .iter()
+                .map(|size| {
+                    let msg = vec![0x5A; *size];
+                    let enc_msg = C::encrypt_for_mode(
+                        &signer_kp.private_key(),
+                        &receiver_kp.public_key(),
+                        &msg,
+                        mode,
+                    )
+                    .unwrap();
+                    let dec_msg = C::decrypt_from_mode(
+                        &receiver_kp.private_key(),
+                        &signer_kp.public_key(),
+                        &enc_msg,
+                        mode,
+                    )
+                    .unwrap();
+                    assert_eq!(dec_msg, msg);
+                    enc_msg.len()
+                })
+                .collect()
+        }
+
+        fn assert_constant_size<C: BlockCipher, Kp: KeyPairSchema>() {
+            let sizes = ciphertext_sizes::<C, Kp>(&[0, 1, 17, 60], EnvelopeMode::ConstantSize);
+            assert!(sizes.iter().all(|size| *size == sizes[0]));
+
+            let sizes = ciphertext_sizes::<C, Kp>(&[61, 100, 252], EnvelopeMode::ConstantSize);
+            assert!(sizes.iter().all(|size| *size == sizes[0]));
+
+            let sizes = ciphertext_sizes::<C, Kp>(&[0, 1, 17, 60], EnvelopeMode::Standard);
+            assert!(sizes[0] < sizes[3]);
+        }
+
+        #[test]
+        fn test_constant_size_sym() {
+            assert_constant_size::<CryptoSym, KpSym>();
+        }
+
+        #[test]
+        fn test_constant_size_nis1() {
+            assert_constant_size::<CryptoNis1, KpNis1>();
+            assert_constant_size::<CryptoNis1V2, KpNis1>();
+        }
+
+        #[test]
+        fn test_buckets() {
+            assert_eq!(envelope_bucket(0), Some(ENVELOPE_BUCKETS[0]));
+            assert_eq!(envelope_bucket(60), Some(64));
+            assert_eq!(envelope_bucket(61), Some(256));
+            assert_eq!(envelope_bucket(4092), Some(4096));
+            assert_eq!(envelope_bucket(4093), None);
+            assert!(pad_to_bucket(&[0; 4093]).is_err());
+
+            let frame = pad_to_bucket(MESSAGE).unwrap();
+            assert_eq!(frame.len(), 64);
+            assert_eq!(unpad_bucket(&frame).unwrap(), MESSAGE);
+        }
+
+        #[test]
+        fn test_unpad_rejects_malformed_frames() {
+            let frame = pad_to_bucket(MESSAGE).unwrap();
+            assert!(unpad_bucket(&frame[..63]).is_err());
+
+            let mut bad_padding = frame.to_vec();
+            bad_padding[63] = 1;
+            assert!(unpad_bucket(&bad_padding).is_err());
+
+            let mut bad_len = frame.to_vec();
+            bad_len[0] = 61;
+            assert!(unpad_bucket(&bad_len).is_err());
+        }
+
+        #[test]
+        fn test_mode_must_match() {
+            let signer_kp = Keypair::<KpSym>::random();
+            let receiver_kp = Keypair::<KpSym>::random();
+
+            let enc_msg = CryptoSym::encrypt_for_mode(
+                &signer_kp.private_key(),
+                &receiver_kp.public_key(),
+                MESSAGE,
+                EnvelopeMode::Standard,
+            )
+            .unwrap();
+            assert!(CryptoSym::decrypt_from_mode(
+                &receiver_kp.private_key(),
+                &signer_kp.public_key(),
+                &enc_msg,
+                EnvelopeMode::ConstantSize,
+            )
+            .is_err());
+        }
+    }
+}
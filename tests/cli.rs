@@ -0,0 +1,86 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(all(test, feature = "cli"))]
+pub mod tests {
+    use std::process::Command;
+
+    use symbol_crypto_core::prelude::{Keypair, KpNis1, KpSym};
+
+    const PRIVATE_KEY: &str = "E88283CE35FE74C89FFCB2D8BFA0A2CF6108BDC0D07606DEE34D161C30AC2F1E";
+    const RECEIVER_KEY: &str = "A22A4BBF126A2D7D7ECE823174DFD184C5DE0FDE4CB2075D30CFA409F7EF8908";
+
+    fn run(args: &[&str]) -> (bool, String) {
+        let output = Command::new(env!("CARGO_BIN_EXE_symbol-crypto"))
+            .args(args)
+            .output()
+            .unwrap();
+        (
+            output.status.success(),
+            String::from_utf8(output.stdout).unwrap().trim().to_string(),
+        )
+    }
+
+    #[test]
+    fn test_derive() {
+        let (ok, public_key) = run(&["derive", PRIVATE_KEY]);
+        assert!(ok);
+        assert_eq!(
+            public_key,
+            format!(
+                "{:X}",
+                Keypair::<KpSym>::from_hex_private_key(PRIVATE_KEY)
+                    .unwrap()
+                    .public_key()
+            )
+        );
+
+        let (ok, public_key) = run(&["--nis1", "derive", PRIVATE_KEY]);
+        assert!(ok);
+        assert_eq!(
+            public_key,
+            format!(
+                "{:X}",
+                Keypair::<KpNis1>::from_hex_private_key(PRIVATE_KEY)
+                    .unwrap()
+                    .public_key()
+            )
+        );
+    }
+
+    #[test]
+    fn test_sign_verify() {
+        let (_, public_key) = run(&["derive", PRIVATE_KEY]);
+        let (ok, signature) = run(&["sign", PRIVATE_KEY, "CAFE"]);
+        assert!(ok);
+
+        assert!(run(&["verify", &public_key, "CAFE", &signature]).0);
+        assert!(!run(&["verify", &public_key, "CAFF", &signature]).0);
+        assert!(!run(&["--nis1", "verify", &public_key, "CAFE", &signature]).0);
+    }
+
+    #[test]
+    fn test_encrypt_decrypt() {
+        let (_, sender_pk) = run(&["derive", PRIVATE_KEY]);
+        let (_, receiver_pk) = run(&["derive", RECEIVER_KEY]);
+
+        let (ok, encrypted) = run(&["encrypt", PRIVATE_KEY, &receiver_pk, "53796D626F6C"]);
+        assert!(ok);
+        let (ok, decrypted) = run(&["decrypt", RECEIVER_KEY, &sender_pk, &encrypted]);
+        assert!(ok);
+        assert_eq!(decrypted, "53796D626F6C");
+    }
+
+    #[test]
+    fn test_invalid_usage() {
+        assert!(!run(&[]).0);
+        assert!(!run(&["unknown"]).0);
+        assert!(!run(&["sign", PRIVATE_KEY]).0);
+        assert!(!run(&["derive", "XYZ"]).0);
+    }
+}
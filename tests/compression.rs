@@ -0,0 +1,141 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(test)]
+pub mod tests {
+    use symbol_crypto_core::prelude::{
+        BlockCipher, CryptoSym, Keypair, KpSym, MAX_MESSAGE_SIZE, SYM_ENCRYPT_OVERHEAD,
+    };
+    use symbol_crypto_core::sym::compression::{
+        compress, decompress, Codec, COMPRESSED_MESSAGE_VERSION, MAX_DECOMPRESSED_SIZE,
+    };
+
+    const DEFLATE_BOMB: &str = "EDC101010000008220FFAFAE2140010000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000C00D";
+
+    fn long_message() -> Vec<u8> {
+        b"transfer 10 xym to the treasury account; "
+            .iter()
+            .cycle()
+            .take(3 * MAX_MESSAGE_SIZE)
+            .copied()
+            .collect()
+    }
+
+    #[test]
+    fn test_encrypt_decrypt_compressed() {
+        let signer = Keypair::<KpSym>::random();
+        let receiver = Keypair::<KpSym>::random();
+        let msg = long_message();
+
+        for codec in [Codec::None, Codec::Deflate, Codec::Zstd].iter() {
+            let enc_msg = CryptoSym::encrypt_for_compressed(
+                &signer.private_key(),
+                &receiver.public_key(),
+                &msg,
+                *codec,
+            )
+            .unwrap();
+            if *codec != Codec::None {
+                assert!(enc_msg.len() <= MAX_MESSAGE_SIZE);
+            }
+
+            let dec_msg = CryptoSym::decrypt_from_compressed(
+                &receiver.private_key(),
+                &signer.public_key(),
+                &enc_msg,
+            )
+            .unwrap();
+            assert_eq!(dec_msg, msg);
+        }
+    }
+
+    #[test]
+    fn test_version_and_codec_bytes() {
+        let msg = long_message();
+        let deflate = compress(&msg, Codec::Deflate).unwrap();
+        assert_eq!(
+            deflate[..2],
+            [COMPRESSED_MESSAGE_VERSION, Codec::Deflate as u8]
+        );
+        let zstd = compress(&msg, Codec::Zstd).unwrap();
+        assert_eq!(zstd[..2], [COMPRESSED_MESSAGE_VERSION, Codec::Zstd as u8]);
+    }
+
+    #[test]
+    fn test_incompressible_message_is_stored() {
+        let msg = Keypair::<KpSym>::random().public_key().to_fixed_bytes();
+        let compressed = compress(&msg, Codec::Deflate).unwrap();
+        assert_eq!(
+            compressed[..2],
+            [COMPRESSED_MESSAGE_VERSION, Codec::None as u8]
+        );
+        assert_eq!(compressed[2..], msg[..]);
+        assert_eq!(decompress(&compressed).unwrap(), msg);
+
+        let signer = Keypair::<KpSym>::random();
+        let enc_msg = CryptoSym::encrypt_for_compressed(
+            &signer.private_key(),
+            &signer.public_key(),
+            &msg,
+            Codec::Deflate,
+        )
+        .unwrap();
+        assert_eq!(enc_msg.len(), SYM_ENCRYPT_OVERHEAD + 2 + msg.len());
+    }
+
+    #[test]
+    fn test_decompress_invalid() {
+        let compressed = compress(&long_message(), Codec::Deflate).unwrap();
+
+        assert!(decompress(&compressed[..1]).is_err());
+
+        let mut version = compressed.clone();
+        version[0] = 0;
+        assert!(decompress(&version).is_err());
+
+        let mut codec = compressed.clone();
+        codec[1] = 9;
+        assert!(decompress(&codec).is_err());
+
+        let mut corrupted = compressed;
+        let len = corrupted.len();
+        corrupted.truncate(len / 2);
+        assert!(decompress(&corrupted).is_err());
+    }
+
+    #[test]
+    fn test_decompression_limit() {
+        assert!(compress(&vec![0u8; MAX_DECOMPRESSED_SIZE + 1], Codec::Deflate).is_err());
+
+        // raw deflate of MAX_DECOMPRESSED_SIZE + 1 zero bytes.
+        let mut bomb = vec![COMPRESSED_MESSAGE_VERSION, Codec::Deflate as u8];
+        bomb.extend_from_slice(&hex::decode(DEFLATE_BOMB).unwrap());
+        assert!(decompress(&bomb).is_err());
+
+        let at_limit = compress(&vec![0u8; MAX_DECOMPRESSED_SIZE], Codec::Deflate).unwrap();
+        assert_eq!(decompress(&at_limit).unwrap().len(), MAX_DECOMPRESSED_SIZE);
+    }
+
+    #[test]
+    fn test_plain_decrypt_sees_the_header() {
+        let signer = Keypair::<KpSym>::random();
+        let msg = long_message();
+        let enc_msg = CryptoSym::encrypt_for_compressed(
+            &signer.private_key(),
+            &signer.public_key(),
+            &msg,
+            Codec::Deflate,
+        )
+        .unwrap();
+
+        let plain =
+            CryptoSym::decrypt_from(&signer.private_key(), &signer.public_key(), &enc_msg).unwrap();
+        assert_eq!(plain[0], COMPRESSED_MESSAGE_VERSION);
+        assert_ne!(plain, msg);
+    }
+}
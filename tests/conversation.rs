@@ -0,0 +1,78 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(test)]
+pub mod tests {
+    use symbol_crypto_core::prelude::{Keypair, KpSym};
+    use symbol_crypto_core::sym::{conversation_key, decrypt_in_thread, encrypt_in_thread};
+
+    #[test]
+    fn test_conversation_key_is_symmetric() {
+        let alice = Keypair::<KpSym>::random();
+        let bob = Keypair::<KpSym>::random();
+
+        assert_eq!(
+            conversation_key(alice.as_ref(), &bob.public_key(), b"thread-1"),
+            conversation_key(bob.as_ref(), &alice.public_key(), b"thread-1")
+        );
+    }
+
+    #[test]
+    fn test_conversation_key_per_thread() {
+        let alice = Keypair::<KpSym>::random();
+        let bob = Keypair::<KpSym>::random();
+        let carol = Keypair::<KpSym>::random();
+
+        let key = conversation_key(alice.as_ref(), &bob.public_key(), b"thread-1");
+        assert_eq!(
+            key,
+            conversation_key(alice.as_ref(), &bob.public_key(), b"thread-1")
+        );
+        assert_ne!(
+            key,
+            conversation_key(alice.as_ref(), &bob.public_key(), b"thread-2")
+        );
+        assert_ne!(
+            key,
+            conversation_key(alice.as_ref(), &carol.public_key(), b"thread-1")
+        );
+    }
+
+    #[test]
+    fn test_thread_messages_with_shared_key() {
+        let alice = Keypair::<KpSym>::random();
+        let bob = Keypair::<KpSym>::random();
+
+        let thread_1 = conversation_key(alice.as_ref(), &bob.public_key(), b"thread-1");
+        let thread_2 = conversation_key(alice.as_ref(), &bob.public_key(), b"thread-2");
+        let enc_msg_1 = encrypt_in_thread(&thread_1, b"first thread").unwrap();
+        let enc_msg_2 = encrypt_in_thread(&thread_2, b"second thread").unwrap();
+
+        // a third party granted the key of the first thread only.
+        let granted = thread_1;
+        assert_eq!(
+            decrypt_in_thread(&granted, &enc_msg_1).unwrap(),
+            b"first thread".to_vec()
+        );
+        assert!(decrypt_in_thread(&granted, &enc_msg_2).is_err());
+    }
+
+    #[test]
+    fn test_decrypt_in_thread_rejects_tampered_message() {
+        let alice = Keypair::<KpSym>::random();
+        let bob = Keypair::<KpSym>::random();
+
+        let key = conversation_key(alice.as_ref(), &bob.public_key(), b"thread-1");
+        let mut enc_msg = encrypt_in_thread(&key, b"hello").unwrap();
+        let last = enc_msg.len() - 1;
+        enc_msg[last] ^= 1;
+
+        assert!(decrypt_in_thread(&key, &enc_msg).is_err());
+        assert!(decrypt_in_thread(&key, &enc_msg[..10]).is_err());
+    }
+}
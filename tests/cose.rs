@@ -0,0 +1,43 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(test)]
+pub mod tests {
+    use symbol_crypto_core::prelude::{Keypair, KpSym};
+    use symbol_crypto_core::sym::cose;
+
+    const PAYLOAD: &[u8] = b"device-identity";
+
+    #[test]
+    fn test_sign1_verify1() {
+        let kp = Keypair::<KpSym>::random();
+
+        let cose = cose::sign1(kp.as_ref(), PAYLOAD, b"aad").unwrap();
+        // tagged COSE_Sign1, tag 18.
+        assert_eq!(cose[0], 0xd2);
+
+        let payload = cose::verify1(&kp.public_key(), &cose, b"aad").unwrap();
+        assert_eq!(payload, PAYLOAD);
+    }
+
+    #[test]
+    fn test_verify1_rejects_wrong_inputs() {
+        let kp = Keypair::<KpSym>::random();
+        let other_kp = Keypair::<KpSym>::random();
+
+        let mut cose = cose::sign1(kp.as_ref(), PAYLOAD, &[]).unwrap();
+
+        assert!(cose::verify1(&other_kp.public_key(), &cose, &[]).is_err());
+        assert!(cose::verify1(&kp.public_key(), &cose, b"aad").is_err());
+
+        let last = cose.len() - 1;
+        cose[last] ^= 0xff;
+        assert!(cose::verify1(&kp.public_key(), &cose, &[]).is_err());
+        assert!(cose::verify1(&kp.public_key(), &cose[..10], &[]).is_err());
+    }
+}
@@ -0,0 +1,70 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(test)]
+pub mod tests {
+    use symbol_crypto_core::prelude::{Keypair, KpSym, PublicKey};
+    use symbol_crypto_core::sym::{backup_for_cosigners, decrypt_share, recover_from_cosigners};
+
+    fn cosigners(count: usize) -> Vec<Keypair<KpSym>> {
+        (0..count).map(|_| Keypair::<KpSym>::random()).collect()
+    }
+
+    fn public_keys(kps: &[Keypair<KpSym>]) -> Vec<PublicKey> {
+        kps.iter().map(|kp| kp.public_key()).collect()
+    }
+
+    #[test]
+    fn test_backup_recover() {
+        let account = Keypair::<KpSym>::random();
+        let cosigners = cosigners(3);
+        let backup = backup_for_cosigners(&account, &public_keys(&cosigners), 2).unwrap();
+        assert_eq!(backup.shares.len(), 3);
+
+        let shares: Vec<_> = cosigners[1..]
+            .iter()
+            .map(|cosigner| decrypt_share(cosigner, &backup).unwrap())
+            .collect();
+        assert_eq!(
+            recover_from_cosigners(&backup, &shares).unwrap(),
+            account.private_key()
+        );
+        assert!(recover_from_cosigners(&backup, &shares[..1]).is_err());
+
+        // only the cosigners can decrypt their share.
+        let outsider = Keypair::<KpSym>::random();
+        assert!(decrypt_share(&outsider, &backup).is_err());
+    }
+
+    #[test]
+    fn test_recover_tampered_share() {
+        let account = Keypair::<KpSym>::random();
+        let cosigners = cosigners(3);
+        let backup = backup_for_cosigners(&account, &public_keys(&cosigners), 2).unwrap();
+
+        let mut shares: Vec<_> = cosigners
+            .iter()
+            .map(|cosigner| decrypt_share(cosigner, &backup).unwrap())
+            .collect();
+        shares[0].value[0] ^= 1;
+
+        let err = recover_from_cosigners(&backup, &shares[..2]).unwrap_err();
+        assert!(err.to_string().contains("commitment"));
+        assert!(recover_from_cosigners(&backup, &shares[1..]).is_ok());
+    }
+
+    #[test]
+    fn test_backup_invalid_cosigners() {
+        let account = Keypair::<KpSym>::random();
+        let cosigners = public_keys(&cosigners(2));
+
+        assert!(backup_for_cosigners(&account, &cosigners, 3).is_err());
+        assert!(backup_for_cosigners(&account, &[cosigners[0], cosigners[0]], 1).is_err());
+        assert!(backup_for_cosigners(&account, &[account.public_key()], 1).is_err());
+    }
+}
@@ -0,0 +1,117 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(test)]
+pub mod tests {
+    use std::collections::BTreeSet;
+
+    use symbol_crypto_core::prelude::{Keypair, KpSym, H256};
+    use symbol_crypto_core::sym::cosigning::{
+        CosigningSession, DetachedCosignature, DETACHED_COSIGNATURE_SIZE,
+    };
+
+    const EXPIRES_AT: u64 = 1_700_000_000;
+
+    fn session(cosigners: &[Keypair<KpSym>], required: usize) -> CosigningSession {
+        let keys: BTreeSet<_> = cosigners.iter().map(|kp| kp.public_key()).collect();
+        CosigningSession::new(H256::repeat_byte(0xAB), keys, required, EXPIRES_AT).unwrap()
+    }
+
+    fn cosigners() -> Vec<Keypair<KpSym>> {
+        (0..3).map(|_| Keypair::<KpSym>::random()).collect()
+    }
+
+    #[test]
+    fn test_collect_until_complete() {
+        let cosigners = cosigners();
+        let mut session = session(&cosigners, 2);
+        let hash = session.aggregate_hash();
+
+        let first = DetachedCosignature::sign(&cosigners[0], hash).unwrap();
+        assert!(!session.add(&first, EXPIRES_AT - 10).unwrap());
+        assert_eq!(session.missing().len(), 2);
+        assert!(session.add(&first, EXPIRES_AT - 10).is_err());
+
+        let second = DetachedCosignature::sign(&cosigners[2], hash).unwrap();
+        assert!(session.add(&second, EXPIRES_AT).unwrap());
+        assert!(session.is_complete());
+        assert_eq!(
+            session.missing(),
+            std::iter::once(cosigners[1].public_key()).collect()
+        );
+        assert_eq!(session.cosignatures(), &[first.cosignature(), second.cosignature()]);
+    }
+
+    #[test]
+    fn test_rejected_cosignatures() {
+        let cosigners = cosigners();
+        let mut session = session(&cosigners, 3);
+        let hash = session.aggregate_hash();
+
+        let stranger = Keypair::<KpSym>::random();
+        let cosignature = DetachedCosignature::sign(&stranger, hash).unwrap();
+        assert!(session.add(&cosignature, 0).is_err());
+
+        let other_aggregate = H256::repeat_byte(0xCD);
+        let cosignature = DetachedCosignature::sign(&cosigners[0], other_aggregate).unwrap();
+        assert!(session.add(&cosignature, 0).is_err());
+
+        let mut cosignature = DetachedCosignature::sign(&cosigners[0], hash).unwrap();
+        assert!(session.add(&cosignature, EXPIRES_AT + 1).is_err());
+        cosignature.signature = cosigners[0].try_sign(b"another message").unwrap();
+        assert!(cosignature.verify().is_err());
+        assert!(session.add(&cosignature, 0).is_err());
+        assert!(session.cosignatures().is_empty());
+    }
+
+    #[test]
+    fn test_new_invalid_threshold() {
+        let cosigners = cosigners();
+        let keys: BTreeSet<_> = cosigners.iter().map(|kp| kp.public_key()).collect();
+        let hash = H256::repeat_byte(0xAB);
+        assert!(CosigningSession::new(hash, keys.clone(), 0, EXPIRES_AT).is_err());
+        assert!(CosigningSession::new(hash, keys, 4, EXPIRES_AT).is_err());
+    }
+
+    #[test]
+    fn test_session_persistence() {
+        let cosigners = cosigners();
+        let mut session = session(&cosigners, 2);
+        let cosignature =
+            DetachedCosignature::sign(&cosigners[1], session.aggregate_hash()).unwrap();
+        session.add(&cosignature, 0).unwrap();
+
+        let bytes = session.to_bytes();
+        let restored = CosigningSession::from_bytes(&bytes).unwrap();
+        assert_eq!(restored, session);
+        assert_eq!(restored.expires_at(), EXPIRES_AT);
+        assert_eq!(restored.required(), 2);
+
+        // a tampered signature is detected on load.
+        let mut tampered = bytes.clone();
+        let last = tampered.len() - 1;
+        tampered[last] ^= 0x01;
+        assert!(CosigningSession::from_bytes(&tampered).is_err());
+
+        for len in 0..bytes.len() {
+            assert!(CosigningSession::from_bytes(&bytes[..len]).is_err());
+        }
+    }
+
+    #[test]
+    fn test_detached_cosignature_bytes() {
+        let kp = Keypair::<KpSym>::random();
+        let cosignature = DetachedCosignature::sign(&kp, H256::repeat_byte(0x11)).unwrap();
+        cosignature.verify().unwrap();
+
+        let bytes = cosignature.to_bytes();
+        assert_eq!(bytes.len(), DETACHED_COSIGNATURE_SIZE);
+        assert_eq!(DetachedCosignature::from_bytes(&bytes).unwrap(), cosignature);
+        assert!(DetachedCosignature::from_bytes(&bytes[1..]).is_err());
+    }
+}
@@ -0,0 +1,73 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(test)]
+pub mod tests {
+    use std::str::FromStr;
+
+    use symbol_crypto_core::prelude::{ct_hex_decode, ct_hex_encode, PrivateKey};
+
+    const PRIVATE_KEY: &str = "575DBB3062267EFF57C970A336EBBC8FBCFE12C5BD3ED7BC11EB0481D7704CED";
+
+    #[test]
+    fn test_ct_hex_encode_matches_hex() {
+        let bytes: Vec<u8> = (0..=255).collect();
+        assert_eq!(ct_hex_encode(&bytes, false), hex::encode(&bytes));
+        assert_eq!(ct_hex_encode(&bytes, true), hex::encode_upper(&bytes));
+        assert_eq!(ct_hex_encode(&[], true), "");
+    }
+
+    #[test]
+    fn test_ct_hex_decode_matches_hex() {
+        let bytes: Vec<u8> = (0..=255).collect();
+        assert_eq!(ct_hex_decode(&hex::encode(&bytes)).unwrap(), bytes);
+        assert_eq!(ct_hex_decode(&hex::encode_upper(&bytes)).unwrap(), bytes);
+        assert_eq!(ct_hex_decode("aBcD").unwrap(), vec![0xAB, 0xCD]);
+        assert!(ct_hex_decode("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_ct_hex_decode_rejects_every_non_hex_byte() {
+        for c in 0..=255u8 {
+            let valid = c.is_ascii_hexdigit();
+            let input = [b'0', c];
+            if let Ok(input) = std::str::from_utf8(&input) {
+                assert_eq!(ct_hex_decode(input).is_ok(), valid, "{:?}", input);
+            }
+        }
+        assert!(ct_hex_decode("0é").is_err());
+    }
+
+    #[test]
+    fn test_ct_hex_decode_rejects_odd_length() {
+        assert!(ct_hex_decode("ABC").is_err());
+    }
+
+    #[test]
+    fn test_private_key_from_hex_ct() {
+        let private_key = PrivateKey::from_hex_ct(PRIVATE_KEY).unwrap();
+        assert_eq!(private_key, PrivateKey::from_str(PRIVATE_KEY).unwrap());
+        assert_eq!(
+            PrivateKey::from_hex_ct(&PRIVATE_KEY.to_lowercase()).unwrap(),
+            private_key
+        );
+        assert_eq!(*private_key.to_hex_ct(), PRIVATE_KEY);
+    }
+
+    #[test]
+    fn test_private_key_from_hex_ct_invalid() {
+        let err = PrivateKey::from_hex_ct("").unwrap_err();
+        assert_eq!(err.to_string(), "private_key it's not hex.");
+
+        let err = PrivateKey::from_hex_ct(&PRIVATE_KEY.replace('5', "x")).unwrap_err();
+        assert_eq!(err.to_string(), "private_key it's not hex.");
+
+        let err = PrivateKey::from_hex_ct(&PRIVATE_KEY[..62]).unwrap_err();
+        assert_eq!(err.to_string(), "private key has unexpected size 62");
+    }
+}
@@ -0,0 +1,66 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(test)]
+pub mod tests {
+    use symbol_crypto_core::prelude::{Keypair, KpSym};
+    use symbol_crypto_core::sym::{
+        create_delegation_request, is_delegation_request, parse_delegation_request,
+        DELEGATION_MESSAGE_LENGTH,
+    };
+
+    #[test]
+    fn test_create_and_parse_delegation_request() {
+        let remote_kp = Keypair::<KpSym>::random();
+        let vrf_kp = Keypair::<KpSym>::random();
+        let node_kp = Keypair::<KpSym>::random();
+
+        let payload = create_delegation_request(
+            &remote_kp.private_key(),
+            &vrf_kp.private_key(),
+            &node_kp.public_key(),
+        )
+        .unwrap();
+
+        assert_eq!(payload.len(), DELEGATION_MESSAGE_LENGTH);
+        assert!(is_delegation_request(&payload));
+
+        let request = parse_delegation_request(&node_kp.private_key(), &payload).unwrap();
+
+        assert_eq!(*request.remote_private_key, remote_kp.private_key());
+        assert_eq!(*request.vrf_private_key, vrf_kp.private_key());
+    }
+
+    #[test]
+    fn test_parse_delegation_request_wrong_node_key() {
+        let remote_kp = Keypair::<KpSym>::random();
+        let node_kp = Keypair::<KpSym>::random();
+        let other_kp = Keypair::<KpSym>::random();
+
+        let payload = create_delegation_request(
+            &remote_kp.private_key(),
+            &remote_kp.private_key(),
+            &node_kp.public_key(),
+        )
+        .unwrap();
+
+        assert!(parse_delegation_request(&other_kp.private_key(), &payload).is_err());
+    }
+
+    #[test]
+    fn test_parse_delegation_request_malformed() {
+        let node_kp = Keypair::<KpSym>::random();
+
+        assert!(parse_delegation_request(&node_kp.private_key(), &[]).is_err());
+        assert!(parse_delegation_request(&node_kp.private_key(), &[0xFE, 0x2A]).is_err());
+
+        let mut payload = vec![0xFF; DELEGATION_MESSAGE_LENGTH];
+        payload[..8].copy_from_slice(&[0xFE, 0x2A, 0x80, 0x61, 0x57, 0x73, 0x01, 0xE2]);
+        assert!(parse_delegation_request(&node_kp.private_key(), &payload).is_err());
+    }
+}
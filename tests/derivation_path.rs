@@ -0,0 +1,69 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(test)]
+pub mod tests {
+    use std::str::FromStr;
+
+    use symbol_crypto_core::prelude::{DerivationPath, HARDENED_OFFSET};
+
+    const SYMBOL_PATH: &str = "m/44'/4343'/0'/0'/0'";
+
+    #[test]
+    fn test_parse_display() {
+        let path = DerivationPath::from_str(SYMBOL_PATH).unwrap();
+        assert_eq!(path.to_string(), SYMBOL_PATH);
+        assert_eq!(path, DerivationPath::symbol_account(0).unwrap());
+        assert_eq!(path.depth(), 5);
+        assert_eq!(
+            path.iter().map(|c| c - HARDENED_OFFSET).collect::<Vec<_>>(),
+            vec![44, 4343, 0, 0, 0]
+        );
+
+        assert_eq!(
+            DerivationPath::from_str("m/44h/43H/2'/0'/0'").unwrap(),
+            DerivationPath::nis1_account(2).unwrap()
+        );
+        assert!(DerivationPath::from_str("m").unwrap().is_master());
+        assert_eq!(
+            DerivationPath::from_str("m/44'").unwrap().child(7).unwrap(),
+            DerivationPath::from_str("m/44'/7'").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_invalid_paths() {
+        for path in [
+            "",
+            "44'/4343'",
+            "m/",
+            "m/44'/4343'/0",
+            "m/44'//0'",
+            "m/-1'",
+            "m/+1'",
+            "m/2147483648'",
+            "m/x'",
+            "n/44'",
+        ]
+        .iter()
+        {
+            assert!(DerivationPath::from_str(path).is_err(), "{}", path);
+        }
+        assert!(DerivationPath::new(vec![44]).is_err());
+        assert!(DerivationPath::new(vec![HARDENED_OFFSET; 256]).is_err());
+    }
+
+    #[test]
+    fn test_serde() {
+        let path = DerivationPath::symbol_account(3).unwrap();
+        let json = serde_json::to_string(&path).unwrap();
+        assert_eq!(json, "\"m/44'/4343'/3'/0'/0'\"");
+        assert_eq!(serde_json::from_str::<DerivationPath>(&json).unwrap(), path);
+        assert!(serde_json::from_str::<DerivationPath>("\"m/44\"").is_err());
+    }
+}
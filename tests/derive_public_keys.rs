@@ -0,0 +1,59 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(test)]
+pub mod tests {
+    use symbol_crypto_core::prelude::{KeyPairSchema, Keypair, KpNis1, KpSym, PrivateKey};
+
+    fn private_keys(count: usize) -> Vec<PrivateKey> {
+        (0..count)
+            .map(|_| Keypair::<KpSym>::random().private_key())
+            .collect()
+    }
+
+    fn assert_matches_keypair<Kp: KeyPairSchema>() {
+        let keys = private_keys(100);
+        let public_keys = Keypair::<Kp>::derive_public_keys(&keys);
+        assert_eq!(public_keys.len(), keys.len());
+        for (private_key, public_key) in keys.iter().zip(public_keys.iter()) {
+            assert_eq!(
+                Keypair::<Kp>::from_private_key(*private_key).public_key(),
+                *public_key
+            );
+        }
+    }
+
+    #[test]
+    fn test_derive_public_keys_sym() {
+        assert_matches_keypair::<KpSym>();
+    }
+
+    #[test]
+    fn test_derive_public_keys_nis1() {
+        assert_matches_keypair::<KpNis1>();
+    }
+
+    #[test]
+    fn test_derive_public_keys_vector() {
+        let private_key: PrivateKey =
+            "575DBB3062267EFF57C970A336EBBC8FBCFE12C5BD3ED7BC11EB0481D7704CED"
+                .parse()
+                .unwrap();
+        let public_keys = Keypair::<KpSym>::derive_public_keys(&[private_key]);
+        assert_eq!(
+            format!("{:X}", public_keys[0]),
+            "2E834140FD66CF87B254A693A2C7862C819217B676D3943267156625E816EC6F"
+        );
+    }
+
+    #[test]
+    fn test_derive_public_keys_empty() {
+        assert!(Keypair::<KpSym>::derive_public_keys(&[]).is_empty());
+        assert!(Keypair::<KpNis1>::derive_public_keys(&[]).is_empty());
+    }
+}
@@ -0,0 +1,66 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(test)]
+pub mod tests {
+    use symbol_crypto_core::prelude::{KeyPairSchema, KpSym, PublicKey};
+
+    const RECEIPT: &[u8] = b"receipt: 100 symbol.xym for order 42";
+
+    #[test]
+    fn test_designated_signature() {
+        let alice = KpSym::random();
+        let bob = KpSym::random();
+        let carol = KpSym::random();
+
+        let signature = alice.sign_for_verifier(&bob.public_key, RECEIPT).unwrap();
+        assert!(bob
+            .verify_designated(&alice.public_key, RECEIPT, signature)
+            .is_ok());
+
+        // neither another verifier nor a plain signature check can validate it.
+        assert!(carol
+            .verify_designated(&alice.public_key, RECEIPT, signature)
+            .is_err());
+        assert!(alice.verify(RECEIPT, signature).is_err());
+
+        assert!(bob
+            .verify_designated(&alice.public_key, b"receipt: 1 symbol.xym", signature)
+            .is_err());
+        assert!(bob
+            .verify_designated(&carol.public_key, RECEIPT, signature)
+            .is_err());
+    }
+
+    #[test]
+    fn test_designated_signature_deterministic() {
+        let alice = KpSym::random();
+        let bob = KpSym::random();
+        let carol = KpSym::random();
+
+        let signature = alice.sign_for_verifier(&bob.public_key, RECEIPT).unwrap();
+        assert_eq!(
+            signature,
+            alice.sign_for_verifier(&bob.public_key, RECEIPT).unwrap()
+        );
+        assert_ne!(
+            signature,
+            alice.sign_for_verifier(&carol.public_key, RECEIPT).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_designated_invalid_verifier() {
+        let alice = KpSym::random();
+        let identity = PublicKey::from_slice(&[
+            1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0,
+        ]);
+        assert!(alice.sign_for_verifier(&identity, RECEIPT).is_err());
+    }
+}
@@ -0,0 +1,60 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(test)]
+pub mod tests {
+    use std::str::FromStr;
+
+    use symbol_crypto_core::prelude::{did, PublicKey};
+
+    // did:key test vector from the did:key method specification.
+    const SPEC_DID: &str = "did:key:z6MkiTBz1ymuepAQ4HEHYSF1H8quG5GLVVQR3djdX3mDooWp";
+    const SPEC_PUBLIC_KEY: &str =
+        "3b6a27bcceb6a42d62a3a8d02a6f0d73653215771de243a63ac048a18b59da29";
+
+    #[test]
+    fn test_did_key_spec_vector() {
+        let pk = PublicKey::from_str(SPEC_PUBLIC_KEY).unwrap();
+
+        assert_eq!(did::to_did_key(&pk), SPEC_DID);
+        assert_eq!(did::from_did_key(SPEC_DID).unwrap(), pk);
+    }
+
+    #[test]
+    fn test_did_key_invalid() {
+        assert!(did::from_did_key("did:web:example.com").is_err());
+        assert!(
+            did::from_did_key("did:key:6MkiTBz1ymuepAQ4HEHYSF1H8quG5GLVVQR3djdX3mDooWp").is_err()
+        );
+        assert!(
+            did::from_did_key("did:key:z6MkiTBz1ymuepAQ4HEHYSF1H8quG5GLVVQR3djdX3mDoo").is_err()
+        );
+        assert!(did::from_did_key(&format!("{}#z6Mkother", SPEC_DID)).is_err());
+    }
+
+    #[test]
+    fn test_did_document() {
+        let pk = PublicKey::from_str(SPEC_PUBLIC_KEY).unwrap();
+        let document = did::did_document(&pk);
+
+        assert_eq!(document.id, SPEC_DID);
+        assert_eq!(document.verification_method.len(), 1);
+
+        let method = &document.verification_method[0];
+        assert_eq!(method.type_, "Ed25519VerificationKey2020");
+        assert_eq!(did::from_did_key(&method.id).unwrap(), pk);
+        assert_eq!(document.authentication, vec![method.id.clone()]);
+
+        let json = serde_json::to_value(&document).unwrap();
+        assert_eq!(
+            json["verificationMethod"][0]["publicKeyMultibase"],
+            method.public_key_multibase.as_str()
+        );
+        assert_eq!(json["@context"][0], "https://www.w3.org/ns/did/v1");
+    }
+}
@@ -0,0 +1,55 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(test)]
+pub mod tests {
+    use std::convert::TryFrom;
+
+    use symbol_crypto_core::prelude::ed25519::{self, Signer, Verifier};
+    use symbol_crypto_core::prelude::{Keypair, KpSym, PrivateKey, PublicKey, Signature};
+
+    const MESSAGE: &[u8] = b"symbol interop";
+
+    #[test]
+    fn test_symbol_to_dalek() {
+        let kp = Keypair::<KpSym>::random();
+        let signature = kp.try_sign(MESSAGE).unwrap();
+
+        let secret = ed25519::SecretKey::from(kp.private_key());
+        let public = ed25519::PublicKey::try_from(kp.public_key()).unwrap();
+        assert_eq!(ed25519::PublicKey::from(&secret), public);
+
+        let signature = ed25519::Signature::try_from(signature).unwrap();
+        assert!(public.verify(MESSAGE, &signature).is_ok());
+    }
+
+    #[test]
+    fn test_dalek_to_symbol() {
+        let secret = ed25519::SecretKey::from_bytes(&[7u8; 32]).unwrap();
+        let public = ed25519::PublicKey::from(&secret);
+        let dalek = ed25519::Keypair { secret, public };
+        let signature: ed25519::Signature = dalek.sign(MESSAGE);
+
+        let kp = Keypair::<KpSym>::from_private_key(PrivateKey::from(&dalek.secret));
+        assert_eq!(kp.public_key(), PublicKey::from(dalek.public));
+        assert_eq!(kp.public_key(), PublicKey::from(&dalek.public));
+        assert!(kp.verify(MESSAGE, Signature::from(signature)).is_ok());
+    }
+
+    #[test]
+    fn test_invalid_conversions() {
+        // not a curve point.
+        let mut bytes = [0u8; 32];
+        bytes[0] = 2;
+        assert!(ed25519::PublicKey::try_from(PublicKey::from(bytes)).is_err());
+
+        // s with its high bits set.
+        let signature = Signature::repeat_byte(0xFF);
+        assert!(ed25519::Signature::try_from(signature).is_err());
+    }
+}
@@ -0,0 +1,97 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(test)]
+pub mod tests {
+    use symbol_crypto_core::prelude::enclave::{SealedKeypair, Sealer};
+    use symbol_crypto_core::prelude::zeroize::Zeroizing;
+    use symbol_crypto_core::prelude::{
+        BlockCipher, CryptoSym, Keypair, KpNis1, KpSym, PrivateKey, PublicKey,
+    };
+
+    /// Stands for the platform sealing: the enclave key never leaves the sealer.
+    struct TestSealer {
+        key: PrivateKey,
+        public_key: PublicKey,
+    }
+
+    impl TestSealer {
+        fn new() -> Self {
+            let kp = Keypair::<KpSym>::random();
+            Self {
+                key: kp.private_key(),
+                public_key: kp.public_key(),
+            }
+        }
+    }
+
+    impl Sealer for TestSealer {
+        fn seal(&self, plaintext: &[u8], aad: &[u8]) -> anyhow::Result<Vec<u8>> {
+            let mut data = (aad.len() as u32).to_le_bytes().to_vec();
+            data.extend_from_slice(aad);
+            data.extend_from_slice(plaintext);
+            CryptoSym::encrypt_for(&self.key, &self.public_key, &data)
+        }
+
+        fn unseal(&self, sealed: &[u8], aad: &[u8]) -> anyhow::Result<Zeroizing<Vec<u8>>> {
+            let data = Zeroizing::new(CryptoSym::decrypt_from(
+                &self.key,
+                &self.public_key,
+                sealed,
+            )?);
+            anyhow::ensure!(
+                data.len() >= 4 + aad.len()
+                    && data[..4] == (aad.len() as u32).to_le_bytes()
+                    && &data[4..4 + aad.len()] == aad,
+                "sealed data aad mismatch."
+            );
+            Ok(Zeroizing::new(data[4 + aad.len()..].to_vec()))
+        }
+    }
+
+    #[test]
+    fn test_seal_unseal_sign() {
+        let sealer = TestSealer::new();
+        let kp = Keypair::<KpSym>::random();
+
+        let sealed = SealedKeypair::seal(&sealer, &kp).unwrap();
+        assert_eq!(sealed.public_key, kp.public_key());
+        assert!(!sealed
+            .sealed
+            .windows(32)
+            .any(|w| w == kp.private_key().as_bytes()));
+
+        let unsealed = sealed.unseal::<KpSym, _>(&sealer).unwrap();
+        assert_eq!(unsealed.private_key(), kp.private_key());
+
+        let signature = sealed.sign::<KpSym, _>(&sealer, b"payload").unwrap();
+        assert!(kp.verify(b"payload", signature).is_ok());
+    }
+
+    #[test]
+    fn test_unseal_is_bound_to_sealer_schema_and_public_key() {
+        let sealer = TestSealer::new();
+        let kp = Keypair::<KpSym>::random();
+        let sealed = SealedKeypair::seal(&sealer, &kp).unwrap();
+
+        assert!(sealed.unseal::<KpSym, _>(&TestSealer::new()).is_err());
+        assert!(sealed.unseal::<KpNis1, _>(&sealer).is_err());
+
+        let mut swapped = sealed.clone();
+        swapped.public_key = Keypair::<KpSym>::random().public_key();
+        assert!(swapped.unseal::<KpSym, _>(&sealer).is_err());
+    }
+
+    #[test]
+    fn test_debug_does_not_show_sealed_bytes() {
+        let sealer = TestSealer::new();
+        let sealed = SealedKeypair::seal(&sealer, &Keypair::<KpSym>::random()).unwrap();
+        let debug = format!("{:?}", sealed);
+        assert!(debug.contains(&format!("[{} bytes]", sealed.sealed.len())));
+    }
+}
@@ -0,0 +1,78 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(test)]
+pub mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    use symbol_crypto_core::prelude::{
+        random_bytes, reset_entropy_source, set_entropy_source, EntropySource, ExternalEntropy,
+        try_random_bytes, Keypair, KpNis1, KpSym, OsEntropy, PrivateKey, SeededEntropy, H256,
+    };
+
+    const SEED: [u8; 32] = [7u8; 32];
+
+    #[test]
+    fn test_seeded_entropy_is_deterministic() {
+        let (a, b) = (SeededEntropy::new(SEED), SeededEntropy::new(SEED));
+        let (mut x, mut y) = ([0u8; 64], [0u8; 64]);
+        a.fill_bytes(&mut x).unwrap();
+        b.fill_bytes(&mut y).unwrap();
+        assert_eq!(x, y);
+
+        a.fill_bytes(&mut x).unwrap();
+        assert_ne!(x, y);
+
+        OsEntropy.fill_bytes(&mut x).unwrap();
+        assert_ne!(x, [0u8; 64]);
+    }
+
+    // the global source is swapped in a single test, the other tests of this file don't use it.
+    #[test]
+    fn test_global_entropy_source() {
+        set_entropy_source(Arc::new(SeededEntropy::new(SEED)));
+        let sym = Keypair::<KpSym>::random();
+        let nis1 = Keypair::<KpNis1>::random();
+        let (mnemonic_key, mnemonic) = PrivateKey::create_with_mnemonic("").unwrap();
+
+        set_entropy_source(Arc::new(SeededEntropy::new(SEED)));
+        assert_eq!(Keypair::<KpSym>::random(), sym);
+        assert_eq!(Keypair::<KpNis1>::random(), nis1);
+        assert_eq!(
+            PrivateKey::create_with_mnemonic("").unwrap(),
+            (mnemonic_key, mnemonic)
+        );
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+        set_entropy_source(Arc::new(ExternalEntropy::new(move |dest: &mut [u8]| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            dest.iter_mut().for_each(|b| *b = 0xAB);
+            Ok(())
+        })));
+        assert_eq!(random_bytes::<4>(), [0xAB; 4]);
+        Keypair::<KpSym>::random();
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        assert_eq!(PrivateKey::random(), PrivateKey::repeat_byte(0xAB));
+        assert_eq!(H256::random(), H256::repeat_byte(0xAB));
+        assert_eq!(calls.load(Ordering::SeqCst), 4);
+
+        set_entropy_source(Arc::new(ExternalEntropy::new(|_: &mut [u8]| {
+            Err(anyhow::anyhow!("device unplugged"))
+        })));
+        assert!(try_random_bytes::<4>().is_err());
+        assert!(PrivateKey::try_random().is_err());
+        assert!(Keypair::<KpSym>::try_random().is_err());
+        assert!(Keypair::<KpNis1>::try_random().is_err());
+
+        reset_entropy_source();
+        assert!(Keypair::<KpSym>::try_random().is_ok());
+        assert_ne!(Keypair::<KpSym>::random(), sym);
+    }
+}
@@ -0,0 +1,126 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(test)]
+pub mod tests {
+    use symbol_crypto_core::prelude::{Keypair, KpSym};
+    use symbol_crypto_core::sym::{
+        epoch_index_at, EpochCertificate, EpochKeys, EPOCH_CERTIFICATE_SIZE,
+    };
+
+    const MASTER: &str = "575DBB3062267EFF57C970A336EBBC8FBCFE12C5BD3ED7BC11EB0481D7704CED";
+    const DAY: u64 = 86_400;
+    const NOW: u64 = 1_700_000_000;
+    const DATA: &[u8] = b"signed by today's key";
+
+    fn master() -> Keypair<KpSym> {
+        Keypair::<KpSym>::from_hex_private_key(MASTER).unwrap()
+    }
+
+    #[test]
+    fn test_derive_is_deterministic() {
+        let index = epoch_index_at(DAY, NOW);
+        let keys = EpochKeys::derive(&master(), DAY, index).unwrap();
+        assert_eq!(keys, EpochKeys::derive(&master(), DAY, index).unwrap());
+
+        let other_epoch = EpochKeys::derive(&master(), DAY, index + 1).unwrap();
+        let other_duration = EpochKeys::derive(&master(), DAY / 2, index).unwrap();
+        assert_ne!(
+            keys.keypair().public_key(),
+            other_epoch.keypair().public_key()
+        );
+        assert_ne!(
+            keys.keypair().public_key(),
+            other_duration.keypair().public_key()
+        );
+        assert_ne!(keys.keypair().public_key(), master().public_key());
+    }
+
+    #[test]
+    fn test_certificate_window() {
+        let index = epoch_index_at(DAY, NOW);
+        let certificate = *EpochKeys::derive(&master(), DAY, index)
+            .unwrap()
+            .certificate();
+
+        assert_eq!(certificate.epoch_index, index);
+        assert_eq!(certificate.valid_from, index * DAY);
+        assert_eq!(certificate.valid_until, (index + 1) * DAY);
+        assert!(certificate.valid_from <= NOW && NOW < certificate.valid_until);
+
+        let master_pk = master().public_key();
+        assert!(certificate.verify(&master_pk, NOW).is_ok());
+        assert!(certificate
+            .verify(&master_pk, certificate.valid_from)
+            .is_ok());
+        assert!(certificate
+            .verify(&master_pk, certificate.valid_until)
+            .is_err());
+        assert!(certificate
+            .verify(&master_pk, certificate.valid_from - 1)
+            .is_err());
+    }
+
+    #[test]
+    fn test_verify_signature() {
+        let keys = EpochKeys::derive(&master(), DAY, epoch_index_at(DAY, NOW)).unwrap();
+        let signature = keys.sign(DATA).unwrap();
+        let certificate = keys.certificate();
+        let master_pk = master().public_key();
+
+        assert!(certificate
+            .verify_signature(&master_pk, NOW, DATA, signature)
+            .is_ok());
+        assert!(certificate
+            .verify_signature(&master_pk, NOW, b"other data", signature)
+            .is_err());
+        assert!(certificate
+            .verify_signature(&master_pk, NOW + DAY, DATA, signature)
+            .is_err());
+        assert!(certificate
+            .verify_signature(
+                &Keypair::<KpSym>::random().public_key(),
+                NOW,
+                DATA,
+                signature
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn test_tampered_certificate() {
+        let certificate = *EpochKeys::derive(&master(), DAY, epoch_index_at(DAY, NOW))
+            .unwrap()
+            .certificate();
+        let master_pk = master().public_key();
+
+        let mut extended = certificate;
+        extended.valid_until += DAY;
+        assert!(extended.verify(&master_pk, NOW).is_err());
+
+        let mut swapped = certificate;
+        swapped.epoch_public_key = Keypair::<KpSym>::random().public_key();
+        assert!(swapped.verify(&master_pk, NOW).is_err());
+    }
+
+    #[test]
+    fn test_certificate_bytes_round_trip() {
+        let certificate = *EpochKeys::derive(&master(), DAY, 3).unwrap().certificate();
+        let bytes = certificate.to_bytes();
+        assert_eq!(bytes.len(), EPOCH_CERTIFICATE_SIZE);
+        assert_eq!(EpochCertificate::from_bytes(&bytes).unwrap(), certificate);
+        assert!(EpochCertificate::from_bytes(&bytes[1..]).is_err());
+    }
+
+    #[test]
+    fn test_derive_invalid() {
+        assert!(EpochKeys::derive(&master(), 0, 1).is_err());
+        assert!(EpochKeys::derive(&master(), DAY, u64::MAX).is_err());
+        assert!(EpochKeys::derive(&master(), u64::MAX, 0).is_ok());
+    }
+}
@@ -0,0 +1,76 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(test)]
+pub mod tests {
+    use std::str::FromStr;
+
+    use symbol_crypto_core::prelude::{
+        hex_to_bytes, KeyPairSchema, Keypair, KpNis1, KpSym, PrivateKey, Scalar32,
+    };
+
+    const PRIVATE_KEY: &str = "575DBB3062267EFF57C970A336EBBC8FBCFE12C5BD3ED7BC11EB0481D7704CED";
+    const MESSAGE: &[u8] = b"signed from the expanded scalar";
+
+    // the clamped lower and the upper half of SHA-512(private key).
+    const SYM_SCALAR: &str = "38280FB3D99FE7764C66320FE0CB091BB0DB3237C82888F37EDFFBDF37549D43";
+    const SYM_NONCE: &str = "FC528EA3CE023D24E91C689A004D929F1A217728150019D6B60B748C1D51D174";
+
+    // the clamped lower and the upper half of Keccak-512(reversed private key).
+    const NIS1_SCALAR: &str = "80636C4B4058685599BE4D3483AA71CCD2E83862C3550AC833DD60DBCE3AC141";
+    const NIS1_NONCE: &str = "2285DCBABECFBAB0B44D30753CF47BC12BF2641C1EA385EA2793976B922741D8";
+
+    fn check_same_signer<Kp: KeyPairSchema>(scalar: &str, nonce: &str) {
+        let kp = Keypair::<Kp>::from_private_key(PrivateKey::from_str(PRIVATE_KEY).unwrap());
+        let scalar_bytes: [u8; 32] = hex_to_bytes(scalar);
+        let scalar = Scalar32::new(scalar_bytes).unwrap();
+        let expanded = Keypair::<Kp>::from_expanded_scalar(&scalar, hex_to_bytes(nonce));
+
+        assert_eq!(expanded.public_key(), kp.public_key());
+        assert_eq!(expanded.private_key(), PrivateKey::zero());
+
+        let signature = expanded.try_sign(MESSAGE).unwrap();
+        assert_eq!(signature, kp.try_sign(MESSAGE).unwrap());
+        assert_eq!(expanded.try_sign(MESSAGE).unwrap(), signature);
+        assert!(kp.verify(MESSAGE, signature).is_ok());
+
+        let parts: [&[u8]; 2] = [b"head", b"tail"];
+        assert_eq!(expanded.sign_vectored(&parts), kp.sign_vectored(&parts));
+
+        let debug = format!("{:?}", expanded);
+        assert!(debug.contains("[redacted]"));
+        assert!(!debug.contains(&format!("{:?}", scalar_bytes)));
+    }
+
+    #[test]
+    fn test_sym_from_expanded_scalar() {
+        check_same_signer::<KpSym>(SYM_SCALAR, SYM_NONCE);
+    }
+
+    #[test]
+    fn test_nis1_from_expanded_scalar() {
+        check_same_signer::<KpNis1>(NIS1_SCALAR, NIS1_NONCE);
+    }
+
+    #[test]
+    fn test_scalar32_rejects_unclamped() {
+        let mut scalar: [u8; 32] = hex_to_bytes(SYM_SCALAR);
+        scalar[0] |= 1;
+        assert!(Scalar32::new(scalar).is_err());
+
+        let mut scalar: [u8; 32] = hex_to_bytes(NIS1_SCALAR);
+        scalar[31] &= 0x3F;
+        assert!(Scalar32::new(scalar).is_err());
+    }
+
+    #[test]
+    fn test_scalar32_rejects_seed() {
+        // a private key (seed) passed as a scalar is almost never clamped.
+        assert!(Scalar32::new(hex_to_bytes(PRIVATE_KEY)).is_err());
+    }
+}
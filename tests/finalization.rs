@@ -0,0 +1,111 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(test)]
+pub mod tests {
+    use symbol_crypto_core::prelude::{Keypair, KpSym, H256};
+    use symbol_crypto_core::sym::finalization::{
+        message, parse_message, verify_message, verify_with_voting_keys, BmTreeSignature,
+        FinalizationStage, StepIdentifier, BM_TREE_SIGNATURE_SIZE,
+    };
+    use symbol_crypto_core::sym::voting_key::PinnedVotingKey;
+
+    const HEIGHT: u64 = 1_234_567;
+
+    fn step() -> StepIdentifier {
+        StepIdentifier {
+            epoch: 42,
+            point: 7,
+            stage: FinalizationStage::Precommit,
+        }
+    }
+
+    #[test]
+    fn test_verify_message() {
+        let root_kp = Keypair::<KpSym>::random();
+        let bottom_kp = Keypair::<KpSym>::random();
+        let hash = H256::repeat_byte(0x5A);
+
+        let signature =
+            BmTreeSignature::sign(&root_kp, &bottom_kp, &step(), HEIGHT, &[hash]).unwrap();
+        verify_message(&signature, &root_kp.public_key(), &step(), HEIGHT, &hash).unwrap();
+
+        // another voting key, step, height or hash.
+        let root_pk = root_kp.public_key();
+        let other_pk = Keypair::<KpSym>::random().public_key();
+        assert!(verify_message(&signature, &other_pk, &step(), HEIGHT, &hash).is_err());
+        let mut other_step = step();
+        other_step.stage = FinalizationStage::Prevote;
+        assert!(verify_message(&signature, &root_pk, &other_step, HEIGHT, &hash).is_err());
+        other_step = step();
+        other_step.epoch += 1;
+        assert!(verify_message(&signature, &root_pk, &other_step, HEIGHT, &hash).is_err());
+        assert!(verify_message(&signature, &root_pk, &step(), HEIGHT + 1, &hash).is_err());
+        let other_hash = H256::repeat_byte(0xA5);
+        assert!(verify_message(&signature, &root_pk, &step(), HEIGHT, &other_hash).is_err());
+    }
+
+    #[test]
+    fn test_bottom_key_not_bound_by_root() {
+        let root_kp = Keypair::<KpSym>::random();
+        let bottom_kp = Keypair::<KpSym>::random();
+        let hash = H256::repeat_byte(0x5A);
+
+        let mut signature =
+            BmTreeSignature::sign(&root_kp, &bottom_kp, &step(), HEIGHT, &[hash]).unwrap();
+        let forged_kp = Keypair::<KpSym>::random();
+        signature.bottom.parent_public_key = forged_kp.public_key();
+        signature.bottom.signature = forged_kp.try_sign(&message(&step(), HEIGHT, &[hash])).unwrap();
+        assert!(verify_message(&signature, &root_kp.public_key(), &step(), HEIGHT, &hash).is_err());
+    }
+
+    #[test]
+    fn test_verify_with_voting_keys() {
+        let root_kp = Keypair::<KpSym>::random();
+        let bottom_kp = Keypair::<KpSym>::random();
+        let hashes = [H256::repeat_byte(0x01), H256::repeat_byte(0x02)];
+        let signature =
+            BmTreeSignature::sign(&root_kp, &bottom_kp, &step(), HEIGHT, &hashes).unwrap();
+
+        let voting_keys = vec![
+            PinnedVotingKey {
+                voting_key: Keypair::<KpSym>::random().public_key(),
+                start_epoch: 1,
+                end_epoch: 41,
+            },
+            PinnedVotingKey {
+                voting_key: root_kp.public_key(),
+                start_epoch: 42,
+                end_epoch: 80,
+            },
+        ];
+        verify_with_voting_keys(&signature, &voting_keys, &step(), HEIGHT, &hashes).unwrap();
+        let previous_keys = &voting_keys[..1];
+        let verified = verify_with_voting_keys(&signature, previous_keys, &step(), HEIGHT, &hashes);
+        assert!(verified.is_err());
+    }
+
+    #[test]
+    fn test_encodings() {
+        let root_kp = Keypair::<KpSym>::random();
+        let bottom_kp = Keypair::<KpSym>::random();
+        let hashes = [H256::repeat_byte(0x01)];
+        let signature =
+            BmTreeSignature::sign(&root_kp, &bottom_kp, &step(), HEIGHT, &hashes).unwrap();
+
+        let bytes = signature.to_bytes();
+        assert_eq!(bytes.len(), BM_TREE_SIGNATURE_SIZE);
+        assert_eq!(BmTreeSignature::from_bytes(&bytes).unwrap(), signature);
+        assert!(BmTreeSignature::from_bytes(&bytes[1..]).is_err());
+
+        let message = message(&step(), HEIGHT, &hashes);
+        assert_eq!(hex::encode_upper(&message[..12]), "2A0000000700000001000000");
+        assert_eq!(parse_message(&message).unwrap(), (step(), HEIGHT, hashes.to_vec()));
+        assert!(parse_message(&message[..message.len() - 1]).is_err());
+    }
+}
@@ -0,0 +1,98 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(test)]
+pub mod tests {
+    use std::str::FromStr;
+
+    use symbol_crypto_core::fixtures::{test_account, test_accounts, TestAccount};
+    use symbol_crypto_core::nis1::address::Address as Nis1Address;
+    use symbol_crypto_core::prelude::{Keypair, KpNis1, KpSym, PublicKey};
+    use symbol_crypto_core::sym::recipient::Address as SymAddress;
+
+    const PUBLIC_KEY: &str = "C5FB65CB902623D93DF2E682FFB13F99D50FAC24D5FF2A42F68C7CA1772FE8A0";
+
+    #[test]
+    fn test_sym_address_from_public_key() {
+        let public_key = PublicKey::from_str(PUBLIC_KEY).unwrap();
+        assert_eq!(
+            SymAddress::from_public_key(0x98, &public_key)
+                .unwrap()
+                .to_string(),
+            "TBLYH55IHPS5QCCMNWR3GZWKV6WMCKPTNI7KSDA"
+        );
+        assert_eq!(
+            SymAddress::from_public_key(0x68, &public_key)
+                .unwrap()
+                .to_string(),
+            "NBLYH55IHPS5QCCMNWR3GZWKV6WMCKPTNKZIBEY"
+        );
+        assert!(SymAddress::from_public_key(0x99, &public_key).is_err());
+    }
+
+    #[test]
+    fn test_nis1_address_from_public_key() {
+        let public_key = PublicKey::from_str(PUBLIC_KEY).unwrap();
+        let address = Nis1Address::from_public_key(0x98, &public_key);
+        assert_eq!(
+            address.to_string(),
+            "TDR4IXOGFXC4DLEEVXM54F3W2DOUPLC6O3NMXM7T"
+        );
+        assert_eq!(
+            hex::encode_upper(address.as_bytes()),
+            "98E3C45DC62DC5C1AC84ADD9DE1776D0DD47AC5E76DACBB3F3"
+        );
+        assert_eq!(
+            Nis1Address::from_str("TDR4IX-OGFXC4-DLEEVX-M54F3W-2DOUPL-C6O3NM-XM7T").unwrap(),
+            address
+        );
+        assert_eq!(
+            Nis1Address::from_public_key(0x68, &public_key).to_string(),
+            "NDR4IXOGFXC4DLEEVXM54F3W2DOUPLC6OYQUVRYB"
+        );
+        assert!(Nis1Address::from_str("TDR4IXOGFXC4DLEEVXM54F3W2DOUPLC6O3NMXM7A").is_err());
+    }
+
+    #[test]
+    fn test_accounts_are_reproducible() {
+        let accounts = test_accounts(b"integration", 5, 0x98).unwrap();
+        assert_eq!(accounts.len(), 5);
+        assert_eq!(test_accounts(b"integration", 5, 0x98).unwrap(), accounts);
+        assert_eq!(test_account(b"integration", 3, 0x98).unwrap(), accounts[3]);
+        assert_ne!(
+            test_accounts(b"other", 1, 0x98).unwrap()[0].private_key,
+            accounts[0].private_key
+        );
+
+        for (index, account) in accounts.iter().enumerate() {
+            assert_eq!(account.index, index as u32);
+            assert_eq!(
+                account.sym_public_key,
+                Keypair::<KpSym>::from_private_key(account.private_key).public_key()
+            );
+            assert_eq!(
+                account.nis1_public_key,
+                Keypair::<KpNis1>::from_private_key(account.private_key).public_key()
+            );
+            assert_eq!(
+                account.sym_address,
+                SymAddress::from_public_key(0x98, &account.sym_public_key).unwrap()
+            );
+            assert!(account.sym_address.to_string().starts_with('T'));
+            assert!(account.nis1_address.to_string().starts_with('T'));
+        }
+    }
+
+    #[test]
+    fn test_accounts_serde() {
+        let accounts = test_accounts(b"integration", 2, 0x68).unwrap();
+        let json = serde_json::to_string(&accounts).unwrap();
+        let decoded: Vec<TestAccount> = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, accounts);
+    }
+}
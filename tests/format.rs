@@ -0,0 +1,55 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(test)]
+pub mod tests {
+    use std::str::FromStr;
+
+    use symbol_crypto_core::prelude::{PublicKey, Signature, H256};
+
+    const PUBLIC_KEY: &str = "2E834140FD66CF87B254A693A2C7862C819217B676D3943267156625E816EC6F";
+
+    #[test]
+    fn test_hex_display_flags() {
+        let pk = PublicKey::from_str(PUBLIC_KEY).unwrap();
+
+        assert_eq!(format!("{:X}", pk.hex_display()), PUBLIC_KEY);
+        assert_eq!(format!("{:x}", pk.hex_display()), PUBLIC_KEY.to_lowercase());
+        assert_eq!(
+            format!("{:#x}", pk.hex_display()),
+            format!("0x{}", PUBLIC_KEY.to_lowercase())
+        );
+        assert_eq!(
+            format!("{:#X}", pk.hex_display()),
+            format!("0X{}", PUBLIC_KEY)
+        );
+        assert_eq!(format!("{:.8}", pk.hex_display()), "2e834140…");
+        assert_eq!(format!("{:.8X}", pk.hex_display()), "2E834140…");
+        assert_eq!(format!("{:.100X}", pk.hex_display()), PUBLIC_KEY);
+
+        let signature = Signature::zero();
+        assert_eq!(format!("{:.4}", signature.hex_display()), "0000…");
+
+        let hash = H256::zero();
+        assert_eq!(format!("{}", hash.hex_display()), "0".repeat(64));
+    }
+
+    #[test]
+    fn test_truncated_form_is_rejected() {
+        let pk = PublicKey::from_str(PUBLIC_KEY).unwrap();
+
+        let truncated = format!("{:.8}", pk.hex_display());
+        assert!(PublicKey::from_str(&truncated).is_err());
+
+        let truncated = format!("{:#.8x}", pk.hex_display());
+        assert!(PublicKey::from_str(&truncated).is_err());
+
+        let truncated = format!("{}", pk);
+        assert!(PublicKey::from_str(&truncated).is_err());
+    }
+}
@@ -0,0 +1,92 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(test)]
+pub mod tests {
+    use symbol_crypto_core::prelude::{Keypair, KpSym};
+    use symbol_crypto_core::sym::forward_secret::{
+        decrypt_forward_secret, encrypt_forward_secret, encrypt_forward_secret_signed,
+        FLAG_SENDER_BINDING,
+    };
+
+    #[test]
+    fn test_anonymous_round_trip() {
+        let receiver = Keypair::<KpSym>::random();
+
+        let envelope = encrypt_forward_secret(&receiver.public_key(), b"hello").unwrap();
+        let decrypted = decrypt_forward_secret(&receiver.private_key(), &envelope).unwrap();
+        assert_eq!(decrypted.message, b"hello".to_vec());
+        assert_eq!(decrypted.sender_public_key, None);
+        assert!(decrypted.verify_sender(&receiver.public_key()).is_err());
+    }
+
+    #[test]
+    fn test_every_message_has_a_new_ephemeral_key() {
+        let receiver = Keypair::<KpSym>::random();
+
+        let first = encrypt_forward_secret(&receiver.public_key(), b"hello").unwrap();
+        let second = encrypt_forward_secret(&receiver.public_key(), b"hello").unwrap();
+        let first = decrypt_forward_secret(&receiver.private_key(), &first).unwrap();
+        let second = decrypt_forward_secret(&receiver.private_key(), &second).unwrap();
+        assert_ne!(first.ephemeral_public_key, second.ephemeral_public_key);
+    }
+
+    #[test]
+    fn test_signed_round_trip() {
+        let sender = Keypair::<KpSym>::random();
+        let receiver = Keypair::<KpSym>::random();
+        let other = Keypair::<KpSym>::random();
+
+        let envelope =
+            encrypt_forward_secret_signed(&sender, &receiver.public_key(), b"hello").unwrap();
+        assert_eq!(envelope[1], FLAG_SENDER_BINDING);
+
+        let decrypted = decrypt_forward_secret(&receiver.private_key(), &envelope).unwrap();
+        assert_eq!(decrypted.message, b"hello".to_vec());
+        assert_eq!(decrypted.sender_public_key, Some(sender.public_key()));
+        assert!(decrypted.verify_sender(&sender.public_key()).is_ok());
+        assert!(decrypted.verify_sender(&other.public_key()).is_err());
+
+        // the sender's long-term key does not decrypt its own messages.
+        assert!(decrypt_forward_secret(&sender.private_key(), &envelope).is_err());
+        assert!(decrypt_forward_secret(&other.private_key(), &envelope).is_err());
+    }
+
+    #[test]
+    fn test_rejects_forged_binding() {
+        let sender = Keypair::<KpSym>::random();
+        let receiver = Keypair::<KpSym>::random();
+        let other = Keypair::<KpSym>::random();
+
+        let mut envelope =
+            encrypt_forward_secret_signed(&sender, &receiver.public_key(), b"hello").unwrap();
+        envelope[34..66].copy_from_slice(other.public_key().as_bytes());
+        assert!(decrypt_forward_secret(&receiver.private_key(), &envelope).is_err());
+    }
+
+    #[test]
+    fn test_rejects_invalid_envelope() {
+        let receiver = Keypair::<KpSym>::random();
+        let envelope = encrypt_forward_secret(&receiver.public_key(), b"hello").unwrap();
+
+        let mut tampered = envelope.clone();
+        tampered[0] = 2;
+        assert!(decrypt_forward_secret(&receiver.private_key(), &tampered).is_err());
+
+        let mut tampered = envelope.clone();
+        tampered[1] = 0x80;
+        assert!(decrypt_forward_secret(&receiver.private_key(), &tampered).is_err());
+
+        let mut tampered = envelope.clone();
+        let last = tampered.len() - 1;
+        tampered[last] ^= 1;
+        assert!(decrypt_forward_secret(&receiver.private_key(), &tampered).is_err());
+
+        assert!(decrypt_forward_secret(&receiver.private_key(), &envelope[..40]).is_err());
+    }
+}
@@ -0,0 +1,46 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(test)]
+pub mod tests {
+    use symbol_crypto_core::prelude::generic_array::typenum::{U32, U64};
+    use symbol_crypto_core::prelude::generic_array::GenericArray;
+    use symbol_crypto_core::prelude::{AesKey, H256, H512};
+
+    #[test]
+    fn test_h256_views_share_memory() {
+        let mut hash = H256::repeat_byte(0xAB);
+        assert_eq!(hash.as_generic_array().as_ptr(), hash.as_bytes().as_ptr());
+        assert_eq!(hash.as_generic_array().as_slice(), hash.as_bytes());
+
+        hash.as_generic_array_mut()[0] = 1;
+        assert_eq!(hash.as_bytes()[0], 1);
+    }
+
+    #[test]
+    fn test_h512_views_share_memory() {
+        let mut hash = H512::repeat_byte(0xCD);
+        assert_eq!(hash.as_generic_array().as_ptr(), hash.as_bytes().as_ptr());
+
+        hash.as_generic_array_mut()[63] = 2;
+        assert_eq!(hash.as_bytes()[63], 2);
+    }
+
+    #[test]
+    fn test_from_round_trip() {
+        let key = AesKey::random();
+        let array: GenericArray<u8, U32> = key.into();
+        assert_eq!(array.as_slice(), key.as_bytes());
+        assert_eq!(H256::from(array), key);
+
+        let hash = H512::random();
+        let array: GenericArray<u8, U64> = hash.into();
+        assert_eq!(array.as_slice(), hash.as_bytes());
+        assert_eq!(H512::from(array), hash);
+    }
+}
@@ -0,0 +1,66 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(test)]
+pub mod tests {
+    use std::io::Error;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+
+    use symbol_crypto_core::prelude::{GuardedKeypair, Keypair, KpNis1, KpSym};
+
+    const NOW: u64 = 1_700_000_000;
+
+    #[test]
+    fn test_rate_limit() {
+        let guarded = GuardedKeypair::new(Keypair::<KpSym>::random()).with_rate_limit(2);
+
+        let signature = guarded.sign(b"one", NOW).unwrap();
+        assert!(guarded.verify(b"one", signature).is_ok());
+        guarded.sign(b"two", NOW + 30).unwrap();
+        assert!(guarded.sign(b"three", NOW + 59).is_err());
+        assert_eq!(guarded.signs_in_window(NOW + 59), 2);
+
+        // the first signature leaves the window.
+        guarded.sign(b"three", NOW + 60).unwrap();
+        assert!(guarded.sign(b"four", NOW + 61).is_err());
+        assert_eq!(guarded.signs_in_window(NOW + 200), 0);
+    }
+
+    #[test]
+    fn test_payload_predicate() {
+        let guarded = GuardedKeypair::new(Keypair::<KpNis1>::random())
+            .with_payload_predicate(|data| data.starts_with(b"transfer:"));
+
+        assert!(guarded.sign(b"transfer:10", NOW).is_ok());
+        assert!(guarded.sign(b"link:harvest", NOW).is_err());
+    }
+
+    #[test]
+    fn test_approval_hook() {
+        let approved = Arc::new(AtomicBool::new(false));
+        let second_operator = approved.clone();
+        let guarded = GuardedKeypair::new(Keypair::<KpSym>::random())
+            .with_rate_limit(1)
+            .with_payload_predicate(|data| !data.is_empty())
+            .with_approval_hook(move |_| match second_operator.load(Ordering::SeqCst) {
+                true => Ok(()),
+                false => Err(Error::other("rejected by the second operator").into()),
+            });
+
+        let err = guarded.sign(b"payload", NOW).unwrap_err();
+        assert!(err.to_string().contains("second operator"));
+        // a denied signature does not count in the rate.
+        assert_eq!(guarded.signs_in_window(NOW), 0);
+
+        approved.store(true, Ordering::SeqCst);
+        assert!(guarded.sign(b"payload", NOW).is_ok());
+        assert!(guarded.sign(b"payload", NOW).is_err());
+        assert!(guarded.sign(b"", NOW + 60).is_err());
+    }
+}
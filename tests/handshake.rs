@@ -0,0 +1,98 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(test)]
+pub mod tests {
+    use symbol_crypto_core::prelude::{Keypair, KpSym};
+    use symbol_crypto_core::sym::handshake::{
+        generate_client_challenge_response, generate_server_challenge_request,
+        generate_server_challenge_response, verify_client_challenge_response,
+        verify_server_challenge_response, ClientChallengeResponse, SecurityMode,
+        ServerChallengeRequest, ServerChallengeResponse, CLIENT_CHALLENGE_RESPONSE_SIZE,
+        SERVER_CHALLENGE_REQUEST_SIZE, SERVER_CHALLENGE_RESPONSE_SIZE,
+    };
+
+    #[test]
+    fn test_handshake() {
+        let server = Keypair::<KpSym>::random();
+        let client = Keypair::<KpSym>::random();
+
+        let request = generate_server_challenge_request();
+        let request_bytes = request.to_bytes();
+        assert_eq!(request_bytes.len(), SERVER_CHALLENGE_REQUEST_SIZE);
+        let received = ServerChallengeRequest::from_bytes(&request_bytes).unwrap();
+        assert_eq!(received, request);
+
+        let response = generate_server_challenge_response(&received, &client, SecurityMode::Signed);
+        let response_bytes = response.to_bytes();
+        assert_eq!(response_bytes.len(), SERVER_CHALLENGE_RESPONSE_SIZE);
+        assert_eq!(response_bytes[SERVER_CHALLENGE_RESPONSE_SIZE - 1], 2);
+        let received = ServerChallengeResponse::from_bytes(&response_bytes).unwrap();
+        assert_eq!(received, response);
+        assert!(verify_server_challenge_response(&received, &request).is_ok());
+
+        let client_response = generate_client_challenge_response(&received, &server);
+        let client_response_bytes = client_response.to_bytes();
+        assert_eq!(client_response_bytes.len(), CLIENT_CHALLENGE_RESPONSE_SIZE);
+        let received = ClientChallengeResponse::from_bytes(&client_response_bytes).unwrap();
+        assert!(
+            verify_client_challenge_response(&received, &server.public_key(), &response).is_ok()
+        );
+    }
+
+    #[test]
+    fn test_handshake_rejects_wrong_signer() {
+        let server = Keypair::<KpSym>::random();
+        let client = Keypair::<KpSym>::random();
+
+        let request = generate_server_challenge_request();
+        let mut response =
+            generate_server_challenge_response(&request, &client, SecurityMode::None);
+        assert!(verify_server_challenge_response(&response, &request).is_ok());
+
+        // the security mode is part of the signed message.
+        response.security_mode = SecurityMode::Signed;
+        assert!(verify_server_challenge_response(&response, &request).is_err());
+        response.security_mode = SecurityMode::None;
+
+        let other_request = generate_server_challenge_request();
+        assert!(verify_server_challenge_response(&response, &other_request).is_err());
+
+        // an impostor server cannot answer for the expected node.
+        let impostor = Keypair::<KpSym>::random();
+        let client_response = generate_client_challenge_response(&response, &impostor);
+        assert!(verify_client_challenge_response(
+            &client_response,
+            &server.public_key(),
+            &response
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_packets_invalid() {
+        let request = generate_server_challenge_request();
+        let mut bytes = request.to_bytes();
+        assert!(ServerChallengeRequest::from_bytes(&bytes[1..]).is_err());
+        bytes[4] = 2;
+        assert!(ServerChallengeRequest::from_bytes(&bytes).is_err());
+
+        let client = Keypair::<KpSym>::random();
+        let mut bytes =
+            generate_server_challenge_response(&request, &client, SecurityMode::None).to_bytes();
+        bytes[SERVER_CHALLENGE_RESPONSE_SIZE - 1] = 3;
+        assert!(ServerChallengeResponse::from_bytes(&bytes).is_err());
+
+        let mut bytes = [0u8; CLIENT_CHALLENGE_RESPONSE_SIZE];
+        bytes[0] = CLIENT_CHALLENGE_RESPONSE_SIZE as u8;
+        bytes[4] = 1;
+        assert!(ClientChallengeResponse::from_bytes(&bytes).is_err());
+        bytes[4] = 2;
+        assert!(ClientChallengeResponse::from_bytes(&bytes).is_ok());
+    }
+}
@@ -0,0 +1,50 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(test)]
+pub mod tests {
+    use symbol_crypto_core::prelude::{H256, H512};
+
+    #[test]
+    fn test_add_sub() {
+        let a = H256::from_low_u64_be(u64::MAX);
+        let b = H256::from_low_u64_be(1);
+
+        let sum = a.wrapping_add(&b);
+        assert_eq!(sum.as_bytes()[23], 1);
+        assert_eq!(sum.to_low_u64_be(), 0);
+        assert!(sum > a);
+        assert_eq!(sum.wrapping_sub(&b), a);
+        assert_eq!(a.wrapping_add_u64(1), sum);
+
+        assert_eq!(b.checked_sub(&a), None);
+        assert_eq!(a.checked_sub(&b), Some(H256::from_low_u64_be(u64::MAX - 1)));
+    }
+
+    #[test]
+    fn test_overflow() {
+        let max = H256::repeat_byte(0xFF);
+        let one = H256::from_low_u64_be(1);
+
+        assert_eq!(max.overflowing_add(&one), (H256::zero(), true));
+        assert_eq!(H256::zero().overflowing_sub(&one), (max, true));
+        assert_eq!(max.checked_add(&one), None);
+
+        let max = H512::repeat_byte(0xFF);
+        assert_eq!(
+            max.wrapping_add(&H512::from_low_u64_be(2)),
+            H512::from_low_u64_be(1)
+        );
+    }
+
+    #[test]
+    fn test_random() {
+        assert_ne!(H256::random(), H256::random());
+        assert_eq!(H256::from_low_u64_le(7).to_low_u64_le(), 7);
+    }
+}
@@ -0,0 +1,151 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(test)]
+pub mod tests {
+    use std::str::FromStr;
+
+    use symbol_crypto_core::prelude::hd::{ExtendedKey, HdVariant};
+    use symbol_crypto_core::prelude::{DerivationPath, Keypair, KpNis1, KpSym, PrivateKey};
+
+    // SLIP-10 ed25519 test vector 1.
+    const SEED: &str = "000102030405060708090a0b0c0d0e0f";
+
+    // (path, chain code, private key, public key)
+    const VECTORS: [(&str, &str, &str, &str); 3] = [
+        (
+            "m",
+            "90046a93de5380a72b5e45010748567d5ea02bbf6522f979e05c0d8d8ca9fffb",
+            "2b4be7f19ee27bbf30c667b642d5f4aa69fd169872f8fc3059c08ebae2eb19e7",
+            "a4b2856bfec510abab89753fac1ac0e1112364e7d250545963f135f2a33188ed",
+        ),
+        (
+            "m/0'",
+            "8b59aa11380b624e81507a27fedda59fea6d0b779a778918a2fd3590e16e9c69",
+            "68e0fe46dfb67e368c75379acec591dad19df3cde26e63b93a8e704f1dade7a3",
+            "8c8a13df77a28f3445213a0f432fde644acaa215fc72dcdf300d5efaa85d350c",
+        ),
+        (
+            "m/0'/1'/2'/2'/1000000000'",
+            "68789923a0cac2cd5a29172a475fe9e0fb14cd6adb5ad98a3fa70333e7afa230",
+            "8f94d394a8e8fd6b1bc2f3f49f5c47e385281d5c17e65324b0f62483e37e8793",
+            "3c24da049451555d51a7014a37337aa4e12d41e485abccfa46b47dfb2af54b7a",
+        ),
+    ];
+
+    fn master(variant: HdVariant) -> ExtendedKey {
+        ExtendedKey::from_seed(&hex::decode(SEED).unwrap(), variant).unwrap()
+    }
+
+    #[test]
+    fn test_symbol_variant_slip10_vectors() {
+        let master = master(HdVariant::Symbol);
+        for (path, chain_code, private_key, public_key) in VECTORS.iter() {
+            let key = master.derive_path(&DerivationPath::from_str(path).unwrap());
+
+            assert_eq!(hex::encode(key.chain_code()), *chain_code, "{}", path);
+            assert_eq!(
+                key.private_key(),
+                PrivateKey::from_str(private_key).unwrap(),
+                "{}",
+                path
+            );
+            assert_eq!(
+                hex::encode(key.keypair::<KpSym>().unwrap().public_key().as_bytes()),
+                *public_key,
+                "{}",
+                path
+            );
+        }
+    }
+
+    #[test]
+    fn test_optin_variant_derives_nis1_keypairs_of_the_same_private_keys() {
+        let symbol = master(HdVariant::Symbol);
+        let optin = master(HdVariant::Optin);
+        for (path, _, _, _) in VECTORS.iter() {
+            let path = DerivationPath::from_str(path).unwrap();
+            let symbol_key = symbol.derive_path(&path);
+            let optin_key = optin.derive_path(&path);
+
+            assert_eq!(optin_key.private_key(), symbol_key.private_key());
+            assert_eq!(optin_key.chain_code(), symbol_key.chain_code());
+
+            let keypair = optin_key.keypair::<KpNis1>().unwrap();
+            assert_eq!(
+                keypair.public_key(),
+                Keypair::<KpNis1>::from_private_key(symbol_key.private_key()).public_key()
+            );
+            assert_ne!(
+                keypair.public_key(),
+                symbol_key.keypair::<KpSym>().unwrap().public_key()
+            );
+        }
+    }
+
+    #[test]
+    fn test_keypair_rejects_the_other_schema() {
+        assert!(master(HdVariant::Symbol).keypair::<KpNis1>().is_err());
+        assert!(master(HdVariant::Optin).keypair::<KpSym>().is_err());
+    }
+
+    #[test]
+    fn test_derive_child_hardens_index() {
+        let master = master(HdVariant::Symbol);
+
+        assert_eq!(
+            master.derive_child(0).private_key(),
+            master.derive_child(0x8000_0000).private_key()
+        );
+        assert_eq!(
+            master
+                .derive_path(&DerivationPath::symbol_account(0).unwrap())
+                .private_key(),
+            master
+                .derive_child(44)
+                .derive_child(4343)
+                .derive_child(0)
+                .derive_child(0)
+                .derive_child(0)
+                .private_key()
+        );
+    }
+
+    #[test]
+    fn test_from_seed_rejects_size() {
+        assert!(ExtendedKey::from_seed(&[0u8; 15], HdVariant::Symbol).is_err());
+        assert!(ExtendedKey::from_seed(&[0u8; 65], HdVariant::Optin).is_err());
+    }
+
+    #[test]
+    fn test_from_mnemonic_matches_bip39_seed() {
+        let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon \
+                        abandon abandon about";
+        let key = ExtendedKey::from_mnemonic(mnemonic, "TREZOR", HdVariant::Symbol).unwrap();
+        let seed = hex::decode(
+            "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04",
+        )
+        .unwrap();
+
+        assert_eq!(
+            key.private_key(),
+            ExtendedKey::from_seed(&seed, HdVariant::Symbol)
+                .unwrap()
+                .private_key()
+        );
+    }
+
+    #[test]
+    fn test_debug_is_redacted() {
+        let key = master(HdVariant::Optin);
+        let debug = format!("{:?}", key);
+
+        assert!(debug.contains("Optin"));
+        assert!(!debug.contains(VECTORS[0].2));
+    }
+}
@@ -0,0 +1,129 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(test)]
+pub mod tests {
+    use std::fmt::Write;
+
+    use symbol_crypto_core::prelude::{
+        create_with_mnemonic_heapless, ct_hex_decode_into, ct_hex_encode_into, BlockCipher,
+        CryptoSym, Keypair, KpSym, PrivateKey, StackBuf, MAX_MESSAGE_SIZE, SYM_ENCRYPT_OVERHEAD,
+    };
+
+    const ENC_SIZE: usize = MAX_MESSAGE_SIZE + SYM_ENCRYPT_OVERHEAD;
+
+    #[test]
+    fn test_stack_buf() {
+        let mut buf = StackBuf::<4>::new();
+        assert!(buf.is_empty());
+        buf.extend_from_slice(&[1, 2, 3]).unwrap();
+        assert!(buf.extend_from_slice(&[4, 5]).is_err());
+        assert_eq!(&buf[..], &[1, 2, 3]);
+        buf.truncate(1);
+        assert_eq!(&buf[..], &[1]);
+        assert_eq!(buf.capacity(), 4);
+        assert!(StackBuf::<2>::zeroed(3).is_err());
+
+        let mut text = StackBuf::<8>::new();
+        write!(text, "ab-{}", 12).unwrap();
+        assert_eq!(text.as_str().unwrap(), "ab-12");
+        assert!(write!(text, "toolong").is_err());
+        assert_eq!(format!("{:?}", text), "StackBuf<8>(len 5)");
+    }
+
+    #[test]
+    fn test_encrypt_decrypt_heapless() {
+        let signer = Keypair::<KpSym>::random();
+        let receiver = Keypair::<KpSym>::random();
+        let msg = b"heapless message";
+
+        let enc = CryptoSym::encrypt_for_heapless::<ENC_SIZE>(
+            &signer.private_key(),
+            &receiver.public_key(),
+            msg,
+        )
+        .unwrap();
+        assert_eq!(enc.len(), msg.len() + SYM_ENCRYPT_OVERHEAD);
+        assert_eq!(
+            CryptoSym::decrypt_from(&receiver.private_key(), &signer.public_key(), &enc).unwrap(),
+            msg
+        );
+
+        let enc =
+            CryptoSym::encrypt_for(&signer.private_key(), &receiver.public_key(), msg).unwrap();
+        let dec = CryptoSym::decrypt_from_heapless::<MAX_MESSAGE_SIZE>(
+            &receiver.private_key(),
+            &signer.public_key(),
+            &enc,
+        )
+        .unwrap();
+        assert_eq!(&dec[..], msg);
+
+        let mut tampered = enc.clone();
+        tampered[SYM_ENCRYPT_OVERHEAD] ^= 1;
+        assert!(CryptoSym::decrypt_from_heapless::<MAX_MESSAGE_SIZE>(
+            &receiver.private_key(),
+            &signer.public_key(),
+            &tampered,
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_encrypt_heapless_capacity() {
+        let signer = Keypair::<KpSym>::random();
+        let receiver = Keypair::<KpSym>::random();
+
+        assert!(
+            CryptoSym::encrypt_for_heapless::<{ 4 + SYM_ENCRYPT_OVERHEAD }>(
+                &signer.private_key(),
+                &receiver.public_key(),
+                &[0u8; 5],
+            )
+            .is_err()
+        );
+        assert!(CryptoSym::decrypt_from_heapless::<4>(
+            &receiver.private_key(),
+            &signer.public_key(),
+            &[0u8; 5 + SYM_ENCRYPT_OVERHEAD],
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_hex_without_allocation() {
+        let private_key = PrivateKey::random();
+        assert_eq!(
+            &private_key.to_hex_ct_array()[..],
+            private_key.to_hex_ct().as_bytes()
+        );
+
+        let mut hex = [0u8; 4];
+        ct_hex_encode_into(&[0xAB, 0x01], false, &mut hex).unwrap();
+        assert_eq!(&hex, b"ab01");
+        assert!(ct_hex_encode_into(&[0xAB], false, &mut hex).is_err());
+
+        let mut bytes = [0u8; 2];
+        ct_hex_decode_into("AB01", &mut bytes).unwrap();
+        assert_eq!(bytes, [0xAB, 0x01]);
+        assert!(ct_hex_decode_into("AB0", &mut bytes).is_err());
+        assert!(ct_hex_decode_into("AB0G", &mut bytes).is_err());
+        assert!(ct_hex_decode_into("AB", &mut bytes).is_err());
+    }
+
+    #[test]
+    fn test_create_with_mnemonic_heapless() {
+        let (private_key, words) = create_with_mnemonic_heapless("password").unwrap();
+        let words = words.as_str().unwrap();
+        assert_eq!(words.split(' ').count(), 24);
+        assert_eq!(
+            PrivateKey::from_mnemonic(words, "password").unwrap(),
+            private_key
+        );
+    }
+}
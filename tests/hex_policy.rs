@@ -0,0 +1,73 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(test)]
+pub mod tests {
+    use std::str::FromStr;
+
+    use symbol_crypto_core::prelude::hex_policy::{de_hex, ser_lower, ser_upper};
+    use symbol_crypto_core::prelude::{HexPolicy, PrivateKey, PublicKey, Signature, H256};
+
+    const PUBLIC_KEY: &str = "2E834140FD66CF87B254A693A2C7862C819217B676D3943267156625E816EC6F";
+
+    fn to_json<F>(ser: F) -> String
+    where
+        F: FnOnce(&mut serde_json::Serializer<&mut Vec<u8>>) -> serde_json::Result<()>,
+    {
+        let mut out = Vec::new();
+        ser(&mut serde_json::Serializer::new(&mut out)).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_to_hex_with() {
+        let pk = PublicKey::from_str(PUBLIC_KEY).unwrap();
+
+        assert_eq!(pk.to_hex_with(HexPolicy::Upper), PUBLIC_KEY);
+        assert_eq!(pk.to_hex_with(HexPolicy::Lower), PUBLIC_KEY.to_lowercase());
+        assert_eq!(pk.to_hex_with(HexPolicy::SYMBOL_REST), PUBLIC_KEY);
+        assert_eq!(pk.to_hex_with(HexPolicy::SDK), PUBLIC_KEY.to_lowercase());
+        assert_eq!(HexPolicy::default(), HexPolicy::SYMBOL_REST);
+
+        assert_eq!(Signature::zero().to_hex_with(HexPolicy::Upper), "0".repeat(128));
+        assert_eq!(
+            H256::repeat_byte(0xab).to_hex_with(HexPolicy::Upper),
+            "AB".repeat(32)
+        );
+        assert_eq!(
+            PrivateKey::repeat_byte(0xcd).to_hex_with(HexPolicy::Lower),
+            "cd".repeat(32)
+        );
+    }
+
+    #[test]
+    fn test_serde_adapters() {
+        let pk = PublicKey::from_str(PUBLIC_KEY).unwrap();
+
+        assert_eq!(to_json(|s| ser_upper(&pk, s)), format!("\"{}\"", PUBLIC_KEY));
+        assert_eq!(
+            to_json(|s| ser_lower(&pk, s)),
+            format!("\"{}\"", PUBLIC_KEY.to_lowercase())
+        );
+
+        for text in [PUBLIC_KEY.to_string(), PUBLIC_KEY.to_lowercase()].iter() {
+            let json = format!("\"{}\"", text);
+            let decoded: PublicKey =
+                de_hex(&mut serde_json::Deserializer::from_str(&json)).unwrap();
+            assert_eq!(decoded, pk);
+        }
+    }
+
+    #[test]
+    fn test_de_hex_rejects_invalid_hex() {
+        let result: serde_json::Result<Signature> =
+            de_hex(&mut serde_json::Deserializer::from_str("\"zz\""));
+
+        assert!(result.is_err());
+    }
+}
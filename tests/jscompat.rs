@@ -0,0 +1,70 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(test)]
+pub mod tests {
+    use symbol_crypto_core::prelude::{BlockCipher, CryptoSym, Keypair, KpSym};
+    use symbol_crypto_core::sym::jscompat::{decode, encode};
+
+    fn hex_keys() -> (Keypair<KpSym>, Keypair<KpSym>, String, String) {
+        let sender = Keypair::<KpSym>::random();
+        let recipient = Keypair::<KpSym>::random();
+        let sender_priv = format!("{:X}", sender.private_key());
+        let recipient_pub = format!("{:X}", recipient.public_key());
+        (sender, recipient, sender_priv, recipient_pub)
+    }
+
+    #[test]
+    fn test_encode_decode() {
+        let (sender, recipient, sender_priv, recipient_pub) = hex_keys();
+        let recipient_priv = format!("{:X}", recipient.private_key());
+        let sender_pub = format!("{:x}", sender.public_key());
+
+        let payload = encode(&sender_priv, &recipient_pub, "hello", false).unwrap();
+        assert_eq!(payload, payload.to_uppercase());
+        assert_eq!(
+            decode(&recipient_priv, &sender_pub, &payload).unwrap(),
+            "68656C6C6F"
+        );
+
+        let payload = encode(&sender_priv, &recipient_pub, "68656c6c6f", true).unwrap();
+        assert_eq!(
+            decode(&recipient_priv, &sender_pub, &payload).unwrap(),
+            "68656C6C6F"
+        );
+
+        let payload = encode(&sender_priv, &recipient_pub, "", false).unwrap();
+        assert_eq!(decode(&recipient_priv, &sender_pub, &payload).unwrap(), "");
+    }
+
+    #[test]
+    fn test_crypto_sym_interop() {
+        let (sender, recipient, sender_priv, recipient_pub) = hex_keys();
+
+        let payload = encode(&sender_priv, &recipient_pub, "symbol", false).unwrap();
+        let msg = CryptoSym::decrypt_from(
+            &recipient.private_key(),
+            &sender.public_key(),
+            &hex::decode(payload).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(msg, b"symbol");
+    }
+
+    #[test]
+    fn test_invalid_inputs() {
+        let (_, recipient, sender_priv, recipient_pub) = hex_keys();
+        let recipient_priv = format!("{:X}", recipient.private_key());
+
+        assert!(encode("zz", &recipient_pub, "msg", false).is_err());
+        assert!(encode(&sender_priv, &recipient_pub, "not hex", true).is_err());
+        assert!(encode(&sender_priv, &recipient_pub, "abc", true).is_err());
+        assert!(decode(&recipient_priv, &recipient_pub, "00").is_err());
+        assert!(decode(&recipient_priv, &recipient_pub, "xyz").is_err());
+    }
+}
@@ -0,0 +1,81 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(test)]
+pub mod tests {
+    use symbol_crypto_core::prelude::kdf::{expand, KdfChoice};
+    use symbol_crypto_core::prelude::{
+        CryptoNis1, CryptoSym, Keypair, KpNis1, KpSym, PublicKey, SharedSecret,
+    };
+
+    // the identity point, of small order.
+    const IDENTITY: &str = "0100000000000000000000000000000000000000000000000000000000000000";
+
+    #[test]
+    fn test_raw_shared_secret_is_symmetric() {
+        let alice = Keypair::<KpSym>::random();
+        let bob = Keypair::<KpSym>::random();
+        assert_eq!(
+            CryptoSym::derive_raw_shared_secret(&alice.private_key(), &bob.public_key()).unwrap(),
+            CryptoSym::derive_raw_shared_secret(&bob.private_key(), &alice.public_key()).unwrap()
+        );
+
+        let alice = Keypair::<KpNis1>::random();
+        let bob = Keypair::<KpNis1>::random();
+        assert_eq!(
+            CryptoNis1::derive_raw_shared_secret(&alice.private_key(), &bob.public_key()).unwrap(),
+            CryptoNis1::derive_raw_shared_secret(&bob.private_key(), &alice.public_key()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_raw_shared_secret_depends_on_schema() {
+        let private_key = Keypair::<KpSym>::random().private_key();
+        let peer = Keypair::<KpSym>::random().public_key();
+        assert_ne!(
+            CryptoSym::derive_raw_shared_secret(&private_key, &peer).unwrap(),
+            CryptoNis1::derive_raw_shared_secret(&private_key, &peer).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_raw_shared_secret_rejects_small_order_key() {
+        let private_key = Keypair::<KpSym>::random().private_key();
+        let identity: PublicKey = IDENTITY.parse().unwrap();
+        assert!(CryptoSym::derive_raw_shared_secret(&private_key, &identity).is_err());
+        assert!(CryptoNis1::derive_raw_shared_secret(&private_key, &identity).is_err());
+    }
+
+    #[test]
+    fn test_expand() {
+        let shared = SharedSecret::repeat_byte(7);
+        for kdf in [KdfChoice::HkdfSha256, KdfChoice::HkdfSha512].iter() {
+            let long = expand(&shared, *kdf, b"my-protocol/keys", 96).unwrap();
+            let short = expand(&shared, *kdf, b"my-protocol/keys", 16).unwrap();
+            assert_eq!(long.len(), 96);
+            assert_eq!(long[..16], short[..]);
+
+            let other = expand(&shared, *kdf, b"my-protocol/other", 16).unwrap();
+            assert_ne!(other, short);
+        }
+        assert_ne!(
+            expand(&shared, KdfChoice::HkdfSha256, b"info", 32).unwrap(),
+            expand(&shared, KdfChoice::HkdfSha512, b"info", 32).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_expand_length_bounds() {
+        let shared = SharedSecret::repeat_byte(7);
+        for kdf in [KdfChoice::HkdfSha256, KdfChoice::HkdfSha512].iter() {
+            assert!(expand(&shared, *kdf, b"", 0).is_err());
+            assert!(expand(&shared, *kdf, b"", kdf.max_len()).is_ok());
+            assert!(expand(&shared, *kdf, b"", kdf.max_len() + 1).is_err());
+        }
+    }
+}
@@ -0,0 +1,68 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(test)]
+pub mod tests {
+    use std::str::FromStr;
+
+    use symbol_crypto_core::prelude::{KeyWeakness, PrivateKey};
+
+    fn key(hex: &str) -> PrivateKey {
+        PrivateKey::from_str(hex).unwrap()
+    }
+
+    #[test]
+    fn test_audit_random_keys_pass() {
+        for _ in 0..100 {
+            assert!(PrivateKey::random().audit().is_ok());
+        }
+    }
+
+    #[test]
+    fn test_audit_all_zero() {
+        let report = PrivateKey::zero().audit();
+
+        assert!(report.has(KeyWeakness::AllZero));
+        assert!(report.has(KeyWeakness::FewDistinctBytes { distinct: 1 }));
+        assert!(!report.has(KeyWeakness::RepeatedPattern { period: 1 }));
+    }
+
+    #[test]
+    fn test_audit_repeated_pattern() {
+        assert!(PrivateKey::repeat_byte(0xab)
+            .audit()
+            .has(KeyWeakness::RepeatedPattern { period: 1 }));
+        assert!(key(&"deadbeef".repeat(8))
+            .audit()
+            .has(KeyWeakness::RepeatedPattern { period: 4 }));
+    }
+
+    #[test]
+    fn test_audit_sequential() {
+        let ascending: String = (0u8..32).map(|b| format!("{:02x}", b)).collect();
+        let descending: String = (0u8..32).rev().map(|b| format!("{:02x}", b * 3)).collect();
+
+        assert!(key(&ascending).audit().has(KeyWeakness::Sequential));
+        assert!(key(&descending).audit().has(KeyWeakness::Sequential));
+    }
+
+    #[test]
+    fn test_audit_known_test_key() {
+        let report =
+            key("575dbb3062267eff57c970a336ebbc8fbcfe12c5bd3ed7bc11eb0481d7704ced").audit();
+
+        assert_eq!(report.weaknesses, vec![KeyWeakness::KnownTestKey]);
+    }
+
+    #[test]
+    fn test_audit_ascii_text() {
+        let report = key(&hex::encode("correct horse battery staple 123")).audit();
+
+        assert!(report.has(KeyWeakness::AsciiText));
+    }
+}
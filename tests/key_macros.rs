@@ -0,0 +1,53 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(test)]
+pub mod tests {
+    use std::str::FromStr;
+
+    use symbol_crypto_core::prelude::{
+        hex_to_bytes, private_key, public_key, Keypair, KpSym, PrivateKey, PublicKey,
+    };
+
+    const PRIVATE_KEY: &str = "575DBB3062267EFF57C970A336EBBC8FBCFE12C5BD3ED7BC11EB0481D7704CED";
+    const PUBLIC_KEY: &str = "2E834140FD66CF87B254A693A2C7862C819217B676D3943267156625E816EC6F";
+
+    const NODE_IDENTITY: PublicKey =
+        public_key!("2E834140FD66CF87B254A693A2C7862C819217B676D3943267156625E816EC6F");
+    const NODE_KEY: PrivateKey =
+        private_key!("575dbb3062267eff57c970a336ebbc8fbcfe12c5bd3ed7bc11eb0481d7704ced");
+
+    #[test]
+    fn test_key_macros() {
+        assert_eq!(NODE_IDENTITY, PublicKey::from_str(PUBLIC_KEY).unwrap());
+        assert_eq!(NODE_KEY, PrivateKey::from_str(PRIVATE_KEY).unwrap());
+        assert_eq!(
+            Keypair::<KpSym>::from_private_key(NODE_KEY).public_key(),
+            NODE_IDENTITY
+        );
+        assert_eq!(PublicKey::from_hex_const(PUBLIC_KEY), NODE_IDENTITY);
+    }
+
+    #[test]
+    fn test_hex_to_bytes() {
+        assert_eq!(hex_to_bytes::<2>("0aFf"), [0x0A, 0xFF]);
+        assert_eq!(hex_to_bytes::<0>(""), [0u8; 0]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_hex_to_bytes_invalid_character() {
+        hex_to_bytes::<2>("0aFg");
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_from_hex_const_invalid_length() {
+        PublicKey::from_hex_const(&PUBLIC_KEY[2..]);
+    }
+}
@@ -0,0 +1,55 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(test)]
+pub mod tests {
+    use symbol_crypto_core::prelude::{KeySummary, Keypair, KpNis1, KpSym};
+
+    const PRIVATE_KEY: &str = "E88283CE35FE74C89FFCB2D8BFA0A2CF6108BDC0D07606DEE34D161C30AC2F1E";
+
+    #[test]
+    fn test_summary_sym() {
+        let kp = Keypair::<KpSym>::from_hex_private_key(PRIVATE_KEY).unwrap();
+        let summary = kp.summary();
+
+        assert_eq!(summary.schema, "symbol");
+        assert_eq!(summary.public_key_hex, format!("{:X}", kp.public_key()));
+        assert_eq!(summary.fingerprint.len(), 16);
+        assert_eq!(summary.created_at, None);
+    }
+
+    #[test]
+    fn test_summary_nis1() {
+        let kp = Keypair::<KpNis1>::from_hex_private_key(PRIVATE_KEY).unwrap();
+        let summary = kp.summary();
+
+        assert_eq!(summary.schema, "nis1");
+        assert_eq!(summary.public_key_hex, format!("{:X}", kp.public_key()));
+        assert_ne!(
+            summary.fingerprint,
+            Keypair::<KpSym>::from_hex_private_key(PRIVATE_KEY)
+                .unwrap()
+                .summary()
+                .fingerprint
+        );
+    }
+
+    #[test]
+    fn test_summary_serde_excludes_secret() {
+        let kp = Keypair::<KpSym>::from_hex_private_key(PRIVATE_KEY).unwrap();
+
+        let mut summary = kp.summary();
+        let json = serde_json::to_string(&summary).unwrap();
+        assert!(!json.to_uppercase().contains(PRIVATE_KEY));
+        assert!(!json.contains("created_at"));
+
+        summary.created_at = Some(1_622_505_600);
+        let json = serde_json::to_string(&summary).unwrap();
+        assert_eq!(serde_json::from_str::<KeySummary>(&json).unwrap(), summary);
+    }
+}
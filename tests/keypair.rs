@@ -98,6 +98,7 @@ pub mod tests {
             }
         }
 
+        #[allow(deprecated)]
         pub mod tests_sign {
             use super::*;
 
@@ -175,8 +176,30 @@ pub mod tests {
 
                 assert_ne!(signature1, signature2);
             }
+
+            #[test]
+            fn test_try_sign_rejects_null_private_key() {
+                use symbol_crypto_core::prelude::KeyPairSchema;
+
+                let public_key = Keypair::<KpSym>::random().public_key();
+                let kp = KpSym::from_null_private_key(public_key);
+                assert!(kp.try_sign(b"payload").is_err());
+
+                let kp = Keypair::<KpSym>::from_private_key(PrivateKey::zero());
+                assert!(kp.try_sign(b"payload").is_ok());
+            }
+
+            #[test]
+            #[should_panic(expected = "keypair cannot sign")]
+            fn test_sign_null_private_key_panic() {
+                use symbol_crypto_core::prelude::KeyPairSchema;
+
+                let public_key = Keypair::<KpSym>::random().public_key();
+                KpSym::from_null_private_key(public_key).sign(b"payload");
+            }
         }
 
+        #[allow(deprecated)]
         pub mod tests_verify {
             use super::*;
 
@@ -243,11 +266,11 @@ pub mod tests {
             #[test]
             fn test_verify_zero_public_key() {
                 let mut key_pair = Keypair::<KpSym>::random();
-                key_pair.0.public_key.0.fill(0);
 
                 let payload = H256::random();
 
                 let signature = key_pair.sign(payload.as_bytes());
+                key_pair.0.public_key.0.fill(0);
                 let is_verified = key_pair.verify(payload.as_bytes(), signature);
 
                 assert!(is_verified.is_err());
@@ -297,6 +320,7 @@ pub mod tests {
             }
         }
 
+        #[allow(deprecated)]
         pub mod tests_sign {
             use super::*;
 
@@ -316,6 +340,19 @@ pub mod tests {
                 // Assert:
                 assert_eq!(signature.encode_hex::<String>(), expected_signature);
             }
+
+            #[test]
+            fn test_try_sign_rejects_null_private_key() {
+                use symbol_crypto_core::prelude::KeyPairSchema;
+
+                let public_key = Keypair::<KpNis1>::random().public_key();
+                let kp = KpNis1::from_null_private_key(public_key);
+                assert!(kp.try_sign(b"payload").is_err());
+
+                // the zero private key is a key like any other.
+                let kp = Keypair::<KpNis1>::from_private_key(PrivateKey::zero());
+                assert!(kp.try_sign(b"payload").is_ok());
+            }
         }
 
         pub mod tests_verify {
@@ -406,4 +443,43 @@ pub mod tests {
             }
         }
     }
+
+    pub mod tests_facade_schema {
+        use symbol_crypto_core::prelude::{KeyPairSchema, KpNis1, KpSym};
+
+        use super::*;
+
+        // generic code bound by the schema trait only.
+        fn signature_of<K: KeyPairSchema>(kp: &K, data: &[u8]) -> Signature {
+            let signature = kp.try_sign(data).unwrap();
+            K::from_null_private_key(kp.public_key()).verify(data, signature).unwrap();
+            signature
+        }
+
+        #[test]
+        fn test_facade_and_bare_schema_are_interchangeable() {
+            let private_key = PrivateKey::repeat_byte(7);
+            let facade = Keypair::<KpSym>::from_private_key(private_key);
+            let bare = KpSym::from_private_key(private_key);
+
+            let data = b"generic over both layers";
+            assert_eq!(signature_of(&facade, data), signature_of(&bare, data));
+            assert_eq!(KeyPairSchema::to_bytes(&facade), bare.to_bytes());
+            assert_eq!(
+                <Keypair<KpNis1> as KeyPairSchema>::SCHEMA_NAME,
+                <KpNis1 as KeyPairSchema>::SCHEMA_NAME
+            );
+        }
+
+        #[test]
+        fn test_facade_schema_constructors() {
+            let kp = <Keypair<KpNis1> as KeyPairSchema>::random();
+            let restored = <Keypair<KpNis1> as KeyPairSchema>::from_bytes(&kp.to_bytes()).unwrap();
+            assert_eq!(restored, kp);
+            assert_eq!(
+                <Keypair<KpNis1> as KeyPairSchema>::derive_public_keys(&[kp.private_key()]),
+                vec![kp.public_key()]
+            );
+        }
+    }
 }
@@ -0,0 +1,43 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(test)]
+pub mod tests {
+    use symbol_crypto_core::prelude::{
+        derive_index_for_label, find_label_collisions, LabelCollision,
+    };
+
+    #[test]
+    fn test_derive_index_for_label_vectors() {
+        assert_eq!(derive_index_for_label("savings"), 1484957162);
+        assert_eq!(derive_index_for_label("trading"), 757054560);
+        assert_eq!(derive_index_for_label(""), 2059496063);
+    }
+
+    #[test]
+    fn test_derive_index_for_label_is_not_hardened() {
+        for i in 0..1000 {
+            assert!(derive_index_for_label(&format!("label-{}", i)) < 0x8000_0000);
+        }
+    }
+
+    #[test]
+    fn test_find_label_collisions() {
+        assert!(find_label_collisions(&["savings", "trading", "savings"]).is_empty());
+
+        let collisions =
+            find_label_collisions(&["account-44575", "savings", "account-32496", "account-44575"]);
+        assert_eq!(
+            collisions,
+            vec![LabelCollision {
+                index: 1266037218,
+                labels: vec!["account-44575", "account-32496"],
+            }]
+        );
+    }
+}
@@ -0,0 +1,57 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(test)]
+pub mod tests {
+    use symbol_crypto_core::prelude::{Keypair, KpSym};
+    use symbol_crypto_core::sym::link_proof::{self, LinkProof, LINK_PROOF_SIZE};
+
+    #[test]
+    fn test_create_verify() {
+        let main = Keypair::<KpSym>::random();
+        let remote = Keypair::<KpSym>::random();
+
+        let proof = link_proof::create(&main, &remote.public_key()).unwrap();
+        assert_eq!(proof.main_public_key, main.public_key());
+        assert_eq!(proof.remote_public_key, remote.public_key());
+        assert!(link_proof::verify(&proof).is_ok());
+
+        let bytes = proof.to_bytes();
+        assert_eq!(bytes.len(), LINK_PROOF_SIZE);
+        assert_eq!(LinkProof::from_bytes(&bytes).unwrap(), proof);
+    }
+
+    #[test]
+    fn test_verify_rejects_tampered_proof() {
+        let main = Keypair::<KpSym>::random();
+        let remote = Keypair::<KpSym>::random();
+        let other = Keypair::<KpSym>::random();
+        let proof = link_proof::create(&main, &remote.public_key()).unwrap();
+
+        let mut tampered = proof;
+        tampered.remote_public_key = other.public_key();
+        assert!(link_proof::verify(&tampered).is_err());
+
+        let mut tampered = proof;
+        tampered.main_public_key = other.public_key();
+        assert!(link_proof::verify(&tampered).is_err());
+
+        // a plain signature of the keys is not a proof.
+        let mut tampered = proof;
+        tampered.signature =
+            main.try_sign(&[main.public_key().as_bytes(), remote.public_key().as_bytes()].concat()).unwrap();
+        assert!(link_proof::verify(&tampered).is_err());
+    }
+
+    #[test]
+    fn test_invalid() {
+        let main = Keypair::<KpSym>::random();
+        assert!(link_proof::create(&main, &main.public_key()).is_err());
+        assert!(LinkProof::from_bytes(&[0u8; LINK_PROOF_SIZE - 1]).is_err());
+    }
+}
@@ -0,0 +1,71 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(test)]
+pub mod tests {
+    use symbol_crypto_core::prelude::memlock::LockedKeypair;
+    use symbol_crypto_core::prelude::{KeyPairSchema, Keypair, KpNis1, KpSym, Signer};
+
+    fn assert_signs_as_keypair<Kp: KeyPairSchema>() {
+        let kp = Keypair::<Kp>::random();
+        let locked = LockedKeypair::<Kp>::from_keypair(kp);
+
+        assert_eq!(locked.public_key(), kp.public_key());
+        let signature = locked.try_sign(b"locked").unwrap();
+        assert_eq!(signature, kp.try_sign(b"locked").unwrap());
+        assert!(locked.verify(b"locked", signature).is_ok());
+        assert!(kp.verify(b"locked", signature).is_ok());
+    }
+
+    #[test]
+    fn test_locked_keypair_sym() {
+        assert_signs_as_keypair::<KpSym>();
+    }
+
+    #[test]
+    fn test_locked_keypair_nis1() {
+        assert_signs_as_keypair::<KpNis1>();
+    }
+
+    #[test]
+    fn test_locked_keypair_from_private_key() {
+        let kp = Keypair::<KpSym>::random();
+        let locked = LockedKeypair::<KpSym>::from_private_key(kp.private_key());
+
+        assert_eq!(Signer::public_key(&locked), kp.public_key());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_locked_keypair_is_locked() {
+        // a single page is within the default RLIMIT_MEMLOCK of every unix.
+        assert!(LockedKeypair::<KpSym>::from_keypair(Keypair::random()).is_locked());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_dropping_a_locked_keypair_keeps_the_others_locked() {
+        let kp = Keypair::<KpSym>::random();
+        let first = LockedKeypair::<KpSym>::from_keypair(kp);
+        let second = LockedKeypair::<KpSym>::from_keypair(Keypair::random());
+        drop(second);
+
+        assert!(first.is_locked());
+        let signature = first.try_sign(b"locked").unwrap();
+        assert!(kp.verify(b"locked", signature).is_ok());
+    }
+
+    #[test]
+    fn test_locked_keypair_debug_is_redacted() {
+        let kp = Keypair::<KpSym>::random();
+        let debug = format!("{:?}", LockedKeypair::<KpSym>::from_keypair(kp));
+
+        assert!(debug.contains("[redacted]"));
+        assert!(!debug.contains(&format!("{:x}", kp.private_key())));
+    }
+}
@@ -0,0 +1,75 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(test)]
+pub mod tests {
+    use symbol_crypto_core::prelude::{Keypair, KpSym};
+    use symbol_crypto_core::sym::{decrypt_metadata, encrypt_metadata};
+
+    const SOURCE_SK: &str = "575DBB3062267EFF57C970A336EBBC8FBCFE12C5BD3ED7BC11EB0481D7704CED";
+    const TARGET_SK: &str = "5B0E3FA5D3B49A79022D7C1E121BA1CBBF4DB5821F47AB8C708EF88DEFC29BFE";
+    const SCOPED_KEY: u64 = 0xDEAD_BEEF;
+    const VALUE: &[u8] = b"kyc:verified";
+
+    fn keypairs() -> (Keypair<KpSym>, Keypair<KpSym>) {
+        (
+            Keypair::from_hex_private_key(SOURCE_SK).unwrap(),
+            Keypair::from_hex_private_key(TARGET_SK).unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_encrypt_metadata_vector() {
+        let (source, target) = keypairs();
+
+        let enc = encrypt_metadata(&source, SCOPED_KEY, &target.public_key(), VALUE).unwrap();
+        assert_eq!(
+            hex::encode_upper(&enc),
+            "4CBDC008BC99C9AA1A88494738177FB1CBC19D892D97F676544C016326C673C8F69A76A2AC7CB58D"
+        );
+    }
+
+    #[test]
+    fn test_decrypt_metadata() {
+        let (source, target) = keypairs();
+        let (source_pk, target_pk) = (source.public_key(), target.public_key());
+
+        let enc = encrypt_metadata(&source, SCOPED_KEY, &target_pk, VALUE).unwrap();
+        for kp in [source, target].iter() {
+            assert_eq!(
+                decrypt_metadata(kp, SCOPED_KEY, &source_pk, &target_pk, &enc).unwrap(),
+                VALUE
+            );
+        }
+
+        assert!(decrypt_metadata(&source, SCOPED_KEY + 1, &source_pk, &target_pk, &enc).is_err());
+        let other = Keypair::<KpSym>::random();
+        assert!(decrypt_metadata(&other, SCOPED_KEY, &source_pk, &target_pk, &enc).is_err());
+        assert!(decrypt_metadata(&source, SCOPED_KEY, &source_pk, &target_pk, &enc[..20]).is_err());
+    }
+
+    #[test]
+    fn test_encrypt_metadata_unrelated_ciphertexts() {
+        let (source, target) = keypairs();
+        let target_pk = target.public_key();
+
+        let enc = encrypt_metadata(&source, SCOPED_KEY, &target_pk, VALUE).unwrap();
+        assert_eq!(
+            enc,
+            encrypt_metadata(&source, SCOPED_KEY, &target_pk, VALUE).unwrap()
+        );
+
+        let other_scope = encrypt_metadata(&source, SCOPED_KEY + 1, &target_pk, VALUE).unwrap();
+        let other_target =
+            encrypt_metadata(&source, SCOPED_KEY, &source.public_key(), VALUE).unwrap();
+        for other in [other_scope, other_target].iter() {
+            assert_ne!(&other[..28], &enc[..28]);
+            assert_ne!(&other[28..], &enc[28..]);
+        }
+    }
+}
@@ -0,0 +1,141 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(test)]
+pub mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+
+    use symbol_crypto_core::prelude::migration::{
+        migrate_blob, migrate_dir, read_legacy, KeyMaterial, KeyMaterialKind, LegacyEncoding,
+        MigrationOptions, MigrationOutcome,
+    };
+    use symbol_crypto_core::prelude::test_keys::{alice, bob};
+    use symbol_crypto_core::prelude::{KpNis1, KpSym};
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir()
+            .join(format!("symbol-crypto-core-migration-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("nested")).unwrap();
+        dir
+    }
+
+    fn bincode_blob(bytes: &[u8]) -> Vec<u8> {
+        [&(bytes.len() as u64).to_le_bytes()[..], bytes].concat()
+    }
+
+    #[test]
+    fn test_read_legacy_encodings() {
+        let kp = alice::<KpSym>();
+        let raw = kp.to_bytes();
+        let json = serde_json::to_vec(&raw.to_vec()).unwrap();
+
+        for (blob, encoding) in [
+            (raw.to_vec(), LegacyEncoding::Raw),
+            (bincode_blob(&raw), LegacyEncoding::Bincode),
+            (json, LegacyEncoding::Json),
+        ]
+        .iter()
+        {
+            let (read, read_encoding) = read_legacy::<KpSym>(blob).unwrap();
+            assert_eq!(read, kp);
+            assert_eq!(read_encoding, *encoding);
+        }
+    }
+
+    #[test]
+    fn test_read_legacy_rejects_the_other_schema() {
+        let raw = alice::<KpSym>().to_bytes();
+        assert!(read_legacy::<KpNis1>(&raw).is_err());
+        assert!(read_legacy::<KpSym>(&raw[..32]).is_err());
+    }
+
+    #[test]
+    fn test_migrate_blob() {
+        let kp = alice::<KpSym>();
+        let migrated = migrate_blob::<KpSym>(&kp.to_bytes(), KeyMaterialKind::Keypair).unwrap();
+        let material = KeyMaterial::from_bytes(&migrated).unwrap();
+        assert_eq!(material.kind(), KeyMaterialKind::Keypair);
+        assert_eq!(material.keypair::<KpSym>().unwrap(), kp);
+        assert!(material.keypair::<KpNis1>().is_err());
+
+        let public = migrate_blob::<KpSym>(&kp.to_bytes(), KeyMaterialKind::PublicOnly).unwrap();
+        let material = KeyMaterial::from_bytes(&public).unwrap();
+        assert_eq!(material.public_key, kp.public_key());
+        assert_eq!(material.private_key, None);
+        assert!(material.keypair::<KpSym>().is_err());
+    }
+
+    #[test]
+    fn test_key_material_rejects_unknown_version() {
+        let mut bytes = KeyMaterial::from_keypair(&bob::<KpSym>(), KeyMaterialKind::Keypair)
+            .to_bytes()
+            .to_vec();
+        assert!(!format!("{:?}", KeyMaterial::from_bytes(&bytes).unwrap())
+            .contains(&format!("{:X}", bob::<KpSym>().private_key())));
+        bytes[4] = 2;
+        assert!(KeyMaterial::from_bytes(&bytes).is_err());
+    }
+
+    #[test]
+    fn test_migrate_dir() {
+        let dir = temp_dir("dir");
+        let alice_kp = alice::<KpSym>();
+        let bob_kp = bob::<KpSym>();
+        fs::write(dir.join("alice.key"), alice_kp.to_bytes()).unwrap();
+        fs::write(dir.join("nested").join("bob.key"), bincode_blob(&bob_kp.to_bytes())).unwrap();
+        fs::write(dir.join("README"), b"not a key").unwrap();
+        fs::write(dir.join("nis1.key"), alice::<KpNis1>().to_bytes()).unwrap();
+
+        let options = MigrationOptions { keep_backup: true, ..Default::default() };
+        let mut calls = Vec::new();
+        let report = migrate_dir::<KpSym, _>(&dir, &options, |progress| {
+            calls.push((progress.done, progress.total, progress.outcome.clone()));
+        })
+        .unwrap();
+
+        assert_eq!(calls.len(), 4);
+        assert!(calls.iter().all(|(_, total, _)| *total == 4));
+        assert_eq!(calls.last().unwrap().0, 4);
+        let migrated = vec![dir.join("alice.key"), dir.join("nested").join("bob.key")];
+        assert_eq!(report.migrated, migrated);
+        assert_eq!(report.unrecognized, vec![dir.join("README")]);
+        assert_eq!(report.failed.len(), 1);
+        assert_eq!(report.failed[0].0, dir.join("nis1.key"));
+
+        let material = KeyMaterial::from_bytes(&fs::read(dir.join("alice.key")).unwrap()).unwrap();
+        assert_eq!(material.keypair::<KpSym>().unwrap(), alice_kp);
+        assert_eq!(fs::read(dir.join("alice.key.bak")).unwrap(), alice_kp.to_bytes().to_vec());
+        assert_eq!(fs::read(dir.join("nis1.key")).unwrap(), alice::<KpNis1>().to_bytes().to_vec());
+
+        // a second run skips the migrated files and ignores the backups.
+        let report = migrate_dir::<KpSym, _>(&dir, &options, |_| {}).unwrap();
+        assert!(report.migrated.is_empty());
+        assert_eq!(report.already_migrated.len(), 2);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_migrate_dir_dry_run() {
+        let dir = temp_dir("dry-run");
+        let kp = alice::<KpSym>();
+        fs::write(dir.join("alice.key"), kp.to_bytes()).unwrap();
+
+        let options = MigrationOptions { dry_run: true, ..Default::default() };
+        let report = migrate_dir::<KpSym, _>(&dir, &options, |progress| {
+            assert_eq!(*progress.outcome, MigrationOutcome::Migrated(LegacyEncoding::Raw));
+        })
+        .unwrap();
+        assert_eq!(report.migrated.len(), 1);
+        assert_eq!(fs::read(dir.join("alice.key")).unwrap(), kp.to_bytes().to_vec());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,67 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(test)]
+pub mod tests {
+    use anyhow::Result;
+
+    use symbol_crypto_core::prelude::{Keypair, KpSym, MockSigner, PublicKey, Signature, Signer};
+
+    // orchestration under test: sign every payload, stop at the first error.
+    fn sign_all<S: Signer>(signer: &S, payloads: &[&[u8]]) -> Result<Vec<Signature>> {
+        payloads.iter().map(|data| signer.try_sign(data)).collect()
+    }
+
+    #[test]
+    fn test_mock_signer_returns_scripted_signatures_in_order() {
+        let signer = MockSigner::new(PublicKey::repeat_byte(1))
+            .with_signature(Signature::repeat_byte(2))
+            .with_signature(Signature::repeat_byte(3));
+
+        let signatures = sign_all(&signer, &[b"a", b"b"]).unwrap();
+
+        assert_eq!(
+            signatures,
+            vec![Signature::repeat_byte(2), Signature::repeat_byte(3)]
+        );
+        assert_eq!(signer.public_key(), PublicKey::repeat_byte(1));
+        assert_eq!(signer.calls(), vec![b"a".to_vec(), b"b".to_vec()]);
+        assert_eq!(signer.remaining(), 0);
+    }
+
+    #[test]
+    fn test_mock_signer_scripted_error() {
+        let signer = MockSigner::new(PublicKey::zero())
+            .with_error("denied")
+            .with_signature(Signature::zero());
+
+        let err = sign_all(&signer, &[b"a", b"b"]).unwrap_err();
+
+        assert_eq!(err.to_string(), "denied");
+        assert_eq!(signer.calls(), vec![b"a".to_vec()]);
+        assert_eq!(signer.remaining(), 1);
+    }
+
+    #[test]
+    fn test_mock_signer_fails_when_script_is_exhausted() {
+        let signer = MockSigner::new(PublicKey::zero());
+
+        assert!(signer.try_sign(b"a").is_err());
+        assert_eq!(signer.calls().len(), 1);
+    }
+
+    #[test]
+    fn test_keypair_is_a_signer() {
+        let kp = Keypair::<KpSym>::random();
+
+        let signatures = sign_all(&kp, &[b"a"]).unwrap();
+
+        assert!(kp.verify(b"a", signatures[0]).is_ok());
+        assert_eq!(Signer::public_key(&kp), kp.public_key());
+    }
+}
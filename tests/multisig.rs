@@ -0,0 +1,163 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(test)]
+pub mod tests {
+    use std::collections::{BTreeMap, BTreeSet};
+
+    use symbol_crypto_core::prelude::PublicKey;
+    use symbol_crypto_core::sym::{check_cosignatures, MultisigEntry, MultisigOperation};
+
+    fn pk(n: u8) -> PublicKey {
+        PublicKey::from([n; 32])
+    }
+
+    fn signers(keys: &[u8]) -> BTreeSet<PublicKey> {
+        keys.iter().map(|n| pk(*n)).collect()
+    }
+
+    fn entry(min_approval: u32, min_removal: u32, cosignatories: &[u8]) -> MultisigEntry {
+        MultisigEntry {
+            min_approval,
+            min_removal,
+            cosignatories: cosignatories.iter().map(|n| pk(*n)).collect(),
+        }
+    }
+
+    #[test]
+    fn test_single_level() {
+        let mut multisig = BTreeMap::new();
+        multisig.insert(pk(0), entry(2, 3, &[1, 2, 3]));
+
+        let check = check_cosignatures(
+            &pk(0),
+            &multisig,
+            &signers(&[1]),
+            MultisigOperation::Approval,
+        )
+        .unwrap();
+        assert!(!check.satisfied);
+        assert_eq!(check.missing, signers(&[2, 3]));
+
+        let check = check_cosignatures(
+            &pk(0),
+            &multisig,
+            &signers(&[1, 3]),
+            MultisigOperation::Approval,
+        )
+        .unwrap();
+        assert!(check.satisfied);
+        assert!(check.missing.is_empty());
+
+        let check = check_cosignatures(
+            &pk(0),
+            &multisig,
+            &signers(&[1, 3]),
+            MultisigOperation::Removal,
+        )
+        .unwrap();
+        assert!(!check.satisfied);
+        assert_eq!(check.missing, signers(&[2]));
+    }
+
+    #[test]
+    fn test_duplicate_cosignatory_counts_once() {
+        let mut multisig = BTreeMap::new();
+        multisig.insert(pk(0), entry(2, 2, &[1, 1, 2]));
+
+        let check = check_cosignatures(
+            &pk(0),
+            &multisig,
+            &signers(&[1]),
+            MultisigOperation::Approval,
+        )
+        .unwrap();
+        assert!(!check.satisfied);
+        assert_eq!(check.missing, signers(&[2]));
+    }
+
+    #[test]
+    fn test_multilevel() {
+        // 0 <- {1, 10}, 10 <- {11, 20}, 20 <- {21, 22}
+        let mut multisig = BTreeMap::new();
+        multisig.insert(pk(0), entry(2, 2, &[1, 10]));
+        multisig.insert(pk(10), entry(1, 1, &[11, 20]));
+        multisig.insert(pk(20), entry(2, 1, &[21, 22]));
+
+        let check = check_cosignatures(
+            &pk(0),
+            &multisig,
+            &signers(&[1, 21]),
+            MultisigOperation::Approval,
+        )
+        .unwrap();
+        assert!(!check.satisfied);
+        assert_eq!(check.missing, signers(&[11, 22]));
+
+        let check = check_cosignatures(
+            &pk(0),
+            &multisig,
+            &signers(&[1, 21, 22]),
+            MultisigOperation::Approval,
+        )
+        .unwrap();
+        assert!(check.satisfied);
+
+        let check = check_cosignatures(
+            &pk(0),
+            &multisig,
+            &signers(&[1, 11]),
+            MultisigOperation::Approval,
+        )
+        .unwrap();
+        assert!(check.satisfied);
+    }
+
+    #[test]
+    fn test_depth_limit() {
+        let mut multisig = BTreeMap::new();
+        multisig.insert(pk(0), entry(1, 1, &[10]));
+        multisig.insert(pk(10), entry(1, 1, &[20]));
+        multisig.insert(pk(20), entry(1, 1, &[30]));
+        multisig.insert(pk(30), entry(1, 1, &[40]));
+
+        assert!(check_cosignatures(
+            &pk(0),
+            &multisig,
+            &signers(&[40]),
+            MultisigOperation::Approval
+        )
+        .is_err());
+        assert!(
+            check_cosignatures(
+                &pk(10),
+                &multisig,
+                &signers(&[40]),
+                MultisigOperation::Approval
+            )
+            .unwrap()
+            .satisfied
+        );
+    }
+
+    #[test]
+    fn test_cosigner_set_hash() {
+        assert_eq!(
+            entry(2, 1, &[1, 2, 3]).hash(),
+            entry(2, 1, &[3, 1, 2]).hash()
+        );
+        assert_ne!(
+            entry(2, 1, &[1, 2, 3]).hash(),
+            entry(2, 2, &[1, 2, 3]).hash()
+        );
+        assert_ne!(
+            entry(2, 1, &[1, 2, 3]).hash(),
+            entry(2, 1, &[1, 2, 4]).hash()
+        );
+    }
+}
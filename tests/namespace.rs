@@ -0,0 +1,62 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(test)]
+pub mod tests {
+    use symbol_crypto_core::sym::namespace::{
+        generate_namespace_id, id_from_full_name, is_valid_name, parent_chain,
+    };
+
+    #[test]
+    fn test_id_from_full_name() {
+        assert_eq!(id_from_full_name("symbol").unwrap(), 0xA95F_1F8A_9615_9516);
+        assert_eq!(
+            id_from_full_name("symbol.xym").unwrap(),
+            0xE74B_99BA_41F4_AFEE
+        );
+        assert_eq!(id_from_full_name("nem.xem").unwrap(), 0xD525_AD41_D95F_CF29);
+        assert_eq!(
+            id_from_full_name("foo.bar.baz").unwrap(),
+            0xD747_F3A2_987A_9B64
+        );
+    }
+
+    #[test]
+    fn test_parent_chain() {
+        assert_eq!(
+            parent_chain("foo.bar.baz").unwrap(),
+            vec![
+                0x82A9_D1AC_587E_C054,
+                0xEC67_3E10_5521_B12F,
+                0xD747_F3A2_987A_9B64
+            ]
+        );
+        assert_eq!(
+            generate_namespace_id(0xA95F_1F8A_9615_9516, "xym").unwrap(),
+            0xE74B_99BA_41F4_AFEE
+        );
+    }
+
+    #[test]
+    fn test_invalid_names() {
+        assert!(is_valid_name("a"));
+        assert!(is_valid_name("0-my_name"));
+        assert!(is_valid_name(&"a".repeat(64)));
+
+        for name in ["", "-a", "_a", "Abc", "a b", "a.b", "añ"].iter() {
+            assert!(!is_valid_name(name), "{:?}", name);
+        }
+        assert!(!is_valid_name(&"a".repeat(65)));
+
+        assert!(id_from_full_name("").is_err());
+        assert!(id_from_full_name("foo..bar").is_err());
+        assert!(id_from_full_name("foo.bar.").is_err());
+        assert!(id_from_full_name("a.b.c.d").is_err());
+        assert!(id_from_full_name("Symbol.xym").is_err());
+    }
+}
@@ -0,0 +1,96 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(test)]
+pub mod tests {
+    use symbol_crypto_core::prelude::{Keypair, KpSym};
+    use symbol_crypto_core::sym::network_gen::{
+        block_hash, verify_block_header, NemesisSigner, IMPORTANCE_BLOCK_HEADER_SIZE,
+        NEMESIS_BLOCK_TYPE, NORMAL_BLOCK_TYPE, VERIFIABLE_ENTITY_HEADER_SIZE,
+    };
+
+    const PRIVATE_KEY: &str = "575DBB3062267EFF57C970A336EBBC8FBCFE12C5BD3ED7BC11EB0481D7704CED";
+    const EPOCH_ADJUSTMENT: u64 = 1_615_853_185;
+
+    fn nemesis_block(network: u8, block_type: u16, height: u64) -> Vec<u8> {
+        let mut block = vec![0u8; IMPORTANCE_BLOCK_HEADER_SIZE + 16];
+        let size = block.len() as u32;
+        block[..4].copy_from_slice(&size.to_le_bytes());
+        block[VERIFIABLE_ENTITY_HEADER_SIZE] = 1;
+        block[VERIFIABLE_ENTITY_HEADER_SIZE + 1] = network;
+        block[VERIFIABLE_ENTITY_HEADER_SIZE + 2..VERIFIABLE_ENTITY_HEADER_SIZE + 4]
+            .copy_from_slice(&block_type.to_le_bytes());
+        block[VERIFIABLE_ENTITY_HEADER_SIZE + 4..VERIFIABLE_ENTITY_HEADER_SIZE + 12]
+            .copy_from_slice(&height.to_le_bytes());
+        block
+    }
+
+    #[test]
+    fn test_generation_hash_seed_is_deterministic() {
+        let kp = Keypair::<KpSym>::from_hex_private_key(PRIVATE_KEY).unwrap();
+        let seed = NemesisSigner::new(&kp, 0x98, EPOCH_ADJUSTMENT).generation_hash_seed();
+
+        assert_eq!(
+            NemesisSigner::new(&kp, 0x98, EPOCH_ADJUSTMENT).generation_hash_seed(),
+            seed
+        );
+        assert_ne!(
+            NemesisSigner::new(&kp, 0x68, EPOCH_ADJUSTMENT).generation_hash_seed(),
+            seed
+        );
+        assert_ne!(
+            NemesisSigner::new(&kp, 0x98, EPOCH_ADJUSTMENT + 1).generation_hash_seed(),
+            seed
+        );
+    }
+
+    #[test]
+    fn test_sign_nemesis_block() {
+        let kp = Keypair::<KpSym>::from_hex_private_key(PRIVATE_KEY).unwrap();
+        let signer = NemesisSigner::new(&kp, 0x98, EPOCH_ADJUSTMENT);
+
+        let mut block = nemesis_block(0x98, NEMESIS_BLOCK_TYPE, 1);
+        let hash = signer.sign_block_header(&mut block).unwrap();
+        assert_eq!(block_hash(&block).unwrap(), hash);
+        assert_eq!(&block[72..104], kp.public_key().as_bytes());
+        assert!(verify_block_header(&block).is_ok());
+
+        // the transactions are not part of the signed header.
+        let last = block.len() - 1;
+        block[last] ^= 0xFF;
+        assert!(verify_block_header(&block).is_ok());
+        assert_eq!(block_hash(&block).unwrap(), hash);
+
+        block[IMPORTANCE_BLOCK_HEADER_SIZE - 1] ^= 0xFF;
+        assert!(verify_block_header(&block).is_err());
+        assert_ne!(block_hash(&block).unwrap(), hash);
+    }
+
+    #[test]
+    fn test_sign_nemesis_block_invalid() {
+        let kp = Keypair::<KpSym>::from_hex_private_key(PRIVATE_KEY).unwrap();
+        let signer = NemesisSigner::new(&kp, 0x98, EPOCH_ADJUSTMENT);
+
+        let mut block = nemesis_block(0x68, NEMESIS_BLOCK_TYPE, 1);
+        assert!(signer.sign_block_header(&mut block).is_err());
+
+        let mut block = nemesis_block(0x98, NORMAL_BLOCK_TYPE, 1);
+        assert!(signer.sign_block_header(&mut block).is_err());
+
+        let mut block = nemesis_block(0x98, NEMESIS_BLOCK_TYPE, 2);
+        assert!(signer.sign_block_header(&mut block).is_err());
+
+        let mut block = nemesis_block(0x98, 0x1234, 1);
+        assert!(signer.sign_block_header(&mut block).is_err());
+
+        let mut block = nemesis_block(0x98, NEMESIS_BLOCK_TYPE, 1);
+        assert!(signer
+            .sign_block_header(&mut block[..IMPORTANCE_BLOCK_HEADER_SIZE - 1])
+            .is_err());
+    }
+}
@@ -0,0 +1,71 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(test)]
+pub mod tests {
+    use std::str::FromStr;
+
+    use sha3::{Digest, Keccak512};
+    use symbol_crypto_core::prelude::{
+        clamp_scalar_bits, hex_to_bytes, Keypair, KpNis1, PrivateKey, KEY_BYTES_SIZE,
+    };
+
+    // nem-library `KeyPair.create` vector.
+    const PRIVATE_KEY: &str = "c9fb7f16b738b783be5192697a684cba4a36adb3d9c22c0808f30ae1d85d384f";
+    const PUBLIC_KEY: &str = "ED9BF729C0D93F238BC4AF468B952C35071D9FE1219B27C30DFE108C2E3DB030";
+    const NIS1_ORDER: &str = "4F385DD8E10AF308082CC2D9B3AD364ABA4C687A699251BE83B738B7167FFBC9";
+
+    // the clamped lower half of Keccak-512(reversed private key), see `expanded_scalar.rs`.
+    const SCALAR_PRIVATE_KEY: &str =
+        "575DBB3062267EFF57C970A336EBBC8FBCFE12C5BD3ED7BC11EB0481D7704CED";
+    const NIS1_SCALAR: &str = "80636C4B4058685599BE4D3483AA71CCD2E83862C3550AC833DD60DBCE3AC141";
+
+    #[test]
+    fn test_reverse_for_nis1_vector() {
+        let private_key = PrivateKey::from_str(PRIVATE_KEY).unwrap();
+        let nis1_order: [u8; KEY_BYTES_SIZE] = hex_to_bytes(NIS1_ORDER);
+
+        assert_eq!(*private_key.reverse_for_nis1(), nis1_order);
+        assert_eq!(PrivateKey::from_nis1_order(&nis1_order), private_key);
+    }
+
+    #[test]
+    fn test_nis1_order_round_trip() {
+        for _ in 0..100 {
+            let private_key = Keypair::<KpNis1>::random().private_key();
+            let nis1_order = private_key.reverse_for_nis1();
+
+            assert_eq!(PrivateKey::from_nis1_order(&nis1_order), private_key);
+            assert_eq!(
+                PrivateKey::from_nis1_order(private_key.as_fixed_bytes()).reverse_for_nis1()[..],
+                private_key.as_bytes()[..]
+            );
+        }
+    }
+
+    #[test]
+    fn test_nis1_order_is_the_hashed_order() {
+        let private_key = PrivateKey::from_str(SCALAR_PRIVATE_KEY).unwrap();
+        let hash = Keccak512::digest(&private_key.reverse_for_nis1()[..]);
+        let mut lower = [0u8; KEY_BYTES_SIZE];
+        lower.copy_from_slice(&hash[..KEY_BYTES_SIZE]);
+
+        assert_eq!(
+            clamp_scalar_bits(lower),
+            hex_to_bytes::<KEY_BYTES_SIZE>(NIS1_SCALAR)
+        );
+    }
+
+    #[test]
+    fn test_keypair_from_nis1_order() {
+        let nis1_order: [u8; KEY_BYTES_SIZE] = hex_to_bytes(NIS1_ORDER);
+        let kp = Keypair::<KpNis1>::from_private_key(PrivateKey::from_nis1_order(&nis1_order));
+
+        assert_eq!(format!("{:X}", kp.public_key()), PUBLIC_KEY);
+    }
+}
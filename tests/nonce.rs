@@ -0,0 +1,89 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(test)]
+pub mod tests {
+    use std::str::FromStr;
+
+    use symbol_crypto_core::prelude::{Keypair, KpNis1, KpSym, PrivateKey};
+    use symbol_crypto_core::{nis1, sym};
+
+    const PRIVATE_KEY: &str = "abf4cf55a2b3f742d7543d9cc17f50447b969e6e06f5ea9195d428ab12b7318d";
+    const DATA: &str =
+        "8ce03cd60514233b86789729102ea09e867fc6d964dea8c2018ef7d0a2e0e24bf7e348e917116690b9";
+
+    // the nonce commitments, first signature halves, of the keypair signing vectors.
+    const SYM_COMMITMENT: &str = "31d272f0662915cac43ab7d721caf65d8601f52b2e793ea1533e7bc20e04ea97";
+    const NIS1_COMMITMENT: &str =
+        "d9cec0cc0e3465fab229f8e1d6db68ab9cc99a18cb0435f70deb6100948576cd";
+
+    fn private_key() -> PrivateKey {
+        PrivateKey::from_str(PRIVATE_KEY).unwrap()
+    }
+
+    #[test]
+    fn test_sym_nonce_commitment_vector() {
+        let prefix = sym::nonce::nonce_prefix(&private_key());
+        let nonce = sym::nonce::derive_nonce(&prefix, &hex::decode(DATA).unwrap());
+
+        assert_eq!(hex::encode(sym::nonce::nonce_commitment(&nonce)), SYM_COMMITMENT);
+    }
+
+    #[test]
+    fn test_nis1_nonce_commitment_vector() {
+        let prefix = nis1::nonce::nonce_prefix(&private_key());
+        let nonce = nis1::nonce::derive_nonce(&prefix, &hex::decode(DATA).unwrap());
+
+        assert_eq!(hex::encode(nis1::nonce::nonce_commitment(&nonce)), NIS1_COMMITMENT);
+    }
+
+    #[test]
+    fn test_nonce_commitment_is_signature_r() {
+        let sym_kp = Keypair::<KpSym>::random();
+        let nis1_kp = Keypair::<KpNis1>::random();
+        let data = b"audited nonce";
+
+        let nonce =
+            sym::nonce::derive_nonce(&sym::nonce::nonce_prefix(&sym_kp.private_key()), data);
+        assert_eq!(
+            sym::nonce::nonce_commitment(&nonce)[..],
+            sym_kp.try_sign(data).unwrap().as_bytes()[..32]
+        );
+
+        let nonce =
+            nis1::nonce::derive_nonce(&nis1::nonce::nonce_prefix(&nis1_kp.private_key()), data);
+        assert_eq!(
+            nis1::nonce::nonce_commitment(&nonce)[..],
+            nis1_kp.try_sign(data).unwrap().as_bytes()[..32]
+        );
+    }
+
+    #[test]
+    fn test_derive_nonce_vectored_matches_joined() {
+        let prefix = sym::nonce::nonce_prefix(&private_key());
+
+        assert_eq!(
+            sym::nonce::derive_nonce_vectored(&prefix, &[b"audited", b" ", b"nonce"]),
+            sym::nonce::derive_nonce(&prefix, b"audited nonce")
+        );
+
+        let prefix = nis1::nonce::nonce_prefix(&private_key());
+        assert_eq!(
+            nis1::nonce::derive_nonce_vectored(&prefix, &[b"audited", b" nonce"]),
+            nis1::nonce::derive_nonce(&prefix, b"audited nonce")
+        );
+    }
+
+    #[test]
+    fn test_nonce_prefix_differs_between_schemas() {
+        assert_ne!(
+            sym::nonce::nonce_prefix(&private_key()),
+            nis1::nonce::nonce_prefix(&private_key())
+        );
+    }
+}
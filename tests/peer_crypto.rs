@@ -0,0 +1,105 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(test)]
+pub mod tests {
+    use symbol_crypto_core::prelude::{Keypair, KpSym};
+    use symbol_crypto_core::sym::handshake::{
+        generate_server_challenge_request, generate_server_challenge_response, SecurityMode,
+    };
+    use symbol_crypto_core::sym::peer_crypto::{PeerCipher, PeerRole, PEER_PACKET_HEADER_SIZE};
+
+    fn connection() -> (PeerCipher, PeerCipher) {
+        let server = Keypair::<KpSym>::random();
+        let client = Keypair::<KpSym>::random();
+
+        let request = generate_server_challenge_request();
+        let response = generate_server_challenge_response(&request, &client, SecurityMode::None);
+
+        (
+            PeerCipher::new(
+                &client,
+                &server.public_key(),
+                &request,
+                &response,
+                PeerRole::Client,
+            ),
+            PeerCipher::new(
+                &server,
+                &client.public_key(),
+                &request,
+                &response,
+                PeerRole::Server,
+            ),
+        )
+    }
+
+    #[test]
+    fn test_encode_decode_both_directions() {
+        let (mut client, mut server) = connection();
+
+        for i in 0..3u8 {
+            let packet = client.encode(&[i; 10]).unwrap();
+            assert_eq!(packet.len(), PEER_PACKET_HEADER_SIZE + 10);
+            assert_eq!(server.packet_size(&packet[..4]).unwrap(), packet.len());
+            assert_eq!(server.decode(&packet).unwrap(), vec![i; 10]);
+
+            let packet = server.encode(b"").unwrap();
+            assert!(client.decode(&packet).unwrap().is_empty());
+        }
+    }
+
+    #[test]
+    fn test_decode_rejects_replay_and_reorder() {
+        let (mut client, mut server) = connection();
+
+        let first = client.encode(b"first").unwrap();
+        let second = client.encode(b"second").unwrap();
+        assert!(server.decode(&second).is_err());
+        assert_eq!(server.decode(&first).unwrap(), b"first");
+        assert!(server.decode(&first).is_err());
+        assert_eq!(server.decode(&second).unwrap(), b"second");
+
+        // a packet is not accepted back by its sender.
+        let packet = client.encode(b"echo").unwrap();
+        assert!(client.decode(&packet).is_err());
+    }
+
+    #[test]
+    fn test_connections_have_distinct_keys() {
+        let (mut client, _) = connection();
+        let (_, mut other_server) = connection();
+
+        let packet = client.encode(b"packet").unwrap();
+        assert!(other_server.decode(&packet).is_err());
+    }
+
+    #[test]
+    fn test_debug_does_not_print_keys() {
+        let (mut client, _) = connection();
+        client.encode(b"packet").unwrap();
+
+        let debug = format!("{:?}", client);
+        assert!(debug.contains("send_counter: 1"));
+        assert!(!debug.contains("key"));
+    }
+
+    #[test]
+    fn test_max_packet_size() {
+        let (client, server) = connection();
+        let mut client = client.with_max_packet_size(PEER_PACKET_HEADER_SIZE + 4);
+        let mut server = server.with_max_packet_size(PEER_PACKET_HEADER_SIZE + 4);
+
+        assert!(client.encode(&[0u8; 5]).is_err());
+        let mut packet = client.encode(&[0u8; 4]).unwrap();
+        packet[..4].copy_from_slice(&(PEER_PACKET_HEADER_SIZE as u32 + 5).to_le_bytes());
+        assert!(server.packet_size(&packet).is_err());
+        assert!(server.decode(&packet).is_err());
+        assert!(server.packet_size(&[0u8; 3]).is_err());
+    }
+}
@@ -0,0 +1,76 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(test)]
+pub mod tests {
+    use symbol_crypto_core::prelude::possession::{
+        create_challenge, respond, verify, PossessionChallenge, PossessionResponse,
+    };
+    use symbol_crypto_core::prelude::{Keypair, KpNis1, KpSym};
+
+    const DOMAIN: &str = "exchange.example.com";
+    const NOW: u64 = 1_650_000_000;
+
+    #[test]
+    fn test_possession() {
+        let kp = Keypair::<KpSym>::random();
+        let challenge = create_challenge(DOMAIN, NOW, 300).unwrap();
+        let response = respond(&kp, &challenge).unwrap();
+
+        assert!(verify::<KpSym>(&kp.public_key(), &challenge, &response, NOW + 10).is_ok());
+        assert!(verify::<KpSym>(&kp.public_key(), &challenge, &response, NOW + 301).is_err());
+        assert!(verify::<KpSym>(&kp.public_key(), &challenge, &response, NOW - 1).is_err());
+
+        let other = Keypair::<KpSym>::random();
+        assert!(verify::<KpSym>(&other.public_key(), &challenge, &response, NOW).is_err());
+    }
+
+    #[test]
+    fn test_possession_domain_separation() {
+        let kp = Keypair::<KpSym>::random();
+        let challenge = create_challenge(DOMAIN, NOW, 300).unwrap();
+        let response = respond(&kp, &challenge).unwrap();
+
+        let replayed = PossessionChallenge {
+            domain: "phishing.example.com".to_string(),
+            ..challenge.clone()
+        };
+        assert!(verify::<KpSym>(&kp.public_key(), &replayed, &response, NOW).is_err());
+
+        // the response is not a plain signature of the nonce.
+        assert!(kp
+            .verify(challenge.nonce.as_bytes(), response.signature)
+            .is_err());
+
+        // nor valid for the other schema.
+        let nis1 = Keypair::<KpNis1>::random();
+        let response = respond(&nis1, &challenge).unwrap();
+        assert!(verify::<KpNis1>(&nis1.public_key(), &challenge, &response, NOW).is_ok());
+        assert_ne!(challenge.message::<KpSym>(), challenge.message::<KpNis1>());
+    }
+
+    #[test]
+    fn test_possession_invalid_challenge() {
+        assert!(create_challenge("", NOW, 300).is_err());
+        assert!(create_challenge(DOMAIN, NOW, 0).is_err());
+        assert!(create_challenge("a\0b", NOW, 300).is_err());
+    }
+
+    #[test]
+    fn test_possession_serde() {
+        let kp = Keypair::<KpSym>::random();
+        let challenge = create_challenge(DOMAIN, NOW, 300).unwrap();
+        let response = respond(&kp, &challenge).unwrap();
+
+        let challenge: PossessionChallenge =
+            serde_json::from_str(&serde_json::to_string(&challenge).unwrap()).unwrap();
+        let response: PossessionResponse =
+            serde_json::from_str(&serde_json::to_string(&response).unwrap()).unwrap();
+        assert!(verify::<KpSym>(&kp.public_key(), &challenge, &response, NOW).is_ok());
+    }
+}
@@ -0,0 +1,66 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(test)]
+pub mod tests {
+    use std::str::FromStr;
+
+    use symbol_crypto_core::prelude::curve25519::edwards::{CompressedEdwardsY, EdwardsPoint};
+    use symbol_crypto_core::prelude::{Keypair, KpNis1, KpSym, PublicKey};
+
+    fn public_key(hex: &str) -> PublicKey {
+        PublicKey::from_str(hex).unwrap()
+    }
+
+    #[test]
+    fn test_point_round_trip() {
+        let sym = Keypair::<KpSym>::random().public_key();
+        let nis1 = Keypair::<KpNis1>::random().public_key();
+
+        for pk in [sym, nis1].iter() {
+            let point = pk.to_point().unwrap();
+            assert_eq!(PublicKey::from_point(&point).unwrap(), *pk);
+            assert_eq!(pk.to_compressed().as_bytes(), pk.as_bytes());
+            assert_eq!(
+                PublicKey::from_compressed(&pk.to_compressed()).unwrap(),
+                *pk
+            );
+        }
+
+        // custom protocols can combine the points of managed keys.
+        let sum = sym.to_point().unwrap() + nis1.to_point().unwrap();
+        let combined = PublicKey::from_point(&sum).unwrap();
+        assert_eq!(
+            combined.to_point().unwrap() - nis1.to_point().unwrap(),
+            sym.to_point().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_point_validation() {
+        // y = 2 is not on the curve.
+        let not_on_curve =
+            public_key("0200000000000000000000000000000000000000000000000000000000000000");
+        assert!(not_on_curve.to_point().is_err());
+
+        // the identity has small order.
+        let identity =
+            public_key("0100000000000000000000000000000000000000000000000000000000000000");
+        assert!(identity.to_point().is_err());
+        assert!(PublicKey::from_point(&EdwardsPoint::default()).is_err());
+
+        // y = 3 is a valid point, its encoding as y = 3 + p is not canonical.
+        let canonical =
+            public_key("0300000000000000000000000000000000000000000000000000000000000000");
+        assert!(canonical.to_point().is_ok());
+        let non_canonical =
+            public_key("F0FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF7F");
+        assert!(non_canonical.to_point().is_err());
+        assert!(PublicKey::from_compressed(&CompressedEdwardsY(non_canonical.0)).is_err());
+    }
+}
@@ -0,0 +1,60 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(test)]
+pub mod tests {
+    use std::str::FromStr;
+
+    use symbol_crypto_core::prelude::{Keypair, KpSym, PrivateKey};
+    use symbol_crypto_core::sym::{
+        derive_purpose_key, PURPOSE_API, PURPOSE_HARVEST, PURPOSE_MESSAGING,
+    };
+
+    const MASTER: &str = "575DBB3062267EFF57C970A336EBBC8FBCFE12C5BD3ED7BC11EB0481D7704CED";
+
+    #[test]
+    fn test_derive_purpose_key_vectors() {
+        let master = PrivateKey::from_str(MASTER).unwrap();
+        let expected = [
+            (
+                PURPOSE_HARVEST,
+                "F764C28ADF90B18F1EBFEA7874D77F1BEAD6060974732C06C16431F396FAB6CA",
+            ),
+            (
+                PURPOSE_MESSAGING,
+                "4FA968C3391A7B8347ABD45500981361A597D7E49957CE12BD2B8682126E5B19",
+            ),
+            (
+                PURPOSE_API,
+                "7015D7E9F54FBEA1800D541AD672269F80810BD463B1D4FB1B6097CE6851C89D",
+            ),
+        ];
+
+        for (purpose, key) in expected.iter() {
+            assert_eq!(
+                derive_purpose_key(&master, purpose),
+                PrivateKey::from_str(key).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_derive_purpose_key_separation() {
+        let master = PrivateKey::from_str(MASTER).unwrap();
+        let harvest = derive_purpose_key(&master, PURPOSE_HARVEST);
+
+        assert_eq!(harvest, derive_purpose_key(&master, PURPOSE_HARVEST));
+        assert_ne!(harvest, master);
+        assert_ne!(harvest, derive_purpose_key(&master, PURPOSE_API));
+        assert_ne!(harvest, derive_purpose_key(&harvest, PURPOSE_HARVEST));
+
+        let kp = Keypair::<KpSym>::from_private_key(harvest);
+        let signature = kp.try_sign(b"harvest").unwrap();
+        assert!(kp.verify(b"harvest", signature).is_ok());
+    }
+}
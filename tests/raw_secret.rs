@@ -0,0 +1,71 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(test)]
+pub mod tests {
+    use std::convert::TryFrom;
+    use std::str::FromStr;
+
+    use symbol_crypto_core::prelude::{
+        hex_to_bytes, BlockCipher, CryptoSym, Keypair, KpSym, PrivateKey, Scalar32, Seed32,
+    };
+
+    const PRIVATE_KEY: &str = "575DBB3062267EFF57C970A336EBBC8FBCFE12C5BD3ED7BC11EB0481D7704CED";
+    const SYM_SCALAR: &str = "38280FB3D99FE7764C66320FE0CB091BB0DB3237C82888F37EDFFBDF37549D43";
+
+    #[test]
+    fn test_seed32_private_key_round_trip() {
+        let private_key = PrivateKey::from_str(PRIVATE_KEY).unwrap();
+        let seed = Seed32::from(private_key);
+        assert_eq!(seed.as_bytes(), private_key.as_fixed_bytes());
+        assert_eq!(seed.to_private_key(), private_key);
+        assert_eq!(PrivateKey::from(&seed), private_key);
+        assert_eq!(Seed32::new(hex_to_bytes(PRIVATE_KEY)), seed);
+    }
+
+    #[test]
+    fn test_scalar32_try_from() {
+        let scalar = Scalar32::try_from(hex_to_bytes::<32>(SYM_SCALAR)).unwrap();
+        assert_eq!(scalar.as_bytes(), &hex_to_bytes::<32>(SYM_SCALAR));
+        assert_eq!(scalar.to_scalar().to_bytes(), *scalar.as_bytes());
+    }
+
+    #[test]
+    fn test_debug_is_redacted() {
+        let seed = Seed32::new(hex_to_bytes(PRIVATE_KEY));
+        let scalar = Scalar32::new(hex_to_bytes(SYM_SCALAR)).unwrap();
+        assert_eq!(format!("{:?}", seed), "Seed32(..)");
+        assert_eq!(format!("{:?}", scalar), "Scalar32(..)");
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_raw_cipher_forms_take_the_seed() {
+        let signer = Keypair::<KpSym>::from_private_key(PrivateKey::from_str(PRIVATE_KEY).unwrap());
+        let receiver = Keypair::<KpSym>::random();
+
+        let enc_msg = CryptoSym::encrypt_message(
+            &Seed32::new(hex_to_bytes(PRIVATE_KEY)),
+            receiver.public_key().as_fixed_bytes(),
+            b"seed, not scalar",
+        )
+        .unwrap();
+        let msg = receiver
+            .decrypt_message(signer.public_key().as_fixed_bytes(), &enc_msg)
+            .unwrap();
+        assert_eq!(msg, b"seed, not scalar");
+
+        let enc_msg = signer
+            .private_key()
+            .encrypt_message::<CryptoSym>(receiver.public_key().as_fixed_bytes(), b"raw")
+            .unwrap();
+        let msg = CryptoSym::decrypt_from(&receiver.private_key(), &signer.public_key(), &enc_msg)
+            .unwrap();
+        assert_eq!(msg, b"raw");
+    }
+}
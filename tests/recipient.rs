@@ -0,0 +1,109 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(test)]
+pub mod tests {
+    use std::str::FromStr;
+
+    use symbol_crypto_core::sym::recipient::{Address, Recipient, RECIPIENT_SIZE};
+
+    const ADDRESSES: [(&str, &str); 3] = [
+        (
+            "TATNE7Q5BITMUTRRN6IB4I7FLSDRDWZA37JGO5Q",
+            "9826D27E1D0A26CA4E316F901E23E55C8711DB20DFD26776",
+        ),
+        (
+            "TBA6LOHEA6A465G2X5MSQF66JBYR254GJDPK7MQ",
+            "9841E5B8E40781CF74DABF592817DE48711D778648DEAFB2",
+        ),
+        (
+            "NBLT42KCICXZE2Q7Q4SWW3GWWE3XWPH3KUBBOEY",
+            "68573E694240AF926A1F87256B6CD6B1377B3CFB55021713",
+        ),
+    ];
+
+    #[test]
+    fn test_address_text_round_trip() {
+        for (text, bytes) in ADDRESSES.iter() {
+            let address = Address::from_str(text).unwrap();
+            assert_eq!(hex::encode_upper(address.as_bytes()), *bytes);
+            assert_eq!(address.to_string(), *text);
+            assert_eq!(
+                Address::from_bytes(&hex::decode(bytes).unwrap()).unwrap(),
+                address
+            );
+        }
+
+        let dashed = "TATNE7-Q5BITM-UTRRN6-IB4I7F-LSDRDW-ZA37JG-O5Q";
+        assert_eq!(
+            Address::from_str(dashed).unwrap().to_string(),
+            ADDRESSES[0].0
+        );
+    }
+
+    #[test]
+    fn test_address_invalid() {
+        // checksum mismatch.
+        assert!(Address::from_str("TATNE7Q5BITMUTRRN6IB4I7FLSDRDWZA37JGO5A").is_err());
+        assert!(Address::from_str("TATNE7Q5BITMUTRRN6IB4I7FLSDRDWZA37JGO5").is_err());
+        assert!(Address::from_str("TATNE7Q5BITMUTRRN6IB4I7FLSDRDWZA37JGO51").is_err());
+
+        let mut bytes = hex::decode(ADDRESSES[0].1).unwrap();
+        bytes[5] ^= 1;
+        assert!(Address::from_bytes(&bytes).is_err());
+        assert!(Address::from_bytes(&bytes[..RECIPIENT_SIZE - 1]).is_err());
+    }
+
+    #[test]
+    fn test_recipient_address() {
+        let address = Address::from_str(ADDRESSES[2].0).unwrap();
+        let recipient = Recipient::from(address);
+        assert_eq!(recipient.network(), 0x68);
+        assert_eq!(recipient.to_bytes(), *address.as_bytes());
+        assert_eq!(
+            Recipient::from_bytes(&recipient.to_bytes()).unwrap(),
+            recipient
+        );
+    }
+
+    #[test]
+    fn test_recipient_alias() {
+        let recipient = Recipient::alias(0x98, "symbol.xym").unwrap();
+        assert_eq!(
+            recipient,
+            Recipient::Alias {
+                network: 0x98,
+                namespace_id: 0xE74B_99BA_41F4_AFEE
+            }
+        );
+
+        let bytes = recipient.to_bytes();
+        assert_eq!(
+            hex::encode_upper(bytes),
+            "99EEAFF441BA994BE7000000000000000000000000000000"
+        );
+        assert_eq!(Recipient::from_bytes(&bytes).unwrap(), recipient);
+        assert_eq!(recipient.network(), 0x98);
+    }
+
+    #[test]
+    fn test_recipient_alias_invalid() {
+        assert!(Recipient::alias(0x99, "symbol.xym").is_err());
+        assert!(Recipient::alias(0x98, "symbol..xym").is_err());
+
+        let mut bytes = Recipient::alias(0x68, "symbol.xym").unwrap().to_bytes();
+        bytes[RECIPIENT_SIZE - 1] = 1;
+        assert!(Recipient::from_bytes(&bytes).is_err());
+
+        // namespace id without the namespace flag.
+        let mut bytes = [0u8; RECIPIENT_SIZE];
+        bytes[0] = 0x69;
+        bytes[1] = 0x01;
+        assert!(Recipient::from_bytes(&bytes).is_err());
+    }
+}
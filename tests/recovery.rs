@@ -0,0 +1,67 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(test)]
+pub mod tests {
+    use symbol_crypto_core::prelude::recovery::{
+        recover_for_public_key, recover_private_key, RecoveryPattern,
+    };
+    use symbol_crypto_core::prelude::{Keypair, KpNis1, KpSym};
+
+    const PRIVATE_KEY: &str = "E88283CE35FE74C89FFCB2D8BFA0A2CF6108BDC0D07606DEE34D161C30AC2F1E";
+
+    #[test]
+    fn test_recover_sym() {
+        let kp = Keypair::<KpSym>::from_hex_private_key(PRIVATE_KEY).unwrap();
+        let pattern = "E88283CE35FE74C89FFCB2D8BFA0A2CF6108BDC0D07606DEE34D161C30AC2?1[E3]";
+
+        let recovered = recover_for_public_key::<KpSym>(pattern, &kp.public_key()).unwrap();
+        assert_eq!(recovered, Some(kp.private_key()));
+    }
+
+    #[test]
+    fn test_recover_nis1_with_check() {
+        let kp = Keypair::<KpNis1>::from_hex_private_key(PRIVATE_KEY).unwrap();
+        let pattern = "e88283ce35fe74c89ffcb2d8bfa0a2cf6108bdc0d07606dee34d161c30ac??1e";
+
+        let recovered = recover_private_key::<KpNis1, _>(pattern, |pk| *pk == kp.public_key());
+        assert_eq!(recovered.unwrap(), Some(kp.private_key()));
+    }
+
+    #[test]
+    fn test_recover_not_found() {
+        let kp = Keypair::<KpSym>::random();
+        let pattern = "E88283CE35FE74C89FFCB2D8BFA0A2CF6108BDC0D07606DEE34D161C30AC2F1?";
+
+        assert_eq!(
+            recover_for_public_key::<KpSym>(pattern, &kp.public_key()).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_pattern() {
+        let pattern = RecoveryPattern::parse(&format!("{}?[1e]", &PRIVATE_KEY[..62])).unwrap();
+
+        assert_eq!(pattern.candidates(), 32);
+        assert_eq!(
+            format!("{:X}", pattern.candidate(0)),
+            format!("{}01", &PRIVATE_KEY[..62])
+        );
+        assert_eq!(
+            format!("{:X}", pattern.candidate(31)),
+            format!("{}FE", &PRIVATE_KEY[..62])
+        );
+
+        assert!(RecoveryPattern::parse("E882").is_err());
+        assert!(RecoveryPattern::parse(&"?".repeat(64)).is_err());
+        assert!(RecoveryPattern::parse(&format!("{}[8", &PRIVATE_KEY[..63])).is_err());
+        assert!(RecoveryPattern::parse(&format!("{}[]", &PRIVATE_KEY[..63])).is_err());
+        assert!(RecoveryPattern::parse(&format!("{}G", &PRIVATE_KEY[..63])).is_err());
+    }
+}
@@ -0,0 +1,85 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(test)]
+pub mod tests {
+    use std::str::FromStr;
+
+    use symbol_crypto_core::prelude::{Keypair, KpSym};
+    use symbol_crypto_core::sym::request_auth::{Authorization, KeyValueSigner};
+
+    const NOW: u64 = 1_700_000_000;
+    const MAX_SKEW: u64 = 30;
+
+    #[test]
+    fn test_canonical_string() {
+        let mut signer = KeyValueSigner::for_request("post", "/node/unlockedaccount").unwrap();
+        signer.push("content-sha256", "AB12").unwrap();
+        assert_eq!(
+            signer.canonical_string(NOW),
+            "method:POST\npath:/node/unlockedaccount\ncontent-sha256:AB12\ntimestamp:1700000000"
+        );
+
+        assert!(signer.push("timestamp", "1").is_err());
+        assert!(signer.push("Host", "node").is_err());
+        assert!(signer.push("a:b", "node").is_err());
+        assert!(signer.push("", "node").is_err());
+        assert!(signer.push("host", "node\npath:/").is_err());
+    }
+
+    #[test]
+    fn test_sign_verify_header() {
+        let kp = Keypair::<KpSym>::random();
+        let signer = KeyValueSigner::for_request("GET", "/node/info").unwrap();
+
+        let header = signer.sign(&kp, NOW).unwrap().to_string();
+        assert!(header.starts_with("Symbol-Ed25519 publicKey="));
+
+        let authorization = Authorization::from_str(&header).unwrap();
+        assert_eq!(authorization.public_key, kp.public_key());
+        assert_eq!(authorization.timestamp, NOW);
+        assert_eq!(signer.verify(&authorization, NOW, MAX_SKEW).unwrap(), kp.public_key());
+
+        // another method or path.
+        let other = KeyValueSigner::for_request("POST", "/node/info").unwrap();
+        assert!(other.verify(&authorization, NOW, MAX_SKEW).is_err());
+        let other = KeyValueSigner::for_request("GET", "/node/server").unwrap();
+        assert!(other.verify(&authorization, NOW, MAX_SKEW).is_err());
+    }
+
+    #[test]
+    fn test_clock_skew() {
+        let kp = Keypair::<KpSym>::random();
+        let signer = KeyValueSigner::for_request("GET", "/node/info").unwrap();
+        let authorization = signer.sign(&kp, NOW).unwrap();
+
+        assert!(signer.verify(&authorization, NOW - MAX_SKEW, MAX_SKEW).is_ok());
+        assert!(signer.verify(&authorization, NOW + MAX_SKEW, MAX_SKEW).is_ok());
+        assert!(signer.verify(&authorization, NOW - MAX_SKEW - 1, MAX_SKEW).is_err());
+        assert!(signer.verify(&authorization, NOW + MAX_SKEW + 1, MAX_SKEW).is_err());
+
+        // the timestamp is signed.
+        let mut shifted = authorization;
+        shifted.timestamp += 1;
+        assert!(signer.verify(&shifted, NOW, MAX_SKEW).is_err());
+    }
+
+    #[test]
+    fn test_parse_invalid_header() {
+        let kp = Keypair::<KpSym>::random();
+        let signer = KeyValueSigner::for_request("GET", "/node/info").unwrap();
+        let header = signer.sign(&kp, NOW).unwrap().to_string();
+
+        assert!(Authorization::from_str(&header.replace("Symbol-Ed25519", "Bearer")).is_err());
+        assert!(Authorization::from_str(&header.replace("timestamp=", "ts=")).is_err());
+        assert!(Authorization::from_str(&format!("{}, timestamp=1", header)).is_err());
+        assert!(Authorization::from_str(&header.replace(&NOW.to_string(), "soon")).is_err());
+        assert!(Authorization::from_str("Symbol-Ed25519 publicKey=00").is_err());
+        assert!(Authorization::from_str("Symbol-Ed25519").is_err());
+    }
+}
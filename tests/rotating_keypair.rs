@@ -0,0 +1,65 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(test)]
+pub mod tests {
+    use symbol_crypto_core::prelude::{Keypair, KpNis1, KpSym, RotatingKeypair};
+
+    const DATA: &[u8] = b"node announcement";
+
+    #[test]
+    fn test_rotate() {
+        let first = Keypair::<KpSym>::random();
+        let second = Keypair::<KpSym>::random();
+        let third = Keypair::<KpSym>::random();
+
+        let mut kp = RotatingKeypair::new(first, 1_000);
+        let old_signature = kp.sign(DATA);
+
+        assert_eq!(kp.rotate(second, 2_000).unwrap(), None);
+        assert_eq!(
+            kp.public_keys(),
+            vec![second.public_key(), first.public_key()]
+        );
+
+        let new_signature = kp.sign(DATA);
+        assert!(second.verify(DATA, new_signature).is_ok());
+        assert_eq!(kp.verify(DATA, new_signature).unwrap(), second.public_key());
+        assert_eq!(kp.verify(DATA, old_signature).unwrap(), first.public_key());
+
+        assert_eq!(kp.rotate(third, 3_000).unwrap(), Some(first.public_key()));
+        assert!(kp.verify(DATA, old_signature).is_err());
+        assert!(kp.verify(DATA, new_signature).is_ok());
+
+        assert_eq!(kp.retire_previous(), Some(second.public_key()));
+        assert!(kp.verify(DATA, new_signature).is_err());
+    }
+
+    #[test]
+    fn test_rotate_invalid() {
+        let first = Keypair::<KpNis1>::random();
+        let mut kp = RotatingKeypair::new(first, 1_000);
+
+        assert!(kp.rotate(Keypair::<KpNis1>::random(), 999).is_err());
+        assert!(kp.rotate(first, 2_000).is_err());
+        assert_eq!(kp.previous, None);
+    }
+
+    #[test]
+    fn test_serde_round_trip() {
+        let mut kp = RotatingKeypair::new(Keypair::<KpSym>::random(), 1_000);
+        kp.rotate(Keypair::<KpSym>::random(), 2_000).unwrap();
+
+        let json = serde_json::to_string(&kp).unwrap();
+        let restored: RotatingKeypair<KpSym> = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, kp);
+
+        let signature = restored.sign(DATA);
+        assert!(kp.verify(DATA, signature).is_ok());
+    }
+}
@@ -0,0 +1,112 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(test)]
+pub mod tests {
+    use std::str::FromStr;
+
+    use symbol_crypto_core::prelude::curve25519::scalar::Scalar;
+    use symbol_crypto_core::prelude::scalar_canonicality::{self, Canonicality};
+    use symbol_crypto_core::prelude::{
+        check_scalar, clamp_scalar_bits, clamped_scalar, scalar_from_h256, Keypair, KpNis1, KpSym,
+        Signature, H256,
+    };
+
+    // the ed25519 group order.
+    const GROUP_ORDER: &str = "EDD3F55C1A631258D69CF7A2DEF9DE1400000000000000000000000000000010";
+
+    #[test]
+    fn test_scalar_round_trip() {
+        let scalar = Scalar::from(123456789u64);
+        let hash = H256::from(scalar);
+
+        assert_eq!(scalar_from_h256(&hash, false).unwrap(), scalar);
+        assert_eq!(H256::from(&scalar_from_h256(&hash, true).unwrap()), hash);
+    }
+
+    #[test]
+    fn test_non_canonical_scalar() {
+        let order = H256::from_str(GROUP_ORDER).unwrap();
+
+        assert!(scalar_from_h256(&order, false).is_err());
+        assert!(check_scalar(order.to_fixed_bytes()).is_err());
+        assert_eq!(scalar_from_h256(&order, true).unwrap(), Scalar::zero());
+
+        let max = H256::repeat_byte(0xFF);
+        assert!(scalar_from_h256(&max, false).is_err());
+        assert!(scalar_from_h256(&max, true).is_ok());
+    }
+
+    #[test]
+    fn test_clamp() {
+        let bits = clamp_scalar_bits([0xFF; 32]);
+        assert_eq!(bits[0], 0xF8);
+        assert_eq!(bits[31], 0x7F);
+        assert_eq!(&bits[1..31], &[0xFF; 30][..]);
+
+        let bits = clamp_scalar_bits([0x00; 32]);
+        assert_eq!(bits[0], 0x00);
+        assert_eq!(bits[31], 0x40);
+
+        let hash = H256::repeat_byte(0xFF);
+        assert_eq!(
+            clamped_scalar(&hash).to_bytes(),
+            clamp_scalar_bits(hash.to_fixed_bytes())
+        );
+    }
+
+    #[test]
+    fn test_canonicality() {
+        let order = H256::from_str(GROUP_ORDER).unwrap().to_fixed_bytes();
+        let mut below_order = order;
+        below_order[0] -= 1;
+
+        assert_eq!(
+            scalar_canonicality::check(&[0x0F; 32]),
+            Canonicality::ReducedFast
+        );
+        assert_eq!(
+            scalar_canonicality::check(&below_order),
+            Canonicality::Canonical
+        );
+        assert_eq!(
+            scalar_canonicality::check(&order),
+            Canonicality::NonCanonical
+        );
+        assert!(!Canonicality::NonCanonical.is_canonical());
+    }
+
+    // s + l must be rejected by both schemas.
+    fn malleate(signature: Signature) -> Signature {
+        let order = H256::from_str(GROUP_ORDER).unwrap();
+        let mut bytes = signature.to_fixed_bytes();
+
+        let mut carry = 0u16;
+        for i in 0..32 {
+            let sum = bytes[32 + i] as u16 + order[i] as u16 + carry;
+            bytes[32 + i] = sum as u8;
+            carry = sum >> 8;
+        }
+        Signature::from(bytes)
+    }
+
+    #[test]
+    fn test_reject_malleable_signature() {
+        let data = b"malleability";
+
+        let kp = Keypair::<KpSym>::random();
+        let signature = kp.try_sign(data).unwrap();
+        assert!(kp.verify(data, signature).is_ok());
+        assert!(kp.verify(data, malleate(signature)).is_err());
+
+        let kp = Keypair::<KpNis1>::random();
+        let signature = kp.try_sign(data).unwrap();
+        assert!(kp.verify(data, signature).is_ok());
+        assert!(kp.verify(data, malleate(signature)).is_err());
+    }
+}
@@ -0,0 +1,81 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(test)]
+pub mod tests {
+    use std::str::FromStr;
+
+    use symbol_crypto_core::prelude::{
+        BlockCipher, CryptoNis1, CryptoSym, Keypair, KpNis1, KpSym, PrivateKey, SecretBlockCipher,
+        SecretKey,
+    };
+
+    const PRIVATE_KEY: &str = "575DBB3062267EFF57C970A336EBBC8FBCFE12C5BD3ED7BC11EB0481D7704CED";
+    const MESSAGE: &[u8] = b"Symbol is awesome from Rust!";
+
+    fn secret_key() -> SecretKey {
+        SecretKey::new(PrivateKey::from_str(PRIVATE_KEY).unwrap())
+    }
+
+    #[test]
+    fn test_keypair_from_secret_key() {
+        let private_key = PrivateKey::from_str(PRIVATE_KEY).unwrap();
+
+        let kp = Keypair::<KpSym>::from_secret_key(&secret_key()).unwrap();
+        assert_eq!(kp, Keypair::<KpSym>::from_private_key(private_key));
+        assert_eq!(kp.secret_key().expose_secret(), &private_key);
+
+        let kp = Keypair::<KpNis1>::from_secret_key(&secret_key()).unwrap();
+        assert_eq!(kp, Keypair::<KpNis1>::from_private_key(private_key));
+    }
+
+    #[test]
+    fn test_secret_key_debug_is_redacted() {
+        assert!(!format!("{:?}", secret_key()).contains(PRIVATE_KEY));
+    }
+
+    #[test]
+    fn test_raw_secret_bytes() {
+        let private_key = PrivateKey::from_str(PRIVATE_KEY).unwrap();
+        let bytes: [u8; 32] = private_key.into();
+
+        let kp = Keypair::<KpSym>::from_secret_key(&bytes).unwrap();
+        assert_eq!(kp, Keypair::<KpSym>::from_private_key(private_key));
+        assert!(Keypair::<KpSym>::from_secret_key(&bytes[..31].to_vec()).is_err());
+    }
+
+    #[test]
+    fn test_encrypt_decrypt_secret() {
+        let signer_sk = secret_key();
+        let receiver_kp = Keypair::<KpSym>::random();
+        let signer_pk = Keypair::<KpSym>::from_secret_key(&signer_sk).unwrap().public_key();
+
+        let enc_msg =
+            CryptoSym::encrypt_for_secret(&signer_sk, &receiver_kp.public_key(), MESSAGE).unwrap();
+        let dec_msg =
+            CryptoSym::decrypt_from(&receiver_kp.private_key(), &signer_pk, &enc_msg).unwrap();
+        assert_eq!(dec_msg, MESSAGE);
+
+        let receiver_sk = receiver_kp.secret_key();
+        let dec_msg = CryptoSym::decrypt_from_secret(&receiver_sk, &signer_pk, &enc_msg).unwrap();
+        assert_eq!(dec_msg, MESSAGE);
+    }
+
+    #[test]
+    fn test_encrypt_decrypt_secret_nis1() {
+        let signer_sk = secret_key();
+        let receiver_kp = Keypair::<KpNis1>::random();
+        let signer_pk = Keypair::<KpNis1>::from_secret_key(&signer_sk).unwrap().public_key();
+
+        let enc_msg =
+            CryptoNis1::encrypt_for_secret(&signer_sk, &receiver_kp.public_key(), MESSAGE).unwrap();
+        let receiver_sk = receiver_kp.secret_key();
+        let dec_msg = CryptoNis1::decrypt_from_secret(&receiver_sk, &signer_pk, &enc_msg).unwrap();
+        assert_eq!(dec_msg, MESSAGE);
+    }
+}
@@ -0,0 +1,54 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(test)]
+pub mod tests {
+    use symbol_crypto_core::prelude::{Keypair, KpNis1, KpSym, SecretSerde};
+
+    const PRIVATE_KEY: &str = "E88283CE35FE74C89FFCB2D8BFA0A2CF6108BDC0D07606DEE34D161C30AC2F1E";
+
+    #[test]
+    fn test_keypair_serializes_public_half_only() {
+        let kp = Keypair::<KpSym>::from_hex_private_key(PRIVATE_KEY).unwrap();
+
+        let bytes: Vec<u8> = serde_json::from_str(&serde_json::to_string(&kp).unwrap()).unwrap();
+        assert_eq!(bytes, kp.public_key().as_bytes());
+
+        let bytes: Vec<u8> = serde_json::from_str(&serde_json::to_string(&kp.0).unwrap()).unwrap();
+        assert_eq!(bytes, kp.public_key().as_bytes());
+
+        let nis1 = Keypair::<KpNis1>::from_hex_private_key(PRIVATE_KEY).unwrap();
+        let bytes: Vec<u8> = serde_json::from_str(&serde_json::to_string(&nis1).unwrap()).unwrap();
+        assert_eq!(bytes, nis1.public_key().as_bytes());
+    }
+
+    #[test]
+    fn test_secret_serde_round_trip() {
+        let kp = Keypair::<KpSym>::from_hex_private_key(PRIVATE_KEY).unwrap();
+
+        let json = serde_json::to_string(&SecretSerde(kp)).unwrap();
+        let bytes: Vec<u8> = serde_json::from_str(&json).unwrap();
+        assert_eq!(bytes, kp.to_bytes());
+
+        let restored: SecretSerde<Keypair<KpSym>> = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.0, kp);
+
+        let restored: SecretSerde<KpSym> =
+            serde_json::from_str(&serde_json::to_string(&SecretSerde(kp.0)).unwrap()).unwrap();
+        assert_eq!(restored.0, kp.0);
+    }
+
+    #[test]
+    fn test_secret_serde_nis1_round_trip() {
+        let kp = Keypair::<KpNis1>::from_hex_private_key(PRIVATE_KEY).unwrap();
+
+        let json = serde_json::to_string(&SecretSerde(kp)).unwrap();
+        let restored: SecretSerde<Keypair<KpNis1>> = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.0, kp);
+    }
+}
@@ -0,0 +1,102 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(test)]
+pub mod tests {
+    use symbol_crypto_core::prelude::{Keypair, KpSym};
+    use symbol_crypto_core::sym::Session;
+
+    fn sessions() -> (Session, Session) {
+        let alice = Keypair::<KpSym>::random();
+        let bob = Keypair::<KpSym>::random();
+
+        let alice_session = Session::initiate(alice.as_ref(), &bob.public_key());
+        let bob_session = Session::accept(bob.as_ref(), &alice.public_key(), alice_session.id());
+        (alice_session, bob_session)
+    }
+
+    #[test]
+    fn test_session_exchange() {
+        let (mut alice, mut bob) = sessions();
+
+        for i in 0..3u8 {
+            let enc_msg = alice.encrypt(&[i; 10]).unwrap();
+            assert_eq!(bob.decrypt(&enc_msg).unwrap(), vec![i; 10]);
+
+            let enc_msg = bob.encrypt(&[i; 20]).unwrap();
+            assert_eq!(alice.decrypt(&enc_msg).unwrap(), vec![i; 20]);
+        }
+    }
+
+    #[test]
+    fn test_session_ratchet_keys_differ() {
+        let (mut alice, _) = sessions();
+
+        let (index1, key1) = alice.ratchet().unwrap();
+        let (index2, key2) = alice.ratchet().unwrap();
+
+        assert_eq!(index1 + 1, index2);
+        assert_ne!(key1, key2);
+    }
+
+    #[test]
+    fn test_session_out_of_order_and_replay() {
+        let (mut alice, mut bob) = sessions();
+
+        let first = alice.encrypt(b"first").unwrap();
+        let second = alice.encrypt(b"second").unwrap();
+
+        assert_eq!(bob.decrypt(&second).unwrap(), b"second");
+        assert_eq!(bob.decrypt(&first).unwrap(), b"first");
+        assert!(bob.decrypt(&first).is_err());
+    }
+
+    #[test]
+    fn test_session_serde_round_trip() {
+        let (mut alice, mut bob) = sessions();
+        let enc_msg = alice.encrypt(b"hello").unwrap();
+
+        let state = serde_json::to_string(&bob).unwrap();
+        let mut restored: Session = serde_json::from_str(&state).unwrap();
+        assert_eq!(restored, bob);
+
+        assert_eq!(restored.decrypt(&enc_msg).unwrap(), b"hello");
+        assert_eq!(bob.decrypt(&enc_msg).unwrap(), b"hello");
+    }
+
+    #[test]
+    fn test_session_forged_message_keeps_state() {
+        use symbol_crypto_core::sym::SESSION_MAX_SKIP;
+
+        let (mut alice, mut bob) = sessions();
+        let first = alice.encrypt(b"first").unwrap();
+
+        // a forged message far ahead must not advance the chain nor fill the skipped keys.
+        let mut forged = first.clone();
+        forged[..4].copy_from_slice(&SESSION_MAX_SKIP.to_le_bytes());
+        let state = bob.clone();
+        assert!(bob.decrypt(&forged).is_err());
+        assert_eq!(bob, state);
+
+        // a message moved to another index is rejected, and leaves the state untouched.
+        let second = alice.encrypt(b"second").unwrap();
+        let mut moved = second.clone();
+        moved[..4].copy_from_slice(&2u32.to_le_bytes());
+        assert!(bob.decrypt(&moved).is_err());
+        assert_eq!(bob, state);
+
+        assert_eq!(bob.decrypt(&first).unwrap(), b"first");
+        assert_eq!(bob.decrypt(&second).unwrap(), b"second");
+    }
+
+    #[test]
+    fn test_session_debug_is_redacted() {
+        let (alice, _) = sessions();
+        assert_eq!(format!("{:?}", alice), format!("Session {{ id: {:?} }}", alice.id()));
+    }
+}
@@ -0,0 +1,99 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(test)]
+pub mod tests {
+    use symbol_crypto_core::prelude::shamir::{combine, refresh_shares, split, zero_shares, Share};
+
+    const SECRET: &[u8] = b"575DBB3062267EFF57C970A336EBBC8F";
+
+    #[test]
+    fn test_split_combine() {
+        let shares = split(SECRET, 3, 5).unwrap();
+        assert_eq!(shares.len(), 5);
+        assert!(shares.iter().all(|share| share.value.len() == SECRET.len()));
+
+        for subset in [[0, 1, 2], [4, 2, 0], [1, 3, 4]].iter() {
+            let subset: Vec<Share> = subset.iter().map(|&i| shares[i].clone()).collect();
+            assert_eq!(combine(&subset).unwrap(), SECRET);
+        }
+        assert_eq!(combine(&shares).unwrap(), SECRET);
+        assert_ne!(combine(&shares[..2]).unwrap(), SECRET);
+    }
+
+    #[test]
+    fn test_known_shares() {
+        // f(x) = 0x2A + 0x07 x over GF(2^8): f(1) = 0x2D, f(2) = 0x24.
+        let shares = vec![
+            Share {
+                index: 1,
+                value: vec![0x2D],
+            },
+            Share {
+                index: 2,
+                value: vec![0x24],
+            },
+        ];
+        assert_eq!(combine(&shares).unwrap(), vec![0x2A]);
+    }
+
+    #[test]
+    fn test_invalid_shares() {
+        assert!(split(SECRET, 0, 3).is_err());
+        assert!(split(SECRET, 4, 3).is_err());
+
+        let shares = split(SECRET, 2, 3).unwrap();
+        assert!(combine(&[]).is_err());
+        assert!(combine(&[shares[0].clone(), shares[0].clone()]).is_err());
+
+        let mut short = shares[1].clone();
+        short.value.pop();
+        assert!(combine(&[shares[0].clone(), short]).is_err());
+    }
+
+    #[test]
+    fn test_refresh_shares() {
+        let shares = split(SECRET, 3, 5).unwrap();
+        let refreshed = refresh_shares(&shares, 3).unwrap();
+
+        assert_ne!(refreshed, shares);
+        for (old, new) in shares.iter().zip(refreshed.iter()) {
+            assert_eq!(old.index, new.index);
+        }
+        assert_eq!(combine(&refreshed[2..]).unwrap(), SECRET);
+        assert_eq!(combine(&refreshed[..3]).unwrap(), SECRET);
+
+        // old and new shares don't mix.
+        let mixed = vec![
+            shares[0].clone(),
+            refreshed[1].clone(),
+            refreshed[2].clone(),
+        ];
+        assert_ne!(combine(&mixed).unwrap(), SECRET);
+    }
+
+    #[test]
+    fn test_distributed_refresh() {
+        let mut shares = split(SECRET, 2, 3).unwrap();
+        let indexes: Vec<u8> = shares.iter().map(|share| share.index).collect();
+
+        // every holder deals a zero sharing, every share adds the delta of each holder.
+        for _ in 0..indexes.len() {
+            let deltas = zero_shares(&indexes, 2, SECRET.len()).unwrap();
+            for (share, delta) in shares.iter_mut().zip(deltas.iter()) {
+                share.refresh(delta).unwrap();
+            }
+        }
+        assert_eq!(combine(&shares[1..]).unwrap(), SECRET);
+
+        let deltas = zero_shares(&indexes, 2, SECRET.len()).unwrap();
+        assert!(shares[0].clone().refresh(&deltas[1]).is_err());
+        assert!(zero_shares(&[1, 1], 2, 4).is_err());
+        assert!(refresh_shares(&[], 2).is_err());
+    }
+}
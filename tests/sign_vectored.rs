@@ -0,0 +1,60 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(test)]
+pub mod tests {
+    use symbol_crypto_core::prelude::{KeyPairSchema, Keypair, KpNis1, KpSym};
+
+    const HEADER: &[u8] = b"header";
+    const BODY: &[u8] = b"8ce03cd60514233b86789729102ea09e867fc6d964dea8c2018ef7d0a2e0e24bf7e348e9";
+    const FOOTER: &[u8] = b"footer";
+
+    fn joined() -> Vec<u8> {
+        [HEADER, BODY, FOOTER].concat()
+    }
+
+    fn check_sign_vectored<Kp: KeyPairSchema>() {
+        let kp = Keypair::<Kp>::random();
+        let parts = [HEADER, BODY, FOOTER];
+
+        let signature = kp.sign_vectored(&parts);
+        assert_eq!(signature, kp.try_sign(&joined()).unwrap());
+        assert!(kp.verify(&joined(), signature).is_ok());
+        assert!(kp.verify_vectored(&parts, signature).is_ok());
+
+        // the split of the data does not matter, only its concatenation.
+        let data = joined();
+        let (left, right) = data.split_at(3);
+        assert!(kp.verify_vectored(&[left, right], signature).is_ok());
+        assert!(kp.verify_vectored(&[HEADER, BODY], signature).is_err());
+        assert!(kp
+            .verify_vectored(&[FOOTER, BODY, HEADER], signature)
+            .is_err());
+
+        let other = Keypair::<Kp>::random();
+        assert!(other.verify_vectored(&parts, signature).is_err());
+    }
+
+    #[test]
+    fn test_sign_vectored_sym() {
+        check_sign_vectored::<KpSym>();
+    }
+
+    #[test]
+    fn test_sign_vectored_nis1() {
+        check_sign_vectored::<KpNis1>();
+    }
+
+    #[test]
+    fn test_sign_vectored_empty() {
+        let kp = Keypair::<KpSym>::random();
+        let signature = kp.sign_vectored(&[]);
+        assert_eq!(signature, kp.try_sign(&[]).unwrap());
+        assert!(kp.verify_vectored(&[&[], &[]], signature).is_ok());
+    }
+}
@@ -0,0 +1,145 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(test)]
+pub mod tests {
+    use symbol_crypto_core::prelude::{
+        public_key, signable, Keypair, KpNis1, KpSym, PublicKey, Signable, SigningBytes,
+    };
+
+    const PRIVATE_KEY: &str = "575DBB3062267EFF57C970A336EBBC8FBCFE12C5BD3ED7BC11EB0481D7704CED";
+
+    #[derive(Debug, Clone)]
+    struct Order {
+        id: u64,
+        recipient: PublicKey,
+        memo: String,
+        tags: Vec<String>,
+        expires: Option<u32>,
+        urgent: bool,
+    }
+
+    signable!(Order, "tests/order/v1", {
+        id,
+        recipient,
+        memo,
+        tags,
+        expires,
+        urgent,
+    });
+
+    struct Pair {
+        left: String,
+        right: String,
+    }
+
+    signable!(Pair, "tests/pair/v1", { left, right });
+
+    fn order() -> Order {
+        Order {
+            id: 0x0102,
+            recipient: public_key!(
+                "2E834140FD66CF87B254A693A2C7862C819217B676D3943267156625E816EC6F"
+            ),
+            memo: "hi".to_string(),
+            tags: vec!["a".to_string()],
+            expires: Some(7),
+            urgent: true,
+        }
+    }
+
+    #[test]
+    fn test_signing_bytes_vector() {
+        let order = order();
+        let mut expected = vec![14];
+        expected.extend_from_slice(b"tests/order/v1");
+        expected.extend_from_slice(&[0x02, 0x01, 0, 0, 0, 0, 0, 0]);
+        expected.extend_from_slice(order.recipient.as_bytes());
+        expected.extend_from_slice(&[2, 0, 0, 0, b'h', b'i']);
+        expected.extend_from_slice(&[1, 0, 0, 0, 1, 0, 0, 0, b'a']);
+        expected.extend_from_slice(&[1, 7, 0, 0, 0]);
+        expected.push(1);
+
+        assert_eq!(order.signing_bytes(), expected);
+    }
+
+    #[test]
+    fn test_sign_with_and_verify() {
+        let order = order();
+        let sym = Keypair::<KpSym>::from_hex_private_key(PRIVATE_KEY).unwrap();
+        let nis1 = Keypair::<KpNis1>::from_hex_private_key(PRIVATE_KEY).unwrap();
+
+        let signature = order.sign_with(&sym).unwrap();
+        assert_eq!(signature, sym.try_sign(&order.signing_bytes()).unwrap());
+        assert!(order.verify::<KpSym>(&sym.public_key(), signature).is_ok());
+        assert!(order
+            .verify::<KpNis1>(&sym.public_key(), signature)
+            .is_err());
+
+        let signature = order.sign_with(&nis1).unwrap();
+        assert!(order
+            .verify::<KpNis1>(&nis1.public_key(), signature)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_verify_rejects_changed_field() {
+        let order = order();
+        let kp = Keypair::<KpSym>::from_hex_private_key(PRIVATE_KEY).unwrap();
+        let signature = order.sign_with(&kp).unwrap();
+
+        let mut changed = order.clone();
+        changed.expires = None;
+        assert!(changed
+            .verify::<KpSym>(&kp.public_key(), signature)
+            .is_err());
+
+        let mut changed = order;
+        changed.tags.push(String::new());
+        assert!(changed
+            .verify::<KpSym>(&kp.public_key(), signature)
+            .is_err());
+    }
+
+    #[test]
+    fn test_length_prefix_separates_fields() {
+        let a = Pair {
+            left: "ab".to_string(),
+            right: "c".to_string(),
+        };
+        let b = Pair {
+            left: "a".to_string(),
+            right: "bc".to_string(),
+        };
+        assert_ne!(a.signing_bytes(), b.signing_bytes());
+    }
+
+    #[test]
+    fn test_domain_separates_types() {
+        let pair = Pair {
+            left: String::new(),
+            right: String::new(),
+        };
+        let kp = Keypair::<KpSym>::from_hex_private_key(PRIVATE_KEY).unwrap();
+        let signature = pair.sign_with(&kp).unwrap();
+
+        let mut fields = vec![];
+        pair.write_fields(&mut fields);
+        assert!(kp.verify(&fields, signature).is_err());
+    }
+
+    #[test]
+    fn test_field_encodings() {
+        let mut out = vec![];
+        (-1i16).write_signing_bytes(&mut out);
+        [9u8; 3].write_signing_bytes(&mut out);
+        b"xy"[..].write_signing_bytes(&mut out);
+        None::<u8>.write_signing_bytes(&mut out);
+        assert_eq!(out, vec![0xFF, 0xFF, 9, 9, 9, 2, 0, 0, 0, b'x', b'y', 0]);
+    }
+}
@@ -0,0 +1,45 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(test)]
+pub mod tests {
+    use symbol_crypto_core::prelude::{
+        sign_audited, signature_mismatch_count, verify_own_signature, Keypair, KpNis1, KpSym,
+        Signature,
+    };
+
+    #[test]
+    fn test_sign_audited() {
+        let kp = Keypair::<KpSym>::random();
+        let signature = sign_audited(&kp, b"payload").unwrap();
+        assert_eq!(signature, kp.try_sign(b"payload").unwrap());
+
+        let kp = Keypair::<KpNis1>::random();
+        let signature = sign_audited(&kp, b"payload").unwrap();
+        assert!(verify_own_signature(&kp, b"payload", &signature).is_ok());
+    }
+
+    #[test]
+    fn test_mismatches_are_counted() {
+        let kp = Keypair::<KpSym>::random();
+        let signature = kp.try_sign(b"payload").unwrap();
+
+        let before = signature_mismatch_count();
+        assert!(verify_own_signature(&kp, b"other payload", &signature).is_err());
+
+        let mut faulty = signature.to_fixed_bytes();
+        faulty[40] ^= 1;
+        assert!(verify_own_signature(&kp, b"payload", &Signature::from(faulty)).is_err());
+
+        let other = Keypair::<KpSym>::random();
+        assert!(verify_own_signature(&other, b"payload", &signature).is_err());
+
+        // tests run in parallel, other tests may only add mismatches.
+        assert!(signature_mismatch_count() >= before + 3);
+    }
+}
@@ -0,0 +1,80 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(test)]
+pub mod tests {
+    use symbol_crypto_core::prelude::{
+        Keypair, KpNis1, KpSym, SignedResponse, SigningRequest, SIGNING_PROTOCOL_VERSION,
+    };
+
+    fn request() -> SigningRequest {
+        SigningRequest::new::<KpSym>(b"transaction payload".to_vec())
+            .with_metadata("network", "testnet")
+            .with_metadata("description", "transfer 10 XYM")
+    }
+
+    #[test]
+    fn test_request_round_trip() {
+        let request = request();
+
+        assert_eq!(request.version, SIGNING_PROTOCOL_VERSION);
+        assert_eq!(request.schema, "symbol");
+
+        let hex = request.to_hex().unwrap();
+        assert!(hex
+            .chars()
+            .all(|c| c.is_ascii_digit() || c.is_ascii_uppercase()));
+        assert_eq!(SigningRequest::from_hex(&hex).unwrap(), request);
+
+        let json = serde_json::to_string(&request).unwrap();
+        assert_eq!(
+            serde_json::from_str::<SigningRequest>(&json).unwrap(),
+            request
+        );
+    }
+
+    #[test]
+    fn test_sign_and_verify() {
+        let request = request();
+        let kp = Keypair::<KpSym>::random();
+
+        let response = request.sign(&kp).unwrap();
+        let response = SignedResponse::from_hex(response.to_hex()).unwrap();
+
+        assert_eq!(response.public_key, kp.public_key());
+        assert!(response.verify::<KpSym>(&request, None).is_ok());
+        assert!(response
+            .verify::<KpSym>(&request, Some(&kp.public_key()))
+            .is_ok());
+        assert!(response
+            .verify::<KpSym>(&request, Some(&Keypair::<KpSym>::random().public_key()))
+            .is_err());
+        assert!(response.verify::<KpNis1>(&request, None).is_err());
+
+        let mut tampered = request.clone();
+        tampered.payload[0] ^= 1;
+        assert!(response.verify::<KpSym>(&tampered, None).is_err());
+    }
+
+    #[test]
+    fn test_invalid_requests() {
+        let request = request();
+
+        assert!(request.sign(&Keypair::<KpNis1>::random()).is_err());
+        assert!(SigningRequest::new::<KpSym>(vec![]).validate().is_err());
+
+        let mut future = request.clone();
+        future.version = 2;
+        assert!(future.validate().is_err());
+
+        let bytes = request.to_bytes().unwrap();
+        assert!(SigningRequest::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+        assert!(SigningRequest::from_bytes(&[bytes.clone(), vec![0]].concat()).is_err());
+        assert!(SignedResponse::from_bytes(&bytes).is_err());
+    }
+}
@@ -0,0 +1,133 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(test)]
+pub mod tests {
+    use symbol_crypto_core::prelude::{Keypair, KpNis1, KpSym, PublicKey};
+    use symbol_crypto_core::tagged_signature::{
+        verify_any, SchemaKind, TaggedSignature, TAGGED_SIGNATURE_SIZE,
+    };
+
+    const PRIVATE_KEY: &str = "575DBB3062267EFF57C970A336EBBC8FBCFE12C5BD3ED7BC11EB0481D7704CED";
+    const DATA: &[u8] = b"proof from either chain";
+
+    fn signatures() -> [TaggedSignature; 2] {
+        let sym = Keypair::<KpSym>::from_hex_private_key(PRIVATE_KEY).unwrap();
+        let nis1 = Keypair::<KpNis1>::from_hex_private_key(PRIVATE_KEY).unwrap();
+        [
+            TaggedSignature::sign(&sym, DATA).unwrap(),
+            TaggedSignature::sign(&nis1, DATA).unwrap(),
+        ]
+    }
+
+    #[test]
+    fn test_sign_and_verify() {
+        let [sym, nis1] = signatures();
+        assert_eq!(sym.schema, SchemaKind::Symbol);
+        assert_eq!(nis1.schema, SchemaKind::Nis1);
+
+        assert!(sym.verify(DATA).is_ok());
+        assert!(nis1.verify(DATA).is_ok());
+        assert!(sym.verify(b"other data").is_err());
+        assert!(nis1.verify(b"other data").is_err());
+    }
+
+    #[test]
+    fn test_verify_with_wrong_schema() {
+        let [mut sym, mut nis1] = signatures();
+        sym.schema = SchemaKind::Nis1;
+        nis1.schema = SchemaKind::Symbol;
+
+        assert!(sym.verify(DATA).is_err());
+        assert!(nis1.verify(DATA).is_err());
+    }
+
+    #[test]
+    fn test_bytes_round_trip() {
+        for signature in signatures().iter() {
+            let bytes = signature.to_bytes();
+            assert_eq!(bytes.len(), TAGGED_SIGNATURE_SIZE);
+            assert_eq!(bytes[0], signature.schema as u8);
+
+            let decoded = TaggedSignature::from_bytes(&bytes).unwrap();
+            assert_eq!(decoded, *signature);
+            assert!(decoded.verify(DATA).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_from_bytes_invalid() {
+        let [sym, _] = signatures();
+        let bytes = sym.to_bytes();
+
+        assert!(TaggedSignature::from_bytes(&bytes[1..]).is_err());
+
+        let mut unknown = bytes;
+        unknown[0] = 0;
+        assert!(TaggedSignature::from_bytes(&unknown).is_err());
+        unknown[0] = 3;
+        assert!(TaggedSignature::from_bytes(&unknown).is_err());
+    }
+
+    #[test]
+    fn test_schema_names() {
+        for schema in [SchemaKind::Symbol, SchemaKind::Nis1].iter() {
+            assert_eq!(SchemaKind::from_name(schema.name()).unwrap(), *schema);
+        }
+        assert_eq!(SchemaKind::Symbol.name(), "symbol");
+        assert_eq!(SchemaKind::Nis1.name(), "nis1");
+        assert!(SchemaKind::from_name("ed25519").is_err());
+    }
+
+    #[test]
+    fn test_serde_round_trip() {
+        for signature in signatures().iter() {
+            let json = serde_json::to_string(signature).unwrap();
+            assert!(json.contains(&format!("\"schema\":\"{}\"", signature.schema.name())));
+
+            let decoded: TaggedSignature = serde_json::from_str(&json).unwrap();
+            assert_eq!(decoded, *signature);
+        }
+    }
+
+    #[test]
+    fn test_verify_any_finds_the_schema() {
+        let [sym, nis1] = signatures();
+        for tagged in [sym, nis1].iter() {
+            let candidates = [
+                (SchemaKind::Symbol, sym.signer),
+                (SchemaKind::Nis1, nis1.signer),
+            ];
+            assert_eq!(verify_any(&candidates, DATA, tagged.signature), Some(tagged.schema));
+            assert_eq!(verify_any(&candidates, b"other data", tagged.signature), None);
+        }
+
+        // the same key under both schemas, only one of them verifies.
+        let candidates = [
+            (SchemaKind::Symbol, nis1.signer),
+            (SchemaKind::Nis1, nis1.signer),
+        ];
+        assert_eq!(verify_any(&candidates, DATA, nis1.signature), Some(SchemaKind::Nis1));
+        assert_eq!(verify_any(&candidates[..1], DATA, nis1.signature), None);
+    }
+
+    #[test]
+    fn test_verify_any_skips_invalid_keys() {
+        let [sym, _] = signatures();
+        // y = 2 is not the y coordinate of a curve point.
+        let mut invalid = [0u8; 32];
+        invalid[0] = 2;
+        let candidates = [
+            (SchemaKind::Nis1, PublicKey::from(invalid)),
+            (SchemaKind::Symbol, PublicKey::from(invalid)),
+            (SchemaKind::Symbol, sym.signer),
+        ];
+        assert_eq!(verify_any(&candidates, DATA, sym.signature), Some(SchemaKind::Symbol));
+        assert_eq!(verify_any(&[], DATA, sym.signature), None);
+    }
+}
@@ -0,0 +1,57 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(test)]
+pub mod tests {
+    use std::str::FromStr;
+
+    use symbol_crypto_core::prelude::{KpNis1, KpSym, PublicKey};
+    use symbol_crypto_core::prelude::test_keys::{alice, bob, by_name, carol, dave, eve, TEST_KEYS};
+
+    #[test]
+    fn test_symbol_public_keys_are_the_documented_ones() {
+        let expected = [
+            (alice::<KpSym>(), "F19FD1CD6B8495EA941CB58BB60D0106773638EBA44286F06F2835329E28A0A2"),
+            (bob::<KpSym>(), "3F4A7A992B63C05CE4E1A6431E3D07C44A5FC165326D12F518319E2F60DC4A70"),
+            (carol::<KpSym>(), "6C2BE98C8321616815B5FF046A97C2C185508D69938C2D096B7A0394A15FDB19"),
+            (dave::<KpSym>(), "F292BC5A4277749AE7E8117FF29005F6F50C4E4DD10354CDAF030239B4750374"),
+            (eve::<KpSym>(), "FFB2D2F93B401ADA7A1CD7DEA536057DF1369DFCEE953529CE22DFAFFB7F84CC"),
+        ];
+        for (kp, public_key) in expected.iter() {
+            assert_eq!(kp.public_key(), PublicKey::from_str(public_key).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_nis1_keypairs_share_the_private_key() {
+        let sym = alice::<KpSym>();
+        let nis1 = alice::<KpNis1>();
+        assert_eq!(sym.private_key(), nis1.private_key());
+        assert_ne!(sym.public_key(), nis1.public_key());
+    }
+
+    #[test]
+    fn test_by_name() {
+        for (name, _) in TEST_KEYS.iter() {
+            assert!(by_name::<KpSym>(name).is_some());
+        }
+        assert_eq!(by_name::<KpSym>("bob").unwrap().public_key(), bob::<KpSym>().public_key());
+        assert!(by_name::<KpSym>("mallory").is_none());
+    }
+
+    #[test]
+    fn test_keys_are_distinct() {
+        let mut public_keys: Vec<_> = TEST_KEYS
+            .iter()
+            .map(|(name, _)| by_name::<KpSym>(name).unwrap().public_key())
+            .collect();
+        public_keys.sort();
+        public_keys.dedup();
+        assert_eq!(public_keys.len(), TEST_KEYS.len());
+    }
+}
@@ -0,0 +1,113 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(test)]
+pub mod tests {
+    use std::str::FromStr;
+
+    use symbol_crypto_core::prelude::{Keypair, KpSym, H256};
+    use symbol_crypto_core::sym::{
+        sign_transaction, transaction_hash, verify_transaction, GenerationHash,
+        MAINNET_GENERATION_HASH, TESTNET_GENERATION_HASH, TRANSACTION_HEADER_SIZE,
+    };
+
+    const PRIVATE_KEY: &str = "575DBB3062267EFF57C970A336EBBC8FBCFE12C5BD3ED7BC11EB0481D7704CED";
+
+    fn transaction() -> Vec<u8> {
+        let mut transaction = vec![0u8; TRANSACTION_HEADER_SIZE];
+        transaction.extend_from_slice(&[0x01, 0x98, 0x54, 0x41, 0xAA, 0xBB, 0xCC]);
+        let size = transaction.len() as u32;
+        transaction[..4].copy_from_slice(&size.to_le_bytes());
+        transaction
+    }
+
+    #[test]
+    fn test_generation_hash_from_str_and_display() {
+        let hex = "57F7DA205008026C776CB6AED843393F04CD458E0AA2D9F1D5F31A402072B2D6";
+        let hash = GenerationHash::from_str(hex).unwrap();
+
+        assert_eq!(hash, MAINNET_GENERATION_HASH);
+        assert_eq!(hash.to_string(), hex);
+        assert_eq!(GenerationHash::from_str(&hex.to_lowercase()).unwrap(), hash);
+        assert_eq!(*hash.as_h256(), H256::from_str(hex).unwrap());
+        assert_ne!(MAINNET_GENERATION_HASH, TESTNET_GENERATION_HASH);
+    }
+
+    #[test]
+    fn test_generation_hash_from_str_rejects_invalid() {
+        assert!(GenerationHash::from_str("57F7DA20").is_err());
+        assert!(GenerationHash::from_str(&"G".repeat(64)).is_err());
+    }
+
+    #[test]
+    fn test_sign_verify_transaction() {
+        let kp = Keypair::<KpSym>::from_hex_private_key(PRIVATE_KEY).unwrap();
+        let mut transaction = transaction();
+
+        let hash = sign_transaction(&kp, &TESTNET_GENERATION_HASH, &mut transaction).unwrap();
+
+        assert_eq!(
+            transaction_hash(&TESTNET_GENERATION_HASH, &transaction).unwrap(),
+            hash
+        );
+        assert!(verify_transaction(&TESTNET_GENERATION_HASH, &transaction).is_ok());
+        assert_eq!(
+            &transaction[8 + 64..8 + 64 + 32],
+            kp.public_key().as_bytes()
+        );
+    }
+
+    #[test]
+    fn test_transaction_is_bound_to_its_network() {
+        let kp = Keypair::<KpSym>::random();
+        let mut transaction = transaction();
+        let hash = sign_transaction(&kp, &TESTNET_GENERATION_HASH, &mut transaction).unwrap();
+
+        assert!(verify_transaction(&MAINNET_GENERATION_HASH, &transaction).is_err());
+        assert_ne!(
+            transaction_hash(&MAINNET_GENERATION_HASH, &transaction).unwrap(),
+            hash
+        );
+    }
+
+    #[test]
+    fn test_verify_transaction_rejects_tampering() {
+        let kp = Keypair::<KpSym>::random();
+        let mut transaction = transaction();
+        sign_transaction(&kp, &MAINNET_GENERATION_HASH, &mut transaction).unwrap();
+
+        let last = transaction.len() - 1;
+        transaction[last] ^= 0x01;
+
+        assert!(verify_transaction(&MAINNET_GENERATION_HASH, &transaction).is_err());
+    }
+
+    #[test]
+    fn test_sign_transaction_checks_size_field() {
+        let kp = Keypair::<KpSym>::random();
+        let mut transaction = transaction();
+        transaction.push(0);
+
+        assert!(sign_transaction(&kp, &MAINNET_GENERATION_HASH, &mut transaction).is_err());
+        assert!(sign_transaction(
+            &kp,
+            &MAINNET_GENERATION_HASH,
+            &mut [0u8; TRANSACTION_HEADER_SIZE]
+        )
+        .is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_generation_hash_serde_matches_h256() {
+        assert_eq!(
+            serde_json::to_string(&MAINNET_GENERATION_HASH).unwrap(),
+            serde_json::to_string(MAINNET_GENERATION_HASH.as_h256()).unwrap()
+        );
+    }
+}
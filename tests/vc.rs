@@ -0,0 +1,98 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(test)]
+pub mod tests {
+    use serde_json::{json, Value};
+
+    use symbol_crypto_core::prelude::{did, Keypair, KpSym};
+    use symbol_crypto_core::sym::vc::{add_proof, verify_proof};
+
+    const PRIVATE_KEY: &str = "E88283CE35FE74C89FFCB2D8BFA0A2CF6108BDC0D07606DEE34D161C30AC2F1E";
+
+    fn credential(issuer: &Keypair<KpSym>) -> Value {
+        json!({
+            "@context": ["https://www.w3.org/ns/credentials/v2"],
+            "type": ["VerifiableCredential"],
+            "issuer": did::to_did_key(&issuer.public_key()),
+            "credentialSubject": { "id": "did:example:subject", "name": "Symbol" }
+        })
+    }
+
+    #[test]
+    fn test_add_and_verify_proof() {
+        let kp = Keypair::<KpSym>::from_hex_private_key(PRIVATE_KEY).unwrap();
+
+        let secured = add_proof(&kp, &credential(&kp), Some("2021-06-01T00:00:00Z")).unwrap();
+        let proof = &secured["proof"];
+
+        assert_eq!(proof["type"], "DataIntegrityProof");
+        assert_eq!(proof["cryptosuite"], "eddsa-jcs-2022");
+        assert_eq!(proof["@context"], credential(&kp)["@context"]);
+        assert_eq!(
+            did::from_did_key(proof["verificationMethod"].as_str().unwrap()).unwrap(),
+            kp.public_key()
+        );
+        assert!(verify_proof(&secured, &kp.public_key()).is_ok());
+    }
+
+    #[test]
+    fn test_verify_proof_unexpected_issuer() {
+        let kp = Keypair::<KpSym>::random();
+        let other = Keypair::<KpSym>::random();
+
+        // a valid self-signed credential from another issuer.
+        let secured = add_proof(&other, &credential(&other), None).unwrap();
+        assert!(verify_proof(&secured, &other.public_key()).is_ok());
+        assert!(verify_proof(&secured, &kp.public_key()).is_err());
+
+        // signed by the expected key, but claiming another issuer.
+        assert!(add_proof(&kp, &credential(&other), None).is_err());
+        let mut claimed = credential(&kp);
+        claimed["issuer"] = json!({ "id": did::to_did_key(&kp.public_key()) });
+        let mut secured = add_proof(&kp, &claimed, None).unwrap();
+        assert!(verify_proof(&secured, &kp.public_key()).is_ok());
+        secured["issuer"]["id"] = did::to_did_key(&other.public_key()).into();
+        assert!(verify_proof(&secured, &kp.public_key()).is_err());
+    }
+
+    #[test]
+    fn test_verify_tampered_proof() {
+        let kp = Keypair::<KpSym>::random();
+        let secured = add_proof(&kp, &credential(&kp), None).unwrap();
+
+        let mut tampered = secured.clone();
+        tampered["credentialSubject"]["name"] = "NEM".into();
+        assert!(verify_proof(&tampered, &kp.public_key()).is_err());
+
+        let mut tampered = secured.clone();
+        tampered["proof"]["proofPurpose"] = "authentication".into();
+        assert!(verify_proof(&tampered, &kp.public_key()).is_err());
+
+        let mut tampered = secured;
+        let other = Keypair::<KpSym>::random();
+        tampered["proof"]["verificationMethod"] = format!(
+            "{}#{}",
+            did::to_did_key(&other.public_key()),
+            did::to_public_key_multibase(&other.public_key())
+        )
+        .into();
+        assert!(verify_proof(&tampered, &kp.public_key()).is_err());
+        assert!(verify_proof(&tampered, &other.public_key()).is_err());
+    }
+
+    #[test]
+    fn test_add_proof_invalid() {
+        let kp = Keypair::<KpSym>::random();
+        let secured = add_proof(&kp, &credential(&kp), None).unwrap();
+
+        assert!(add_proof(&kp, &secured, None).is_err());
+        assert!(add_proof(&kp, &json!(["not", "an", "object"]), None).is_err());
+        assert!(verify_proof(&credential(&kp), &kp.public_key()).is_err());
+    }
+}
@@ -0,0 +1,92 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(test)]
+pub mod tests {
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::Arc;
+
+    use symbol_crypto_core::prelude::test_keys::{alice, bob};
+    use symbol_crypto_core::prelude::{
+        KpNis1, KpSym, Signature, VerifierCache, VerifierCacheEvent, VerifierCacheStats,
+    };
+
+    #[test]
+    fn test_hits_after_a_successful_verification() {
+        let kp = alice::<KpSym>();
+        let signature = kp.try_sign(b"cosignature").unwrap();
+        let cache = VerifierCache::<KpSym>::new(8).unwrap();
+
+        assert!(!cache.contains(&kp.public_key(), b"cosignature", &signature));
+        cache.verify(&kp.public_key(), b"cosignature", signature).unwrap();
+        cache.verify(&kp.public_key(), b"cosignature", signature).unwrap();
+        assert!(cache.contains(&kp.public_key(), b"cosignature", &signature));
+        assert_eq!(
+            cache.stats(),
+            VerifierCacheStats { hits: 1, misses: 1, evictions: 0, len: 1 }
+        );
+    }
+
+    #[test]
+    fn test_invalid_signatures_are_not_memoized() {
+        let kp = alice::<KpSym>();
+        let cache = VerifierCache::<KpSym>::new(8).unwrap();
+
+        for _ in 0..2 {
+            assert!(cache.verify(&kp.public_key(), b"data", Signature::zero()).is_err());
+        }
+        let signature = kp.try_sign(b"data").unwrap();
+        assert!(cache.verify(&kp.public_key(), b"other data", signature).is_err());
+        assert!(cache.is_empty());
+        assert_eq!(cache.stats().misses, 3);
+    }
+
+    #[test]
+    fn test_cache_is_per_schema() {
+        let kp = alice::<KpSym>();
+        let signature = kp.try_sign(b"data").unwrap();
+        let cache = VerifierCache::<KpNis1>::new(8).unwrap();
+        assert!(cache.verify(&kp.public_key(), b"data", signature).is_err());
+    }
+
+    #[test]
+    fn test_least_recently_used_is_evicted() {
+        let kp = bob::<KpSym>();
+        let payloads: Vec<&[u8]> = vec![b"first", b"second", b"third"];
+        let signatures: Vec<_> = payloads.iter().map(|data| kp.try_sign(data).unwrap()).collect();
+
+        let evictions = Arc::new(AtomicU64::new(0));
+        let counter = Arc::clone(&evictions);
+        let cache = VerifierCache::<KpSym>::new(2).unwrap().with_metrics_hook(move |event| {
+            if event == VerifierCacheEvent::Eviction {
+                counter.fetch_add(1, Ordering::SeqCst);
+            }
+        });
+
+        cache.verify(&kp.public_key(), payloads[0], signatures[0]).unwrap();
+        cache.verify(&kp.public_key(), payloads[1], signatures[1]).unwrap();
+        // the first verification is used again, the second one is now the oldest.
+        cache.verify(&kp.public_key(), payloads[0], signatures[0]).unwrap();
+        cache.verify(&kp.public_key(), payloads[2], signatures[2]).unwrap();
+
+        assert_eq!(cache.len(), 2);
+        assert!(cache.contains(&kp.public_key(), payloads[0], &signatures[0]));
+        assert!(!cache.contains(&kp.public_key(), payloads[1], &signatures[1]));
+        assert!(cache.contains(&kp.public_key(), payloads[2], &signatures[2]));
+        assert_eq!(evictions.load(Ordering::SeqCst), 1);
+        assert_eq!(cache.stats().evictions, 1);
+
+        cache.clear();
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_zero_capacity_is_rejected() {
+        assert!(VerifierCache::<KpSym>::new(0).is_err());
+    }
+}
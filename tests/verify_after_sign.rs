@@ -0,0 +1,34 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(test)]
+pub mod tests {
+    use symbol_crypto_core::prelude::{KeyPairSchema, Keypair, KpNis1, KpSym};
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_try_sign() {
+        let kp = Keypair::<KpSym>::random();
+        assert_eq!(kp.try_sign(b"payload").unwrap(), kp.sign(b"payload"));
+
+        let kp = Keypair::<KpNis1>::random();
+        assert_eq!(kp.try_sign(b"payload").unwrap(), kp.sign(b"payload"));
+    }
+
+    #[test]
+    fn test_try_sign_rejects_faulty_signature() {
+        // a keypair whose private key does not match its public key signs like a faulted device.
+        let public_key = Keypair::<KpSym>::random().public_key();
+        let kp = KpSym::from_null_private_key(public_key);
+        assert!(kp.try_sign(b"payload").is_err());
+
+        let public_key = Keypair::<KpNis1>::random().public_key();
+        let kp = KpNis1::from_null_private_key(public_key);
+        assert!(kp.try_sign(b"payload").is_err());
+    }
+}
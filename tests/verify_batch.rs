@@ -0,0 +1,78 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(test)]
+pub mod tests {
+    use symbol_crypto_core::prelude::{KeyPairSchema, Keypair, KpNis1, KpSym, VerifyItem};
+
+    const MESSAGES: [&[u8]; 3] = [b"transfer 1", b"transfer 2", b"transfer 3"];
+
+    fn items<Kp: KeyPairSchema>() -> Vec<VerifyItem<'static>> {
+        MESSAGES
+            .iter()
+            .map(|data| {
+                let kp = Keypair::<Kp>::random();
+                VerifyItem::new(kp.public_key(), data, kp.try_sign(data).unwrap())
+            })
+            .collect()
+    }
+
+    fn assert_all_valid<Kp: KeyPairSchema>() {
+        let items = items::<Kp>();
+
+        assert!(Keypair::<Kp>::verify_each(&items).iter().all(Result::is_ok));
+        assert!(Keypair::<Kp>::verify_all_fast(&items).is_ok());
+    }
+
+    fn assert_reports_invalid<Kp: KeyPairSchema>() {
+        let mut items = items::<Kp>();
+        items[1].data = b"tampered";
+
+        let results = Keypair::<Kp>::verify_each(&items);
+        assert_eq!(results.len(), 3);
+        assert!(results[0].is_ok());
+        assert!(results[1].is_err());
+        assert!(results[2].is_ok());
+
+        let err = Keypair::<Kp>::verify_all_fast(&items).unwrap_err();
+        assert!(err.to_string().contains("item 1"));
+    }
+
+    #[test]
+    fn test_verify_batch_valid_sym() {
+        assert_all_valid::<KpSym>();
+    }
+
+    #[test]
+    fn test_verify_batch_valid_nis1() {
+        assert_all_valid::<KpNis1>();
+    }
+
+    #[test]
+    fn test_verify_batch_invalid_sym() {
+        assert_reports_invalid::<KpSym>();
+    }
+
+    #[test]
+    fn test_verify_batch_invalid_nis1() {
+        assert_reports_invalid::<KpNis1>();
+    }
+
+    #[test]
+    fn test_verify_batch_other_schema_is_invalid() {
+        let items = items::<KpNis1>();
+
+        assert!(Keypair::<KpSym>::verify_all_fast(&items).is_err());
+    }
+
+    #[test]
+    fn test_verify_batch_empty() {
+        assert!(Keypair::<KpSym>::verify_each(&[]).is_empty());
+        assert!(Keypair::<KpNis1>::verify_all_fast(&[]).is_ok());
+    }
+}
@@ -0,0 +1,63 @@
+// Copyright 2021 BlockPuppets developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(test)]
+pub mod tests {
+    use std::str::FromStr;
+
+    use symbol_crypto_core::prelude::PublicKey;
+    use symbol_crypto_core::sym::voting_key::{
+        parse_voting_keys, voting_key_for_epoch, voting_keys_to_bytes, PinnedVotingKey,
+        PINNED_VOTING_KEY_SIZE,
+    };
+
+    const VOTING_KEY: &str = "C5FB65CB902623D93DF2E682FFB13F99D50FAC24D5FF2A42F68C7CA1772FE8A0";
+
+    fn pinned(start_epoch: u32, end_epoch: u32) -> PinnedVotingKey {
+        PinnedVotingKey {
+            voting_key: PublicKey::from_str(VOTING_KEY).unwrap(),
+            start_epoch,
+            end_epoch,
+        }
+    }
+
+    #[test]
+    fn test_pinned_voting_key_bytes() {
+        let key = pinned(0x0102, 0x0A0B0C);
+        let bytes = key.to_bytes();
+        assert_eq!(bytes.len(), PINNED_VOTING_KEY_SIZE);
+        assert_eq!(hex::encode_upper(&bytes[32..]), "020100000C0B0A00");
+        assert_eq!(PinnedVotingKey::from_bytes(&bytes).unwrap(), key);
+
+        assert!(PinnedVotingKey::from_bytes(&bytes[1..]).is_err());
+        assert!(PinnedVotingKey::from_bytes(&pinned(10, 9).to_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_voting_key_for_epoch() {
+        let keys = vec![pinned(1, 180), pinned(181, 360)];
+        let bytes = voting_keys_to_bytes(&keys);
+        assert_eq!(parse_voting_keys(&bytes).unwrap(), keys);
+
+        assert!(keys[0].is_active(1) && keys[0].is_active(180));
+        assert_eq!(voting_key_for_epoch(&keys, 180), Some(&keys[0]));
+        assert_eq!(voting_key_for_epoch(&keys, 181), Some(&keys[1]));
+        assert_eq!(voting_key_for_epoch(&keys, 0), None);
+        assert_eq!(voting_key_for_epoch(&keys, 361), None);
+    }
+
+    #[test]
+    fn test_parse_voting_keys_invalid() {
+        let overlapping = voting_keys_to_bytes(&[pinned(181, 360), pinned(1, 181)]);
+        assert!(parse_voting_keys(&overlapping).is_err());
+
+        let bytes = voting_keys_to_bytes(&[pinned(1, 180)]);
+        assert!(parse_voting_keys(&bytes[..PINNED_VOTING_KEY_SIZE - 1]).is_err());
+        assert!(parse_voting_keys(&[]).unwrap().is_empty());
+    }
+}